@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// A comprehensive S3-compatible storage CLI tool for Cloud.ru OBS and similar services
 #[derive(Parser, Debug)]
@@ -16,14 +16,305 @@ pub struct Args {
     #[arg(short, long, default_value = "ru-moscow-1", global = true)]
     pub region: String,
 
-    /// Timeout (in seconds) for all HTTP operations
+    /// Profile for credentials, region and OTEL settings (overrides AWS_PROFILE)
+    #[arg(long, value_name = "NAME", global = true)]
+    pub profile: Option<String>,
+
+    /// Assume this IAM role via STS, using the profile's credentials to call AssumeRole
+    #[arg(long, value_name = "ARN", global = true)]
+    pub role_arn: Option<String>,
+
+    /// External ID required by the role's trust policy
+    #[arg(long, value_name = "ID", global = true, requires = "role_arn")]
+    pub external_id: Option<String>,
+
+    /// Timeout (in seconds) for each request that doesn't carry object data
+    /// (list, head, delete, tagging...); transfers are exempt. 0 disables it
     #[arg(long, default_value_t = 10, global = true)]
     pub timeout: u64,
 
+    /// Seconds to wait for a connection to the endpoint [default: --timeout]
+    #[arg(long, value_name = "SECONDS", global = true)]
+    pub connect_timeout: Option<u64>,
+
+    /// Seconds to wait for the first byte of a response, for downloads and
+    /// metadata requests (uploads and copies are exempt)
+    #[arg(long, value_name = "SECONDS", global = true)]
+    pub read_timeout: Option<u64>,
+
+    /// Retries for throttled, 5xx and connection errors, with exponential backoff
+    #[arg(long, default_value_t = 3, global = true)]
+    pub max_retries: usize,
+
+    /// Cap combined upload and download throughput (e.g. 10MB/s, 512KiB/s)
+    #[arg(long, value_name = "RATE", global = true)]
+    pub max_bandwidth: Option<String>,
+
+    /// Record OpenTelemetry metrics for read-only commands (ls, du, head-object)
+    #[arg(long, global = true)]
+    pub otel_read_ops: bool,
+
+    /// Leave the bucket label off transfer metrics (for thousands of buckets)
+    #[arg(long, global = true)]
+    pub otel_no_bucket_labels: bool,
+
+    /// On exit, write run metrics to this file in Prometheus text format
+    /// (for node_exporter's textfile collector)
+    #[arg(long, value_name = "PATH", global = true)]
+    pub metrics_file: Option<String>,
+
+    /// Print a one-line JSON summary (operations, bytes, files, errors,
+    /// duration) to stderr when the command finishes, even if it failed
+    #[arg(long, global = true)]
+    pub summary_json: bool,
+
+    /// Metrics backend; statsd and dogstatsd also send metrics to a StatsD agent over UDP
+    #[arg(long, value_enum, default_value_t = MetricsBackend::Otlp, global = true)]
+    pub metrics_backend: MetricsBackend,
+
+    /// StatsD agent address for --metrics-backend statsd/dogstatsd
+    #[arg(
+        long,
+        value_name = "HOST:PORT",
+        default_value = "127.0.0.1:8125",
+        global = true
+    )]
+    pub statsd_addr: String,
+
+    /// Output format (text, table, json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    pub output: OutputFormat,
+
+    /// When to use colored output (auto, always, never)
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto, global = true)]
+    pub color: ColorChoice,
+
+    /// Transfer progress display for cp and sync (auto shows a bar on a terminal)
+    #[arg(long, value_enum, default_value_t = ProgressMode::Auto, global = true)]
+    pub progress: ProgressMode,
+
+    /// Disable progress output regardless of --progress
+    #[arg(long, global = true)]
+    pub no_progress: bool,
+
+    /// Directory for download temp files (default: beside the target; env: OBSCTL_TMPDIR)
+    #[arg(long, value_name = "PATH", global = true)]
+    pub temp_dir: Option<String>,
+
+    /// Fail requests when the bucket isn't owned by this AWS account ID
+    #[arg(long, value_name = "ACCOUNT_ID", global = true)]
+    pub expected_bucket_owner: Option<String>,
+
+    /// Request signing version; v2 is only for legacy S3-compatible gateways
+    #[arg(long, value_enum, global = true)]
+    pub signature_version: Option<SignatureVersion>,
+
+    /// Bucket addressing: path or virtual-hosted [default: auto, or
+    /// addressing_style in the profile]
+    #[arg(long, value_enum, global = true)]
+    pub addressing_style: Option<AddressingStyle>,
+
+    /// PEM bundle of extra CA certificates to trust for the S3 endpoint
+    /// (env: AWS_CA_BUNDLE, or ca_bundle in the profile)
+    #[arg(long, value_name = "PATH", global = true)]
+    pub ca_bundle: Option<String>,
+
+    /// Skip TLS certificate verification for self-signed development endpoints.
+    /// Insecure: never use it against production endpoints
+    #[arg(long, global = true, conflicts_with = "ca_bundle")]
+    pub no_verify_ssl: bool,
+
+    /// Send S3 requests through this proxy (http://[user:pass@]host:port or
+    /// socks5://host:port); overrides HTTPS_PROXY/HTTP_PROXY, NO_PROXY still applies
+    #[arg(long, value_name = "URL", global = true)]
+    pub proxy: Option<String>,
+
+    /// Base64-encoded 256-bit customer key for SSE-C objects, sent on object
+    /// reads, writes, HEAD requests and copy destinations
+    #[arg(long, value_name = "BASE64", global = true)]
+    pub sse_c_key: Option<String>,
+
+    /// Base64-encoded customer key of an SSE-C copy source, sent as the
+    /// copy-source key on server-side copies
+    #[arg(long, value_name = "BASE64", global = true)]
+    pub sse_c_copy_source_key: Option<String>,
+
+    /// Algorithm for --sse-c-key and --sse-c-copy-source-key [default: AES256]
+    #[arg(long, value_name = "ALGORITHM", global = true)]
+    pub sse_c_algorithm: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+/// Output format for command results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Plain text output (default)
+    #[default]
+    Text,
+    /// Aligned table with column headers (implies --long for ls)
+    Table,
+    /// Machine-readable JSON, including errors
+    Json,
+}
+
+/// Where metrics go besides the OpenTelemetry exporter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MetricsBackend {
+    /// OpenTelemetry (OTLP) only, when enabled
+    Otlp,
+    /// Plain StatsD counters and timers over UDP
+    Statsd,
+    /// StatsD with DogStatsD `#key:value` tags (operation, bucket, error type)
+    Dogstatsd,
+}
+
+/// Color mode for terminal output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Colorize only when stdout is a terminal
+    Auto,
+    /// Always colorize
+    Always,
+    /// Never colorize
+    Never,
+}
+
+impl ColorChoice {
+    /// Apply this choice to the global `colored` override
+    pub fn apply(self) {
+        use std::io::IsTerminal;
+
+        let enabled = match self {
+            ColorChoice::Auto => std::io::stdout().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        };
+        colored::control::set_override(enabled);
+    }
+}
+
+/// Progress display for transfers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// Progress bar when stderr is a terminal, status lines otherwise
+    Auto,
+    /// Single updating progress bar
+    Bar,
+    /// Periodic plain-text status lines
+    Lines,
+    /// No progress output
+    None,
+}
+
+/// Request signing scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SignatureVersion {
+    /// Legacy Signature Version 2 (HMAC-SHA1), long-term access keys only
+    V2,
+    /// Signature Version 4 (default)
+    V4,
+}
+
+impl SignatureVersion {
+    /// Parse the `signature_version` config value; accepts the AWS CLI
+    /// spellings `s3` (SigV2) and `s3v4` as well
+    pub fn from_config_value(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "v2" | "s3" => Some(SignatureVersion::V2),
+            "v4" | "s3v4" => Some(SignatureVersion::V4),
+            _ => None,
+        }
+    }
+}
+
+/// How bucket names are put into request URLs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AddressingStyle {
+    /// Path style for custom endpoints and signature v2, virtual-hosted for AWS (default)
+    Auto,
+    /// Bucket in the path: https://endpoint/bucket/key
+    Path,
+    /// Bucket in the host name: https://bucket.endpoint/key
+    Virtual,
+}
+
+impl AddressingStyle {
+    /// Parse the AWS CLI's `addressing_style` config value
+    pub fn from_config_value(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "auto" => Some(AddressingStyle::Auto),
+            "path" => Some(AddressingStyle::Path),
+            "virtual" => Some(AddressingStyle::Virtual),
+            _ => None,
+        }
+    }
+}
+
+/// HTTP method a presigned URL is signed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PresignMethod {
+    /// Download the object (default)
+    Get,
+    /// Upload to the key
+    Put,
+    /// Delete the object
+    Delete,
+}
+
+impl PresignMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PresignMethod::Get => "GET",
+            PresignMethod::Put => "PUT",
+            PresignMethod::Delete => "DELETE",
+        }
+    }
+}
+
+/// Retrieval speed (and price) for restoring archived objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RestoreTier {
+    /// Hours (3-5 for GLACIER, up to 12 for DEEP_ARCHIVE)
+    Standard,
+    /// Minutes; GLACIER only
+    Expedited,
+    /// Cheapest, up to 48 hours
+    Bulk,
+}
+
+/// Additional checksum S3 computes and stores for an upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ChecksumAlgorithm {
+    Crc32,
+    Crc32c,
+    Crc64nvme,
+    Sha1,
+    Sha256,
+}
+
+/// State `wait` blocks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WaitCondition {
+    /// Until the object (or bucket) appears
+    Exists,
+    /// Until it is gone
+    NotExists,
+}
+
+/// Change to make with `bucket versioning`, or report the current state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VersioningAction {
+    /// Keep every version of every object
+    Enable,
+    /// Stop creating new versions; a versioned bucket can't go back to unversioned
+    #[value(alias = "disable")]
+    Suspend,
+    /// Print Enabled, Suspended or Disabled
+    Status,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// List objects in bucket (equivalent to aws s3 ls)
@@ -52,6 +343,10 @@ pub enum Commands {
         #[arg(long)]
         pattern: Option<String>,
 
+        /// Match --pattern without regard to case
+        #[arg(long, requires = "pattern")]
+        ignore_case: bool,
+
         // Date filtering
         /// Show objects created after date (YYYYMMDD or relative like '7d')
         #[arg(long)]
@@ -78,6 +373,14 @@ pub enum Commands {
         #[arg(long)]
         max_size: Option<String>,
 
+        /// Show only keys with this file extension (repeatable, e.g. --extension jpg --extension png)
+        #[arg(long, value_name = "EXT")]
+        extension: Vec<String>,
+
+        /// Show only objects in these storage classes (comma-separated, e.g. STANDARD,GLACIER)
+        #[arg(long, value_name = "CLASS", value_delimiter = ',')]
+        storage_class: Vec<String>,
+
         // Result limiting
         /// Maximum number of results to return
         #[arg(long)]
@@ -92,18 +395,69 @@ pub enum Commands {
         tail: Option<usize>,
 
         // Sorting
-        /// Sort results by field (name, size, created, modified). Supports multi-level sorting like 'modified:desc,size:asc'
+        /// Sort results by field (name, size, created, modified). Supports multi-level sorting like 'modified:desc,size:asc',
+        /// and 'name:natural' to order embedded numbers numerically (file2 before file10)
         #[arg(long)]
         sort_by: Option<String>,
 
         /// Reverse sort order (only for single field sorting)
         #[arg(long)]
         reverse: bool,
+
+        /// Show only directory prefixes (synthesized from keys with --recursive)
+        #[arg(long, conflicts_with = "files_only")]
+        folders_only: bool,
+
+        /// Show only objects, hiding directory prefixes
+        #[arg(long, conflicts_with = "folders_only")]
+        files_only: bool,
+
+        /// Show the object owner in --long and table output
+        #[arg(long)]
+        show_owner: bool,
+
+        /// Fetch each object's ACL and flag publicly readable objects (one request per object)
+        #[arg(long)]
+        show_acl: bool,
+
+        /// Add an ETag column to --long and table output
+        #[arg(long)]
+        show_etag: bool,
+
+        /// Fill in missing dates and storage classes with a HeadObject request per
+        /// incomplete object before filtering (extra requests are billed)
+        #[arg(long)]
+        enrich: bool,
+
+        /// Print only the number of matching objects (with --summarize, also their total size)
+        #[arg(
+            long,
+            conflicts_with_all = ["head", "tail", "max_results", "sort_by", "folders_only", "long"]
+        )]
+        count: bool,
+
+        /// List every object version and delete marker (versioned buckets)
+        #[arg(long, conflicts_with_all = ["count", "enrich", "show_acl"])]
+        versions: bool,
+
+        /// List each top-level prefix concurrently, with up to N requests in flight
+        /// (recursive listings of buckets with many prefixes)
+        #[arg(long, value_name = "N", requires = "recursive", conflicts_with_all = ["versions", "count"])]
+        parallel: Option<usize>,
+
+        /// After the listing, keep polling the prefix and print objects as they
+        /// appear, like tail -f (stop with Ctrl-C)
+        #[arg(short, long, conflicts_with_all = ["versions", "count"])]
+        follow: bool,
+
+        /// Seconds between polls in --follow mode
+        #[arg(long, default_value_t = 5, requires = "follow")]
+        interval: u64,
     },
 
     /// Copy files/objects (equivalent to aws s3 cp)
     Cp {
-        /// Source (local path or s3://bucket/key)
+        /// Source (local path, s3://bucket/key, or '-' for stdin)
         source: String,
 
         /// Destination (local path or s3://bucket/key)
@@ -114,7 +468,7 @@ pub enum Commands {
         recursive: bool,
 
         /// Dry run mode
-        #[arg(long, default_value_t = false)]
+        #[arg(long, alias = "dry-run", default_value_t = false)]
         dryrun: bool,
 
         /// Maximum parallel operations
@@ -132,6 +486,59 @@ pub enum Commands {
         /// Exclude files that match pattern
         #[arg(long)]
         exclude: Option<String>,
+
+        /// Skip local directories matching this name or relative path pattern without
+        /// walking into them (repeatable, e.g. --exclude-dir node_modules --exclude-dir .git)
+        #[arg(long, value_name = "PATTERN")]
+        exclude_dir: Vec<String>,
+
+        /// Content-Type for uploaded objects; detected from the file extension when omitted
+        #[arg(long)]
+        content_type: Option<String>,
+
+        /// User metadata (x-amz-meta-*) for uploaded or copied objects (repeatable).
+        /// S3-to-S3 copies replace the source's metadata (MetadataDirective REPLACE)
+        #[arg(long, value_name = "KEY=VALUE")]
+        metadata: Vec<String>,
+
+        /// Object tag for uploaded or copied objects (repeatable, at most 10)
+        #[arg(long = "tag", value_name = "KEY=VALUE")]
+        tags: Vec<String>,
+
+        /// Inflate gzip objects (Content-Encoding: gzip or .gz keys) while downloading
+        #[arg(long, default_value_t = false)]
+        decompress: bool,
+
+        /// Verify transferred local files against a sha256sum-style manifest
+        #[arg(long, value_name = "FILE")]
+        checksum_manifest: Option<String>,
+
+        /// After a successful recursive upload, write a JSON listing of every key
+        /// written (with sizes and SHA-256 sums) to this key under the destination
+        #[arg(long, value_name = "KEY", requires = "recursive")]
+        write_manifest: Option<String>,
+
+        /// Upload files at least this large in parts (e.g. 64MiB, 1GB) [default: 100MiB]
+        #[arg(long, value_name = "SIZE")]
+        multipart_threshold: Option<String>,
+
+        /// Size of each multipart upload part, 5MiB to 5GiB [default: 16MiB]
+        #[arg(long, value_name = "SIZE")]
+        part_size: Option<String>,
+
+        /// Storage class for uploaded and copied objects (STANDARD, STANDARD_IA,
+        /// INTELLIGENT_TIERING, GLACIER, DEEP_ARCHIVE, ...)
+        #[arg(long, value_name = "CLASS")]
+        storage_class: Option<String>,
+
+        /// Have S3 compute and store this checksum for uploaded objects (and each
+        /// part of multipart uploads), so `verify` can check them without the ETag
+        #[arg(long, value_enum, value_name = "ALGORITHM")]
+        checksum_algorithm: Option<ChecksumAlgorithm>,
+
+        /// Print only errors and a final summary line instead of per-file output
+        #[arg(long)]
+        quiet: bool,
     },
 
     /// Sync directories (equivalent to aws s3 sync)
@@ -147,7 +554,7 @@ pub enum Commands {
         delete: bool,
 
         /// Dry run mode
-        #[arg(long, default_value_t = false)]
+        #[arg(long, alias = "dry-run", default_value_t = false)]
         dryrun: bool,
 
         /// Maximum parallel operations
@@ -161,6 +568,84 @@ pub enum Commands {
         /// Exclude files that match pattern
         #[arg(long)]
         exclude: Option<String>,
+
+        /// Compare only sizes, ignoring modification times (wins over --exact-timestamps)
+        #[arg(long)]
+        size_only: bool,
+
+        /// When syncing from S3 to local, re-download same-size files unless their
+        /// modification time matches the object's exactly
+        #[arg(long)]
+        exact_timestamps: bool,
+
+        /// Abort before deleting if more than N files would be deleted (recommended with --delete)
+        #[arg(long, requires = "delete")]
+        max_delete: Option<usize>,
+
+        /// Abort before deleting if more than this percentage of destination files would be deleted
+        #[arg(long, requires = "delete")]
+        max_delete_percent: Option<f64>,
+
+        /// After syncing, re-list both sides and fail if any source file is missing or differs in size
+        #[arg(long)]
+        verify: bool,
+
+        /// Verify transferred local files against a sha256sum-style manifest
+        #[arg(long, value_name = "FILE")]
+        checksum_manifest: Option<String>,
+
+        /// After a successful upload sync, write a JSON listing of every key
+        /// written (with sizes and SHA-256 sums) to this key under the destination
+        #[arg(long, value_name = "KEY")]
+        write_manifest: Option<String>,
+
+        /// Content-Type for uploaded objects; detected from the file extension when omitted
+        #[arg(long)]
+        content_type: Option<String>,
+
+        /// User metadata for uploaded objects (repeatable)
+        #[arg(long, value_name = "KEY=VALUE")]
+        metadata: Vec<String>,
+
+        /// Also re-sync unchanged objects whose Content-Type or user metadata differ
+        /// from what the upload would set (one HeadObject per unchanged object)
+        #[arg(long)]
+        compare_metadata: bool,
+
+        /// Also compare content: hash same-size files with MD5 and transfer them when
+        /// the hash differs from the object's ETag or stored x-amz-meta-checksum
+        #[arg(long)]
+        checksum: bool,
+
+        /// Print only errors and a final summary line instead of per-file output
+        #[arg(long)]
+        quiet: bool,
+    },
+
+    /// Move files/objects: copy, then delete each source once its copy succeeds
+    /// (equivalent to aws s3 mv)
+    Mv {
+        /// Source (local path or s3://bucket/key)
+        source: String,
+
+        /// Destination (local path or s3://bucket/key)
+        dest: String,
+
+        /// Move everything under the source directory or prefix
+        #[arg(long, default_value_t = false)]
+        recursive: bool,
+
+        /// Print what would be moved without moving anything
+        #[arg(long, alias = "dry-run", default_value_t = false)]
+        dryrun: bool,
+
+        /// Only move paths (relative to the source) that match pattern
+        #[arg(long)]
+        include: Option<String>,
+
+        /// Skip paths (relative to the source) that match pattern
+        #[arg(long)]
+        exclude: Option<String>,
     },
 
     /// Remove objects (equivalent to aws s3 rm)
@@ -173,7 +658,7 @@ pub enum Commands {
         recursive: bool,
 
         /// Dry run mode
-        #[arg(long, default_value_t = false)]
+        #[arg(long, alias = "dry-run", default_value_t = false)]
         dryrun: bool,
 
         /// Include files that match pattern
@@ -183,6 +668,32 @@ pub enum Commands {
         /// Exclude files that match pattern
         #[arg(long)]
         exclude: Option<String>,
+
+        /// Skip objects under legal hold or unexpired retention instead of
+        /// failing; --ignore-locked=false aborts on the first locked object
+        #[arg(
+            long,
+            default_value_t = true,
+            action = clap::ArgAction::Set,
+            num_args = 0..=1,
+            default_missing_value = "true",
+            require_equals = true
+        )]
+        ignore_locked: bool,
+
+        /// Delete without listing matched keys and asking to confirm first
+        /// (--recursive only; the prompt is shown when stdin is a terminal)
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Print only errors and a final summary line instead of per-file output
+        #[arg(long)]
+        quiet: bool,
+
+        /// Permanently delete this version of the object instead of adding a
+        /// delete marker (see ls --versions)
+        #[arg(long, conflicts_with = "recursive")]
+        version_id: Option<String>,
     },
 
     /// Create a new bucket (equivalent to aws s3 mb)
@@ -211,6 +722,16 @@ pub enum Commands {
         /// Wildcard pattern for bucket names (e.g., "test-*", "*-prod", "user-?-bucket")
         #[arg(long)]
         pattern: Option<String>,
+
+        /// Match --pattern without regard to case
+        #[arg(long, requires = "pattern")]
+        ignore_case: bool,
+    },
+
+    /// Bucket maintenance operations
+    Bucket {
+        #[command(subcommand)]
+        command: BucketCommands,
     },
 
     /// Generate presigned URLs (equivalent to aws s3 presign)
@@ -218,9 +739,25 @@ pub enum Commands {
         /// S3 URI (s3://bucket/key)
         s3_uri: String,
 
-        /// URL expiration time in seconds
+        /// URL expiration time in seconds (at most 604800, i.e. 7 days)
         #[arg(long, default_value_t = 3600)]
         expires_in: u64,
+
+        /// HTTP method the URL is valid for
+        #[arg(long, value_enum, default_value_t = PresignMethod::Get)]
+        method: PresignMethod,
+
+        /// Content-Type baked into a PUT URL's signature; uploads must send the same header
+        #[arg(long)]
+        content_type: Option<String>,
+
+        /// Presign every object under the prefix, printing one "key<TAB>url" line each
+        #[arg(long, conflicts_with = "keys_from_file")]
+        recursive: bool,
+
+        /// Presign each key listed in FILE (one key or s3:// URI per line)
+        #[arg(long, value_name = "FILE")]
+        keys_from_file: Option<String>,
     },
 
     /// Show object metadata (equivalent to aws s3api head-object)
@@ -233,6 +770,96 @@ pub enum Commands {
         /// S3 key
         #[arg(long)]
         key: String,
+
+        /// Also fetch and print the first N bytes of the object (binary content
+        /// is shown as a hex dump)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        preview_bytes: Option<u64>,
+
+        /// Preview the last --preview-bytes bytes instead of the first
+        #[arg(long, requires = "preview_bytes")]
+        preview_tail: bool,
+
+        /// Also show the object's additional checksum (CRC32, CRC32C, CRC64NVME,
+        /// SHA1 or SHA256) and, for multipart uploads, the part count
+        #[arg(long)]
+        checksum: bool,
+    },
+
+    /// Check whether an object (or bucket) exists, for scripts: prints nothing
+    /// and exits 0 if it does, 1 if not, 3 if access is denied, 2 on other errors
+    Exists {
+        /// S3 URI (s3://bucket/key, or s3://bucket for the bucket itself)
+        s3_uri: String,
+
+        /// Don't print the error message either; only the exit code tells
+        #[arg(short, long)]
+        quiet: bool,
+    },
+
+    /// Block until an object (or bucket) exists or is gone, polling with HeadObject
+    /// (like aws s3api wait object-exists); exits 1 if the wait times out
+    Wait {
+        /// exists or not-exists
+        #[arg(value_enum)]
+        condition: WaitCondition,
+
+        /// S3 URI (s3://bucket/key, or s3://bucket for the bucket itself)
+        s3_uri: String,
+
+        /// Give up after this many seconds; 0 waits indefinitely
+        #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+        wait_timeout: u64,
+
+        /// Seconds between polls
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+
+    /// Get, set or remove the tags on an existing object
+    Tag {
+        #[command(subcommand)]
+        command: TagCommands,
+    },
+
+    /// Restore GLACIER / DEEP_ARCHIVE objects so they can be downloaded
+    Restore {
+        /// S3 URI (s3://bucket/key, or s3://bucket/prefix with --recursive)
+        s3_uri: String,
+
+        /// Days to keep the restored copy available
+        #[arg(long, default_value_t = 7)]
+        days: i32,
+
+        /// Retrieval tier
+        #[arg(long, value_enum, default_value_t = RestoreTier::Standard)]
+        tier: RestoreTier,
+
+        /// Restore every archived object under the prefix
+        #[arg(long)]
+        recursive: bool,
+    },
+
+    /// Stream an object's contents to stdout
+    Cat {
+        /// S3 URI (s3://bucket/key)
+        s3_uri: String,
+
+        /// Only stream this byte range (bytes=0-1023, bytes=1024- or bytes=-1024)
+        #[arg(long, conflicts_with = "follow")]
+        range: Option<String>,
+
+        /// Keep polling for appended bytes, like tail -f (stop with Ctrl-C)
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Seconds between polls in --follow mode
+        #[arg(long, default_value_t = 2, requires = "follow")]
+        interval: u64,
+
+        /// Inflate gzip objects (Content-Encoding: gzip or .gz keys) while streaming
+        #[arg(long, default_value_t = false, conflicts_with_all = ["range", "follow"])]
+        decompress: bool,
     },
 
     /// Show storage usage statistics (custom extension)
@@ -249,48 +876,124 @@ pub enum Commands {
         summarize: bool,
     },
 
+    /// Report objects with identical content (same ETag and size) and the bytes deduping would free
+    #[command(name = "find-dupes")]
+    FindDupes {
+        /// S3 URI (s3://bucket/prefix)
+        s3_uri: String,
+
+        /// Ignore objects smaller than this (e.g. 1MB, 512KiB; bare numbers are MB)
+        #[arg(long)]
+        min_size: Option<String>,
+
+        /// Human readable sizes
+        #[arg(long, default_value_t = false)]
+        human_readable: bool,
+    },
+
+    /// Compare a local file or directory with S3 by size and checksum, without
+    /// transferring anything; exits non-zero on any missing, extra or differing file
+    Verify {
+        /// S3 URI (s3://bucket/prefix, or s3://bucket/key for a single file)
+        s3_uri: String,
+
+        /// Local file or directory
+        local_path: String,
+    },
+
     /// Configuration management and setup guidance
     Config {
         #[command(subcommand)]
         command: Option<ConfigCommands>,
     },
+
+    /// Show version and build information
+    Version {
+        /// Print build metadata as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum BucketCommands {
+    /// Abort incomplete multipart uploads that are still accruing storage charges
+    #[command(name = "clean-multipart")]
+    CleanMultipart {
+        /// S3 URI (s3://bucket or s3://bucket/prefix)
+        s3_uri: String,
+
+        /// Only abort uploads initiated before this age or date (7d, 2w, 20240101)
+        #[arg(long)]
+        older_than: Option<String>,
+
+        /// Show what would be aborted without aborting anything
+        #[arg(long, alias = "dry-run")]
+        dryrun: bool,
+    },
+    /// Enable or suspend object versioning, or show its status
+    Versioning {
+        /// enable, suspend (alias: disable) or status
+        #[arg(value_enum)]
+        action: VersioningAction,
+
+        /// S3 URI (s3://bucket)
+        s3_uri: String,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TagCommands {
+    /// Print an object's tags as KEY=VALUE lines
+    Get {
+        /// S3 URI (s3://bucket/key)
+        s3_uri: String,
+    },
+    /// Replace an object's tag set
+    Set {
+        /// S3 URI (s3://bucket/key)
+        s3_uri: String,
+
+        /// Tags as KEY=VALUE pairs (at most 10)
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        tags: Vec<String>,
+    },
+    /// Remove every tag from an object
+    Remove {
+        /// S3 URI (s3://bucket/key)
+        s3_uri: String,
+    },
 }
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum ConfigCommands {
     /// Interactive configuration setup (like 'aws configure')
-    Configure {
-        /// AWS profile name
-        #[arg(long, default_value = "default")]
-        profile: String,
-    },
+    Configure,
     /// Set a configuration value
     Set {
         /// Configuration key (e.g., region, aws_access_key_id, endpoint_url)
         key: String,
         /// Configuration value
         value: String,
-        /// AWS profile name
-        #[arg(long, default_value = "default")]
-        profile: String,
     },
     /// Get a configuration value
     Get {
         /// Configuration key to retrieve
         key: String,
-        /// AWS profile name
-        #[arg(long, default_value = "default")]
-        profile: String,
+    },
+    /// Remove a configuration value
+    Unset {
+        /// Configuration key to remove
+        key: String,
     },
     /// List all configuration for a profile
     List {
-        /// AWS profile name
-        #[arg(long, default_value = "default")]
-        profile: String,
         /// Show file paths where configuration is stored
         #[arg(long)]
         files: bool,
     },
+    /// List the profiles in ~/.aws/config and ~/.aws/credentials
+    ListProfiles,
     /// Dashboard management commands
     Dashboard {
         #[command(subcommand)]
@@ -382,17 +1085,31 @@ mod tests {
             human_readable,
             summarize,
             pattern,
+            ignore_case,
             created_after,
             created_before,
             modified_after,
             modified_before,
             min_size,
             max_size,
+            extension,
+            storage_class,
             max_results,
             head,
             tail,
             sort_by,
             reverse,
+            folders_only,
+            files_only,
+            show_owner,
+            show_acl,
+            show_etag,
+            enrich,
+            count,
+            versions,
+            parallel,
+            follow,
+            interval,
         } = args.command
         {
             assert_eq!(path, Some("s3://my-bucket".to_string()));
@@ -401,22 +1118,126 @@ mod tests {
             assert!(human_readable);
             assert!(!summarize);
             assert_eq!(pattern, None);
+            assert!(!ignore_case);
             assert_eq!(created_after, None);
             assert_eq!(created_before, None);
             assert_eq!(modified_after, None);
             assert_eq!(modified_before, None);
             assert_eq!(min_size, None);
             assert_eq!(max_size, None);
+            assert!(extension.is_empty());
+            assert!(storage_class.is_empty());
             assert_eq!(max_results, None);
             assert_eq!(head, None);
             assert_eq!(tail, None);
             assert_eq!(sort_by, None);
             assert!(!reverse);
+            assert!(!folders_only);
+            assert!(!files_only);
+            assert!(!show_owner);
+            assert!(!show_acl);
+            assert!(!show_etag);
+            assert!(!enrich);
+            assert!(!count);
+            assert!(!versions);
+            assert_eq!(parallel, None);
+            assert!(!follow);
+            assert_eq!(interval, 5);
         } else {
             panic!("Expected Ls command");
         }
     }
 
+    #[test]
+    fn test_ls_extension_is_repeatable() {
+        let args = Args::parse_from([
+            "obsctl",
+            "ls",
+            "s3://b/",
+            "--extension",
+            "jpg",
+            "--extension",
+            "png",
+        ]);
+        if let Commands::Ls { extension, .. } = args.command {
+            assert_eq!(extension, vec!["jpg", "png"]);
+        } else {
+            panic!("Expected Ls command");
+        }
+    }
+
+    #[test]
+    fn test_ls_storage_class_takes_a_list() {
+        let args = Args::parse_from([
+            "obsctl",
+            "ls",
+            "s3://b/",
+            "--storage-class",
+            "STANDARD,glacier",
+        ]);
+        if let Commands::Ls { storage_class, .. } = args.command {
+            assert_eq!(storage_class, vec!["STANDARD", "glacier"]);
+        } else {
+            panic!("Expected Ls command");
+        }
+    }
+
+    #[test]
+    fn test_ls_folders_and_files_only_conflict() {
+        let args = Args::parse_from(["obsctl", "ls", "s3://b/", "--folders-only"]);
+        assert!(matches!(
+            args.command,
+            Commands::Ls {
+                folders_only: true,
+                files_only: false,
+                ..
+            }
+        ));
+
+        let result =
+            Args::try_parse_from(["obsctl", "ls", "s3://b/", "--folders-only", "--files-only"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ls_count_conflicts_with_row_limits() {
+        let args = Args::parse_from(["obsctl", "ls", "s3://b/", "--count", "--min-size", "1GB"]);
+        assert!(matches!(args.command, Commands::Ls { count: true, .. }));
+
+        let result = Args::try_parse_from(["obsctl", "ls", "s3://b/", "--count", "--head", "5"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sync_max_delete_requires_delete() {
+        let args = Args::parse_from([
+            "obsctl",
+            "sync",
+            "./dir",
+            "s3://bucket/dir",
+            "--delete",
+            "--max-delete",
+            "10",
+        ]);
+        assert!(matches!(
+            args.command,
+            Commands::Sync {
+                max_delete: Some(10),
+                ..
+            }
+        ));
+
+        let result = Args::try_parse_from([
+            "obsctl",
+            "sync",
+            "./dir",
+            "s3://bucket/dir",
+            "--max-delete-percent",
+            "5",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_cp_command_parsing() {
         let args = Args::parse_from([
@@ -510,6 +1331,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mv_command_parsing() {
+        let args = Args::parse_from([
+            "obsctl",
+            "mv",
+            "s3://bucket/logs/",
+            "s3://archive/logs/",
+            "--recursive",
+            "--exclude",
+            "*.tmp",
+        ]);
+
+        if let Commands::Mv {
+            source,
+            dest,
+            recursive,
+            dryrun,
+            include,
+            exclude,
+        } = args.command
+        {
+            assert_eq!(source, "s3://bucket/logs/");
+            assert_eq!(dest, "s3://archive/logs/");
+            assert!(recursive);
+            assert!(!dryrun);
+            assert_eq!(include, None);
+            assert_eq!(exclude.as_deref(), Some("*.tmp"));
+        } else {
+            panic!("Expected Mv command");
+        }
+    }
+
+    #[test]
+    fn test_quiet_flag() {
+        for argv in [
+            vec!["obsctl", "rm", "s3://bucket/dir/", "--recursive", "--quiet"],
+            vec!["obsctl", "cp", "./a", "s3://bucket/a", "--quiet"],
+            vec!["obsctl", "sync", "./dir", "s3://bucket/dir", "--quiet"],
+        ] {
+            let args = Args::parse_from(&argv);
+            let quiet = match args.command {
+                Commands::Rm { quiet, .. }
+                | Commands::Cp { quiet, .. }
+                | Commands::Sync { quiet, .. } => quiet,
+                _ => panic!("Unexpected command for {argv:?}"),
+            };
+            assert!(quiet, "{argv:?}");
+        }
+    }
+
+    #[test]
+    fn test_dry_run_alias() {
+        for argv in [
+            vec!["obsctl", "rm", "s3://bucket/file", "--dry-run"],
+            vec!["obsctl", "mv", "./a", "s3://bucket/a", "--dry-run"],
+            vec!["obsctl", "cp", "./a", "s3://bucket/a", "--dry-run"],
+            vec!["obsctl", "sync", "./dir", "s3://bucket/dir", "--dry-run"],
+            vec![
+                "obsctl",
+                "bucket",
+                "clean-multipart",
+                "s3://bucket",
+                "--dry-run",
+            ],
+        ] {
+            let args = Args::parse_from(&argv);
+            let dryrun = match args.command {
+                Commands::Rm { dryrun, .. }
+                | Commands::Mv { dryrun, .. }
+                | Commands::Cp { dryrun, .. }
+                | Commands::Sync { dryrun, .. }
+                | Commands::Bucket {
+                    command: BucketCommands::CleanMultipart { dryrun, .. },
+                } => dryrun,
+                _ => panic!("unexpected command for {argv:?}"),
+            };
+            assert!(dryrun, "--dry-run not accepted by {argv:?}");
+        }
+    }
+
+    #[test]
+    fn test_rm_ignore_locked_flag() {
+        let parse = |extra: &[&str]| {
+            let mut argv = vec!["obsctl", "rm", "s3://bucket/prefix/", "--recursive"];
+            argv.extend_from_slice(extra);
+            match Args::parse_from(argv).command {
+                Commands::Rm { ignore_locked, .. } => ignore_locked,
+                _ => panic!("Expected Rm command"),
+            }
+        };
+
+        assert!(parse(&[]));
+        assert!(parse(&["--ignore-locked"]));
+        assert!(!parse(&["--ignore-locked=false"]));
+    }
+
     #[test]
     fn test_mb_command_parsing() {
         let args = Args::parse_from(["obsctl", "mb", "s3://new-bucket"]);
@@ -521,6 +1438,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ignore_case_requires_pattern() {
+        let args = Args::parse_from(["obsctl", "ls", "--pattern", "media-*", "--ignore-case"]);
+        assert!(matches!(
+            args.command,
+            Commands::Ls {
+                ignore_case: true,
+                ..
+            }
+        ));
+
+        assert!(Args::try_parse_from(["obsctl", "rb", "--ignore-case"]).is_err());
+    }
+
     #[test]
     fn test_rb_command_parsing() {
         let args = Args::parse_from(["obsctl", "rb", "s3://old-bucket", "--force"]);
@@ -531,6 +1462,7 @@ mod tests {
             all,
             confirm,
             pattern,
+            ignore_case,
         } = args.command
         {
             assert_eq!(s3_uri, Some("s3://old-bucket".to_string()));
@@ -538,6 +1470,7 @@ mod tests {
             assert!(!all);
             assert!(!confirm);
             assert_eq!(pattern, None);
+            assert!(!ignore_case);
         } else {
             panic!("Expected Rb command");
         }
@@ -553,9 +1486,46 @@ mod tests {
             "7200",
         ]);
 
-        if let Commands::Presign { s3_uri, expires_in } = args.command {
+        if let Commands::Presign {
+            s3_uri,
+            expires_in,
+            method,
+            content_type,
+            recursive,
+            keys_from_file,
+        } = args.command
+        {
             assert_eq!(s3_uri, "s3://bucket/file");
             assert_eq!(expires_in, 7200);
+            assert_eq!(method, PresignMethod::Get);
+            assert_eq!(content_type, None);
+            assert!(!recursive);
+            assert_eq!(keys_from_file, None);
+        } else {
+            panic!("Expected Presign command");
+        }
+    }
+
+    #[test]
+    fn test_presign_put_parsing() {
+        let args = Args::parse_from([
+            "obsctl",
+            "presign",
+            "s3://bucket/upload.csv",
+            "--method",
+            "put",
+            "--content-type",
+            "text/csv",
+        ]);
+
+        if let Commands::Presign {
+            method,
+            content_type,
+            ..
+        } = args.command
+        {
+            assert_eq!(method, PresignMethod::Put);
+            assert_eq!(content_type.as_deref(), Some("text/csv"));
         } else {
             panic!("Expected Presign command");
         }
@@ -572,14 +1542,36 @@ mod tests {
             "my-key",
         ]);
 
-        if let Commands::HeadObject { bucket, key } = args.command {
+        if let Commands::HeadObject {
+            bucket,
+            key,
+            preview_bytes,
+            preview_tail,
+            checksum,
+        } = args.command
+        {
             assert_eq!(bucket, "my-bucket");
             assert_eq!(key, "my-key");
+            assert_eq!(preview_bytes, None);
+            assert!(!preview_tail);
+            assert!(!checksum);
         } else {
             panic!("Expected HeadObject command");
         }
     }
 
+    #[test]
+    fn test_verify_command_parsing() {
+        let args = Args::parse_from(["obsctl", "verify", "s3://bucket/backup/", "./backup"]);
+
+        if let Commands::Verify { s3_uri, local_path } = args.command {
+            assert_eq!(s3_uri, "s3://bucket/backup/");
+            assert_eq!(local_path, "./backup");
+        } else {
+            panic!("Expected Verify command");
+        }
+    }
+
     #[test]
     fn test_du_command_parsing() {
         let args = Args::parse_from([
@@ -604,6 +1596,220 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_dupes_parsing() {
+        let args = Args::parse_from([
+            "obsctl",
+            "find-dupes",
+            "s3://bucket/photos/",
+            "--min-size",
+            "1MB",
+        ]);
+
+        if let Commands::FindDupes {
+            s3_uri,
+            min_size,
+            human_readable,
+        } = args.command
+        {
+            assert_eq!(s3_uri, "s3://bucket/photos/");
+            assert_eq!(min_size.as_deref(), Some("1MB"));
+            assert!(!human_readable);
+        } else {
+            panic!("Expected FindDupes command");
+        }
+    }
+
+    #[test]
+    fn test_restore_parsing() {
+        let args = Args::parse_from([
+            "obsctl",
+            "restore",
+            "s3://bucket/archive/",
+            "--recursive",
+            "--days",
+            "3",
+            "--tier",
+            "bulk",
+        ]);
+
+        if let Commands::Restore {
+            s3_uri,
+            days,
+            tier,
+            recursive,
+        } = args.command
+        {
+            assert_eq!(s3_uri, "s3://bucket/archive/");
+            assert_eq!(days, 3);
+            assert_eq!(tier, RestoreTier::Bulk);
+            assert!(recursive);
+        } else {
+            panic!("Expected Restore command");
+        }
+    }
+
+    #[test]
+    fn test_cat_follow_parsing() {
+        let args = Args::parse_from(["obsctl", "cat", "-f", "--interval", "5", "s3://b/live.log"]);
+        assert!(matches!(
+            args.command,
+            Commands::Cat {
+                follow: true,
+                interval: 5,
+                ..
+            }
+        ));
+
+        // --interval only makes sense while following
+        let result = Args::try_parse_from(["obsctl", "cat", "--interval", "5", "s3://b/live.log"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_exists_parsing() {
+        let args = Args::parse_from(["obsctl", "exists", "-q", "s3://b/flag"]);
+        if let Commands::Exists { s3_uri, quiet } = args.command {
+            assert_eq!(s3_uri, "s3://b/flag");
+            assert!(quiet);
+        } else {
+            panic!("Expected Exists command");
+        }
+    }
+
+    #[test]
+    fn test_wait_parsing() {
+        let args = Args::parse_from([
+            "obsctl",
+            "wait",
+            "not-exists",
+            "s3://b/lock",
+            "--wait-timeout",
+            "60",
+        ]);
+        assert!(matches!(
+            args.command,
+            Commands::Wait {
+                condition: WaitCondition::NotExists,
+                wait_timeout: 60,
+                interval: 5,
+                ..
+            }
+        ));
+        assert!(Args::try_parse_from(["obsctl", "wait", "appears", "s3://b/k"]).is_err());
+    }
+
+    #[test]
+    fn test_ls_follow_parsing() {
+        let args = Args::parse_from(["obsctl", "ls", "s3://logs/", "--follow", "--interval", "30"]);
+        assert!(matches!(
+            args.command,
+            Commands::Ls {
+                follow: true,
+                interval: 30,
+                ..
+            }
+        ));
+
+        let result = Args::try_parse_from(["obsctl", "ls", "s3://logs/", "-f", "--versions"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cat_range_parsing() {
+        let args = Args::parse_from(["obsctl", "cat", "--range", "bytes=0-1023", "s3://b/log.gz"]);
+        if let Commands::Cat { range, .. } = args.command {
+            assert_eq!(range.as_deref(), Some("bytes=0-1023"));
+        } else {
+            panic!("Expected Cat command");
+        }
+
+        // A fixed range can't be followed
+        let result = Args::try_parse_from(["obsctl", "cat", "--range", "0-9", "-f", "s3://b/log"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cat_decompress_parsing() {
+        let args = Args::parse_from(["obsctl", "cat", "--decompress", "s3://b/log.gz"]);
+        assert!(matches!(
+            args.command,
+            Commands::Cat {
+                decompress: true,
+                ..
+            }
+        ));
+
+        // Offsets into a compressed object don't map onto the inflated output
+        for conflicting in [["--range", "0-9"], ["-f", "--interval=2"]] {
+            let mut argv = vec!["obsctl", "cat", "--decompress"];
+            argv.extend(conflicting);
+            argv.push("s3://b/log.gz");
+            assert!(Args::try_parse_from(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_bucket_clean_multipart_parsing() {
+        let args = Args::parse_from([
+            "obsctl",
+            "bucket",
+            "clean-multipart",
+            "s3://bucket",
+            "--older-than",
+            "7d",
+            "--dryrun",
+        ]);
+
+        if let Commands::Bucket {
+            command:
+                BucketCommands::CleanMultipart {
+                    s3_uri,
+                    older_than,
+                    dryrun,
+                },
+        } = args.command
+        {
+            assert_eq!(s3_uri, "s3://bucket");
+            assert_eq!(older_than, Some("7d".to_string()));
+            assert!(dryrun);
+        } else {
+            panic!("Expected bucket clean-multipart command");
+        }
+    }
+
+    #[test]
+    fn test_bucket_versioning_parsing() {
+        for (word, expected) in [
+            ("enable", VersioningAction::Enable),
+            ("suspend", VersioningAction::Suspend),
+            ("disable", VersioningAction::Suspend),
+            ("status", VersioningAction::Status),
+        ] {
+            let args = Args::parse_from(["obsctl", "bucket", "versioning", word, "s3://bucket"]);
+            if let Commands::Bucket {
+                command: BucketCommands::Versioning { action, s3_uri },
+            } = args.command
+            {
+                assert_eq!(action, expected);
+                assert_eq!(s3_uri, "s3://bucket");
+            } else {
+                panic!("Expected bucket versioning command");
+            }
+        }
+    }
+
+    #[test]
+    fn test_version_command_parsing() {
+        let args = Args::parse_from(["obsctl", "version", "--json"]);
+
+        if let Commands::Version { json } = args.command {
+            assert!(json);
+        } else {
+            panic!("Expected Version command");
+        }
+    }
+
     #[test]
     fn test_global_args_parsing() {
         let args = Args::parse_from([
@@ -663,6 +1869,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cp_metadata_is_repeatable() {
+        let args = Args::parse_from([
+            "obsctl",
+            "cp",
+            "s3://bucket/a.txt",
+            "s3://bucket/b.txt",
+            "--metadata",
+            "owner=web",
+            "--metadata",
+            "build=42",
+        ]);
+
+        if let Commands::Cp { metadata, .. } = args.command {
+            assert_eq!(metadata, vec!["owner=web", "build=42"]);
+        } else {
+            panic!("Expected Cp command");
+        }
+    }
+
+    #[test]
+    fn test_tag_command_parsing() {
+        let args = Args::parse_from([
+            "obsctl",
+            "tag",
+            "set",
+            "s3://bucket/key",
+            "env=prod",
+            "team=data",
+        ]);
+        if let Commands::Tag {
+            command: TagCommands::Set { s3_uri, tags },
+        } = args.command
+        {
+            assert_eq!(s3_uri, "s3://bucket/key");
+            assert_eq!(tags, vec!["env=prod", "team=data"]);
+        } else {
+            panic!("Expected Tag set command");
+        }
+
+        assert!(Args::try_parse_from(["obsctl", "tag", "set", "s3://bucket/key"]).is_err());
+
+        let args = Args::parse_from(["obsctl", "cp", "./a", "s3://b/a", "--tag", "env=dev"]);
+        if let Commands::Cp { tags, .. } = args.command {
+            assert_eq!(tags, vec!["env=dev"]);
+        } else {
+            panic!("Expected Cp command");
+        }
+    }
+
+    #[test]
+    fn test_ls_parallel_requires_recursive() {
+        let args = Args::parse_from([
+            "obsctl",
+            "ls",
+            "s3://bucket/",
+            "--recursive",
+            "--parallel",
+            "8",
+        ]);
+        assert!(matches!(
+            args.command,
+            Commands::Ls {
+                parallel: Some(8),
+                ..
+            }
+        ));
+
+        assert!(Args::try_parse_from(["obsctl", "ls", "s3://bucket/", "--parallel", "8"]).is_err());
+        assert!(Args::try_parse_from([
+            "obsctl",
+            "ls",
+            "s3://bucket/",
+            "--recursive",
+            "--parallel",
+            "8",
+            "--count",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_versions_flags() {
+        let args = Args::parse_from(["obsctl", "ls", "s3://bucket/logs/", "--versions"]);
+        assert!(matches!(args.command, Commands::Ls { versions: true, .. }));
+
+        let args = Args::parse_from(["obsctl", "rm", "s3://bucket/a.txt", "--version-id", "abc"]);
+        if let Commands::Rm { version_id, .. } = args.command {
+            assert_eq!(version_id.as_deref(), Some("abc"));
+        } else {
+            panic!("Expected Rm command");
+        }
+
+        assert!(Args::try_parse_from([
+            "obsctl",
+            "rm",
+            "s3://bucket/logs/",
+            "--recursive",
+            "--version-id",
+            "abc"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_config_command_parsing() {
         // Test config command with no subcommand (show all)
@@ -679,11 +1989,10 @@ mod tests {
     fn test_config_command_with_subcommands() {
         // Test config configure subcommand
         let args = Args::parse_from(["obsctl", "config", "configure", "--profile", "dev"]);
+        assert_eq!(args.profile.as_deref(), Some("dev"));
 
         if let Commands::Config { command } = args.command {
-            if let Some(ConfigCommands::Configure { profile }) = command {
-                assert_eq!(profile, "dev");
-            } else {
+            if !matches!(command, Some(ConfigCommands::Configure)) {
                 panic!("Expected Configure subcommand");
             }
         } else {
@@ -700,17 +2009,12 @@ mod tests {
             "--profile",
             "production",
         ]);
+        assert_eq!(args.profile.as_deref(), Some("production"));
 
         if let Commands::Config { command } = args.command {
-            if let Some(ConfigCommands::Set {
-                key,
-                value,
-                profile,
-            }) = command
-            {
+            if let Some(ConfigCommands::Set { key, value }) = command {
                 assert_eq!(key, "region");
                 assert_eq!(value, "us-west-2");
-                assert_eq!(profile, "production");
             } else {
                 panic!("Expected Set subcommand");
             }
@@ -758,4 +2062,21 @@ mod tests {
             panic!("Expected Config command");
         }
     }
+
+    #[test]
+    fn test_signature_version_config_values() {
+        assert_eq!(
+            SignatureVersion::from_config_value("s3"),
+            Some(SignatureVersion::V2)
+        );
+        assert_eq!(
+            SignatureVersion::from_config_value("V2"),
+            Some(SignatureVersion::V2)
+        );
+        assert_eq!(
+            SignatureVersion::from_config_value("s3v4"),
+            Some(SignatureVersion::V4)
+        );
+        assert_eq!(SignatureVersion::from_config_value("v3"), None);
+    }
 }
@@ -1,30 +1,32 @@
 use anyhow::Result;
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::types::{BucketVersioningStatus, MfaDeleteStatus, VersioningConfiguration};
 use base64::{engine::general_purpose::STANDARD as b64, Engine as _};
+use chrono::{DateTime, Utc};
 use log::info;
 use md5;
 use std::time::Instant;
 
+use crate::args::VersioningAction;
+use crate::commands::du::format_size_human_readable;
+use crate::commands::partial::PartialFailures;
+use crate::commands::s3_uri::S3Uri;
 use crate::config::Config;
+use crate::filtering::parse_date_filter;
 use crate::utils::filter_by_enhanced_pattern;
 
+/// Maximum concurrent ListParts calls when sizing incomplete uploads
+const MULTIPART_SCAN_CONCURRENCY: usize = 8;
+
 pub async fn create_bucket(config: &Config, bucket_name: &str, region: Option<&str>) -> Result<()> {
     let start_time = Instant::now();
     info!("Creating bucket: {bucket_name}");
 
-    let mut create_request = config.client.create_bucket().bucket(bucket_name);
-
-    // Set region if provided and not us-east-1 (which doesn't need location constraint)
-    if let Some(region_name) = region {
-        if region_name != "us-east-1" {
-            let location_constraint =
-                aws_sdk_s3::types::BucketLocationConstraint::from(region_name);
-            let create_bucket_config = aws_sdk_s3::types::CreateBucketConfiguration::builder()
-                .location_constraint(location_constraint)
-                .build();
-
-            create_request = create_request.create_bucket_configuration(create_bucket_config);
-        }
-    }
+    let create_request = config
+        .client
+        .create_bucket()
+        .bucket(bucket_name)
+        .set_create_bucket_configuration(create_bucket_configuration(region));
 
     match create_request.send().await {
         Ok(_) => {
@@ -64,6 +66,20 @@ pub async fn create_bucket(config: &Config, bucket_name: &str, region: Option<&s
     }
 }
 
+/// Outside us-east-1 S3 rejects a CreateBucket without a LocationConstraint
+/// naming the region (IllegalLocationConstraintException), while us-east-1
+/// itself must have the constraint left out
+fn create_bucket_configuration(
+    region: Option<&str>,
+) -> Option<aws_sdk_s3::types::CreateBucketConfiguration> {
+    let region = region.filter(|region| !region.is_empty() && *region != "us-east-1")?;
+    Some(
+        aws_sdk_s3::types::CreateBucketConfiguration::builder()
+            .location_constraint(aws_sdk_s3::types::BucketLocationConstraint::from(region))
+            .build(),
+    )
+}
+
 pub async fn delete_bucket(config: &Config, bucket_name: &str, force: bool) -> Result<()> {
     let start_time = Instant::now();
     info!("Deleting bucket: {bucket_name}");
@@ -133,6 +149,7 @@ async fn delete_all_objects(config: &Config, bucket_name: &str) -> Result<()> {
 
     let mut continuation_token: Option<String> = None;
     let mut deleted_count = 0;
+    let mut failures = PartialFailures::new("delete_objects");
 
     let result: anyhow::Result<()> = async {
         loop {
@@ -164,6 +181,7 @@ async fn delete_all_objects(config: &Config, bucket_name: &str) -> Result<()> {
 
                 // Perform batch deletion if we have objects to delete
                 if !objects_to_delete.is_empty() {
+                    failures.add_attempted(objects_to_delete.len());
                     let delete_request = aws_sdk_s3::types::Delete::builder()
                         .set_objects(Some(objects_to_delete))
                         .build()
@@ -171,7 +189,7 @@ async fn delete_all_objects(config: &Config, bucket_name: &str) -> Result<()> {
 
                     // For MinIO compatibility, compute and add Content-MD5 header
                     // MinIO requires this header for batch deletion operations
-                    config
+                    let output = config
                         .client
                         .delete_objects()
                         .bucket(bucket_name)
@@ -196,6 +214,8 @@ async fn delete_all_objects(config: &Config, bucket_name: &str) -> Result<()> {
                         })
                         .send()
                         .await?;
+                    failures.record_delete_errors(bucket_name, output.errors());
+                    deleted_count -= output.errors().len() as u64;
                 }
             }
 
@@ -232,7 +252,7 @@ async fn delete_all_objects(config: &Config, bucket_name: &str) -> Result<()> {
             }
 
             info!("Successfully deleted {deleted_count} objects");
-            Ok(())
+            failures.into_result()
         }
         Err(e) => {
             let error_msg = format!("Failed to delete objects in bucket {bucket_name}: {e}");
@@ -254,6 +274,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
 
     let mut key_marker: Option<String> = None;
     let mut version_id_marker: Option<String> = None;
+    let mut failures = PartialFailures::new("delete_objects");
 
     loop {
         let mut list_request = config.client.list_object_versions().bucket(bucket_name);
@@ -306,6 +327,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
 
         // Perform batch deletion if we have objects to delete
         if !objects_to_delete.is_empty() {
+            failures.add_attempted(objects_to_delete.len());
             let delete_request = aws_sdk_s3::types::Delete::builder()
                 .set_objects(Some(objects_to_delete))
                 .build()
@@ -313,7 +335,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
 
             // For MinIO compatibility, compute and add Content-MD5 header
             // MinIO requires this header for batch deletion operations
-            config
+            let output = config
                 .client
                 .delete_objects()
                 .bucket(bucket_name)
@@ -338,6 +360,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
                 })
                 .send()
                 .await?;
+            failures.record_delete_errors(bucket_name, output.errors());
         }
 
         // Check if there are more versions to delete
@@ -349,7 +372,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
         }
     }
 
-    Ok(())
+    failures.into_result()
 }
 
 pub async fn delete_all_buckets(config: &Config, force: bool, confirm: bool) -> Result<()> {
@@ -405,6 +428,7 @@ pub async fn delete_all_buckets(config: &Config, force: bool, confirm: bool) ->
 pub async fn delete_buckets_by_pattern(
     config: &Config,
     pattern: &str,
+    ignore_case: bool,
     force: bool,
     confirm: bool,
 ) -> Result<()> {
@@ -429,7 +453,8 @@ pub async fn delete_buckets_by_pattern(
         .collect();
 
     // Filter by pattern
-    let matching_bucket_names = filter_by_enhanced_pattern(&all_bucket_names, pattern, false)?;
+    let matching_bucket_names =
+        filter_by_enhanced_pattern(&all_bucket_names, pattern, false, ignore_case)?;
 
     if matching_bucket_names.is_empty() {
         println!("No buckets match the pattern '{pattern}'");
@@ -484,6 +509,279 @@ pub async fn delete_buckets_by_pattern(
     Ok(())
 }
 
+/// An in-progress multipart upload found by `bucket clean-multipart`
+#[derive(Debug, Clone)]
+struct IncompleteUpload {
+    key: String,
+    upload_id: String,
+    initiated: Option<DateTime<Utc>>,
+}
+
+/// Enable or suspend versioning on a bucket, or print whether it is
+/// Enabled, Suspended or Disabled (never enabled)
+pub async fn versioning(config: &Config, s3_uri: &str, action: VersioningAction) -> Result<()> {
+    let start_time = Instant::now();
+    let uri = S3Uri::parse(s3_uri)?;
+    let bucket = &uri.bucket;
+
+    let status = match action {
+        VersioningAction::Enable => Some(BucketVersioningStatus::Enabled),
+        VersioningAction::Suspend => Some(BucketVersioningStatus::Suspended),
+        VersioningAction::Status => None,
+    };
+
+    match status {
+        None => {
+            let response = config
+                .client
+                .get_bucket_versioning()
+                .bucket(bucket)
+                .send()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to get versioning for s3://{bucket}: {e}"))?;
+            println!("{}", versioning_status(response.status()));
+            if response.mfa_delete() == Some(&MfaDeleteStatus::Enabled) {
+                println!("MFA delete: Enabled");
+            }
+        }
+        Some(status) => {
+            info!("Setting versioning on s3://{bucket} to {}", status.as_str());
+            config
+                .client
+                .put_bucket_versioning()
+                .bucket(bucket)
+                .versioning_configuration(
+                    VersioningConfiguration::builder()
+                        .status(status.clone())
+                        .build(),
+                )
+                .send()
+                .await
+                .map_err(|e| versioning_error(bucket, e.code(), e.message(), &e.to_string()))?;
+            println!("versioning: s3://{bucket} {}", status.as_str());
+        }
+    }
+
+    {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS
+            .operations_total
+            .add(1, &[KeyValue::new("operation", "bucket_versioning")]);
+        OTEL_INSTRUMENTS.operation_duration.record(
+            start_time.elapsed().as_millis() as f64 / 1000.0,
+            &[KeyValue::new("operation", "bucket_versioning")],
+        );
+    }
+
+    Ok(())
+}
+
+/// GetBucketVersioning leaves the status out for buckets that were never versioned
+fn versioning_status(status: Option<&BucketVersioningStatus>) -> &'static str {
+    match status {
+        Some(BucketVersioningStatus::Enabled) => "Enabled",
+        Some(BucketVersioningStatus::Suspended) => "Suspended",
+        _ => "Disabled",
+    }
+}
+
+/// Buckets with MFA delete enabled only accept versioning changes signed by
+/// the root account with an MFA token, which obsctl can't send, so that
+/// rejection is spelled out instead of passed on as a bare AccessDenied
+fn versioning_error(
+    bucket: &str,
+    code: Option<&str>,
+    message: Option<&str>,
+    fallback: &str,
+) -> anyhow::Error {
+    let detail = match (code, message) {
+        (Some(code), Some(message)) => format!("{code}: {message}"),
+        (Some(code), None) => code.to_string(),
+        _ => fallback.to_string(),
+    };
+    if message.is_some_and(|message| message.to_lowercase().contains("mfa")) {
+        anyhow::anyhow!(
+            "Failed to change versioning on s3://{bucket}: {detail}. The bucket has MFA delete \
+             enabled, so versioning can only be changed by the root account with an MFA \
+             device (aws s3api put-bucket-versioning --mfa)"
+        )
+    } else {
+        anyhow::anyhow!("Failed to change versioning on s3://{bucket}: {detail}")
+    }
+}
+
+/// Whether an upload started before the `--older-than` cutoff. Uploads with no
+/// initiation time are kept when a cutoff is given, since their age is unknown.
+fn initiated_before(initiated: Option<DateTime<Utc>>, cutoff: Option<DateTime<Utc>>) -> bool {
+    match (cutoff, initiated) {
+        (None, _) => true,
+        (Some(cutoff), Some(initiated)) => initiated < cutoff,
+        (Some(_), None) => false,
+    }
+}
+
+/// Abort incomplete multipart uploads in a bucket (optionally under a prefix),
+/// reporting how much stored part data is reclaimed
+pub async fn clean_multipart(
+    config: &Config,
+    s3_uri: &str,
+    older_than: Option<&str>,
+    dryrun: bool,
+) -> Result<()> {
+    use futures::stream::{self, StreamExt};
+
+    let start_time = Instant::now();
+    let uri = S3Uri::parse(s3_uri)?;
+    let cutoff = older_than
+        .map(|value| parse_date_filter(value, chrono::Utc::now()))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid --older-than value: {e}"))?;
+
+    info!("Listing incomplete multipart uploads in {s3_uri}");
+
+    let mut uploads = Vec::new();
+    let mut key_marker: Option<String> = None;
+    let mut upload_id_marker: Option<String> = None;
+
+    loop {
+        let mut request = config.client.list_multipart_uploads().bucket(&uri.bucket);
+        if !uri.key_or_empty().is_empty() {
+            request = request.prefix(uri.key_or_empty());
+        }
+        if let Some(marker) = &key_marker {
+            request = request.key_marker(marker);
+        }
+        if let Some(marker) = &upload_id_marker {
+            request = request.upload_id_marker(marker);
+        }
+
+        let response = request.send().await?;
+
+        for upload in response.uploads() {
+            if let (Some(key), Some(upload_id)) = (upload.key(), upload.upload_id()) {
+                let initiated = upload
+                    .initiated()
+                    .and_then(|dt| DateTime::from_timestamp(dt.secs(), dt.subsec_nanos()));
+                if initiated_before(initiated, cutoff) {
+                    uploads.push(IncompleteUpload {
+                        key: key.to_string(),
+                        upload_id: upload_id.to_string(),
+                        initiated,
+                    });
+                }
+            }
+        }
+
+        if response.is_truncated().unwrap_or(false) {
+            key_marker = response.next_key_marker().map(|m| m.to_string());
+            upload_id_marker = response.next_upload_id_marker().map(|m| m.to_string());
+        } else {
+            break;
+        }
+    }
+
+    // Sum uploaded part sizes to estimate the storage each abort reclaims
+    let part_bytes: Vec<i64> = stream::iter(uploads.iter())
+        .map(|upload| uploaded_part_bytes(config, &uri.bucket, upload))
+        .buffered(MULTIPART_SCAN_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut aborted = 0u64;
+    let mut reclaimed_bytes = 0i64;
+
+    for (upload, bytes) in uploads.iter().zip(part_bytes) {
+        let initiated = upload
+            .initiated
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let target = format!(
+            "s3://{}/{} (upload {}, initiated {initiated}, {bytes} bytes)",
+            uri.bucket, upload.key, upload.upload_id
+        );
+
+        if dryrun {
+            println!("(dryrun) abort: {target}");
+        } else {
+            config
+                .client
+                .abort_multipart_upload()
+                .bucket(&uri.bucket)
+                .key(&upload.key)
+                .upload_id(&upload.upload_id)
+                .send()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to abort upload for {}: {e}", upload.key))?;
+            println!("abort: {target}");
+        }
+        aborted += 1;
+        reclaimed_bytes += bytes;
+    }
+
+    let verb = if dryrun { "Would abort" } else { "Aborted" };
+    println!(
+        "{verb} {aborted} incomplete multipart uploads, reclaiming ~{} of stored parts",
+        format_size_human_readable(reclaimed_bytes)
+    );
+
+    if !dryrun {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS
+            .operations_total
+            .add(1, &[KeyValue::new("operation", "clean_multipart")]);
+
+        let duration_seconds = start_time.elapsed().as_millis() as f64 / 1000.0;
+        OTEL_INSTRUMENTS.operation_duration.record(
+            duration_seconds,
+            &[KeyValue::new("operation", "clean_multipart")],
+        );
+    }
+
+    Ok(())
+}
+
+/// Total size of the parts uploaded so far; 0 when the parts can't be listed
+async fn uploaded_part_bytes(config: &Config, bucket: &str, upload: &IncompleteUpload) -> i64 {
+    let mut total = 0;
+    let mut part_marker: Option<String> = None;
+
+    loop {
+        let mut request = config
+            .client
+            .list_parts()
+            .bucket(bucket)
+            .key(&upload.key)
+            .upload_id(&upload.upload_id);
+        if let Some(marker) = &part_marker {
+            request = request.part_number_marker(marker);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                log::debug!("Failed to list parts for {}: {e}", upload.key);
+                return total;
+            }
+        };
+
+        total += response
+            .parts()
+            .iter()
+            .map(|part| part.size().unwrap_or(0))
+            .sum::<i64>();
+
+        if response.is_truncated().unwrap_or(false) {
+            part_marker = response.next_part_number_marker().map(|m| m.to_string());
+        } else {
+            return total;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -505,7 +803,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -613,4 +920,88 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_create_bucket_configuration() {
+        assert!(create_bucket_configuration(Some("us-east-1")).is_none());
+        assert!(create_bucket_configuration(None).is_none());
+
+        let configuration = create_bucket_configuration(Some("eu-west-1")).unwrap();
+        assert_eq!(
+            configuration.location_constraint(),
+            Some(&aws_sdk_s3::types::BucketLocationConstraint::EuWest1)
+        );
+
+        // Regions the SDK doesn't know yet are passed through as-is
+        let configuration = create_bucket_configuration(Some("ru-moscow-1")).unwrap();
+        assert_eq!(
+            configuration
+                .location_constraint()
+                .map(|constraint| constraint.as_str()),
+            Some("ru-moscow-1")
+        );
+    }
+
+    #[test]
+    fn test_versioning_status() {
+        assert_eq!(
+            versioning_status(Some(&BucketVersioningStatus::Enabled)),
+            "Enabled"
+        );
+        assert_eq!(
+            versioning_status(Some(&BucketVersioningStatus::Suspended)),
+            "Suspended"
+        );
+        assert_eq!(versioning_status(None), "Disabled");
+    }
+
+    #[test]
+    fn test_versioning_error_explains_mfa_delete() {
+        let err = versioning_error(
+            "bucket",
+            Some("AccessDenied"),
+            Some("Mfa Authentication must be used for this request"),
+            "service error",
+        );
+        assert!(err.to_string().contains("MFA delete"));
+
+        let err = versioning_error(
+            "bucket",
+            Some("NoSuchBucket"),
+            Some("The specified bucket does not exist"),
+            "service error",
+        );
+        assert_eq!(
+            err.to_string(),
+            "Failed to change versioning on s3://bucket: NoSuchBucket: The specified bucket does not exist"
+        );
+
+        let err = versioning_error("bucket", None, None, "dispatch failure");
+        assert!(err.to_string().ends_with("dispatch failure"));
+    }
+
+    #[test]
+    fn test_initiated_before_cutoff() {
+        let cutoff = Utc::now() - chrono::Duration::days(7);
+        let old = Some(Utc::now() - chrono::Duration::days(30));
+        let recent = Some(Utc::now() - chrono::Duration::days(1));
+
+        assert!(initiated_before(old, Some(cutoff)));
+        assert!(!initiated_before(recent, Some(cutoff)));
+        // Unknown age is only cleaned when no cutoff was requested
+        assert!(!initiated_before(None, Some(cutoff)));
+        assert!(initiated_before(None, None));
+        assert!(initiated_before(recent, None));
+    }
+
+    #[tokio::test]
+    async fn test_clean_multipart_rejects_bad_older_than() {
+        let config = create_mock_config();
+
+        let result = clean_multipart(&config, "s3://bucket", Some("soon"), true).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid --older-than"));
+    }
 }
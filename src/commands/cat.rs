@@ -0,0 +1,285 @@
+use anyhow::Result;
+use log::{debug, info};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::commands::decompress::{gzip_reader, is_gzip_object};
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::config::Config;
+
+pub async fn execute(
+    config: &Config,
+    s3_uri: &str,
+    range: Option<&str>,
+    follow: bool,
+    interval: u64,
+    decompress: bool,
+) -> Result<()> {
+    let start_time = Instant::now();
+
+    if !is_s3_uri(s3_uri) {
+        return Err(anyhow::anyhow!(
+            "cat command only works with S3 URIs (s3://...)"
+        ));
+    }
+
+    let uri = S3Uri::parse(s3_uri)?;
+
+    if uri.key.is_none() || uri.key_or_empty().is_empty() {
+        return Err(anyhow::anyhow!(
+            "cat requires a specific object key, not just a bucket"
+        ));
+    }
+
+    let range = range.map(parse_range).transpose()?;
+
+    info!("Streaming {s3_uri} to stdout");
+
+    let mut stdout = tokio::io::stdout();
+    let (mut offset, mut etag) =
+        write_object(config, &uri, range.as_deref(), decompress, &mut stdout).await?;
+
+    if follow {
+        follow_object(config, &uri, interval, &mut offset, &mut etag, &mut stdout).await?;
+    }
+
+    {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS
+            .operations_total
+            .add(1, &[KeyValue::new("operation", "cat")]);
+        OTEL_INSTRUMENTS.bytes_downloaded_total.add(offset, &[]);
+
+        let duration_seconds = start_time.elapsed().as_millis() as f64 / 1000.0;
+        OTEL_INSTRUMENTS
+            .operation_duration
+            .record(duration_seconds, &[KeyValue::new("operation", "cat")]);
+    }
+
+    Ok(())
+}
+
+/// Normalise `--range` to a single HTTP byte range: `bytes=0-1023`,
+/// `bytes=1024-` or `bytes=-1024` (the `bytes=` prefix may be left off)
+fn parse_range(range: &str) -> Result<String> {
+    let spec = range.trim();
+    let spec = spec.strip_prefix("bytes=").unwrap_or(spec);
+
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid --range '{range}': expected bytes=START-END, bytes=START- or bytes=-LENGTH"
+        )
+    };
+    let (start, end) = spec.split_once('-').ok_or_else(invalid)?;
+    let parse = |n: &str| (!n.is_empty()).then(|| n.parse::<u64>()).transpose();
+    match (parse(start), parse(end)) {
+        (Ok(Some(start)), Ok(Some(end))) if start <= end => {}
+        (Ok(Some(_)), Ok(None)) | (Ok(None), Ok(Some(_))) => {}
+        _ => return Err(invalid()),
+    }
+
+    Ok(format!("bytes={spec}"))
+}
+
+/// Stream an object (or one byte range of it) to `out` chunk by chunk,
+/// returning the bytes written and the object's ETag. With `decompress`,
+/// gzip objects are inflated on the way through.
+async fn write_object<W: AsyncWrite + Unpin>(
+    config: &Config,
+    uri: &S3Uri,
+    range: Option<&str>,
+    decompress: bool,
+    out: &mut W,
+) -> Result<(u64, Option<String>)> {
+    let request = config
+        .client
+        .get_object()
+        .bucket(&uri.bucket)
+        .key(uri.key_or_empty())
+        .set_range(range.map(str::to_string));
+
+    let response = request.send().await.map_err(|e| {
+        let error_msg = format!(
+            "Failed to read s3://{}/{}: {e}",
+            uri.bucket,
+            uri.key_or_empty()
+        );
+        {
+            use crate::otel::OTEL_INSTRUMENTS;
+            OTEL_INSTRUMENTS.record_error_with_type(&error_msg);
+        }
+        anyhow::anyhow!(error_msg)
+    })?;
+
+    let etag = response.e_tag().map(|tag| tag.to_string());
+    if decompress && is_gzip_object(response.content_encoding(), uri.key_or_empty()) {
+        let mut decoder = gzip_reader(response.body.into_async_read(), &uri.to_string()).await?;
+        let written = tokio::io::copy(&mut decoder, out).await?;
+        out.flush().await?;
+        return Ok((written, etag));
+    }
+
+    let mut body = response.body;
+    let mut written = 0u64;
+    while let Some(chunk) = body.try_next().await? {
+        out.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+    }
+    out.flush().await?;
+
+    Ok((written, etag))
+}
+
+/// What `--follow` should do after polling the object's current size and ETag
+#[derive(Debug, PartialEq, Eq)]
+enum FollowAction {
+    /// Nothing new yet
+    Wait,
+    /// The object grew; fetch the suffix starting at this offset
+    Append(u64),
+    /// The object shrank or was replaced in place; start over from the beginning
+    Restart,
+}
+
+fn next_follow_action(
+    offset: u64,
+    last_etag: Option<&str>,
+    size: u64,
+    etag: Option<&str>,
+) -> FollowAction {
+    if size > offset {
+        // Appends change the ETag too, so growth alone decides
+        FollowAction::Append(offset)
+    } else if size < offset || (last_etag.is_some() && etag != last_etag) {
+        FollowAction::Restart
+    } else {
+        FollowAction::Wait
+    }
+}
+
+/// Poll the object like `tail -f`, writing newly appended bytes until Ctrl-C
+async fn follow_object<W: AsyncWrite + Unpin>(
+    config: &Config,
+    uri: &S3Uri,
+    interval: u64,
+    offset: &mut u64,
+    etag: &mut Option<String>,
+    out: &mut W,
+) -> Result<()> {
+    let interval = Duration::from_secs(interval.max(1));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => {
+                debug!("Stopping --follow on Ctrl-C");
+                return Ok(());
+            }
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        let head = config
+            .client
+            .head_object()
+            .bucket(&uri.bucket)
+            .key(uri.key_or_empty())
+            .send()
+            .await?;
+        let size = head.content_length().unwrap_or(0).max(0) as u64;
+
+        match next_follow_action(*offset, etag.as_deref(), size, head.e_tag()) {
+            FollowAction::Wait => {}
+            FollowAction::Append(from) => {
+                let (written, new_etag) =
+                    write_object(config, uri, Some(&format!("bytes={from}-")), false, out).await?;
+                *offset += written;
+                *etag = new_etag;
+            }
+            FollowAction::Restart => {
+                eprintln!(
+                    "obsctl: s3://{}/{}: object was truncated or replaced, following from start",
+                    uri.bucket,
+                    uri.key_or_empty()
+                );
+                let (written, new_etag) = write_object(config, uri, None, false, out).await?;
+                *offset = written;
+                *etag = new_etag;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_client::canned_config;
+
+    #[tokio::test]
+    async fn test_execute_requires_object_key() {
+        let config = canned_config(|_, _| (404, String::new()));
+
+        let result = execute(&config, "/local/file.log", None, false, 2, false).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("only works with S3 URIs"));
+
+        let result = execute(&config, "s3://bucket", None, false, 2, false).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("requires a specific object key"));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-1023").unwrap(), "bytes=0-1023");
+        assert_eq!(parse_range("1024-").unwrap(), "bytes=1024-");
+        assert_eq!(parse_range("bytes=-500").unwrap(), "bytes=-500");
+
+        for invalid in [
+            "bytes=",
+            "bytes=-",
+            "100",
+            "bytes=10-5",
+            "bytes=a-b",
+            "0-1,5-9",
+        ] {
+            assert!(
+                parse_range(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_follow_action() {
+        // Unchanged object
+        assert_eq!(
+            next_follow_action(100, Some("\"a\""), 100, Some("\"a\"")),
+            FollowAction::Wait
+        );
+        // Appended bytes are fetched from the last offset
+        assert_eq!(
+            next_follow_action(100, Some("\"a\""), 150, Some("\"b\"")),
+            FollowAction::Append(100)
+        );
+        // Truncation or same-size replacement restarts
+        assert_eq!(
+            next_follow_action(100, Some("\"a\""), 40, Some("\"b\"")),
+            FollowAction::Restart
+        );
+        assert_eq!(
+            next_follow_action(100, Some("\"a\""), 100, Some("\"b\"")),
+            FollowAction::Restart
+        );
+        // Without an initial ETag only size changes matter
+        assert_eq!(
+            next_follow_action(100, None, 100, Some("\"b\"")),
+            FollowAction::Wait
+        );
+    }
+}
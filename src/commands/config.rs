@@ -2,24 +2,23 @@ use anyhow::Result;
 use base64::{engine::general_purpose, Engine as _};
 use colored::Colorize;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::PathBuf;
 
 use crate::args::{ConfigCommands, DashboardCommands};
 
-/// Execute config command based on subcommand
-pub async fn execute(command: Option<ConfigCommands>) -> Result<()> {
+/// Execute config command based on subcommand; `profile` is the active
+/// profile from `--profile` or `AWS_PROFILE`
+pub async fn execute(command: Option<ConfigCommands>, profile: &str) -> Result<()> {
     match command {
-        Some(ConfigCommands::Configure { profile }) => configure_interactive(&profile).await,
-        Some(ConfigCommands::Set {
-            key,
-            value,
-            profile,
-        }) => set_config_value(&key, &value, &profile).await,
-        Some(ConfigCommands::Get { key, profile }) => get_config_value(&key, &profile).await,
-        Some(ConfigCommands::List { profile, files }) => list_config(&profile, files).await,
+        Some(ConfigCommands::Configure) => configure_interactive(profile).await,
+        Some(ConfigCommands::Set { key, value }) => set_config_value(&key, &value, profile).await,
+        Some(ConfigCommands::Get { key }) => get_config_value(&key, profile).await,
+        Some(ConfigCommands::Unset { key }) => unset_config_value(&key, profile).await,
+        Some(ConfigCommands::List { files }) => list_config(profile, files).await,
+        Some(ConfigCommands::ListProfiles) => list_profiles(profile).await,
         Some(ConfigCommands::Dashboard { command }) => execute_dashboard_command(command).await,
         Some(ConfigCommands::Example) => show_config_file_example().await,
         Some(ConfigCommands::Env) => show_environment_variables().await,
@@ -134,6 +133,10 @@ async fn configure_interactive(profile: &str) -> Result<()> {
 async fn set_config_value(key: &str, value: &str, profile: &str) -> Result<()> {
     let profile_name = profile;
 
+    if key == "endpoint_url" {
+        crate::config::validate_endpoint_url(value)?;
+    }
+
     // Determine if this is a credential or config value
     if is_credential_key(key) {
         set_credential_value(key, value, profile_name).await?;
@@ -187,6 +190,39 @@ async fn get_config_value(key: &str, profile: &str) -> Result<()> {
     Ok(())
 }
 
+/// Remove a configuration value
+async fn unset_config_value(key: &str, profile: &str) -> Result<()> {
+    let (path, is_config, kind) = if is_credential_key(key) {
+        (get_credentials_file_path()?, false, "credential")
+    } else {
+        (get_config_file_path()?, true, "config")
+    };
+
+    let mut sections = load_ini_file(&path)?;
+    if remove_ini_key(&mut sections, profile, key) {
+        save_ini_file(&path, &sections, is_config)?;
+        println!("{} {}", format!("✅ Unset {kind}:").green(), key.cyan());
+    } else {
+        println!(
+            "{}",
+            format!("Key '{key}' not found in profile '{profile}'").yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Drop `key` from a profile section; false if it wasn't set
+fn remove_ini_key(
+    sections: &mut HashMap<String, HashMap<String, String>>,
+    profile: &str,
+    key: &str,
+) -> bool {
+    sections
+        .get_mut(profile)
+        .is_some_and(|section| section.remove(key).is_some())
+}
+
 /// List all configuration values
 async fn list_config(profile: &str, show_files: bool) -> Result<()> {
     let profile_name = profile;
@@ -251,6 +287,76 @@ async fn list_config(profile: &str, show_files: bool) -> Result<()> {
     Ok(())
 }
 
+/// Where a profile is defined
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ProfileSources {
+    config: bool,
+    credentials: bool,
+}
+
+/// Union of the profile sections in the config and credentials files, sorted
+/// by name. Non-profile sections (`[endpoints]`, `[sso-session ...]`,
+/// `[services ...]`) are left out.
+fn collect_profiles(
+    config: &HashMap<String, HashMap<String, String>>,
+    credentials: &HashMap<String, HashMap<String, String>>,
+) -> BTreeMap<String, ProfileSources> {
+    let is_profile = |name: &&String| {
+        name.as_str() != "endpoints"
+            && !name.starts_with("sso-session ")
+            && !name.starts_with("services ")
+    };
+
+    let mut profiles: BTreeMap<String, ProfileSources> = BTreeMap::new();
+    for name in config.keys().filter(is_profile) {
+        profiles.entry(name.clone()).or_default().config = true;
+    }
+    for name in credentials.keys().filter(is_profile) {
+        profiles.entry(name.clone()).or_default().credentials = true;
+    }
+    profiles
+}
+
+/// List known profiles, marking the active one (`--profile` or `AWS_PROFILE`)
+async fn list_profiles(active: &str) -> Result<()> {
+    let config = load_ini_file(&get_config_file_path()?)?;
+    let credentials = load_ini_file(&get_credentials_file_path()?)?;
+    let profiles = collect_profiles(&config, &credentials);
+
+    if profiles.is_empty() {
+        println!("{}", "No profiles found".yellow());
+        println!(
+            "Run {} to set up configuration",
+            "obsctl config configure".cyan()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Profiles:".bold().blue());
+    for (name, sources) in &profiles {
+        let kind = if sources.credentials {
+            "credentials".green()
+        } else {
+            "config only".dimmed()
+        };
+        if name == active {
+            println!("* {} ({kind})", name.cyan().bold());
+        } else {
+            println!("  {name} ({kind})");
+        }
+    }
+
+    if !profiles.contains_key(active) {
+        println!();
+        println!(
+            "{}",
+            format!("Active profile '{active}' is not defined in either file").yellow()
+        );
+    }
+
+    Ok(())
+}
+
 /// Helper functions for file management
 fn get_aws_dir() -> Result<PathBuf> {
     let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"))?;
@@ -262,6 +368,9 @@ fn get_config_file_path() -> Result<PathBuf> {
 }
 
 fn get_credentials_file_path() -> Result<PathBuf> {
+    if let Ok(path) = std::env::var("AWS_SHARED_CREDENTIALS_FILE") {
+        return Ok(PathBuf::from(path));
+    }
     Ok(get_aws_dir()?.join("credentials"))
 }
 
@@ -397,10 +506,9 @@ fn prompt_for_value(prompt: &str, current: Option<&String>, hide_input: bool) ->
     io::stdout().flush()?;
 
     let mut input = String::new();
-    if hide_input {
-        // For secrets, we'll still use regular input for simplicity
-        // In a production tool, you'd want to use a crate like `rpassword`
-        io::stdin().read_line(&mut input)?;
+    if hide_input && io::stdin().is_terminal() {
+        // Read secrets without echo; piped setup scripts fall through to plain input
+        input = rpassword::read_password()?;
     } else {
         io::stdin().read_line(&mut input)?;
     }
@@ -433,7 +541,15 @@ async fn show_all_config_help() -> Result<()> {
         "  {} - Get configuration value",
         "obsctl config get <key>".cyan()
     );
+    println!(
+        "  {} - Remove configuration value",
+        "obsctl config unset <key>".cyan()
+    );
     println!("  {} - List all configuration", "obsctl config list".cyan());
+    println!(
+        "  {} - List available profiles",
+        "obsctl config list-profiles".cyan()
+    );
     println!();
 
     println!("{}", "Dashboard Commands:".bold());
@@ -1334,3 +1450,55 @@ async fn show_system_info() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections(names: &[&str]) -> HashMap<String, HashMap<String, String>> {
+        names
+            .iter()
+            .map(|name| (name.to_string(), HashMap::new()))
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_profiles() {
+        let config = sections(&["default", "staging", "endpoints", "sso-session corp"]);
+        let credentials = sections(&["default", "ci"]);
+
+        let profiles = collect_profiles(&config, &credentials);
+        let names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+        assert_eq!(names, ["ci", "default", "staging"]);
+
+        assert_eq!(
+            profiles["default"],
+            ProfileSources {
+                config: true,
+                credentials: true
+            }
+        );
+        assert_eq!(
+            profiles["staging"],
+            ProfileSources {
+                config: true,
+                credentials: false
+            }
+        );
+        assert!(profiles["ci"].credentials && !profiles["ci"].config);
+    }
+
+    #[test]
+    fn test_remove_ini_key() {
+        let mut sections = sections(&["default"]);
+        sections
+            .get_mut("default")
+            .unwrap()
+            .insert("region".to_string(), "us-west-2".to_string());
+
+        assert!(!remove_ini_key(&mut sections, "default", "endpoint_url"));
+        assert!(!remove_ini_key(&mut sections, "dev", "region"));
+        assert!(remove_ini_key(&mut sections, "default", "region"));
+        assert!(sections["default"].is_empty());
+    }
+}
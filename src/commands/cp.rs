@@ -1,13 +1,110 @@
 use anyhow::Result;
 use aws_sdk_s3::primitives::ByteStream;
-use log::info;
-use std::path::Path;
+use aws_sdk_s3::types::{
+    ChecksumAlgorithm, CompletedMultipartUpload, CompletedPart, MetadataDirective, StorageClass,
+    TaggingDirective,
+};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 
+use crate::commands::decompress::{gzip_reader, is_gzip_object};
+use crate::commands::manifest::{CompletionManifest, ManifestVerifier};
+use crate::commands::partial::PartialFailures;
 use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::commands::staging;
+use crate::commands::sync;
+use crate::commands::upload::{self, MultipartSettings};
 use crate::config::Config;
+use crate::events::TransferKind;
+use crate::progress::{FileBars, ProgressDisplay, TransferProgress};
+use crate::utils::{detect_mime_type, glob_path_match, has_wildcards, wildcard_match};
+
+/// Source value that reads upload content from stdin
+pub const STDIN_SOURCE: &str = "-";
+
+/// Part size used when streaming input of unknown length (S3 minimum is 5 MiB)
+const STREAM_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Largest object a single CopyObject request can copy
+pub const MAX_COPY_OBJECT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Smallest part used for UploadPartCopy; grown for objects that would
+/// otherwise need more than `MAX_UPLOAD_PARTS` parts
+const COPY_PART_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Most parts S3 accepts in one multipart upload
+const MAX_UPLOAD_PARTS: u64 = 10_000;
+
+/// Maximum concurrent UploadPartCopy requests per object
+const COPY_PART_CONCURRENCY: usize = 8;
+
+/// Per-transfer settings shared by the upload and download paths
+#[derive(Debug, Clone, Default)]
+pub struct TransferOptions {
+    /// Explicit Content-Type for uploaded objects
+    pub content_type: Option<String>,
+    /// Inflate gzip-encoded objects while downloading
+    pub decompress: bool,
+    /// Progress display for recursive transfers
+    pub progress: ProgressDisplay,
+    /// Per-file byte bars, drawn under the aggregate bar of a batch
+    pub file_bars: FileBars,
+    /// Directory for staging downloads instead of beside the target (`--temp-dir`)
+    pub temp_dir: Option<PathBuf>,
+    /// Check each transferred local file against a manifest (`--checksum-manifest`)
+    pub checksum_manifest: Option<Arc<ManifestVerifier>>,
+    /// Completion marker written after a successful recursive upload (`--write-manifest`)
+    pub write_manifest: Option<Arc<CompletionManifest>>,
+    /// User metadata (`x-amz-meta-*`) for uploaded objects
+    pub metadata: HashMap<String, String>,
+    /// URL-encoded tag set (`k1=v1&k2=v2`) for uploaded objects (`--tag`)
+    pub tagging: Option<String>,
+    /// Size at which single-file uploads switch to multipart, and the part size
+    pub multipart: MultipartSettings,
+    /// Storage class for uploaded and copied objects (`--storage-class`)
+    pub storage_class: Option<StorageClass>,
+    /// Local directories pruned from recursive and glob uploads (`--exclude-dir`)
+    pub exclude_dirs: Vec<String>,
+    /// Additional checksum for uploaded objects and parts (`--checksum-algorithm`)
+    pub checksum_algorithm: Option<ChecksumAlgorithm>,
+    /// Summary lines only, no per-file output (`--quiet`)
+    pub quiet: bool,
+}
+
+impl TransferOptions {
+    /// Content-Type for an upload: `--content-type` when given, otherwise
+    /// detected from the file extension so objects aren't stored as
+    /// `binary/octet-stream`
+    pub fn content_type_for(&self, path: &str) -> Option<String> {
+        self.content_type
+            .clone()
+            .or_else(|| detect_mime_type(path).map(str::to_string))
+    }
+}
+
+/// What a glob upload would do: one line per file, or under `quiet` only
+/// how many files would be uploaded
+fn glob_dryrun_lines(files: &[(PathBuf, String)], dest_uri: &S3Uri, quiet: bool) -> Vec<String> {
+    if quiet {
+        return vec![format!("(dryrun) would upload {} file(s)", files.len())];
+    }
+    files
+        .iter()
+        .map(|(path, relative)| {
+            format!(
+                "(dryrun) upload: {} to s3://{}/{}",
+                path.display(),
+                dest_uri.bucket,
+                object_key(dest_uri, relative)
+            )
+        })
+        .collect()
+}
 
 #[allow(clippy::too_many_arguments)]
 pub async fn execute(
@@ -20,19 +117,49 @@ pub async fn execute(
     force: bool,
     include: Option<&str>,
     exclude: Option<&str>,
+    options: &TransferOptions,
 ) -> Result<()> {
     let start_time = Instant::now();
     info!("Copying from {source} to {dest}");
 
+    let source_is_s3 = is_s3_uri(source);
+    let dest_is_s3 = is_s3_uri(dest);
+    let glob_source = !source_is_s3 && dest_is_s3 && is_local_glob(source);
+
     if dryrun {
-        info!("[DRY RUN] Would copy from {source} to {dest}");
+        if glob_source {
+            let dest_uri = S3Uri::parse(dest)?;
+            let files = expand_local_glob(source, &options.exclude_dirs)?;
+            for line in glob_dryrun_lines(&files, &dest_uri, options.quiet) {
+                println!("{line}");
+            }
+        } else {
+            info!("[DRY RUN] Would copy from {source} to {dest}");
+        }
         return Ok(());
     }
 
-    let source_is_s3 = is_s3_uri(source);
-    let dest_is_s3 = is_s3_uri(dest);
+    // A single file gets its own byte bar; batches set theirs up below
+    let single_file_options;
+    let options = if !recursive && !glob_source && !options.file_bars.is_enabled() {
+        single_file_options = TransferOptions {
+            file_bars: FileBars::new(options.progress),
+            ..options.clone()
+        };
+        &single_file_options
+    } else {
+        options
+    };
 
     let result = match (source_is_s3, dest_is_s3) {
+        (false, true) if source == STDIN_SOURCE => {
+            // Stream stdin to a single S3 object
+            upload_stdin_to_s3(config, dest, recursive, options).await
+        }
+        (false, true) if glob_source => {
+            // Every local file matching a pattern such as **/*.log
+            upload_glob_to_s3(config, source, dest, options).await
+        }
         (false, true) => {
             // Local to S3 upload
             upload_to_s3(
@@ -44,6 +171,7 @@ pub async fn execute(
                 force,
                 include,
                 exclude,
+                options,
             )
             .await
         }
@@ -58,6 +186,7 @@ pub async fn execute(
                 force,
                 include,
                 exclude,
+                options,
             )
             .await
         }
@@ -72,6 +201,7 @@ pub async fn execute(
                 force,
                 include,
                 exclude,
+                options,
             )
             .await
         }
@@ -83,6 +213,18 @@ pub async fn execute(
         }
     };
 
+    // Report the manifest check once every file has been transferred
+    let result = match (&result, &options.checksum_manifest) {
+        (Ok(()), Some(verifier)) => verifier.finish(),
+        _ => result,
+    };
+
+    // The completion marker must only appear once everything else has succeeded
+    let result = match (result, &options.write_manifest) {
+        (Ok(()), Some(manifest)) => manifest.upload(config).await,
+        (result, _) => result,
+    };
+
     let duration = start_time.elapsed();
 
     // Record overall cp operation metrics using proper OTEL SDK
@@ -126,15 +268,21 @@ async fn upload_to_s3(
     _force: bool,
     _include: Option<&str>,
     _exclude: Option<&str>,
+    options: &TransferOptions,
 ) -> Result<()> {
     let dest_uri = S3Uri::parse(dest)?;
 
     if recursive {
         info!("Recursive upload from {source} to {dest}");
-        upload_directory_to_s3(config, source, &dest_uri).await
+        upload_directory_to_s3(config, source, &dest_uri, options).await
     } else {
         info!("Single file upload from {source} to {dest}");
-        upload_file_to_s3(config, source, &dest_uri).await
+        upload_file_to_s3(config, source, &dest_uri, options).await?;
+        if let Some(verifier) = &options.checksum_manifest {
+            let key = verifier.manifest().single_file_key(source);
+            verifier.verify_file(&key, Path::new(source)).await?;
+        }
+        Ok(())
     }
 }
 
@@ -148,15 +296,36 @@ async fn download_from_s3(
     _force: bool,
     _include: Option<&str>,
     _exclude: Option<&str>,
+    options: &TransferOptions,
 ) -> Result<()> {
     let source_uri = S3Uri::parse(source)?;
 
+    if let Some(temp_dir) = &options.temp_dir {
+        let target_dir = if recursive {
+            Path::new(dest)
+        } else {
+            Path::new(dest).parent().unwrap_or(Path::new("."))
+        };
+        if staging::same_filesystem(temp_dir, target_dir) == Some(false) {
+            warn!(
+                "--temp-dir {} is on a different filesystem than {}; downloads will be copied into place instead of renamed atomically",
+                temp_dir.display(),
+                target_dir.display()
+            );
+        }
+    }
+
     if recursive {
         info!("Recursive download from {source} to {dest}");
-        download_directory_from_s3(config, &source_uri, dest).await
+        download_directory_from_s3(config, &source_uri, dest, options).await
     } else {
         info!("Single file download from {source} to {dest}");
-        download_file_from_s3(config, &source_uri, dest).await
+        download_file_from_s3(config, &source_uri, dest, options).await?;
+        if let Some(verifier) = &options.checksum_manifest {
+            let key = verifier.manifest().single_file_key(dest);
+            verifier.verify_file(&key, Path::new(dest)).await?;
+        }
+        Ok(())
     }
 }
 
@@ -170,77 +339,311 @@ async fn copy_s3_to_s3(
     _force: bool,
     _include: Option<&str>,
     _exclude: Option<&str>,
+    options: &TransferOptions,
 ) -> Result<()> {
     let source_uri = S3Uri::parse(source)?;
     let dest_uri = S3Uri::parse(dest)?;
 
     info!("S3 to S3 copy from {source} to {dest}");
 
-    let copy_source = format!("{}/{}", source_uri.bucket, source_uri.key_or_empty());
-
-    config
-        .client
-        .copy_object()
-        .copy_source(&copy_source)
-        .bucket(&dest_uri.bucket)
-        .key(dest_uri.key_or_empty())
-        .send()
-        .await?;
+    copy_object(config, &source_uri, &dest_uri, options).await?;
 
     info!("Successfully copied {source} to {dest}");
     Ok(())
 }
 
-async fn upload_file_to_s3(config: &Config, local_path: &str, s3_uri: &S3Uri) -> Result<()> {
+/// Server-side copy of a single object. Objects over the 5 GiB CopyObject
+/// limit are copied part by part with UploadPartCopy. With `--metadata` the
+/// copy gets that user metadata instead of the source's.
+pub async fn copy_object(
+    config: &Config,
+    source: &S3Uri,
+    dest: &S3Uri,
+    options: &TransferOptions,
+) -> Result<()> {
     let start_time = Instant::now();
-    let path = Path::new(local_path);
-
-    if !path.exists() {
-        // Record error using proper OTEL SDK
-        {
-            use crate::otel::OTEL_INSTRUMENTS;
+    let copy_source = copy_source(&source.bucket, source.key_or_empty());
 
-            OTEL_INSTRUMENTS.record_error_with_type("Local file does not exist");
+    let head_request = config
+        .client
+        .head_object()
+        .bucket(&source.bucket)
+        .key(source.key_or_empty());
+    let head = head_request.send().await?;
+    let size = head.content_length().unwrap_or(0).max(0) as u64;
+    let metadata = (!options.metadata.is_empty()).then(|| options.metadata.clone());
+
+    let method = if size > MAX_COPY_OBJECT_SIZE {
+        info!("{source} is {size} bytes, copying with UploadPartCopy");
+        multipart_copy(config, source, dest, &head, size, options).await?;
+        "upload_part_copy"
+    } else {
+        let mut request = config
+            .client
+            .copy_object()
+            .copy_source(&copy_source)
+            .bucket(&dest.bucket)
+            .key(dest.key_or_empty())
+            .set_storage_class(options.storage_class.clone());
+        if let Some(metadata) = metadata {
+            // REPLACE drops every header of the source, so the standard ones
+            // are carried over from its HeadObject response
+            request = request
+                .metadata_directive(MetadataDirective::Replace)
+                .set_metadata(Some(sync::keep_stored_checksum(metadata, &head)))
+                .set_content_type(head.content_type().map(str::to_string))
+                .set_content_encoding(head.content_encoding().map(str::to_string))
+                .set_content_disposition(head.content_disposition().map(str::to_string))
+                .set_content_language(head.content_language().map(str::to_string))
+                .set_cache_control(head.cache_control().map(str::to_string));
+        }
+        if let Some(tagging) = &options.tagging {
+            request = request
+                .tagging_directive(TaggingDirective::Replace)
+                .tagging(tagging);
         }
+        request.send().await?;
+        "copy_object"
+    };
 
-        return Err(anyhow::anyhow!("Local file does not exist: {}", local_path));
+    {
+        use crate::otel::{GLOBAL_METRICS, OTEL_INSTRUMENTS};
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS
+            .copies_total
+            .add(1, &[KeyValue::new("method", method)]);
+        OTEL_INSTRUMENTS
+            .bytes_copied_total
+            .add(size, &[KeyValue::new("method", method)]);
+        GLOBAL_METRICS
+            .record_copy(size, start_time.elapsed().as_millis() as u64)
+            .await;
     }
 
-    if !path.is_file() {
-        // Record error using proper OTEL SDK
-        {
-            use crate::otel::OTEL_INSTRUMENTS;
+    Ok(())
+}
 
-            OTEL_INSTRUMENTS.record_error_with_type("Path is not a file");
-        }
+/// `x-amz-copy-source` value for an object: the bucket and the key,
+/// percent-encoded so keys with spaces, `+`, `?` or non-ASCII characters
+/// name the right source
+pub fn copy_source(bucket: &str, key: &str) -> String {
+    use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+    /// Everything but the RFC 3986 unreserved set and the `/` between segments
+    const COPY_SOURCE_KEY: &AsciiSet = &NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'_')
+        .remove(b'.')
+        .remove(b'~')
+        .remove(b'/');
+
+    format!("{bucket}/{}", utf8_percent_encode(key, COPY_SOURCE_KEY))
+}
+
+/// Byte ranges (part number, first byte, last byte) covering an object of
+/// `size` bytes in as few parts as the part-size limits allow
+fn copy_part_ranges(size: u64) -> Vec<(i32, u64, u64)> {
+    let part_size = COPY_PART_SIZE.max(size.div_ceil(MAX_UPLOAD_PARTS));
+    (0..size.div_ceil(part_size))
+        .map(|i| {
+            let start = i * part_size;
+            let end = (start + part_size).min(size) - 1;
+            (i as i32 + 1, start, end)
+        })
+        .collect()
+}
+
+/// Copy an object over 5 GiB with UploadPartCopy. Unlike CopyObject this
+/// doesn't carry the source's headers over by itself, so they are set on
+/// the new upload from the source's HeadObject response, with `--metadata`
+/// replacing the source's user metadata when given. The source's tags are
+/// not copied; `--tag` sets the new object's tags.
+async fn multipart_copy(
+    config: &Config,
+    source: &S3Uri,
+    dest: &S3Uri,
+    head: &aws_sdk_s3::operation::head_object::HeadObjectOutput,
+    size: u64,
+    options: &TransferOptions,
+) -> Result<()> {
+    use futures::stream::{self, StreamExt, TryStreamExt};
 
-        return Err(anyhow::anyhow!("Path is not a file: {}", local_path));
+    let key = dest.key_or_empty();
+    let copy_source = &copy_source(&source.bucket, source.key_or_empty());
+    let created = config
+        .client
+        .create_multipart_upload()
+        .bucket(&dest.bucket)
+        .key(key)
+        .set_content_type(head.content_type().map(str::to_string))
+        .set_content_encoding(head.content_encoding().map(str::to_string))
+        .set_content_disposition(head.content_disposition().map(str::to_string))
+        .set_content_language(head.content_language().map(str::to_string))
+        .set_cache_control(head.cache_control().map(str::to_string))
+        .set_metadata(if options.metadata.is_empty() {
+            head.metadata().cloned()
+        } else {
+            Some(sync::keep_stored_checksum(options.metadata.clone(), head))
+        })
+        .set_tagging(options.tagging.clone())
+        .set_storage_class(options.storage_class.clone())
+        .send()
+        .await?;
+    let upload_id = created
+        .upload_id()
+        .ok_or_else(|| anyhow::anyhow!("No upload ID returned for {}", dest))?
+        .to_string();
+
+    let result: Result<()> = async {
+        let mut parts: Vec<CompletedPart> = stream::iter(copy_part_ranges(size))
+            .map(|(part_number, start, end)| {
+                let upload_id = &upload_id;
+                async move {
+                    let response = config
+                        .client
+                        .upload_part_copy()
+                        .bucket(&dest.bucket)
+                        .key(key)
+                        .upload_id(upload_id)
+                        .part_number(part_number)
+                        .copy_source(copy_source)
+                        .copy_source_range(format!("bytes={start}-{end}"))
+                        .send()
+                        .await?;
+                    Ok::<_, anyhow::Error>(
+                        CompletedPart::builder()
+                            .part_number(part_number)
+                            .set_e_tag(
+                                response
+                                    .copy_part_result()
+                                    .and_then(|result| result.e_tag())
+                                    .map(|s| s.to_string()),
+                            )
+                            .build(),
+                    )
+                }
+            })
+            .buffer_unordered(COPY_PART_CONCURRENCY)
+            .try_collect()
+            .await?;
+        parts.sort_by_key(|part| part.part_number());
+
+        config
+            .client
+            .complete_multipart_upload()
+            .bucket(&dest.bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await?;
+        Ok(())
     }
+    .await;
 
-    // Read the file content and get size
-    let file_content = fs::read(local_path).await?;
-    let file_size = file_content.len() as u64;
-    let byte_stream = ByteStream::from(file_content);
+    if let Err(e) = result {
+        if let Err(cleanup) = abort_multipart_upload(config, dest, &upload_id).await {
+            return Err(anyhow::anyhow!(
+                "Failed to copy {source} to {dest}: {e}; {cleanup}"
+            ));
+        }
+        return Err(anyhow::anyhow!("Failed to copy {source} to {dest}: {e}"));
+    }
+    Ok(())
+}
 
-    // Upload to S3
-    match config
+/// Abort a failed multipart upload so its parts stop accruing storage,
+/// reporting an abort that fails as a partial failure
+pub async fn abort_multipart_upload(
+    config: &Config,
+    s3_uri: &S3Uri,
+    upload_id: &str,
+) -> Result<()> {
+    let aborted = config
         .client
-        .put_object()
+        .abort_multipart_upload()
         .bucket(&s3_uri.bucket)
         .key(s3_uri.key_or_empty())
-        .body(byte_stream)
+        .upload_id(upload_id)
         .send()
-        .await
-    {
-        Ok(_) => {
-            let duration = start_time.elapsed();
+        .await;
+    let mut failures = PartialFailures::new("multipart_abort");
+    failures.add_attempted(1);
+    if let Err(abort_error) = aborted {
+        failures.record(
+            &format!("{s3_uri} (upload ID {upload_id})"),
+            &format!("uploaded parts were left behind: {abort_error}"),
+        );
+    }
+    failures.into_result()
+}
 
-            // Record upload success using proper OTEL SDK
-            {
-                use crate::otel::OTEL_INSTRUMENTS;
+pub async fn upload_file_to_s3(
+    config: &Config,
+    local_path: &str,
+    s3_uri: &S3Uri,
+    options: &TransferOptions,
+) -> Result<()> {
+    let start_time = Instant::now();
+    let path = Path::new(local_path);
 
-                OTEL_INSTRUMENTS.record_upload(file_size, duration.as_millis() as u64);
-            }
+    if !path.exists() {
+        let err = anyhow::anyhow!("Local file does not exist: {}", local_path);
+        config.events.on_error("upload", &err);
+        return Err(err);
+    }
+
+    if !path.is_file() {
+        let err = anyhow::anyhow!("Path is not a file: {}", local_path);
+        config.events.on_error("upload", &err);
+        return Err(err);
+    }
+
+    let file_size = fs::metadata(local_path).await?.len();
+    let dest = s3_uri.to_string();
+    config
+        .events
+        .on_transfer_start(TransferKind::Upload, local_path, &dest, Some(file_size));
+
+    let progress = options.file_bars.start(local_path, Some(file_size));
+    let result = if options.multipart.applies_to(file_size) {
+        upload::upload_file_multipart(config, path, s3_uri, file_size, options, &progress).await
+    } else {
+        let mut request = config
+            .client
+            .put_object()
+            .bucket(&s3_uri.bucket)
+            .key(s3_uri.key_or_empty())
+            .set_content_type(options.content_type_for(local_path))
+            .set_storage_class(options.storage_class.clone())
+            .set_tagging(options.tagging.clone())
+            .set_checksum_algorithm(options.checksum_algorithm.clone())
+            .body(ByteStream::from(upload::read_file(config, path).await?));
+        if !options.metadata.is_empty() {
+            request = request.set_metadata(Some(options.metadata.clone()));
+        }
+        request
+            .send()
+            .await
+            .map(|_| progress.inc(file_size))
+            .map_err(anyhow::Error::from)
+    };
+
+    match result {
+        Ok(_) => {
+            let duration = start_time.elapsed();
+            config.events.on_transfer_complete(
+                TransferKind::Upload,
+                local_path,
+                &dest,
+                file_size,
+                duration,
+            );
 
             info!(
                 "Successfully uploaded {} to s3://{}/{} ({} bytes in {:?})",
@@ -258,31 +661,237 @@ async fn upload_file_to_s3(config: &Config, local_path: &str, s3_uri: &S3Uri) ->
             Ok(())
         }
         Err(e) => {
-            // Record error using proper OTEL SDK
-            {
-                use crate::otel::OTEL_INSTRUMENTS;
+            let err = anyhow::anyhow!("Failed to upload {}: {}", local_path, e);
+            config.events.on_error("upload", &err);
+            Err(err)
+        }
+    }
+}
+
+async fn upload_stdin_to_s3(
+    config: &Config,
+    dest: &str,
+    recursive: bool,
+    options: &TransferOptions,
+) -> Result<()> {
+    if recursive {
+        return Err(anyhow::anyhow!(
+            "--recursive cannot be used when reading from stdin"
+        ));
+    }
 
-                OTEL_INSTRUMENTS
-                    .record_error_with_type(&format!("Failed to upload {local_path}: {e}"));
+    let dest_uri = S3Uri::parse(dest)?;
+    if dest_uri.key_or_empty().is_empty() || dest_uri.key_or_empty().ends_with('/') {
+        return Err(anyhow::anyhow!(
+            "Destination must be a full object key when reading from stdin: {}",
+            dest
+        ));
+    }
+
+    info!("Streaming stdin to {dest}");
+    let mut stdin = upload::throttle(config, tokio::io::stdin());
+    upload_stream_to_s3(config, &mut stdin, &dest_uri, options).await?;
+    Ok(())
+}
+
+/// Upload a stream of unknown length, buffering it into multipart parts.
+/// Streams that fit in a single part are sent with a plain PutObject.
+async fn upload_stream_to_s3<R: AsyncRead + Unpin>(
+    config: &Config,
+    reader: &mut R,
+    s3_uri: &S3Uri,
+    options: &TransferOptions,
+) -> Result<u64> {
+    let start_time = Instant::now();
+    let key = s3_uri.key_or_empty();
+    let dest = s3_uri.to_string();
+    config
+        .events
+        .on_transfer_start(TransferKind::Upload, "-", &dest, None);
+
+    let progress = options.file_bars.start(key, None);
+    let first_chunk = read_chunk(reader, STREAM_PART_SIZE).await?;
+
+    let total_bytes = if first_chunk.len() < STREAM_PART_SIZE {
+        let size = first_chunk.len() as u64;
+        let request = config
+            .client
+            .put_object()
+            .bucket(&s3_uri.bucket)
+            .key(key)
+            .set_content_type(options.content_type_for(key))
+            .set_storage_class(options.storage_class.clone())
+            .set_tagging(options.tagging.clone())
+            .set_checksum_algorithm(options.checksum_algorithm.clone())
+            .body(ByteStream::from(first_chunk));
+        request
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to upload stdin to {}: {}", s3_uri, e))?;
+        progress.inc(size);
+        size
+    } else {
+        let request = config
+            .client
+            .create_multipart_upload()
+            .bucket(&s3_uri.bucket)
+            .key(key)
+            .set_content_type(options.content_type_for(key))
+            .set_storage_class(options.storage_class.clone())
+            .set_tagging(options.tagging.clone())
+            .set_checksum_algorithm(options.checksum_algorithm.clone());
+        let created = request.send().await?;
+        let upload_id = created
+            .upload_id()
+            .ok_or_else(|| anyhow::anyhow!("No upload ID returned for {}", s3_uri))?
+            .to_string();
+
+        let parts_result: Result<u64> = async {
+            let mut parts = Vec::new();
+            let mut total = 0u64;
+            let mut chunk = first_chunk;
+
+            while !chunk.is_empty() {
+                let part_number = parts.len() as i32 + 1;
+                let part_len = chunk.len() as u64;
+                total += part_len;
+
+                let response = config
+                    .client
+                    .upload_part()
+                    .bucket(&s3_uri.bucket)
+                    .key(key)
+                    .upload_id(&upload_id)
+                    .part_number(part_number)
+                    .set_checksum_algorithm(options.checksum_algorithm.clone())
+                    .body(ByteStream::from(chunk))
+                    .send()
+                    .await?;
+                progress.inc(part_len);
+
+                parts.push(upload::completed_part(part_number, &response));
+
+                chunk = read_chunk(reader, STREAM_PART_SIZE).await?;
+            }
+
+            config
+                .client
+                .complete_multipart_upload()
+                .bucket(&s3_uri.bucket)
+                .key(key)
+                .upload_id(&upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(parts))
+                        .build(),
+                )
+                .send()
+                .await?;
+
+            Ok(total)
+        }
+        .await;
+
+        match parts_result {
+            Ok(total) => total,
+            Err(e) => {
+                // Don't leave orphaned parts behind, and say so if they were
+                if let Err(cleanup) = abort_multipart_upload(config, s3_uri, &upload_id).await {
+                    return Err(anyhow::anyhow!(
+                        "Failed to upload stdin to {s3_uri}: {e}; {cleanup}"
+                    ));
+                }
+                return Err(anyhow::anyhow!(
+                    "Failed to upload stdin to {}: {}",
+                    s3_uri,
+                    e
+                ));
             }
+        }
+    };
+
+    let duration = start_time.elapsed();
+    config
+        .events
+        .on_transfer_complete(TransferKind::Upload, "-", &dest, total_bytes, duration);
+
+    info!("Successfully uploaded stdin to {s3_uri} ({total_bytes} bytes in {duration:?})");
+    Ok(total_bytes)
+}
+
+/// Parse `--storage-class`, accepting any class the SDK knows (case-insensitive)
+pub fn parse_storage_class(value: &str) -> Result<StorageClass> {
+    let class = value.trim().to_uppercase();
+    if !StorageClass::values().contains(&class.as_str()) {
+        return Err(anyhow::anyhow!(
+            "Unknown storage class '{value}'. Accepted values: {}",
+            StorageClass::values().join(", ")
+        ));
+    }
+    Ok(StorageClass::from(class.as_str()))
+}
 
-            Err(anyhow::anyhow!("Failed to upload {}: {}", local_path, e))
+/// The SDK's name for a `--checksum-algorithm` choice
+pub fn sdk_checksum_algorithm(algorithm: crate::args::ChecksumAlgorithm) -> ChecksumAlgorithm {
+    use crate::args::ChecksumAlgorithm as Arg;
+
+    match algorithm {
+        Arg::Crc32 => ChecksumAlgorithm::Crc32,
+        Arg::Crc32c => ChecksumAlgorithm::Crc32C,
+        Arg::Crc64nvme => ChecksumAlgorithm::Crc64Nvme,
+        Arg::Sha1 => ChecksumAlgorithm::Sha1,
+        Arg::Sha256 => ChecksumAlgorithm::Sha256,
+    }
+}
+
+/// Parse `KEY=VALUE` pairs for `x-amz-meta-*` headers. S3 stores metadata
+/// keys lowercased and only carries printable ASCII without spaces in them.
+pub fn parse_metadata(pairs: &[String]) -> Result<HashMap<String, String>> {
+    let mut metadata = HashMap::new();
+    for pair in pairs {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid metadata '{pair}': expected KEY=VALUE"))?;
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(anyhow::anyhow!(
+                "Invalid metadata key '{key}': use printable ASCII without spaces"
+            ));
         }
+        metadata.insert(key.to_lowercase(), value.to_string());
     }
+    Ok(metadata)
+}
+
+/// Read up to `size` bytes, stopping early only at end of stream
+async fn read_chunk<R: AsyncRead + Unpin>(reader: &mut R, size: usize) -> Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(size);
+    (&mut *reader)
+        .take(size as u64)
+        .read_to_end(&mut buffer)
+        .await?;
+    Ok(buffer)
 }
 
-async fn download_file_from_s3(config: &Config, s3_uri: &S3Uri, local_path: &str) -> Result<()> {
+pub async fn download_file_from_s3(
+    config: &Config,
+    s3_uri: &S3Uri,
+    local_path: &str,
+    options: &TransferOptions,
+) -> Result<()> {
     let start_time = Instant::now();
+    let source = s3_uri.to_string();
+    config
+        .events
+        .on_transfer_start(TransferKind::Download, &source, local_path, None);
 
     // Get the object from S3
-    match config
+    let request = config
         .client
         .get_object()
         .bucket(&s3_uri.bucket)
-        .key(s3_uri.key_or_empty())
-        .send()
-        .await
-    {
+        .key(s3_uri.key_or_empty());
+    match request.send().await {
         Ok(response) => {
             // Create parent directories if they don't exist
             let local_path_obj = Path::new(local_path);
@@ -290,20 +899,47 @@ async fn download_file_from_s3(config: &Config, s3_uri: &S3Uri, local_path: &str
                 fs::create_dir_all(parent).await?;
             }
 
-            // Read the response body and write to file
-            let mut file = fs::File::create(local_path).await?;
-            let mut body = response.body.into_async_read();
-            let bytes_written = tokio::io::copy(&mut body, &mut file).await?;
-            file.flush().await?;
+            let gzip = options.decompress
+                && is_gzip_object(response.content_encoding(), s3_uri.key_or_empty());
+
+            // Stage the body in a temp file so the target only ever holds a
+            // complete download
+            let staged = staging::staging_path(local_path_obj, options.temp_dir.as_deref());
+            let written: Result<u64> = async {
+                let mut file = fs::File::create(&staged).await?;
+                let progress = options.file_bars.start(
+                    local_path,
+                    response.content_length().map(|len| len.max(0) as u64),
+                );
+                let mut body =
+                    progress.wrap(upload::throttle(config, response.body.into_async_read()));
+                let bytes_written = if gzip {
+                    let mut decoder = gzip_reader(body, &s3_uri.to_string()).await?;
+                    tokio::io::copy(&mut decoder, &mut file).await?
+                } else {
+                    tokio::io::copy(&mut body, &mut file).await?
+                };
+                file.flush().await?;
+                Ok(bytes_written)
+            }
+            .await;
+            let bytes_written = match written {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    let _ = fs::remove_file(&staged).await;
+                    return Err(e);
+                }
+            };
+            staging::commit(&staged, local_path_obj).await?;
 
             let duration = start_time.elapsed();
-
-            // Record comprehensive download metrics
-            {
-                use crate::otel::OTEL_INSTRUMENTS;
-
-                OTEL_INSTRUMENTS.record_download(bytes_written, duration.as_millis() as u64);
-            }
+            config.events.on_transfer_complete(
+                TransferKind::Download,
+                &source,
+                local_path,
+                bytes_written,
+                duration,
+            );
 
             info!(
                 "Successfully downloaded s3://{}/{} to {} ({} bytes in {:?})",
@@ -321,69 +957,204 @@ async fn download_file_from_s3(config: &Config, s3_uri: &S3Uri, local_path: &str
             Ok(())
         }
         Err(e) => {
-            // Record error using proper OTEL SDK
-            {
-                use crate::otel::OTEL_INSTRUMENTS;
-
-                OTEL_INSTRUMENTS.record_error_with_type(&format!(
-                    "Failed to download s3://{}/{}: {}",
-                    s3_uri.bucket,
-                    s3_uri.key_or_empty(),
-                    e
-                ));
-            }
-
-            Err(anyhow::anyhow!(
+            let err = anyhow::anyhow!(
                 "Failed to download s3://{}/{}: {}",
                 s3_uri.bucket,
                 s3_uri.key_or_empty(),
                 e
-            ))
+            );
+            config.events.on_error("download", &err);
+            Err(err)
         }
     }
 }
 
-async fn upload_directory_to_s3(config: &Config, local_dir: &str, s3_uri: &S3Uri) -> Result<()> {
+/// A local source that is a glob pattern rather than an existing path
+fn is_local_glob(source: &str) -> bool {
+    source != STDIN_SOURCE && has_wildcards(source) && !Path::new(source).exists()
+}
+
+/// Local files matching a glob such as `logs/**/*.log`, each paired with the
+/// relative path its object key mirrors. Relative patterns resolve against
+/// the working directory and keep the path as matched; absolute patterns
+/// keep the path below their leading literal directories.
+fn expand_local_glob(pattern: &str, exclude_dirs: &[String]) -> Result<Vec<(PathBuf, String)>> {
+    use walkdir::WalkDir;
+
+    let pattern = pattern.trim_start_matches("./");
+    let absolute = pattern.starts_with('/');
+    let base = pattern
+        .split('/')
+        .take_while(|segment| !has_wildcards(segment))
+        .collect::<Vec<_>>()
+        .join("/");
+    let walk_root = match (base.is_empty(), absolute) {
+        (true, true) => "/",
+        (true, false) => ".",
+        (false, _) => base.as_str(),
+    };
+
+    let mut matches = Vec::new();
+    let walker = WalkDir::new(walk_root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !is_excluded_dir(entry, Path::new(walk_root), exclude_dirs));
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let below_base = entry
+            .path()
+            .strip_prefix(walk_root)?
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/");
+        let matched = if base.is_empty() {
+            below_base.clone()
+        } else {
+            format!("{base}/{below_base}")
+        };
+        if glob_path_match(pattern, &matched) {
+            let relative = if absolute { below_base } else { matched };
+            matches.push((entry.into_path(), relative));
+        }
+    }
+
+    if matches.is_empty() {
+        return Err(anyhow::anyhow!("No local files match {pattern}"));
+    }
+    Ok(matches)
+}
+
+/// `--exclude-dir`: a directory below the walk root whose name or path
+/// relative to the root matches one of the patterns
+fn is_excluded_dir(entry: &walkdir::DirEntry, root: &Path, patterns: &[String]) -> bool {
+    if patterns.is_empty() || entry.depth() == 0 || !entry.file_type().is_dir() {
+        return false;
+    }
+    let name = entry.file_name().to_string_lossy();
+    let relative = entry
+        .path()
+        .strip_prefix(root)
+        .unwrap_or(entry.path())
+        .to_string_lossy()
+        .replace(std::path::MAIN_SEPARATOR, "/");
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim_end_matches('/');
+        wildcard_match(pattern, &name) || wildcard_match(pattern, &relative)
+    })
+}
+
+/// Key for `relative` placed under the destination prefix
+fn object_key(s3_uri: &S3Uri, relative: &str) -> String {
+    match s3_uri.key_or_empty().trim_end_matches('/') {
+        "" => relative.to_string(),
+        prefix => format!("{prefix}/{relative}"),
+    }
+}
+
+async fn upload_glob_to_s3(
+    config: &Config,
+    pattern: &str,
+    dest: &str,
+    options: &TransferOptions,
+) -> Result<()> {
+    let dest_uri = S3Uri::parse(dest)?;
+    info!("Glob upload from {pattern} to {dest}");
+
+    let files = expand_local_glob(pattern, &options.exclude_dirs)?
+        .into_iter()
+        .map(|(path, relative)| {
+            let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            (path, relative, size)
+        })
+        .collect();
+    upload_files_to_s3(config, pattern, files, &dest_uri, options).await
+}
+
+async fn upload_directory_to_s3(
+    config: &Config,
+    local_dir: &str,
+    s3_uri: &S3Uri,
+    options: &TransferOptions,
+) -> Result<()> {
+    // Collect the files first so progress has totals to work against
+    let files = collect_local_files(local_dir, &options.exclude_dirs)?;
+    upload_files_to_s3(config, local_dir, files, s3_uri, options).await
+}
+
+/// Every file under `local_dir` as `(path, relative path, size)`. Directories
+/// matching `exclude_dirs` are pruned, so their contents are never read.
+fn collect_local_files(
+    local_dir: &str,
+    exclude_dirs: &[String],
+) -> Result<Vec<(PathBuf, String, u64)>> {
     use walkdir::WalkDir;
 
-    let start_time = Instant::now();
     let base_path = Path::new(local_dir);
-    let mut total_files = 0u64;
-    let mut total_bytes = 0u64;
 
-    for entry in WalkDir::new(local_dir) {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(local_dir)
+        .into_iter()
+        .filter_entry(|entry| !is_excluded_dir(entry, base_path, exclude_dirs));
+    for entry in walker {
         let entry = entry?;
-        let path = entry.path();
+        if entry.path().is_file() {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let relative = entry
+                .path()
+                .strip_prefix(base_path)?
+                .to_string_lossy()
+                .to_string();
+            files.push((entry.into_path(), relative, size));
+        }
+    }
+    Ok(files)
+}
 
-        if path.is_file() {
-            // Calculate relative path from base directory
-            let relative_path = path.strip_prefix(base_path)?;
-            let s3_key = if s3_uri.key.is_none() || s3_uri.key_or_empty().is_empty() {
-                relative_path.to_string_lossy().to_string()
-            } else {
-                format!(
-                    "{}/{}",
-                    s3_uri.key_or_empty(),
-                    relative_path.to_string_lossy()
-                )
-            };
+/// Upload `(path, relative path, size)` entries under the destination prefix
+async fn upload_files_to_s3(
+    config: &Config,
+    source: &str,
+    files: Vec<(PathBuf, String, u64)>,
+    s3_uri: &S3Uri,
+    options: &TransferOptions,
+) -> Result<()> {
+    let start_time = Instant::now();
 
-            // Create S3 URI for this file
-            let file_s3_uri = S3Uri {
-                bucket: s3_uri.bucket.clone(),
-                key: Some(s3_key),
-            };
+    let total_files = files.len() as u64;
+    let total_bytes: u64 = files.iter().map(|(_, _, size)| size).sum();
+    let mut tracker = TransferProgress::new(options.progress, total_files, total_bytes);
+    let options = &TransferOptions {
+        file_bars: tracker.file_bars(),
+        ..options.clone()
+    };
 
-            // Get file size before upload
-            if let Ok(metadata) = path.metadata() {
-                total_bytes += metadata.len();
-            }
-            total_files += 1;
+    for (path, relative_path, size) in &files {
+        // Create S3 URI for this file
+        let file_s3_uri = S3Uri {
+            bucket: s3_uri.bucket.clone(),
+            key: Some(object_key(s3_uri, relative_path)),
+        };
 
-            // Upload the file
-            upload_file_to_s3(config, path.to_str().unwrap(), &file_s3_uri).await?;
+        // Upload the file
+        if let Err(e) =
+            upload_file_to_s3(config, path.to_str().unwrap(), &file_s3_uri, options).await
+        {
+            tracker.eprintln(&format!("upload failed: {}: {e}", path.display()));
+            return Err(e);
+        }
+        if let Some(verifier) = &options.checksum_manifest {
+            verifier.verify_file(relative_path, path).await?;
+        }
+        if let Some(manifest) = &options.write_manifest {
+            manifest
+                .record(file_s3_uri.key_or_empty(), path, *size)
+                .await?;
         }
+        tracker.file_done(*size);
     }
+    tracker.finish();
 
     let duration = start_time.elapsed();
 
@@ -410,8 +1181,8 @@ async fn upload_directory_to_s3(config: &Config, local_dir: &str, s3_uri: &S3Uri
     }
 
     info!(
-        "Successfully uploaded directory {} to s3://{}/{} ({} files, {} bytes in {:?})",
-        local_dir,
+        "Successfully uploaded {} to s3://{}/{} ({} files, {} bytes in {:?})",
+        source,
         s3_uri.bucket,
         s3_uri.key_or_empty(),
         total_files,
@@ -425,10 +1196,9 @@ async fn download_directory_from_s3(
     config: &Config,
     s3_uri: &S3Uri,
     local_dir: &str,
+    options: &TransferOptions,
 ) -> Result<()> {
     let start_time = Instant::now();
-    let mut total_files = 0u64;
-    let mut total_bytes = 0u64;
 
     // List all objects with the prefix
     let mut list_request = config.client.list_objects_v2().bucket(&s3_uri.bucket);
@@ -438,38 +1208,54 @@ async fn download_directory_from_s3(
     }
 
     let response = list_request.send().await?;
+    let objects = response.contents.unwrap_or_default();
+
+    let total_files = objects.len() as u64;
+    let total_bytes: u64 = objects
+        .iter()
+        .map(|object| object.size.unwrap_or(0) as u64)
+        .sum();
+    let mut tracker = TransferProgress::new(options.progress, total_files, total_bytes);
+    let options = &TransferOptions {
+        file_bars: tracker.file_bars(),
+        ..options.clone()
+    };
 
-    if let Some(objects) = response.contents {
-        for object in objects {
-            if let Some(key) = object.key {
-                // Calculate local file path
-                let local_file_path = if s3_uri.key_or_empty().is_empty() {
-                    format!("{local_dir}/{key}")
-                } else {
-                    // Remove the prefix from the key
-                    let relative_key = key
-                        .strip_prefix(&format!("{}/", s3_uri.key_or_empty()))
-                        .unwrap_or(&key);
-                    format!("{local_dir}/{relative_key}")
-                };
-
-                // Create S3 URI for this object
-                let object_s3_uri = S3Uri {
-                    bucket: s3_uri.bucket.clone(),
-                    key: Some(key),
-                };
+    for object in objects {
+        if let Some(key) = object.key {
+            // Calculate local file path
+            let relative_key = if s3_uri.key_or_empty().is_empty() {
+                key.clone()
+            } else {
+                // Remove the prefix from the key
+                key.strip_prefix(&format!("{}/", s3_uri.key_or_empty()))
+                    .unwrap_or(&key)
+                    .to_string()
+            };
+            let local_file_path = format!("{local_dir}/{relative_key}");
 
-                // Track file size from S3 object info
-                if let Some(size) = object.size {
-                    total_bytes += size as u64;
-                }
-                total_files += 1;
+            // Create S3 URI for this object
+            let object_s3_uri = S3Uri {
+                bucket: s3_uri.bucket.clone(),
+                key: Some(key),
+            };
 
-                // Download the file
-                download_file_from_s3(config, &object_s3_uri, &local_file_path).await?;
+            // Download the file
+            if let Err(e) =
+                download_file_from_s3(config, &object_s3_uri, &local_file_path, options).await
+            {
+                tracker.eprintln(&format!("download failed: {object_s3_uri}: {e}"));
+                return Err(e);
             }
+            if let Some(verifier) = &options.checksum_manifest {
+                verifier
+                    .verify_file(&relative_key, Path::new(&local_file_path))
+                    .await?;
+            }
+            tracker.file_done(object.size.unwrap_or(0) as u64);
         }
     }
+    tracker.finish();
 
     let duration = start_time.elapsed();
 
@@ -560,7 +1346,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -578,12 +1373,180 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_expand_local_glob() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("app/2024")).unwrap();
+        std::fs::write(root.join("top.log"), "a").unwrap();
+        std::fs::write(root.join("notes.txt"), "b").unwrap();
+        std::fs::write(root.join("app/web.log"), "c").unwrap();
+        std::fs::write(root.join("app/2024/jan.log"), "d").unwrap();
+
+        let pattern = format!("{}/**/*.log", root.display());
+        let relative: Vec<String> = expand_local_glob(&pattern, &[])
+            .unwrap()
+            .into_iter()
+            .map(|(_, relative)| relative)
+            .collect();
+        assert_eq!(relative, vec!["app/2024/jan.log", "app/web.log", "top.log"]);
+
+        let pattern = format!("{}/app/*.log", root.display());
+        let matches = expand_local_glob(&pattern, &[]).unwrap();
+        assert_eq!(
+            matches,
+            vec![(root.join("app/web.log"), "web.log".to_string())]
+        );
+
+        let pattern = format!("{}/**/*.csv", root.display());
+        assert!(expand_local_glob(&pattern, &[])
+            .unwrap_err()
+            .to_string()
+            .contains("No local files match"));
+    }
+
+    #[test]
+    fn test_exclude_dir_prunes_local_walk() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        for dir in [
+            "src",
+            "node_modules/pkg",
+            ".git/objects",
+            "build/cache",
+            "docs/build",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("file.js"), "x").unwrap();
+        }
+        std::fs::write(root.join("index.js"), "x").unwrap();
+
+        let relative = |files: Vec<(PathBuf, String, u64)>| -> Vec<String> {
+            let mut names: Vec<String> = files
+                .into_iter()
+                .map(|(_, relative, _)| relative.replace(std::path::MAIN_SEPARATOR, "/"))
+                .collect();
+            names.sort();
+            names
+        };
+        let dir = root.to_str().unwrap();
+
+        assert_eq!(collect_local_files(dir, &[]).unwrap().len(), 6);
+
+        // Names match at any depth; paths with a slash are relative to the root
+        let excludes = vec![
+            "node_modules".to_string(),
+            ".git/".to_string(),
+            "build/cache".to_string(),
+        ];
+        assert_eq!(
+            relative(collect_local_files(dir, &excludes).unwrap()),
+            vec!["docs/build/file.js", "index.js", "src/file.js"]
+        );
+
+        let pattern = format!("{}/**/*.js", root.display());
+        let globbed: Vec<String> = expand_local_glob(&pattern, &["node_modules".to_string()])
+            .unwrap()
+            .into_iter()
+            .map(|(_, relative)| relative)
+            .collect();
+        assert!(!globbed.iter().any(|path| path.contains("node_modules")));
+        assert_eq!(globbed.len(), 5);
+    }
+
+    #[test]
+    fn test_content_type_for() {
+        let options = TransferOptions::default();
+        assert_eq!(
+            options.content_type_for("data/report.json").as_deref(),
+            Some("application/json")
+        );
+        assert_eq!(options.content_type_for("blob.bin"), None);
+
+        let options = TransferOptions {
+            content_type: Some("text/plain".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.content_type_for("data/report.json").as_deref(),
+            Some("text/plain")
+        );
+    }
+
+    #[test]
+    fn test_object_key() {
+        let bucket_only = S3Uri::parse("s3://bucket").unwrap();
+        assert_eq!(object_key(&bucket_only, "a/b.log"), "a/b.log");
+
+        let prefix = S3Uri::parse("s3://bucket/logs/").unwrap();
+        assert_eq!(object_key(&prefix, "a/b.log"), "logs/a/b.log");
+
+        let prefix = S3Uri::parse("s3://bucket/logs").unwrap();
+        assert_eq!(object_key(&prefix, "a/b.log"), "logs/a/b.log");
+    }
+
+    #[tokio::test]
+    async fn test_execute_dry_run_with_glob() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.log"), "a").unwrap();
+
+        let config = create_mock_config();
+        let pattern = format!("{}/**/*.log", temp_dir.path().display());
+        let result = execute(
+            &config,
+            &pattern,
+            "s3://bucket/logs/",
+            false,
+            true,
+            4,
+            false,
+            None,
+            None,
+            &TransferOptions::default(),
         )
         .await;
 
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_copy_source_encodes_key() {
+        assert_eq!(copy_source("bucket", "logs/a.log"), "bucket/logs/a.log");
+        assert_eq!(
+            copy_source("bucket", "my dir/a+b?.txt"),
+            "bucket/my%20dir/a%2Bb%3F.txt"
+        );
+        assert_eq!(copy_source("bucket", "café.txt"), "bucket/caf%C3%A9.txt");
+    }
+
+    #[test]
+    fn test_glob_dryrun_lines() {
+        let dest_uri = S3Uri::parse("s3://bucket/logs/").unwrap();
+        let files = vec![
+            (PathBuf::from("/data/a.log"), "a.log".to_string()),
+            (PathBuf::from("/data/b/c.log"), "b/c.log".to_string()),
+        ];
+
+        assert_eq!(
+            glob_dryrun_lines(&files, &dest_uri, false),
+            vec![
+                "(dryrun) upload: /data/a.log to s3://bucket/logs/a.log",
+                "(dryrun) upload: /data/b/c.log to s3://bucket/logs/b/c.log",
+            ]
+        );
+        assert_eq!(
+            glob_dryrun_lines(&files, &dest_uri, true),
+            vec!["(dryrun) would upload 2 file(s)"]
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_local_to_local_error() {
         let config = create_mock_config();
@@ -598,6 +1561,7 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
         )
         .await;
 
@@ -608,6 +1572,169 @@ mod tests {
             .contains("Local to local copy not supported"));
     }
 
+    #[test]
+    fn test_sdk_checksum_algorithm() {
+        use crate::args::ChecksumAlgorithm as Arg;
+
+        assert_eq!(
+            sdk_checksum_algorithm(Arg::Crc32c),
+            ChecksumAlgorithm::Crc32C
+        );
+        assert_eq!(
+            sdk_checksum_algorithm(Arg::Crc64nvme),
+            ChecksumAlgorithm::Crc64Nvme
+        );
+        assert_eq!(
+            sdk_checksum_algorithm(Arg::Sha256),
+            ChecksumAlgorithm::Sha256
+        );
+    }
+
+    #[test]
+    fn test_parse_storage_class() {
+        assert_eq!(
+            parse_storage_class("STANDARD_IA").unwrap(),
+            StorageClass::StandardIa
+        );
+        assert_eq!(
+            parse_storage_class("deep_archive").unwrap(),
+            StorageClass::DeepArchive
+        );
+
+        let message = parse_storage_class("FROZEN").unwrap_err().to_string();
+        assert!(message.contains("Unknown storage class 'FROZEN'"));
+        assert!(message.contains("INTELLIGENT_TIERING"));
+    }
+
+    #[tokio::test]
+    async fn test_copy_object_sends_storage_class() {
+        let (config, requests) =
+            crate::commands::test_client::recording_config(|method, _| match method {
+                "PUT" => (200, "<CopyObjectResult></CopyObjectResult>".to_string()),
+                _ => (200, String::new()),
+            });
+        let options = TransferOptions {
+            storage_class: Some(StorageClass::Glacier),
+            ..Default::default()
+        };
+
+        copy_object(
+            &config,
+            &S3Uri::parse("s3://src/a.txt").unwrap(),
+            &S3Uri::parse("s3://dst/a.txt").unwrap(),
+            &options,
+        )
+        .await
+        .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let copy = requests
+            .iter()
+            .find(|request| request.method == "PUT")
+            .expect("CopyObject request");
+        assert_eq!(
+            copy.headers.get("x-amz-storage-class").map(String::as_str),
+            Some("GLACIER")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_object_advances_metrics() {
+        use crate::otel::GLOBAL_METRICS;
+        use std::sync::atomic::Ordering;
+
+        let config = crate::commands::test_client::canned_config(|method, _| match method {
+            "PUT" => (200, "<CopyObjectResult></CopyObjectResult>".to_string()),
+            _ => (200, String::new()),
+        });
+        let copies = GLOBAL_METRICS.copies_total.load(Ordering::Relaxed);
+
+        copy_object(
+            &config,
+            &S3Uri::parse("s3://src/a.txt").unwrap(),
+            &S3Uri::parse("s3://dst/a.txt").unwrap(),
+            &TransferOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(GLOBAL_METRICS.copies_total.load(Ordering::Relaxed) > copies);
+    }
+
+    #[test]
+    fn test_copy_part_ranges() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+
+        // 6 GiB in 512 MiB parts, the last one ending on the final byte
+        let ranges = copy_part_ranges(6 * GIB);
+        assert_eq!(ranges.len(), 12);
+        assert_eq!(ranges[0], (1, 0, COPY_PART_SIZE - 1));
+        assert_eq!(ranges[11], (12, 11 * COPY_PART_SIZE, 6 * GIB - 1));
+
+        // A partial last part
+        let ranges = copy_part_ranges(5 * GIB + 1);
+        assert_eq!(ranges.len(), 11);
+        assert_eq!(ranges[10], (11, 5 * GIB, 5 * GIB));
+
+        // 5 TiB would need 10240 parts at 512 MiB, so parts grow to fit
+        let ranges = copy_part_ranges(5 * 1024 * GIB);
+        assert!(ranges.len() as u64 <= MAX_UPLOAD_PARTS);
+        assert_eq!(ranges.last().unwrap().2, 5 * 1024 * GIB - 1);
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let metadata = parse_metadata(&[
+            "Owner=data-team".to_string(),
+            "build=2024.05=rc1".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(metadata.get("owner").map(String::as_str), Some("data-team"));
+        assert_eq!(
+            metadata.get("build").map(String::as_str),
+            Some("2024.05=rc1")
+        );
+
+        assert!(parse_metadata(&["no-separator".to_string()]).is_err());
+        assert!(parse_metadata(&["bad key=x".to_string()]).is_err());
+        assert!(parse_metadata(&["=x".to_string()]).is_err());
+        assert!(parse_metadata(&["clé=x".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_chunk_splits_stream() {
+        let data = vec![7u8; 10];
+        let mut reader = std::io::Cursor::new(data);
+
+        assert_eq!(read_chunk(&mut reader, 4).await.unwrap().len(), 4);
+        assert_eq!(read_chunk(&mut reader, 4).await.unwrap().len(), 4);
+        assert_eq!(read_chunk(&mut reader, 4).await.unwrap().len(), 2);
+        assert!(read_chunk(&mut reader, 4).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stdin_upload_requires_object_key() {
+        let config = create_mock_config();
+
+        let result = upload_stdin_to_s3(
+            &config,
+            "s3://bucket/prefix/",
+            false,
+            &TransferOptions::default(),
+        )
+        .await;
+        assert!(result.unwrap_err().to_string().contains("full object key"));
+
+        let result = upload_stdin_to_s3(
+            &config,
+            "s3://bucket/out.dat",
+            true,
+            &TransferOptions::default(),
+        )
+        .await;
+        assert!(result.unwrap_err().to_string().contains("--recursive"));
+    }
+
     #[tokio::test]
     async fn test_upload_file_to_s3_nonexistent_file() {
         let config = create_mock_config();
@@ -616,7 +1743,13 @@ mod tests {
             key: Some("test.txt".to_string()),
         };
 
-        let result = upload_file_to_s3(&config, "/nonexistent/file.txt", &s3_uri).await;
+        let result = upload_file_to_s3(
+            &config,
+            "/nonexistent/file.txt",
+            &s3_uri,
+            &TransferOptions::default(),
+        )
+        .await;
 
         assert!(result.is_err());
         assert!(result
@@ -634,7 +1767,13 @@ mod tests {
             key: Some("test.txt".to_string()),
         };
 
-        let result = upload_file_to_s3(&config, temp_dir.path().to_str().unwrap(), &s3_uri).await;
+        let result = upload_file_to_s3(
+            &config,
+            temp_dir.path().to_str().unwrap(),
+            &s3_uri,
+            &TransferOptions::default(),
+        )
+        .await;
 
         assert!(result.is_err());
         assert!(result
@@ -657,6 +1796,7 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
         )
         .await;
 
@@ -676,6 +1816,7 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
         )
         .await;
 
@@ -695,6 +1836,7 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
         )
         .await;
 
@@ -708,8 +1850,15 @@ mod tests {
 
         // Test recursive upload (will fail due to no AWS connection, but tests routing)
         let result_recursive = upload_to_s3(
-            &config, "/tmp", dest_uri, true, // recursive
-            4, false, None, None,
+            &config,
+            "/tmp",
+            dest_uri,
+            true, // recursive
+            4,
+            false,
+            None,
+            None,
+            &TransferOptions::default(),
         )
         .await;
         assert!(result_recursive.is_err());
@@ -724,6 +1873,7 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
         )
         .await;
         assert!(result_single.is_err());
@@ -744,6 +1894,7 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
         )
         .await;
         assert!(result_recursive.is_err());
@@ -758,6 +1909,7 @@ mod tests {
             false,
             None,
             None,
+            &TransferOptions::default(),
         )
         .await;
         assert!(result_single.is_err());
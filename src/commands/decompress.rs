@@ -0,0 +1,85 @@
+use anyhow::Result;
+use async_compression::tokio::bufread::GzipDecoder;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+/// Magic bytes at the start of every gzip member
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Check whether an object is gzip-compressed based on its Content-Encoding
+/// header or, failing that, a `.gz` key suffix
+pub fn is_gzip_object(content_encoding: Option<&str>, key: &str) -> bool {
+    let encoded = content_encoding.is_some_and(|encoding| {
+        encoding
+            .split(',')
+            .any(|value| matches!(value.trim().to_lowercase().as_str(), "gzip" | "x-gzip"))
+    });
+
+    encoded || key.to_lowercase().ends_with(".gz")
+}
+
+/// Wrap a reader in a gzip decoder, verifying the gzip header up front so that
+/// non-gzip content fails with a clear message instead of a decoder error
+pub async fn gzip_reader<R>(reader: R, source: &str) -> Result<GzipDecoder<BufReader<R>>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffered = BufReader::new(reader);
+    let header = buffered.fill_buf().await?;
+
+    if header.len() < GZIP_MAGIC.len() || header[..GZIP_MAGIC.len()] != GZIP_MAGIC {
+        return Err(anyhow::anyhow!(
+            "{source} is not gzip-compressed (missing gzip header); rerun without --decompress"
+        ));
+    }
+
+    let mut decoder = GzipDecoder::new(buffered);
+    // Concatenated gzip members (e.g. appended log chunks) decode as one stream
+    decoder.multiple_members(true);
+    Ok(decoder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_compression::tokio::write::GzipEncoder;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzipEncoder::new(Vec::new());
+        encoder.write_all(data).await.unwrap();
+        encoder.shutdown().await.unwrap();
+        encoder.into_inner()
+    }
+
+    #[test]
+    fn test_is_gzip_object() {
+        assert!(is_gzip_object(Some("gzip"), "log"));
+        assert!(is_gzip_object(Some("identity, GZIP"), "log"));
+        assert!(is_gzip_object(None, "logs/app.log.gz"));
+        assert!(is_gzip_object(None, "logs/APP.GZ"));
+        assert!(!is_gzip_object(Some("br"), "log.txt"));
+        assert!(!is_gzip_object(None, "log.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_gzip_reader_decodes() {
+        let compressed = gzip(b"hello world\n").await;
+
+        let mut reader = gzip_reader(std::io::Cursor::new(compressed), "s3://b/k.gz")
+            .await
+            .unwrap();
+        let mut output = String::new();
+        reader.read_to_string(&mut output).await.unwrap();
+
+        assert_eq!(output, "hello world\n");
+    }
+
+    #[tokio::test]
+    async fn test_gzip_reader_rejects_plain_data() {
+        let result = gzip_reader(std::io::Cursor::new(b"plain text".to_vec()), "s3://b/k").await;
+
+        let err = result.err().unwrap().to_string();
+        assert!(err.contains("not gzip-compressed"));
+        assert!(err.contains("s3://b/k"));
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::Result;
 use log::info;
 use std::collections::HashMap;
+use std::io::Write;
 use std::time::Instant;
 
+use crate::args::OutputFormat;
+use crate::commands::paginate::list_objects_v2_resumable;
 use crate::commands::s3_uri::{is_s3_uri, S3Uri};
 use crate::config::Config;
 
@@ -12,8 +15,20 @@ pub async fn execute(
     human_readable: bool,
     summarize: bool,
     max_depth: Option<usize>,
+    output: OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
-    execute_with_metrics_control(config, s3_uri, human_readable, summarize, max_depth, true).await
+    execute_with_metrics_control(
+        config,
+        s3_uri,
+        human_readable,
+        summarize,
+        max_depth,
+        true,
+        output,
+        out,
+    )
+    .await
 }
 
 pub async fn execute_transparent(
@@ -23,9 +38,20 @@ pub async fn execute_transparent(
     summarize: bool,
     max_depth: Option<usize>,
 ) -> Result<()> {
-    execute_with_metrics_control(config, s3_uri, human_readable, summarize, max_depth, false).await
+    execute_with_metrics_control(
+        config,
+        s3_uri,
+        human_readable,
+        summarize,
+        max_depth,
+        false,
+        OutputFormat::Text,
+        &mut std::io::stdout(),
+    )
+    .await
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn execute_with_metrics_control(
     config: &Config,
     s3_uri: &str,
@@ -33,6 +59,8 @@ async fn execute_with_metrics_control(
     summarize: bool,
     max_depth: Option<usize>,
     record_user_operation: bool,
+    output: OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
     let start_time = Instant::now();
 
@@ -54,8 +82,17 @@ async fn execute_with_metrics_control(
             let total_size: i64 = objects.iter().map(|obj| obj.size).sum();
             let object_count = objects.len();
 
-            // Record comprehensive du operation metrics using proper OTEL SDK
-            {
+            // In-process totals (--metrics-file, --summary-json) count every explicit du
+            if record_user_operation {
+                crate::otel::GLOBAL_METRICS
+                    .record_list(duration.as_millis() as u64)
+                    .await;
+            }
+
+            // Record comprehensive du operation metrics using proper OTEL SDK.
+            // An explicit du is a read, so it records nothing unless read
+            // metrics are on; the transparent du after uploads always does.
+            if !record_user_operation || config.otel.read_operations {
                 use crate::otel::OTEL_INSTRUMENTS;
                 use opentelemetry::KeyValue;
 
@@ -65,21 +102,10 @@ async fn execute_with_metrics_control(
                 // Only record user operation metrics if this is an explicit user command
                 if record_user_operation {
                     // Basic du operation metrics - only for explicit user commands
-                    OTEL_INSTRUMENTS.operations_total.add(
-                        1,
-                        &[
-                            KeyValue::new("operation", "du"),
-                            KeyValue::new("bucket", bucket_str.clone()),
-                        ],
-                    );
-
-                    let duration_seconds = duration.as_millis() as f64 / 1000.0;
-                    OTEL_INSTRUMENTS.operation_duration.record(
-                        duration_seconds,
-                        &[
-                            KeyValue::new("operation", "du"),
-                            KeyValue::new("bucket", bucket_str.clone()),
-                        ],
+                    OTEL_INSTRUMENTS.record_list(
+                        "du",
+                        duration.as_millis() as u64,
+                        config.otel.bucket_label(&bucket_str),
                     );
                 }
 
@@ -151,34 +177,27 @@ async fn execute_with_metrics_control(
             }
 
             let directory_sizes = calculate_directory_sizes(&objects, max_depth);
-
-            if summarize {
-                let size_str = if human_readable {
-                    format_size_human_readable(total_size)
-                } else {
-                    total_size.to_string()
-                };
-                println!("{size_str} {s3_uri}");
+            let class_sizes = calculate_storage_class_sizes(&objects);
+
+            if output == OutputFormat::Json {
+                write_usage_json(
+                    out,
+                    &directory_sizes,
+                    &class_sizes,
+                    total_size,
+                    object_count,
+                    summarize,
+                )?;
             } else {
-                // Sort by path for consistent output
-                let mut sorted_dirs: Vec<_> = directory_sizes.iter().collect();
-                sorted_dirs.sort_by_key(|&(path, _)| path);
-
-                for (path, size) in sorted_dirs {
-                    let size_str = if human_readable {
-                        format_size_human_readable(*size)
-                    } else {
-                        size.to_string()
-                    };
-
-                    let display_path = if path.is_empty() {
-                        s3_uri.to_string()
-                    } else {
-                        format!("{}/{}", s3_uri.trim_end_matches('/'), path)
-                    };
-
-                    println!("{size_str} {display_path}");
-                }
+                write_usage(
+                    out,
+                    s3_uri,
+                    &directory_sizes,
+                    &class_sizes,
+                    total_size,
+                    human_readable,
+                    summarize,
+                )?;
             }
 
             Ok(())
@@ -197,10 +216,111 @@ async fn execute_with_metrics_control(
     }
 }
 
+/// Print the `du` report: one total with `--summarize`, otherwise one line
+/// per directory sorted by path, then the per-storage-class breakdown
+fn write_usage(
+    out: &mut dyn Write,
+    s3_uri: &str,
+    directory_sizes: &HashMap<String, i64>,
+    class_sizes: &HashMap<String, i64>,
+    total_size: i64,
+    human_readable: bool,
+    summarize: bool,
+) -> std::io::Result<()> {
+    let format_size = |size: i64| {
+        if human_readable {
+            format_size_human_readable(size)
+        } else {
+            size.to_string()
+        }
+    };
+
+    let breakdown = sorted_by_size(class_sizes)
+        .into_iter()
+        .map(|(class, size)| format!("{class}: {}", format_size(size)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if summarize {
+        writeln!(out, "{} {s3_uri}", format_size(total_size))?;
+        if !breakdown.is_empty() {
+            writeln!(out, "{breakdown}")?;
+        }
+        return Ok(());
+    }
+
+    // Sort by path for consistent output
+    let mut sorted_dirs: Vec<_> = directory_sizes.iter().collect();
+    sorted_dirs.sort_by_key(|&(path, _)| path);
+
+    for (path, size) in sorted_dirs {
+        let display_path = if path.is_empty() {
+            s3_uri.to_string()
+        } else {
+            format!("{}/{}", s3_uri.trim_end_matches('/'), path)
+        };
+
+        writeln!(out, "{} {display_path}", format_size(*size))?;
+    }
+    if !breakdown.is_empty() {
+        writeln!(out, "{breakdown}")?;
+    }
+    Ok(())
+}
+
+/// Storage classes largest first, ties by name
+fn sorted_by_size(class_sizes: &HashMap<String, i64>) -> Vec<(&str, i64)> {
+    let mut classes: Vec<(&str, i64)> = class_sizes
+        .iter()
+        .map(|(class, size)| (class.as_str(), *size))
+        .collect();
+    classes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    classes
+}
+
+/// `--output json` form of the report, always in raw bytes:
+/// `{"total_bytes", "object_count", "storage_classes": {class: bytes},
+/// "prefixes": [{"prefix", "bytes"}]}` with prefixes as key paths sorted by
+/// path and left empty under `--summarize`
+fn write_usage_json(
+    out: &mut dyn Write,
+    directory_sizes: &HashMap<String, i64>,
+    class_sizes: &HashMap<String, i64>,
+    total_size: i64,
+    object_count: usize,
+    summarize: bool,
+) -> Result<()> {
+    let mut prefixes: Vec<(&String, &i64)> = if summarize {
+        Vec::new()
+    } else {
+        // The empty path is the whole listing, already given as total_bytes
+        directory_sizes
+            .iter()
+            .filter(|(path, _)| !path.is_empty())
+            .collect()
+    };
+    prefixes.sort();
+
+    let report = serde_json::json!({
+        "total_bytes": total_size,
+        "object_count": object_count,
+        "storage_classes": class_sizes
+            .iter()
+            .collect::<std::collections::BTreeMap<_, _>>(),
+        "prefixes": prefixes
+            .into_iter()
+            .map(|(prefix, bytes)| serde_json::json!({ "prefix": prefix, "bytes": bytes }))
+            .collect::<Vec<_>>(),
+    });
+    writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+    Ok(())
+}
+
 #[derive(Debug)]
 struct ObjectInfo {
     key: String,
     size: i64,
+    storage_class: String,
 }
 
 async fn scan_objects(
@@ -210,39 +330,39 @@ async fn scan_objects(
 ) -> Result<Vec<ObjectInfo>> {
     let start_time = Instant::now();
     let mut objects = Vec::new();
-    let mut continuation_token: Option<String> = None;
     let mut page_count = 0;
 
     let result: Result<Vec<ObjectInfo>> = async {
-        loop {
-            let mut request = config.client.list_objects_v2().bucket(bucket);
-
-            if let Some(prefix_val) = prefix {
-                request = request.prefix(prefix_val);
-            }
+        let mut request = config.client.list_objects_v2().bucket(bucket);
 
-            if let Some(token) = continuation_token {
-                request = request.continuation_token(token);
-            }
+        if let Some(prefix_val) = prefix {
+            request = request.prefix(prefix_val);
+        }
 
-            let response = request.send().await?;
+        list_objects_v2_resumable(config, request, |response| {
             page_count += 1;
 
             if let Some(contents) = response.contents {
                 for object in contents {
                     if let Some(key) = object.key {
                         let size = object.size.unwrap_or(0);
-                        objects.push(ObjectInfo { key, size });
+                        // Listings without a class (common on MinIO) are STANDARD, as in ls
+                        let storage_class = object
+                            .storage_class
+                            .map(|class| class.as_str().to_string())
+                            .unwrap_or_else(|| "STANDARD".to_string());
+                        objects.push(ObjectInfo {
+                            key,
+                            size,
+                            storage_class,
+                        });
                     }
                 }
             }
 
-            if response.is_truncated.unwrap_or(false) {
-                continuation_token = response.next_continuation_token;
-            } else {
-                break;
-            }
-        }
+            Ok(())
+        })
+        .await?;
 
         Ok(objects)
     }
@@ -257,21 +377,17 @@ async fn scan_objects(
                 use crate::otel::OTEL_INSTRUMENTS;
                 use opentelemetry::KeyValue;
 
-                OTEL_INSTRUMENTS
-                    .operations_total
-                    .add(1, &[KeyValue::new("operation", "scan_objects")]);
-
-                let duration_seconds = duration.as_millis() as f64 / 1000.0;
-                OTEL_INSTRUMENTS.operation_duration.record(
-                    duration_seconds,
-                    &[KeyValue::new("operation", "scan_objects")],
-                );
-
                 // Record pagination metrics
-                OTEL_INSTRUMENTS.operations_total.add(
-                    page_count,
-                    &[KeyValue::new("operation", "list_objects_page")],
-                );
+                if crate::otel::record_read_operation(
+                    &config.otel,
+                    &[KeyValue::new("operation", "scan_objects")],
+                    duration,
+                ) {
+                    OTEL_INSTRUMENTS.operations_total.add(
+                        page_count,
+                        &[KeyValue::new("operation", "list_objects_page")],
+                    );
+                }
             }
 
             Ok(objects)
@@ -290,6 +406,14 @@ async fn scan_objects(
     }
 }
 
+fn calculate_storage_class_sizes(objects: &[ObjectInfo]) -> HashMap<String, i64> {
+    let mut class_sizes = HashMap::new();
+    for object in objects {
+        *class_sizes.entry(object.storage_class.clone()).or_insert(0) += object.size;
+    }
+    class_sizes
+}
+
 fn calculate_directory_sizes(
     objects: &[ObjectInfo],
     max_depth: Option<usize>,
@@ -324,7 +448,7 @@ fn calculate_directory_sizes(
     directory_sizes
 }
 
-fn format_size_human_readable(size: i64) -> String {
+pub fn format_size_human_readable(size: i64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size_f = size as f64;
     let mut unit_index = 0;
@@ -362,7 +486,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -370,7 +503,16 @@ mod tests {
     async fn test_execute_non_s3_uri() {
         let config = create_mock_config();
 
-        let result = execute(&config, "/local/path", false, false, None).await;
+        let result = execute(
+            &config,
+            "/local/path",
+            false,
+            false,
+            None,
+            OutputFormat::Text,
+            &mut Vec::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         assert!(result
@@ -384,8 +526,13 @@ mod tests {
         let config = create_mock_config();
 
         let result = execute(
-            &config, "s3://", // invalid S3 URI
-            false, false, None,
+            &config,
+            "s3://", // invalid S3 URI
+            false,
+            false,
+            None,
+            OutputFormat::Text,
+            &mut Vec::new(),
         )
         .await;
 
@@ -396,7 +543,16 @@ mod tests {
     async fn test_execute_valid_s3_uri() {
         let config = create_mock_config();
 
-        let result = execute(&config, "s3://test-bucket/path/", false, false, None).await;
+        let result = execute(
+            &config,
+            "s3://test-bucket/path/",
+            false,
+            false,
+            None,
+            OutputFormat::Text,
+            &mut Vec::new(),
+        )
+        .await;
 
         // Will fail due to no AWS connection, but tests the routing
         assert!(result.is_err());
@@ -406,7 +562,16 @@ mod tests {
     async fn test_execute_with_summarize() {
         let config = create_mock_config();
 
-        let result = execute(&config, "s3://test-bucket", true, true, None).await;
+        let result = execute(
+            &config,
+            "s3://test-bucket",
+            true,
+            true,
+            None,
+            OutputFormat::Text,
+            &mut Vec::new(),
+        )
+        .await;
 
         // Will fail due to no AWS connection, but tests the routing
         assert!(result.is_err());
@@ -422,6 +587,8 @@ mod tests {
             false,
             false,
             Some(2),
+            OutputFormat::Text,
+            &mut Vec::new(),
         )
         .await;
 
@@ -435,18 +602,22 @@ mod tests {
             ObjectInfo {
                 key: "file1.txt".to_string(),
                 size: 100,
+                storage_class: "STANDARD".to_string(),
             },
             ObjectInfo {
                 key: "dir1/file2.txt".to_string(),
                 size: 200,
+                storage_class: "STANDARD".to_string(),
             },
             ObjectInfo {
                 key: "dir1/subdir/file3.txt".to_string(),
                 size: 300,
+                storage_class: "STANDARD".to_string(),
             },
             ObjectInfo {
                 key: "dir2/file4.txt".to_string(),
                 size: 400,
+                storage_class: "STANDARD".to_string(),
             },
         ];
 
@@ -470,6 +641,7 @@ mod tests {
         let objects = vec![ObjectInfo {
             key: "dir1/subdir1/subdir2/file.txt".to_string(),
             size: 100,
+            storage_class: "STANDARD".to_string(),
         }];
 
         let sizes = calculate_directory_sizes(&objects, Some(2));
@@ -481,6 +653,113 @@ mod tests {
         assert!(!sizes.contains_key("dir1/subdir1/subdir2"));
     }
 
+    #[test]
+    fn test_calculate_storage_class_sizes() {
+        let objects = vec![
+            ObjectInfo {
+                key: "a.txt".to_string(),
+                size: 100,
+                storage_class: "STANDARD".to_string(),
+            },
+            ObjectInfo {
+                key: "old/b.tar".to_string(),
+                size: 5000,
+                storage_class: "GLACIER".to_string(),
+            },
+            ObjectInfo {
+                key: "c.txt".to_string(),
+                size: 200,
+                storage_class: "STANDARD".to_string(),
+            },
+        ];
+
+        let class_sizes = calculate_storage_class_sizes(&objects);
+        assert_eq!(class_sizes.len(), 2);
+        assert_eq!(class_sizes.get("STANDARD"), Some(&300));
+        assert_eq!(class_sizes.get("GLACIER"), Some(&5000));
+    }
+
+    #[test]
+    fn test_write_usage() {
+        let sizes = HashMap::from([
+            ("logs".to_string(), 2048i64),
+            (String::new(), 3072),
+            ("data".to_string(), 1024),
+        ]);
+        let class_sizes = HashMap::from([
+            ("STANDARD".to_string(), 1024i64),
+            ("GLACIER".to_string(), 2048),
+        ]);
+
+        let mut out = Vec::new();
+        write_usage(
+            &mut out,
+            "s3://bucket/",
+            &sizes,
+            &class_sizes,
+            3072,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "3072 s3://bucket/\n1024 s3://bucket/data\n2048 s3://bucket/logs\n\
+             GLACIER: 2048, STANDARD: 1024\n"
+        );
+
+        let mut out = Vec::new();
+        write_usage(
+            &mut out,
+            "s3://bucket/",
+            &sizes,
+            &class_sizes,
+            3072,
+            true,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "3.0 KB s3://bucket/\nGLACIER: 2.0 KB, STANDARD: 1.0 KB\n"
+        );
+    }
+
+    #[test]
+    fn test_write_usage_json() {
+        let sizes = HashMap::from([
+            ("logs".to_string(), 2048i64),
+            (String::new(), 3072),
+            ("data".to_string(), 1024),
+        ]);
+
+        let class_sizes = HashMap::from([
+            ("STANDARD".to_string(), 1024i64),
+            ("GLACIER".to_string(), 2048),
+        ]);
+
+        let mut out = Vec::new();
+        write_usage_json(&mut out, &sizes, &class_sizes, 3072, 3, false).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "total_bytes": 3072,
+                "object_count": 3,
+                "storage_classes": { "GLACIER": 2048, "STANDARD": 1024 },
+                "prefixes": [
+                    { "prefix": "data", "bytes": 1024 },
+                    { "prefix": "logs", "bytes": 2048 }
+                ]
+            })
+        );
+
+        let mut out = Vec::new();
+        write_usage_json(&mut out, &sizes, &class_sizes, 3072, 3, true).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value["prefixes"], serde_json::json!([]));
+    }
+
     #[test]
     fn test_format_size_human_readable() {
         assert_eq!(format_size_human_readable(0), "0 B");
@@ -512,6 +791,7 @@ mod tests {
         let obj = ObjectInfo {
             key: "test.txt".to_string(),
             size: 1024,
+            storage_class: "STANDARD".to_string(),
         };
 
         let debug_str = format!("{obj:?}");
@@ -534,6 +814,7 @@ mod tests {
         let objects = vec![ObjectInfo {
             key: "file.txt".to_string(),
             size: 100,
+            storage_class: "STANDARD".to_string(),
         }];
 
         let sizes = calculate_directory_sizes(&objects, None);
@@ -552,4 +833,38 @@ mod tests {
         assert!(!is_s3_uri("/local/path"));
         assert!(!is_s3_uri("http://example.com"));
     }
+
+    #[tokio::test]
+    async fn test_du_advances_metrics() {
+        use crate::otel::GLOBAL_METRICS;
+        use std::sync::atomic::Ordering;
+
+        let config = crate::commands::test_client::canned_config(|_, _| {
+            (
+                200,
+                "<ListBucketResult>\
+                   <Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                   <Contents><Key>logs/a.gz</Key><Size>1000</Size></Contents>\
+                 </ListBucketResult>"
+                    .to_string(),
+            )
+        });
+        let lists = GLOBAL_METRICS.lists_total.load(Ordering::Relaxed);
+        let mut out = Vec::new();
+
+        execute(
+            &config,
+            "s3://bucket",
+            false,
+            true,
+            None,
+            OutputFormat::Text,
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert!(String::from_utf8(out).unwrap().contains("1000"));
+        assert!(GLOBAL_METRICS.lists_total.load(Ordering::Relaxed) > lists);
+    }
 }
@@ -0,0 +1,161 @@
+use anyhow::Result;
+use aws_sdk_s3::error::SdkError;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use log::debug;
+
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::config::Config;
+use crate::errors::ExitCodeError;
+
+/// The object isn't there
+pub const EXIT_NOT_FOUND: i32 = 1;
+
+/// The request failed for another reason (network, bad URI, throttling...)
+pub const EXIT_ERROR: i32 = 2;
+
+/// S3 refused the request
+pub const EXIT_ACCESS_DENIED: i32 = 3;
+
+/// What a HEAD request said about the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Presence {
+    Exists,
+    NotFound,
+    AccessDenied,
+    Failed,
+}
+
+impl Presence {
+    /// HEAD responses have no body, so the status code is all there is
+    fn from_status(status: Option<u16>) -> Self {
+        match status {
+            Some(404) => Presence::NotFound,
+            Some(401 | 403) => Presence::AccessDenied,
+            _ => Presence::Failed,
+        }
+    }
+
+    /// What a failed request says, with the error for the message
+    fn from_error<E>(err: SdkError<E, HttpResponse>) -> (Self, Option<anyhow::Error>)
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let status = err
+            .raw_response()
+            .map(|response| response.status().as_u16());
+        (Self::from_status(status), Some(err.into()))
+    }
+}
+
+/// The object, or the bucket for a bare `s3://bucket`, that `s3_uri` names
+pub fn parse_target(command: &str, s3_uri: &str) -> Result<S3Uri> {
+    if !is_s3_uri(s3_uri) {
+        return Err(anyhow::anyhow!(
+            "{command} only works with S3 URIs (s3://...), got '{s3_uri}'"
+        ));
+    }
+    S3Uri::parse(s3_uri)
+}
+
+/// Ask S3 whether the target exists: HeadObject for a key, HeadBucket for a
+/// bare bucket. Failures come with the error for reporting.
+pub async fn check(config: &Config, uri: &S3Uri) -> (Presence, Option<anyhow::Error>) {
+    if uri.key_or_empty().is_empty() {
+        let request = config.client.head_bucket().bucket(&uri.bucket);
+        match request.send().await {
+            Ok(_) => (Presence::Exists, None),
+            Err(e) => Presence::from_error(e),
+        }
+    } else {
+        let request = config
+            .client
+            .head_object()
+            .bucket(&uri.bucket)
+            .key(uri.key_or_empty());
+        match request.send().await {
+            Ok(_) => (Presence::Exists, None),
+            Err(e) => Presence::from_error(e),
+        }
+    }
+}
+
+/// Check that an object (HeadObject) or, for a bare `s3://bucket`, a bucket
+/// (HeadBucket) exists. Success prints nothing; anything else becomes an
+/// [`ExitCodeError`] so scripts can tell "missing" from "forbidden" from
+/// "broken". Without s3:ListBucket, S3 reports missing objects as 403.
+pub async fn execute(config: &Config, s3_uri: &str, quiet: bool) -> Result<()> {
+    let fail = |code: i32, message: String| -> anyhow::Error {
+        ExitCodeError {
+            code,
+            message: (!quiet).then_some(message),
+        }
+        .into()
+    };
+
+    let uri = parse_target("exists", s3_uri).map_err(|e| fail(EXIT_ERROR, e.to_string()))?;
+    let (presence, error) = check(config, &uri).await;
+    debug!("exists {uri}: {presence:?}");
+
+    match presence {
+        Presence::Exists => Ok(()),
+        Presence::NotFound => Err(fail(EXIT_NOT_FOUND, format!("{uri} does not exist"))),
+        Presence::AccessDenied => {
+            crate::otel::OTEL_INSTRUMENTS
+                .record_error_with_type(&format!("Access denied checking {uri}"));
+            Err(fail(EXIT_ACCESS_DENIED, format!("access denied to {uri}")))
+        }
+        Presence::Failed => {
+            let reason = error.map(|e| format!("{e:#}")).unwrap_or_default();
+            let message = format!("Failed to check {uri}: {reason}");
+            crate::otel::OTEL_INSTRUMENTS.record_error_with_type(&message);
+            Err(fail(EXIT_ERROR, message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_client::canned_config;
+
+    async fn exit_code(status: u16, s3_uri: &str, quiet: bool) -> Option<(i32, Option<String>)> {
+        let config = canned_config(move |_, _| (status, String::new()));
+        execute(&config, s3_uri, quiet).await.err().map(|e| {
+            let exit = e.downcast::<ExitCodeError>().unwrap();
+            (exit.code, exit.message)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_exit_codes() {
+        assert_eq!(exit_code(200, "s3://bucket/key", false).await, None);
+        assert_eq!(exit_code(200, "s3://bucket", false).await, None);
+        assert_eq!(
+            exit_code(404, "s3://bucket/key", false).await,
+            Some((
+                EXIT_NOT_FOUND,
+                Some("s3://bucket/key does not exist".to_string())
+            ))
+        );
+        assert_eq!(
+            exit_code(403, "s3://bucket/key", false).await.unwrap().0,
+            EXIT_ACCESS_DENIED
+        );
+        assert_eq!(
+            exit_code(500, "s3://bucket/key", false).await.unwrap().0,
+            EXIT_ERROR
+        );
+        assert_eq!(
+            exit_code(200, "./local.txt", false).await.unwrap().0,
+            EXIT_ERROR
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quiet_drops_the_message() {
+        assert_eq!(
+            exit_code(404, "s3://bucket/key", true).await,
+            Some((EXIT_NOT_FOUND, None))
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use anyhow::Result;
+use log::info;
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::Instant;
+
+use crate::commands::du::format_size_human_readable;
+use crate::commands::paginate::list_objects_v2_resumable;
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::config::Config;
+use crate::filtering::parse_size_filter;
+
+/// Objects grouped by (ETag, size) while a listing streams past. Only keys
+/// are kept per group, so memory grows with the key count rather than with
+/// the full listing rows.
+///
+/// ETag equality is a content match for single-part uploads. Multipart
+/// ETags depend on the part size and SSE-KMS ETags are not content hashes,
+/// so identical objects uploaded those ways can be missed, but different
+/// content is never reported as a duplicate.
+#[derive(Debug)]
+struct DuplicateIndex {
+    min_size: i64,
+    groups: HashMap<(String, i64), Vec<String>>,
+}
+
+#[derive(Debug, PartialEq)]
+struct DuplicateSet {
+    etag: String,
+    size: i64,
+    keys: Vec<String>,
+}
+
+impl DuplicateSet {
+    /// Bytes freed by keeping one copy and deleting the rest
+    fn reclaimable(&self) -> i64 {
+        self.size * (self.keys.len() as i64 - 1)
+    }
+}
+
+impl DuplicateIndex {
+    fn new(min_size: i64) -> Self {
+        Self {
+            min_size,
+            groups: HashMap::new(),
+        }
+    }
+
+    /// Empty objects all share one ETag and free nothing, so they are skipped
+    /// along with anything under `--min-size`
+    fn add(&mut self, key: String, etag: &str, size: i64) {
+        if size == 0 || size < self.min_size {
+            return;
+        }
+        self.groups
+            .entry((etag.trim_matches('"').to_string(), size))
+            .or_default()
+            .push(key);
+    }
+
+    /// Groups with more than one key, largest savings first
+    fn into_duplicate_sets(self) -> Vec<DuplicateSet> {
+        let mut sets: Vec<DuplicateSet> = self
+            .groups
+            .into_iter()
+            .filter(|(_, keys)| keys.len() > 1)
+            .map(|((etag, size), mut keys)| {
+                keys.sort();
+                DuplicateSet { etag, size, keys }
+            })
+            .collect();
+        sets.sort_by(|a, b| {
+            b.reclaimable()
+                .cmp(&a.reclaimable())
+                .then_with(|| a.etag.cmp(&b.etag))
+        });
+        sets
+    }
+}
+
+pub async fn execute(
+    config: &Config,
+    s3_uri: &str,
+    min_size: Option<&str>,
+    human_readable: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let start_time = Instant::now();
+
+    if !is_s3_uri(s3_uri) {
+        return Err(anyhow::anyhow!(
+            "find-dupes command only works with S3 URIs (s3://...)"
+        ));
+    }
+
+    let uri = S3Uri::parse(s3_uri)?;
+    let min_size = min_size.map(parse_size_filter).transpose()?.unwrap_or(0);
+
+    info!("Looking for duplicate objects in: {s3_uri}");
+
+    let mut index = DuplicateIndex::new(min_size);
+    let mut scanned = 0u64;
+
+    let mut request = config.client.list_objects_v2().bucket(&uri.bucket);
+    if let Some(prefix) = uri.key.as_deref() {
+        request = request.prefix(prefix);
+    }
+
+    let result = list_objects_v2_resumable(config, request, |response| {
+        for object in response.contents.unwrap_or_default() {
+            scanned += 1;
+            if let (Some(key), Some(etag)) = (object.key, object.e_tag) {
+                index.add(key, &etag, object.size.unwrap_or(0));
+            }
+        }
+        Ok(())
+    })
+    .await;
+
+    if let Err(e) = result {
+        use crate::otel::OTEL_INSTRUMENTS;
+
+        OTEL_INSTRUMENTS.record_error_with_type(&format!(
+            "Failed to scan objects in bucket {}: {e}",
+            uri.bucket
+        ));
+        return Err(e);
+    }
+
+    let sets = index.into_duplicate_sets();
+
+    {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS
+            .operations_total
+            .add(1, &[KeyValue::new("operation", "find_dupes")]);
+        OTEL_INSTRUMENTS.operation_duration.record(
+            start_time.elapsed().as_millis() as f64 / 1000.0,
+            &[KeyValue::new("operation", "find_dupes")],
+        );
+    }
+
+    info!(
+        "Scanned {scanned} objects, found {} duplicate sets",
+        sets.len()
+    );
+
+    write_report(out, &uri.bucket, &sets, human_readable)?;
+    Ok(())
+}
+
+/// One block per duplicate set followed by a total of reclaimable bytes
+fn write_report(
+    out: &mut dyn Write,
+    bucket: &str,
+    sets: &[DuplicateSet],
+    human_readable: bool,
+) -> std::io::Result<()> {
+    let format_size = |size: i64| {
+        if human_readable {
+            format_size_human_readable(size)
+        } else {
+            format!("{size} bytes")
+        }
+    };
+
+    for set in sets {
+        writeln!(
+            out,
+            "{} copies of {}, {} reclaimable (etag {})",
+            set.keys.len(),
+            format_size(set.size),
+            format_size(set.reclaimable()),
+            set.etag
+        )?;
+        for key in &set.keys {
+            writeln!(out, "  s3://{bucket}/{key}")?;
+        }
+        writeln!(out)?;
+    }
+
+    let duplicates: usize = sets.iter().map(|set| set.keys.len() - 1).sum();
+    let reclaimable: i64 = sets.iter().map(DuplicateSet::reclaimable).sum();
+    writeln!(
+        out,
+        "Total: {} duplicate sets, {duplicates} redundant copies, {} reclaimable",
+        sets.len(),
+        format_size(reclaimable)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_sets_grouped_by_etag_and_size() {
+        let mut index = DuplicateIndex::new(0);
+        index.add("b/copy.bin".to_string(), "\"aaa\"", 100);
+        index.add("a/orig.bin".to_string(), "\"aaa\"", 100);
+        index.add("unique.bin".to_string(), "\"bbb\"", 100);
+        // Same ETag but a different size is not the same content
+        index.add("other.bin".to_string(), "\"aaa\"", 50);
+        index.add("big1".to_string(), "\"ccc\"", 1000);
+        index.add("big2".to_string(), "\"ccc\"", 1000);
+        index.add("empty1".to_string(), "\"d41d\"", 0);
+        index.add("empty2".to_string(), "\"d41d\"", 0);
+
+        let sets = index.into_duplicate_sets();
+        assert_eq!(
+            sets,
+            vec![
+                DuplicateSet {
+                    etag: "ccc".to_string(),
+                    size: 1000,
+                    keys: vec!["big1".to_string(), "big2".to_string()],
+                },
+                DuplicateSet {
+                    etag: "aaa".to_string(),
+                    size: 100,
+                    keys: vec!["a/orig.bin".to_string(), "b/copy.bin".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_min_size_skips_small_objects() {
+        let mut index = DuplicateIndex::new(500);
+        index.add("small1".to_string(), "aaa", 100);
+        index.add("small2".to_string(), "aaa", 100);
+        index.add("large1".to_string(), "bbb", 500);
+        index.add("large2".to_string(), "bbb", 500);
+        index.add("large3".to_string(), "bbb", 500);
+
+        let sets = index.into_duplicate_sets();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].reclaimable(), 1000);
+    }
+
+    #[test]
+    fn test_write_report() {
+        let sets = vec![DuplicateSet {
+            etag: "aaa".to_string(),
+            size: 2048,
+            keys: vec!["a.bin".to_string(), "b.bin".to_string()],
+        }];
+
+        let mut out = Vec::new();
+        write_report(&mut out, "bucket", &sets, false).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "2 copies of 2048 bytes, 2048 bytes reclaimable (etag aaa)\n\
+             \x20 s3://bucket/a.bin\n\
+             \x20 s3://bucket/b.bin\n\
+             \n\
+             Total: 1 duplicate sets, 1 redundant copies, 2048 bytes reclaimable\n"
+        );
+    }
+}
@@ -49,9 +49,16 @@ pub async fn execute(
 
     // Use the cp command to perform the actual download
     let result = cp::execute(
-        config, s3_uri, &dest, recursive, false, // dryrun = false
+        config,
+        s3_uri,
+        &dest,
+        recursive,
+        false, // dryrun = false
         1,     // max_concurrent = 1 (get is typically single-threaded)
-        force, include, exclude,
+        force,
+        include,
+        exclude,
+        &cp::TransferOptions::default(),
     )
     .await;
 
@@ -118,7 +125,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
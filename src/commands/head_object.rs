@@ -1,11 +1,335 @@
 use anyhow::Result;
+use aws_sdk_s3::operation::get_object_attributes::GetObjectAttributesOutput;
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::types::ObjectAttributes;
 use log::info;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::time::Instant;
 
+use crate::args::OutputFormat;
 use crate::commands::s3_uri::{is_s3_uri, S3Uri};
 use crate::config::Config;
 
-pub async fn execute(config: &Config, s3_uri: &str) -> Result<()> {
+/// What head-object reports about an object. Absent headers are `null` in
+/// `--output json`; `metadata` holds the `x-amz-meta-*` user metadata.
+#[derive(Debug, Serialize)]
+struct ObjectMetadata {
+    key: String,
+    content_length: Option<i64>,
+    content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    storage_class: Option<String>,
+    server_side_encryption: Option<String>,
+    version_id: Option<String>,
+    metadata: BTreeMap<String, String>,
+    /// `--checksum`; left out of the JSON when not asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksums: Option<ObjectChecksums>,
+    /// `--preview-bytes`; left out of the JSON when not asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview: Option<ObjectPreview>,
+}
+
+impl ObjectMetadata {
+    fn from_response(key: &str, response: HeadObjectOutput) -> Result<Self> {
+        Ok(Self {
+            key: key.to_string(),
+            content_length: response.content_length,
+            content_type: response.content_type,
+            etag: response.e_tag,
+            last_modified: response
+                .last_modified
+                .map(|time| time.fmt(aws_smithy_types::date_time::Format::DateTime))
+                .transpose()?,
+            storage_class: response
+                .storage_class
+                .map(|class| class.as_str().to_string()),
+            server_side_encryption: response
+                .server_side_encryption
+                .map(|sse| sse.as_str().to_string()),
+            version_id: response.version_id,
+            metadata: response.metadata.unwrap_or_default().into_iter().collect(),
+            checksums: None,
+            preview: None,
+        })
+    }
+
+    /// One `Header: value` line per header present, user metadata last
+    fn write_text(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        writeln!(out, "Key: {}", self.key)?;
+        if let Some(content_length) = self.content_length {
+            writeln!(out, "Content-Length: {content_length}")?;
+        }
+        let headers = [
+            ("Content-Type", &self.content_type),
+            ("ETag", &self.etag),
+            ("Last-Modified", &self.last_modified),
+            ("Storage-Class", &self.storage_class),
+            ("Server-Side-Encryption", &self.server_side_encryption),
+            ("VersionId", &self.version_id),
+        ];
+        for (name, value) in headers {
+            if let Some(value) = value {
+                writeln!(out, "{name}: {value}")?;
+            }
+        }
+        for (key, value) in &self.metadata {
+            writeln!(out, "Metadata-{key}: {value}")?;
+        }
+        if let Some(checksums) = &self.checksums {
+            checksums.write_text(out)?;
+        }
+        if let Some(preview) = &self.preview {
+            preview.write_text(out, self.content_length)?;
+        }
+        Ok(())
+    }
+}
+
+/// The additional checksum S3 stores for the object (from
+/// GetObjectAttributes), base64 encoded as S3 returns it. Objects uploaded
+/// without one have every algorithm `null`.
+#[derive(Debug, Default, Serialize)]
+struct ObjectChecksums {
+    crc32: Option<String>,
+    crc32c: Option<String>,
+    crc64nvme: Option<String>,
+    sha1: Option<String>,
+    sha256: Option<String>,
+    /// `FULL_OBJECT`, or `COMPOSITE` for a checksum of the part checksums
+    checksum_type: Option<String>,
+    /// Set for multipart uploads
+    parts_count: Option<i32>,
+}
+
+impl ObjectChecksums {
+    fn from_attributes(attributes: &GetObjectAttributesOutput) -> Self {
+        let checksum = attributes.checksum();
+        let value = |get: fn(&aws_sdk_s3::types::Checksum) -> Option<&str>| {
+            checksum.and_then(get).map(str::to_string)
+        };
+        Self {
+            crc32: value(|c| c.checksum_crc32()),
+            crc32c: value(|c| c.checksum_crc32_c()),
+            crc64nvme: value(|c| c.checksum_crc64_nvme()),
+            sha1: value(|c| c.checksum_sha1()),
+            sha256: value(|c| c.checksum_sha256()),
+            checksum_type: checksum
+                .and_then(|c| c.checksum_type())
+                .map(|kind| kind.as_str().to_string()),
+            parts_count: attributes
+                .object_parts()
+                .and_then(|parts| parts.total_parts_count()),
+        }
+    }
+
+    fn write_text(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        let checksums = [
+            ("CRC32", &self.crc32),
+            ("CRC32C", &self.crc32c),
+            ("CRC64NVME", &self.crc64nvme),
+            ("SHA1", &self.sha1),
+            ("SHA256", &self.sha256),
+        ];
+        let mut any = false;
+        for (name, value) in checksums {
+            if let Some(value) = value {
+                writeln!(out, "Checksum-{name}: {value}")?;
+                any = true;
+            }
+        }
+        if !any {
+            writeln!(
+                out,
+                "Checksum: none (uploaded without an additional checksum)"
+            )?;
+        }
+        if let Some(checksum_type) = &self.checksum_type {
+            writeln!(out, "Checksum-Type: {checksum_type}")?;
+        }
+        if let Some(parts_count) = self.parts_count {
+            writeln!(out, "Parts-Count: {parts_count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Fetch the object's checksum and part count
+async fn fetch_checksums(config: &Config, uri: &S3Uri) -> Result<ObjectChecksums> {
+    let request = config
+        .client
+        .get_object_attributes()
+        .bucket(&uri.bucket)
+        .key(uri.key_or_empty())
+        .object_attributes(ObjectAttributes::Checksum)
+        .object_attributes(ObjectAttributes::ObjectParts)
+        // Only the total is shown, not the parts themselves
+        .max_parts(1);
+    let attributes = request
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get checksums for {uri}: {e}"))?;
+
+    Ok(ObjectChecksums::from_attributes(&attributes))
+}
+
+/// A byte range of the object fetched for `--preview-bytes`. Text that is
+/// valid UTF-8 without control characters is shown as is; anything else as
+/// hex, so binary content can't garble the terminal.
+#[derive(Debug, Serialize)]
+struct ObjectPreview {
+    offset: u64,
+    length: usize,
+    /// `text` or `hex`
+    encoding: &'static str,
+    /// The text, or the bytes as one lowercase hex string
+    content: String,
+    #[serde(skip)]
+    bytes: Vec<u8>,
+}
+
+impl ObjectPreview {
+    fn new(offset: u64, bytes: Vec<u8>) -> Self {
+        let (encoding, content) = match preview_text(&bytes, offset > 0) {
+            Some(text) => ("text", text),
+            None => (
+                "hex",
+                bytes.iter().map(|byte| format!("{byte:02x}")).collect(),
+            ),
+        };
+        Self {
+            offset,
+            length: bytes.len(),
+            encoding,
+            content,
+            bytes,
+        }
+    }
+
+    fn write_text(&self, out: &mut dyn Write, total: Option<i64>) -> std::io::Result<()> {
+        let end = self.offset + self.length as u64;
+        match total {
+            Some(total) => writeln!(out, "Preview (bytes {}-{end} of {total}):", self.offset)?,
+            None => writeln!(out, "Preview (bytes {}-{end}):", self.offset)?,
+        }
+        if self.encoding == "text" {
+            write!(out, "{}", self.content)?;
+            if !self.content.ends_with('\n') {
+                writeln!(out)?;
+            }
+            return Ok(());
+        }
+        for line in hex_dump(self.offset, &self.bytes) {
+            writeln!(out, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The bytes as text, or `None` if they aren't printable UTF-8. A range can
+/// cut a multi-byte character in two, so a partial character at the end (and
+/// at the start, for ranges not starting at zero) is dropped.
+fn preview_text(bytes: &[u8], mid_object: bool) -> Option<String> {
+    let mut bytes = bytes;
+    if mid_object {
+        let continuation = bytes
+            .iter()
+            .take(3)
+            .take_while(|byte| (0x80..0xc0).contains(*byte))
+            .count();
+        bytes = &bytes[continuation..];
+    }
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&bytes[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+    text.chars()
+        .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .then(|| text.to_string())
+}
+
+/// `xxd`-style lines: offset, 16 hex bytes, then the printable ASCII
+fn hex_dump(offset: u64, bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{byte:02x}")).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<47}  |{ascii}|",
+                offset + (row * 16) as u64,
+                hex.join(" ")
+            )
+        })
+        .collect()
+}
+
+/// The `Range` header for a preview of `length` bytes and the offset it
+/// starts at, or `None` for an empty object (S3 rejects ranges on those)
+fn preview_range(length: u64, tail: bool, size: Option<i64>) -> Option<(u64, String)> {
+    match size.map(|size| size.max(0) as u64) {
+        Some(0) => None,
+        Some(size) if tail => {
+            let start = size.saturating_sub(length);
+            Some((start, format!("bytes={start}-")))
+        }
+        Some(size) => Some((0, format!("bytes=0-{}", length.min(size) - 1))),
+        // Without a size the tail offset is unknown; report it from zero
+        None if tail => Some((0, format!("bytes=-{length}"))),
+        None => Some((0, format!("bytes=0-{}", length - 1))),
+    }
+}
+
+/// Fetch the preview range with a ranged GetObject
+async fn fetch_preview(
+    config: &Config,
+    uri: &S3Uri,
+    length: u64,
+    tail: bool,
+    size: Option<i64>,
+) -> Result<ObjectPreview> {
+    let Some((offset, range)) = preview_range(length, tail, size) else {
+        return Ok(ObjectPreview::new(0, Vec::new()));
+    };
+
+    let request = config
+        .client
+        .get_object()
+        .bucket(&uri.bucket)
+        .key(uri.key_or_empty())
+        .range(range);
+    let response = request
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read preview of {uri}: {e}"))?;
+    let bytes = response.body.collect().await?.into_bytes().to_vec();
+
+    Ok(ObjectPreview::new(offset, bytes))
+}
+
+pub async fn execute(
+    config: &Config,
+    s3_uri: &str,
+    preview_bytes: Option<u64>,
+    preview_tail: bool,
+    checksum: bool,
+    output: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
     let start_time = Instant::now();
 
     if !is_s3_uri(s3_uri) {
@@ -24,13 +348,12 @@ pub async fn execute(config: &Config, s3_uri: &str) -> Result<()> {
 
     info!("Getting metadata for: {s3_uri}");
 
-    let result = config
+    let request = config
         .client
         .head_object()
         .bucket(&uri.bucket)
-        .key(uri.key_or_empty())
-        .send()
-        .await;
+        .key(uri.key_or_empty());
+    let result = request.send().await;
 
     match result {
         Ok(response) => {
@@ -38,62 +361,29 @@ pub async fn execute(config: &Config, s3_uri: &str) -> Result<()> {
 
             // Record head_object operation using proper OTEL SDK
             {
-                use crate::otel::OTEL_INSTRUMENTS;
                 use opentelemetry::KeyValue;
 
-                OTEL_INSTRUMENTS
-                    .operations_total
-                    .add(1, &[KeyValue::new("operation", "head_object")]);
-
-                let duration_seconds = duration.as_millis() as f64 / 1000.0;
-                OTEL_INSTRUMENTS.operation_duration.record(
-                    duration_seconds,
+                crate::otel::record_read_operation(
+                    &config.otel,
                     &[KeyValue::new("operation", "head_object")],
+                    duration,
                 );
             }
 
-            // Print object metadata
-            println!("Key: {}", uri.key_or_empty());
-
-            if let Some(content_length) = response.content_length {
-                println!("Content-Length: {content_length}");
-            }
-
-            if let Some(content_type) = response.content_type {
-                println!("Content-Type: {content_type}");
-            }
-
-            if let Some(etag) = response.e_tag {
-                println!("ETag: {etag}");
+            let mut metadata = ObjectMetadata::from_response(uri.key_or_empty(), response)?;
+            if checksum {
+                metadata.checksums = Some(fetch_checksums(config, &uri).await?);
             }
-
-            if let Some(last_modified) = response.last_modified {
-                println!(
-                    "Last-Modified: {}",
-                    last_modified.fmt(aws_smithy_types::date_time::Format::DateTime)?
+            if let Some(length) = preview_bytes {
+                metadata.preview = Some(
+                    fetch_preview(config, &uri, length, preview_tail, metadata.content_length)
+                        .await?,
                 );
             }
-
-            if let Some(storage_class) = response.storage_class {
-                println!("Storage-Class: {}", storage_class.as_str());
-            }
-
-            if let Some(server_side_encryption) = response.server_side_encryption {
-                println!(
-                    "Server-Side-Encryption: {}",
-                    server_side_encryption.as_str()
-                );
-            }
-
-            if let Some(version_id) = response.version_id {
-                println!("VersionId: {version_id}");
-            }
-
-            // Print any custom metadata
-            if let Some(metadata) = response.metadata {
-                for (key, value) in metadata {
-                    println!("Metadata-{key}: {value}");
-                }
+            if output == OutputFormat::Json {
+                writeln!(out, "{}", serde_json::to_string_pretty(&metadata)?)?;
+            } else {
+                metadata.write_text(out)?;
             }
 
             Ok(())
@@ -137,7 +427,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -145,7 +444,16 @@ mod tests {
     async fn test_execute_non_s3_uri() {
         let config = create_mock_config();
 
-        let result = execute(&config, "/local/path/file.txt").await;
+        let result = execute(
+            &config,
+            "/local/path/file.txt",
+            None,
+            false,
+            false,
+            OutputFormat::Text,
+            &mut Vec::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         assert!(result
@@ -159,7 +467,13 @@ mod tests {
         let config = create_mock_config();
 
         let result = execute(
-            &config, "s3://", // invalid S3 URI
+            &config,
+            "s3://", // invalid S3 URI
+            None,
+            false,
+            false,
+            OutputFormat::Text,
+            &mut Vec::new(),
         )
         .await;
 
@@ -173,6 +487,11 @@ mod tests {
         let result = execute(
             &config,
             "s3://bucket", // bucket without key
+            None,
+            false,
+            false,
+            OutputFormat::Text,
+            &mut Vec::new(),
         )
         .await;
 
@@ -190,6 +509,11 @@ mod tests {
         let result = execute(
             &config,
             "s3://bucket/", // bucket with empty key
+            None,
+            false,
+            false,
+            OutputFormat::Text,
+            &mut Vec::new(),
         )
         .await;
 
@@ -204,12 +528,68 @@ mod tests {
     async fn test_execute_valid_s3_uri() {
         let config = create_mock_config();
 
-        let result = execute(&config, "s3://bucket/file.txt").await;
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            None,
+            false,
+            false,
+            OutputFormat::Text,
+            &mut Vec::new(),
+        )
+        .await;
 
         // Will fail due to no AWS connection, but tests the routing
         assert!(result.is_err());
     }
 
+    fn sample_response() -> HeadObjectOutput {
+        HeadObjectOutput::builder()
+            .content_length(1024)
+            .content_type("text/plain")
+            .e_tag("\"d41d8cd98f00b204e9800998ecf8427e\"")
+            .last_modified(aws_smithy_types::DateTime::from_secs(1_705_314_600))
+            .metadata("owner", "web")
+            .metadata("build", "42")
+            .build()
+    }
+
+    #[test]
+    fn test_object_metadata_text() {
+        let metadata = ObjectMetadata::from_response("a.txt", sample_response()).unwrap();
+        let mut out = Vec::new();
+        metadata.write_text(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Key: a.txt\n\
+             Content-Length: 1024\n\
+             Content-Type: text/plain\n\
+             ETag: \"d41d8cd98f00b204e9800998ecf8427e\"\n\
+             Last-Modified: 2024-01-15T10:30:00Z\n\
+             Metadata-build: 42\n\
+             Metadata-owner: web\n"
+        );
+    }
+
+    #[test]
+    fn test_object_metadata_json() {
+        let metadata = ObjectMetadata::from_response("a.txt", sample_response()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::json!({
+                "key": "a.txt",
+                "content_length": 1024,
+                "content_type": "text/plain",
+                "etag": "\"d41d8cd98f00b204e9800998ecf8427e\"",
+                "last_modified": "2024-01-15T10:30:00Z",
+                "storage_class": null,
+                "server_side_encryption": null,
+                "version_id": null,
+                "metadata": { "build": "42", "owner": "web" }
+            })
+        );
+    }
+
     #[test]
     fn test_s3_uri_validation() {
         // Test that we can distinguish valid from invalid URIs
@@ -255,4 +635,165 @@ mod tests {
             assert!(!value.is_empty());
         }
     }
+
+    #[test]
+    fn test_preview_range() {
+        assert_eq!(
+            preview_range(64, false, Some(1024)),
+            Some((0, "bytes=0-63".to_string()))
+        );
+        // Asking for more than the object holds
+        assert_eq!(
+            preview_range(64, false, Some(10)),
+            Some((0, "bytes=0-9".to_string()))
+        );
+        assert_eq!(
+            preview_range(64, true, Some(1024)),
+            Some((960, "bytes=960-".to_string()))
+        );
+        assert_eq!(
+            preview_range(64, true, Some(10)),
+            Some((0, "bytes=0-".to_string()))
+        );
+        assert_eq!(preview_range(64, false, Some(0)), None);
+    }
+
+    #[test]
+    fn test_preview_text_detection() {
+        assert_eq!(
+            preview_text(b"id,name\n1,caf\xc3\xa9\n", false).as_deref(),
+            Some("id,name\n1,caf\u{e9}\n")
+        );
+        // A character cut in half at either end of the range
+        assert_eq!(preview_text(b"caf\xc3", false).as_deref(), Some("caf"));
+        assert_eq!(preview_text(b"\xa9 ok", true).as_deref(), Some(" ok"));
+        // Binary and terminal escapes go to hex
+        assert_eq!(preview_text(b"\x89PNG\r\n\x1a\n\0", false), None);
+        assert_eq!(preview_text(b"\x1b[2Jgotcha", false), None);
+    }
+
+    #[test]
+    fn test_preview_hex_dump() {
+        let preview = ObjectPreview::new(16, b"\x1f\x8b\x08\0hello".to_vec());
+        assert_eq!(preview.encoding, "hex");
+        assert_eq!(preview.content, "1f8b080068656c6c6f");
+
+        let mut out = Vec::new();
+        preview.write_text(&mut out, Some(100)).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Preview (bytes 16-25 of 100):\n\
+             00000010  1f 8b 08 00 68 65 6c 6c 6f                       |....hello|\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_preview() {
+        let config = crate::commands::test_client::canned_config(|method, _| match method {
+            "GET" => (200, "id,name\n1,alice\n".to_string()),
+            _ => (200, String::new()),
+        });
+        let mut out = Vec::new();
+
+        execute(
+            &config,
+            "s3://bucket/users.csv",
+            Some(64),
+            false,
+            false,
+            OutputFormat::Json,
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            json["preview"],
+            serde_json::json!({
+                "offset": 0,
+                "length": 16,
+                "encoding": "text",
+                "content": "id,name\n1,alice\n"
+            })
+        );
+    }
+
+    #[test]
+    fn test_object_checksums_text() {
+        let attributes = GetObjectAttributesOutput::builder()
+            .checksum(
+                aws_sdk_s3::types::Checksum::builder()
+                    .checksum_crc32_c("yZRlqg==")
+                    .checksum_type(aws_sdk_s3::types::ChecksumType::Composite)
+                    .build(),
+            )
+            .object_parts(
+                aws_sdk_s3::types::GetObjectAttributesParts::builder()
+                    .total_parts_count(12)
+                    .build(),
+            )
+            .build();
+        let checksums = ObjectChecksums::from_attributes(&attributes);
+        let mut out = Vec::new();
+        checksums.write_text(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Checksum-CRC32C: yZRlqg==\n\
+             Checksum-Type: COMPOSITE\n\
+             Parts-Count: 12\n"
+        );
+
+        let mut out = Vec::new();
+        ObjectChecksums::default().write_text(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Checksum: none (uploaded without an additional checksum)\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_checksum() {
+        let config = crate::commands::test_client::canned_config(|_, uri| {
+            if uri.contains("attributes") {
+                (
+                    200,
+                    "<GetObjectAttributesResponse>\
+                       <Checksum><ChecksumSHA256>n4bQgYhMfWWaL+qgxVrQFaO/TxsrC4Is0V1sFbDwCgg=</ChecksumSHA256>\
+                       <ChecksumType>FULL_OBJECT</ChecksumType></Checksum>\
+                     </GetObjectAttributesResponse>"
+                        .to_string(),
+                )
+            } else {
+                (200, String::new())
+            }
+        });
+        let mut out = Vec::new();
+
+        execute(
+            &config,
+            "s3://bucket/backup.tar",
+            None,
+            false,
+            true,
+            OutputFormat::Json,
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            json["checksums"],
+            serde_json::json!({
+                "crc32": null,
+                "crc32c": null,
+                "crc64nvme": null,
+                "sha1": null,
+                "sha256": "n4bQgYhMfWWaL+qgxVrQFaO/TxsrC4Is0V1sFbDwCgg=",
+                "checksum_type": "FULL_OBJECT",
+                "parts_count": null
+            })
+        );
+    }
 }
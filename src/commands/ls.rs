@@ -1,59 +1,162 @@
 use anyhow::Result;
-use aws_sdk_s3::types::Object;
+use aws_sdk_s3::operation::list_objects_v2::builders::ListObjectsV2FluentBuilder;
+use aws_sdk_s3::types::{DeleteMarkerEntry, Grant, Object, ObjectVersion, Permission};
 use chrono::{DateTime, Utc};
-use log::info;
-use std::time::Instant;
-
+use colored::Colorize;
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crate::args::OutputFormat;
+use crate::commands::cp::parse_storage_class;
+use crate::commands::paginate::list_objects_v2_resumable;
 use crate::commands::s3_uri::parse_ls_path;
 use crate::config::Config;
 use crate::filtering::{
-    apply_filters, parse_date_filter, parse_size_filter, parse_sort_config, validate_filter_config,
-    EnhancedObjectInfo, FilterConfig,
+    apply_filters, parse_date_filter, parse_size_filter, parse_sort_config, passes_filters,
+    validate_filter_config, EnhancedObjectInfo, FilterConfig,
 };
 use crate::utils::filter_by_enhanced_pattern;
 
-#[allow(clippy::too_many_arguments)]
+/// Maximum concurrent GetObjectAcl requests for `--show-acl`
+const ACL_CONCURRENCY: usize = 16;
+
+/// Maximum concurrent HeadObject requests for `--enrich`
+const ENRICH_CONCURRENCY: usize = 16;
+
+/// What to list and how to show it: the `ls` flags
+#[derive(Debug, Clone, Default)]
+pub struct LsOptions {
+    /// Size, date and storage class columns (`--long`)
+    pub long: bool,
+    /// List every key under the prefix instead of one level (`--recursive`)
+    pub recursive: bool,
+    /// Sizes in KiB, MiB, ... instead of bytes (`--human-readable`)
+    pub human_readable: bool,
+    /// Print the object count and total size (`--summarize`)
+    pub summarize: bool,
+    /// Bucket name pattern when listing buckets (`--pattern`)
+    pub pattern: Option<String>,
+    /// Match `pattern` case-insensitively (`--ignore-case`)
+    pub ignore_case: bool,
+    /// Log level (`--debug`); bucket listings add detail at debug and trace
+    pub debug_level: String,
+    /// Only objects created after this date (`--created-after`)
+    pub created_after: Option<String>,
+    /// Only objects created before this date (`--created-before`)
+    pub created_before: Option<String>,
+    /// Only objects modified after this date (`--modified-after`)
+    pub modified_after: Option<String>,
+    /// Only objects modified before this date (`--modified-before`)
+    pub modified_before: Option<String>,
+    /// Only objects of at least this size (`--min-size`)
+    pub min_size: Option<String>,
+    /// Only objects of at most this size (`--max-size`)
+    pub max_size: Option<String>,
+    /// Only keys with one of these extensions (`--extension`)
+    pub extensions: Vec<String>,
+    /// Only objects in one of these storage classes (`--storage-class`)
+    pub storage_classes: Vec<String>,
+    /// Stop after this many matching objects (`--max-results`)
+    pub max_results: Option<usize>,
+    /// Only the first N objects after sorting (`--head`)
+    pub head: Option<usize>,
+    /// Only the last N objects after sorting (`--tail`)
+    pub tail: Option<usize>,
+    /// Sort fields, e.g. `modified:desc,size` (`--sort-by`)
+    pub sort_by: Option<String>,
+    /// Reverse the sort order (`--reverse`)
+    pub reverse: bool,
+    /// Text, table or JSON output (`--output`)
+    pub output: OutputFormat,
+    /// Only prefixes, no objects (`--folders-only`)
+    pub folders_only: bool,
+    /// Only objects, no prefixes (`--files-only`)
+    pub files_only: bool,
+    /// Owner column (`--show-owner`)
+    pub show_owner: bool,
+    /// Public ACL column, one GetObjectAcl per object shown (`--show-acl`)
+    pub show_acl: bool,
+    /// ETag column (`--show-etag`)
+    pub show_etag: bool,
+    /// HeadObject each object to fill in what the listing lacks (`--enrich`)
+    pub enrich: bool,
+    /// Only count the matching objects (`--count`)
+    pub count: bool,
+    /// List object versions and delete markers (`--versions`)
+    pub versions: bool,
+    /// List recursive prefixes with this many concurrent requests (`--parallel`)
+    pub parallel: Option<usize>,
+    /// Keep polling and print new objects as they appear (`--follow`)
+    pub follow: bool,
+    /// Seconds between `--follow` polls (`--interval`)
+    pub interval: u64,
+}
+
 pub async fn execute(
     config: &Config,
     path: Option<&str>,
-    long: bool,
-    recursive: bool,
-    human_readable: bool,
-    summarize: bool,
-    pattern: Option<&str>,
-    debug_level: &str,
-    created_after: Option<&str>,
-    created_before: Option<&str>,
-    modified_after: Option<&str>,
-    modified_before: Option<&str>,
-    min_size: Option<&str>,
-    max_size: Option<&str>,
-    max_results: Option<usize>,
-    head: Option<usize>,
-    tail: Option<usize>,
-    sort_by: Option<&str>,
-    reverse: bool,
+    options: &LsOptions,
+    out: &mut dyn Write,
 ) -> Result<()> {
+    let LsOptions {
+        long,
+        recursive,
+        human_readable,
+        summarize,
+        ignore_case,
+        max_results,
+        head,
+        tail,
+        reverse,
+        output,
+        folders_only,
+        files_only,
+        show_owner,
+        show_acl,
+        show_etag,
+        enrich,
+        count,
+        versions,
+        parallel,
+        follow,
+        interval,
+        ..
+    } = *options;
+
+    if follow && path.is_none() {
+        anyhow::bail!("--follow needs an S3 URI to watch (e.g. obsctl ls s3://logs/ --follow)");
+    }
+
     let start_time = Instant::now();
+    let table = output == OutputFormat::Table;
+    let json = output == OutputFormat::Json;
 
     // Build filter configuration from CLI arguments
     let filter_config = build_filter_config(
-        created_after,
-        created_before,
-        modified_after,
-        modified_before,
-        min_size,
-        max_size,
+        options.created_after.as_deref(),
+        options.created_before.as_deref(),
+        options.modified_after.as_deref(),
+        options.modified_before.as_deref(),
+        options.min_size.as_deref(),
+        options.max_size.as_deref(),
+        &options.extensions,
+        &options.storage_classes,
         max_results,
         head,
         tail,
-        sort_by,
+        options.sort_by.as_deref(),
         reverse,
+        Utc::now(),
     )?;
 
     // Validate filter configuration
     validate_filter_config(&filter_config)?;
 
+    // Everything the initial listing saw, so --follow only prints what's new
+    let mut watermark = FollowWatermark::default();
+
     // If no path is provided, list all buckets (with optional pattern filtering)
     let result = if path.is_none() {
         list_all_buckets(
@@ -61,8 +164,34 @@ pub async fn execute(
             long,
             human_readable,
             summarize,
-            pattern,
-            debug_level,
+            options.pattern.as_deref(),
+            ignore_case,
+            &options.debug_level,
+            out,
+        )
+        .await
+    } else if versions {
+        list_versions(
+            config,
+            path,
+            recursive,
+            &filter_config,
+            long || table,
+            human_readable,
+            json,
+            out,
+        )
+        .await
+    } else if count {
+        count_objects(
+            config,
+            path,
+            recursive,
+            &filter_config,
+            summarize,
+            human_readable,
+            enrich,
+            out,
         )
         .await
     } else {
@@ -80,51 +209,103 @@ pub async fn execute(
             request = request.delimiter("/");
         }
 
-        let mut continuation_token: Option<String> = None;
+        if show_owner {
+            request = request.fetch_owner(true);
+        }
+
         let mut total_objects = 0;
         let mut total_size = 0i64;
         let mut all_objects = Vec::new();
         let mut common_prefixes = Vec::new();
 
         let list_result: anyhow::Result<()> = async {
-            loop {
-                let mut req = request.clone();
-                if let Some(token) = &continuation_token {
-                    req = req.continuation_token(token);
-                }
-
-                let response = req.send().await?;
+            if let Some(concurrency) = parallel.filter(|_| recursive) {
+                // --enrich fills in fields the filters read, and --folders-only
+                // derives prefixes from every key, so both need the full listing
+                let prefilter = !enrich && !folders_only;
+                all_objects =
+                    list_recursive_parallel(config, request, &bucket, concurrency, |obj| {
+                        !prefilter || passes_filters(obj, &filter_config)
+                    })
+                    .await?;
+            } else {
+                list_objects_v2_resumable(config, request, |response| {
+                    // Collect common prefixes (directories) when not recursive
+                    for prefix_info in response.common_prefixes() {
+                        if let Some(prefix) = prefix_info.prefix() {
+                            common_prefixes.push(prefix.to_string());
+                        }
+                    }
 
-                // Collect common prefixes (directories) when not recursive
-                for prefix_info in response.common_prefixes() {
-                    if let Some(prefix) = prefix_info.prefix() {
-                        common_prefixes.push(prefix.to_string());
+                    // Collect all objects for filtering
+                    for object in response.contents() {
+                        let enhanced_obj = convert_to_enhanced_object_info(object, &bucket);
+                        all_objects.push(enhanced_obj);
                     }
-                }
 
-                // Collect all objects for filtering
-                for object in response.contents() {
-                    let enhanced_obj = convert_to_enhanced_object_info(object, &bucket);
-                    all_objects.push(enhanced_obj);
-                }
+                    Ok(())
+                })
+                .await?;
+            }
 
-                // Check if there are more objects to fetch
-                if response.is_truncated().unwrap_or(false) {
-                    continuation_token = response.next_continuation_token().map(|s| s.to_string());
-                } else {
-                    break;
-                }
+            if enrich {
+                enrich_objects(config, &bucket, &mut all_objects).await;
+            }
+
+            if follow {
+                all_objects.iter().for_each(|obj| watermark.observe(obj));
             }
 
             // Apply advanced filtering to collected objects
-            let filtered_objects = apply_filters(&all_objects, &filter_config);
+            let mut filtered_objects = apply_filters(&all_objects, &filter_config);
+
+            if files_only {
+                common_prefixes.clear();
+            } else if folders_only {
+                if recursive {
+                    // No delimiter was sent, so derive the prefixes from the keys
+                    let keys: Vec<&str> = all_objects.iter().map(|obj| obj.key.as_str()).collect();
+                    common_prefixes = synthesize_prefixes(&keys, &prefix);
+                }
+                filtered_objects.clear();
+            }
+
+            // Per-object ACL lookups are only worth it for rows that will be shown
+            let acl_flags = if show_acl && (long || table) {
+                Some(fetch_public_acl_flags(config, &bucket, &filtered_objects).await)
+            } else {
+                None
+            };
+            let extra = ExtraColumns {
+                owner: show_owner,
+                acl: acl_flags.as_ref(),
+                etag: show_etag,
+            };
+
+            if json {
+                print_json(out, &common_prefixes, &filtered_objects)?;
+                return Ok(());
+            }
+
+            if table {
+                total_objects = filtered_objects.len();
+                total_size = filtered_objects.iter().map(|obj| obj.size).sum();
+                print_table(
+                    out,
+                    &common_prefixes,
+                    &filtered_objects,
+                    human_readable,
+                    extra,
+                )?;
+                return Ok(());
+            }
 
             // Display common prefixes (directories) first
             for prefix in &common_prefixes {
                 if long {
-                    println!("{:>12} {:>19} {}/", "DIR", "", prefix);
+                    print_long_format_prefix(out, prefix, extra)?;
                 } else {
-                    println!("{prefix}/");
+                    writeln!(out, "{prefix}/")?;
                 }
             }
 
@@ -134,9 +315,9 @@ pub async fn execute(
                 total_size += enhanced_obj.size;
 
                 if long {
-                    print_enhanced_long_format(enhanced_obj, human_readable);
+                    print_enhanced_long_format(out, enhanced_obj, human_readable, extra)?;
                 } else {
-                    println!("{}", enhanced_obj.key);
+                    writeln!(out, "{}", enhanced_obj.key)?;
                 }
             }
 
@@ -146,9 +327,10 @@ pub async fn execute(
 
         match list_result {
             Ok(_) => {
-                if long || summarize {
-                    println!();
-                    println!(
+                if (long || summarize || table) && !json {
+                    writeln!(out)?;
+                    writeln!(
+                        out,
                         "Total: {} objects, {} bytes",
                         total_objects,
                         if human_readable {
@@ -156,7 +338,7 @@ pub async fn execute(
                         } else {
                             total_size.to_string()
                         }
-                    );
+                    )?;
                 }
                 Ok(())
             }
@@ -170,26 +352,65 @@ pub async fn execute(
 
             // Record ls operation using proper OTEL SDK
             {
-                use crate::otel::OTEL_INSTRUMENTS;
-                use opentelemetry::KeyValue;
+                use crate::otel::{GLOBAL_METRICS, OTEL_INSTRUMENTS};
 
                 let operation_type = if path.is_none() {
                     "ls_buckets"
+                } else if versions {
+                    "ls_versions"
                 } else if recursive {
                     "ls_recursive"
                 } else {
                     "ls_objects"
                 };
 
-                OTEL_INSTRUMENTS
-                    .operations_total
-                    .add(1, &[KeyValue::new("operation", operation_type)]);
+                let duration_ms = duration.as_millis() as u64;
+                if config.otel.read_operations {
+                    let bucket = path
+                        .and_then(|path| parse_ls_path(Some(path)).ok())
+                        .map(|(bucket, _)| bucket);
+                    let bucket_label = bucket
+                        .as_deref()
+                        .and_then(|bucket| config.otel.bucket_label(bucket));
+                    OTEL_INSTRUMENTS.record_list(operation_type, duration_ms, bucket_label);
+                }
+                GLOBAL_METRICS.record_list(duration_ms).await;
+            }
 
-                let duration_seconds = duration.as_millis() as f64 / 1000.0;
-                OTEL_INSTRUMENTS.operation_duration.record(
-                    duration_seconds,
-                    &[KeyValue::new("operation", operation_type)],
-                );
+            if follow {
+                let (bucket, prefix) = parse_ls_path(path)?;
+                let follow = FollowListing {
+                    bucket: &bucket,
+                    prefix: &prefix,
+                    recursive,
+                    show_owner,
+                };
+                let extra = ExtraColumns {
+                    owner: show_owner,
+                    acl: None,
+                    etag: show_etag,
+                };
+                let print = |out: &mut dyn Write, obj: &EnhancedObjectInfo| -> Result<()> {
+                    if json {
+                        writeln!(out, "{}", serde_json::to_string(obj)?)?;
+                    } else if long || table {
+                        print_enhanced_long_format(out, obj, human_readable, extra)?;
+                    } else {
+                        writeln!(out, "{}", obj.key)?;
+                    }
+                    Ok(())
+                };
+                out.flush()?;
+                return follow_prefix(
+                    config,
+                    &follow,
+                    interval,
+                    &filter_config,
+                    &mut watermark,
+                    out,
+                    print,
+                )
+                .await;
             }
 
             Ok(())
@@ -208,13 +429,443 @@ pub async fn execute(
     }
 }
 
+/// How far behind the newest last-modified time `--follow` keeps looking.
+/// A multipart object's LastModified is when its upload started, so one
+/// that completes late shows up with an older timestamp than objects
+/// already printed.
+const FOLLOW_LOOKBACK: chrono::Duration = chrono::Duration::hours(24);
+
+/// The objects `--follow` has seen within `FOLLOW_LOOKBACK` of the newest
+/// last-modified time, by key with their ETag and timestamp. An object is
+/// new when its key hasn't been seen or it has changed since; anything
+/// older than the lookback window counts as already seen.
+#[derive(Debug, Default)]
+struct FollowWatermark {
+    latest: Option<DateTime<Utc>>,
+    seen: HashMap<String, (Option<String>, DateTime<Utc>)>,
+}
+
+impl FollowWatermark {
+    fn cutoff(&self) -> Option<DateTime<Utc>> {
+        self.latest.map(|latest| latest - FOLLOW_LOOKBACK)
+    }
+
+    fn is_new(&self, obj: &EnhancedObjectInfo) -> bool {
+        let Some(modified) = obj.modified else {
+            return false;
+        };
+        if self.cutoff().is_some_and(|cutoff| modified < cutoff) {
+            return false;
+        }
+        self.seen
+            .get(&obj.key)
+            .is_none_or(|(etag, seen_at)| *etag != obj.etag || *seen_at != modified)
+    }
+
+    fn observe(&mut self, obj: &EnhancedObjectInfo) {
+        let Some(modified) = obj.modified else {
+            return;
+        };
+        self.seen
+            .insert(obj.key.clone(), (obj.etag.clone(), modified));
+        if self.latest.is_none_or(|latest| modified > latest) {
+            self.latest = Some(modified);
+            let cutoff = modified - FOLLOW_LOOKBACK;
+            self.seen.retain(|_, (_, seen_at)| *seen_at >= cutoff);
+        }
+    }
+}
+
+/// The listing `--follow` repeats on every poll
+struct FollowListing<'a> {
+    bucket: &'a str,
+    prefix: &'a str,
+    recursive: bool,
+    show_owner: bool,
+}
+
+/// List the prefix again and return the objects not seen before, oldest
+/// first, recording them as seen
+async fn poll_new_objects(
+    config: &Config,
+    listing: &FollowListing<'_>,
+    watermark: &mut FollowWatermark,
+) -> Result<Vec<EnhancedObjectInfo>> {
+    let mut request = config.client.list_objects_v2().bucket(listing.bucket);
+    if !listing.prefix.is_empty() {
+        request = request.prefix(listing.prefix);
+    }
+    if !listing.recursive {
+        request = request.delimiter("/");
+    }
+    if listing.show_owner {
+        request = request.fetch_owner(true);
+    }
+
+    let mut new_objects = Vec::new();
+    list_objects_v2_resumable(config, request, |response| {
+        for object in response.contents() {
+            let obj = convert_to_enhanced_object_info(object, listing.bucket);
+            if watermark.is_new(&obj) {
+                new_objects.push(obj);
+            }
+        }
+        Ok(())
+    })
+    .await?;
+
+    new_objects.sort_by(|a, b| a.modified.cmp(&b.modified).then_with(|| a.key.cmp(&b.key)));
+    new_objects.iter().for_each(|obj| watermark.observe(obj));
+    Ok(new_objects)
+}
+
+/// `--follow`: poll the prefix every `interval` seconds, printing objects
+/// that pass the filters as they appear, until Ctrl-C. Output is flushed
+/// after each poll so a closed pipe ends the process straight away.
+async fn follow_prefix<Print>(
+    config: &Config,
+    listing: &FollowListing<'_>,
+    interval: u64,
+    filter_config: &FilterConfig,
+    watermark: &mut FollowWatermark,
+    out: &mut dyn Write,
+    print: Print,
+) -> Result<()>
+where
+    Print: Fn(&mut dyn Write, &EnhancedObjectInfo) -> Result<()>,
+{
+    let interval = Duration::from_secs(interval.max(1));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => {
+                debug!("Stopping --follow on Ctrl-C");
+                return Ok(());
+            }
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        for obj in poll_new_objects(config, listing, watermark).await? {
+            if passes_filters(&obj, filter_config) {
+                print(out, &obj)?;
+            }
+        }
+        out.flush()?;
+    }
+}
+
+/// `--parallel`: list the top level of the request's prefix with a delimiter,
+/// then each common prefix below it recursively, up to `concurrency` at a
+/// time. Rows failing `keep` are dropped as pages arrive, so memory follows
+/// the matches rather than the whole bucket.
+async fn list_recursive_parallel<Keep>(
+    config: &Config,
+    request: ListObjectsV2FluentBuilder,
+    bucket: &str,
+    concurrency: usize,
+    keep: Keep,
+) -> Result<Vec<EnhancedObjectInfo>>
+where
+    Keep: Fn(&EnhancedObjectInfo) -> bool,
+{
+    use futures::stream::{self, StreamExt, TryStreamExt};
+
+    let mut top_level = Vec::new();
+    let mut prefixes = Vec::new();
+    list_objects_v2_resumable(config, request.clone().delimiter("/"), |response| {
+        prefixes.extend(
+            response
+                .common_prefixes()
+                .iter()
+                .filter_map(|prefix| prefix.prefix().map(str::to_string)),
+        );
+        top_level.extend(
+            response
+                .contents()
+                .iter()
+                .map(|object| convert_to_enhanced_object_info(object, bucket))
+                .filter(|obj| keep(obj)),
+        );
+        Ok(())
+    })
+    .await?;
+
+    info!(
+        "Listing {} prefixes in s3://{bucket} with up to {concurrency} concurrent requests",
+        prefixes.len()
+    );
+
+    let nested: Vec<Vec<EnhancedObjectInfo>> = stream::iter(prefixes)
+        .map(|prefix| {
+            let request = request.clone().prefix(prefix);
+            let keep = &keep;
+            async move {
+                let mut objects = Vec::new();
+                list_objects_v2_resumable(config, request, |response| {
+                    objects.extend(
+                        response
+                            .contents()
+                            .iter()
+                            .map(|object| convert_to_enhanced_object_info(object, bucket))
+                            .filter(|obj| keep(obj)),
+                    );
+                    Ok(())
+                })
+                .await?;
+                Ok::<_, anyhow::Error>(objects)
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .try_collect()
+        .await?;
+
+    Ok(merge_in_key_order(top_level, nested))
+}
+
+/// Prefixes finish in any order; sorting by key restores the order a serial
+/// listing returns, so `--sort-by` ties and unsorted output stay deterministic
+fn merge_in_key_order(
+    mut objects: Vec<EnhancedObjectInfo>,
+    nested: Vec<Vec<EnhancedObjectInfo>>,
+) -> Vec<EnhancedObjectInfo> {
+    objects.extend(nested.into_iter().flatten());
+    objects.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+    objects
+}
+
+/// `--versions`: every version and delete marker under the prefix, newest
+/// first within each key
+#[allow(clippy::too_many_arguments)]
+async fn list_versions(
+    config: &Config,
+    path: Option<&str>,
+    recursive: bool,
+    filter_config: &FilterConfig,
+    long: bool,
+    human_readable: bool,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let (bucket, prefix) = parse_ls_path(path)?;
+
+    info!("Listing object versions in s3://{bucket}/{prefix}");
+
+    let mut rows = Vec::new();
+    let mut common_prefixes = Vec::new();
+    let mut key_marker: Option<String> = None;
+    let mut version_id_marker: Option<String> = None;
+
+    loop {
+        let mut request = config
+            .client
+            .list_object_versions()
+            .bucket(&bucket)
+            .set_key_marker(key_marker.take())
+            .set_version_id_marker(version_id_marker.take());
+        if !prefix.is_empty() {
+            request = request.prefix(&prefix);
+        }
+        if !recursive {
+            request = request.delimiter("/");
+        }
+
+        let response = request.send().await?;
+
+        common_prefixes.extend(
+            response
+                .common_prefixes()
+                .iter()
+                .filter_map(|prefix_info| prefix_info.prefix())
+                .map(str::to_string),
+        );
+        rows.extend(response.versions().iter().map(version_row));
+        rows.extend(response.delete_markers().iter().map(delete_marker_row));
+
+        if response.is_truncated() != Some(true) {
+            break;
+        }
+        key_marker = response.next_key_marker().map(str::to_string);
+        version_id_marker = response.next_version_id_marker().map(str::to_string);
+    }
+
+    // Versions and delete markers arrive as separate lists
+    rows.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| b.modified.cmp(&a.modified)));
+    let rows = apply_filters(&rows, filter_config);
+
+    if json {
+        return print_json(out, &common_prefixes, &rows);
+    }
+
+    for prefix in &common_prefixes {
+        if long {
+            writeln!(
+                out,
+                "{:>12} {:>19} {:VERSION_ID_WIDTH$} {prefix}",
+                "DIR", "", ""
+            )?;
+        } else {
+            writeln!(out, "{:VERSION_ID_WIDTH$} {prefix}", "")?;
+        }
+    }
+    for row in &rows {
+        print_version_line(out, row, long, human_readable)?;
+    }
+    Ok(())
+}
+
+fn version_row(version: &ObjectVersion) -> EnhancedObjectInfo {
+    let modified = version.last_modified().map(|dt| {
+        DateTime::<Utc>::from_timestamp(dt.secs(), dt.subsec_nanos()).unwrap_or_else(Utc::now)
+    });
+    EnhancedObjectInfo {
+        key: version.key().unwrap_or("").to_string(),
+        size: version.size().unwrap_or(0),
+        created: modified,
+        modified,
+        storage_class: version.storage_class().map(|sc| sc.as_str().to_string()),
+        etag: version.e_tag().map(|tag| tag.to_string()),
+        owner: None,
+        version_id: version.version_id().map(str::to_string),
+        delete_marker: false,
+    }
+}
+
+fn delete_marker_row(marker: &DeleteMarkerEntry) -> EnhancedObjectInfo {
+    let modified = marker.last_modified().map(|dt| {
+        DateTime::<Utc>::from_timestamp(dt.secs(), dt.subsec_nanos()).unwrap_or_else(Utc::now)
+    });
+    EnhancedObjectInfo {
+        key: marker.key().unwrap_or("").to_string(),
+        size: 0,
+        created: modified,
+        modified,
+        storage_class: None,
+        etag: None,
+        owner: None,
+        version_id: marker.version_id().map(str::to_string),
+        delete_marker: true,
+    }
+}
+
+/// One `--versions` line: the version ID (`null` for objects written before
+/// versioning was enabled) and key, with size and date under `--long`
+fn print_version_line(
+    out: &mut dyn Write,
+    obj: &EnhancedObjectInfo,
+    long: bool,
+    human_readable: bool,
+) -> std::io::Result<()> {
+    let version_id = obj.version_id.as_deref().unwrap_or("null");
+    let marker = if obj.delete_marker {
+        " (delete marker)"
+    } else {
+        ""
+    };
+
+    if !long {
+        return writeln!(out, "{version_id:<VERSION_ID_WIDTH$} {}{marker}", obj.key);
+    }
+
+    let size = if human_readable {
+        format_size(obj.size)
+    } else {
+        obj.size.to_string()
+    };
+    let modified = obj
+        .modified
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    writeln!(
+        out,
+        "{size:>12} {modified} {version_id:<VERSION_ID_WIDTH$} {}{marker}",
+        obj.key
+    )
+}
+
+/// `--count`: tally matching objects page by page without keeping any rows
+#[allow(clippy::too_many_arguments)]
+async fn count_objects(
+    config: &Config,
+    path: Option<&str>,
+    recursive: bool,
+    filter_config: &FilterConfig,
+    summarize: bool,
+    human_readable: bool,
+    enrich: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let (bucket, prefix) = parse_ls_path(path)?;
+
+    info!("Counting objects in s3://{bucket}/{prefix}");
+
+    let mut request = config.client.list_objects_v2().bucket(&bucket);
+    if !prefix.is_empty() {
+        request = request.prefix(&prefix);
+    }
+    if !recursive {
+        request = request.delimiter("/");
+    }
+
+    let mut total_objects = 0u64;
+    let mut total_size = 0i64;
+    // Only incomplete rows are held back for `--enrich`; the rest are tallied as they arrive
+    let mut incomplete = Vec::new();
+
+    list_objects_v2_resumable(config, request, |response| {
+        for object in response.contents() {
+            let enhanced_obj = convert_to_enhanced_object_info(object, &bucket);
+            if enrich && needs_enrichment(&enhanced_obj) {
+                incomplete.push(enhanced_obj);
+            } else if passes_filters(&enhanced_obj, filter_config) {
+                total_objects += 1;
+                total_size += enhanced_obj.size;
+            }
+        }
+        Ok(())
+    })
+    .await?;
+
+    if !incomplete.is_empty() {
+        enrich_objects(config, &bucket, &mut incomplete).await;
+        for enhanced_obj in incomplete
+            .iter()
+            .filter(|obj| passes_filters(obj, filter_config))
+        {
+            total_objects += 1;
+            total_size += enhanced_obj.size;
+        }
+    }
+
+    if summarize {
+        writeln!(
+            out,
+            "Total: {} objects, {} bytes",
+            total_objects,
+            if human_readable {
+                format_size(total_size)
+            } else {
+                total_size.to_string()
+            }
+        )?;
+    } else {
+        writeln!(out, "{total_objects}")?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn list_all_buckets(
     config: &Config,
     long: bool,
     human_readable: bool,
     summarize: bool,
     pattern: Option<&str>,
+    ignore_case: bool,
     debug_level: &str,
+    out: &mut dyn Write,
 ) -> Result<()> {
     let start_time = Instant::now();
 
@@ -242,7 +893,7 @@ async fn list_all_buckets(
 
         // Filter by pattern if provided
         let filtered_bucket_names = if let Some(pattern_str) = pattern {
-            filter_by_enhanced_pattern(&all_bucket_names, pattern_str, false)?
+            filter_by_enhanced_pattern(&all_bucket_names, pattern_str, false, ignore_case)?
         } else {
             all_bucket_names.clone()
         };
@@ -279,37 +930,46 @@ async fn list_all_buckets(
                                 } else {
                                     total_size.to_string()
                                 };
-                                println!(
+                                writeln!(
+                                    out,
                                     "{:>12} {} {} ({} objects, {} bytes)",
                                     "BUCKET", creation_date, bucket_name, object_count, size_str
-                                );
+                                )?;
                             }
                             Err(_) => {
-                                println!("{:>12} {} {}", "BUCKET", creation_date, bucket_name);
+                                writeln!(
+                                    out,
+                                    "{:>12} {} {}",
+                                    "BUCKET", creation_date, bucket_name
+                                )?;
                             }
                         }
                     } else {
-                        println!("{:>12} {} {}", "BUCKET", creation_date, bucket_name);
+                        writeln!(out, "{:>12} {} {}", "BUCKET", creation_date, bucket_name)?;
                     }
                 } else {
-                    println!("{bucket_name}");
+                    writeln!(out, "{bucket_name}")?;
                 }
             }
         }
 
         if long || summarize {
-            println!();
+            writeln!(out)?;
             if let Some(pattern_str) = pattern {
-                println!("Total: {total_buckets} buckets matching pattern '{pattern_str}'");
+                writeln!(
+                    out,
+                    "Total: {total_buckets} buckets matching pattern '{pattern_str}'"
+                )?;
                 if total_buckets != all_bucket_names.len() {
-                    println!(
+                    writeln!(
+                        out,
                         "({} buckets total, {} filtered out)",
                         all_bucket_names.len(),
                         all_bucket_names.len() - total_buckets
-                    );
+                    )?;
                 }
             } else {
-                println!("Total: {total_buckets} buckets");
+                writeln!(out, "Total: {total_buckets} buckets")?;
             }
         }
 
@@ -323,17 +983,12 @@ async fn list_all_buckets(
 
             // Record bucket listing using proper OTEL SDK
             {
-                use crate::otel::OTEL_INSTRUMENTS;
                 use opentelemetry::KeyValue;
 
-                OTEL_INSTRUMENTS
-                    .operations_total
-                    .add(1, &[KeyValue::new("operation", "list_buckets")]);
-
-                let duration_seconds = duration.as_millis() as f64 / 1000.0;
-                OTEL_INSTRUMENTS.operation_duration.record(
-                    duration_seconds,
+                crate::otel::record_read_operation(
+                    &config.otel,
                     &[KeyValue::new("operation", "list_buckets")],
+                    duration,
                 );
             }
 
@@ -398,25 +1053,21 @@ async fn get_bucket_size(config: &Config, bucket_name: &str) -> Result<(i32, i64
                 use crate::otel::OTEL_INSTRUMENTS;
                 use opentelemetry::KeyValue;
 
-                OTEL_INSTRUMENTS
-                    .operations_total
-                    .add(1, &[KeyValue::new("operation", "bucket_size")]);
-
-                let duration_seconds = duration.as_millis() as f64 / 1000.0;
-                OTEL_INSTRUMENTS.operation_duration.record(
-                    duration_seconds,
-                    &[KeyValue::new("operation", "bucket_size")],
-                );
-
                 // Record the scanned objects and bytes
-                OTEL_INSTRUMENTS.files_uploaded_total.add(
-                    objects as u64,
-                    &[KeyValue::new("operation", "bucket_size_scan")],
-                );
-                OTEL_INSTRUMENTS.bytes_uploaded_total.add(
-                    size as u64,
-                    &[KeyValue::new("operation", "bucket_size_scan")],
-                );
+                if crate::otel::record_read_operation(
+                    &config.otel,
+                    &[KeyValue::new("operation", "bucket_size")],
+                    duration,
+                ) {
+                    OTEL_INSTRUMENTS.files_uploaded_total.add(
+                        objects as u64,
+                        &[KeyValue::new("operation", "bucket_size_scan")],
+                    );
+                    OTEL_INSTRUMENTS.bytes_uploaded_total.add(
+                        size as u64,
+                        &[KeyValue::new("operation", "bucket_size_scan")],
+                    );
+                }
             }
 
             Ok((objects, size))
@@ -435,7 +1086,12 @@ async fn get_bucket_size(config: &Config, bucket_name: &str) -> Result<(i32, i64
     }
 }
 
-fn print_enhanced_long_format(obj: &EnhancedObjectInfo, human_readable: bool) {
+fn print_enhanced_long_format(
+    out: &mut dyn Write,
+    obj: &EnhancedObjectInfo,
+    human_readable: bool,
+    extra: ExtraColumns,
+) -> std::io::Result<()> {
     let size_str = if human_readable {
         format!("{:>12}", format_size(obj.size))
     } else {
@@ -447,14 +1103,406 @@ fn print_enhanced_long_format(obj: &EnhancedObjectInfo, human_readable: bool) {
         .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
         .unwrap_or_else(|| "unknown".to_string());
 
-    // Add storage class information if available
-    let storage_info = obj
-        .storage_class
-        .as_ref()
-        .map(|sc| format!(" [{sc}]"))
+    let etag_info = extra
+        .etag_cell(obj)
+        .map(|etag| format!("{etag:<ETAG_WIDTH$} "))
         .unwrap_or_default();
 
-    println!("{} {} {}{}", size_str, modified, obj.key, storage_info);
+    let mut access_info = String::new();
+    if let Some(owner) = extra.owner_cell(obj) {
+        access_info.push_str(&format!(" owner={owner}"));
+    }
+    if let Some(acl) = extra.acl_cell(obj) {
+        access_info.push_str(&format!(" acl={acl}"));
+    }
+
+    writeln!(
+        out,
+        "{} {} {:<STORAGE_CLASS_WIDTH$} {}{}{}",
+        size_str,
+        modified,
+        storage_class_or_default(obj),
+        etag_info,
+        obj.key,
+        access_info
+    )
+}
+
+/// A `--long` directory line, padded so the prefix lines up with the keys
+fn print_long_format_prefix(
+    out: &mut dyn Write,
+    prefix: &str,
+    extra: ExtraColumns,
+) -> std::io::Result<()> {
+    let etag_blank = if extra.etag {
+        format!("{:ETAG_WIDTH$} ", "")
+    } else {
+        String::new()
+    };
+    writeln!(
+        out,
+        "{:>12} {:>19} {:<STORAGE_CLASS_WIDTH$} {etag_blank}{prefix}/",
+        "DIR", "", ""
+    )
+}
+
+/// Objects listed without a storage class (common on MinIO) are shown as
+/// STANDARD, as the AWS CLI does
+fn storage_class_or_default(obj: &EnhancedObjectInfo) -> &str {
+    obj.storage_class.as_deref().unwrap_or("STANDARD")
+}
+
+/// Build the set of "directory" prefixes below `base_prefix` implied by a
+/// recursive listing, in the same trailing-slash form S3 uses for common prefixes
+fn synthesize_prefixes(keys: &[&str], base_prefix: &str) -> Vec<String> {
+    let mut prefixes = std::collections::BTreeSet::new();
+
+    for key in keys {
+        let Some(relative) = key.strip_prefix(base_prefix) else {
+            continue;
+        };
+        let mut end = 0;
+        while let Some(pos) = relative[end..].find('/') {
+            end += pos + 1;
+            prefixes.insert(format!("{base_prefix}{}", &relative[..end]));
+        }
+    }
+
+    prefixes.into_iter().collect()
+}
+
+/// `--output json`: one array of the listed objects, led by `{"prefix": ...}`
+/// entries for the common prefixes of a non-recursive listing
+fn print_json(
+    out: &mut dyn Write,
+    common_prefixes: &[String],
+    objects: &[EnhancedObjectInfo],
+) -> Result<()> {
+    let mut entries: Vec<serde_json::Value> = common_prefixes
+        .iter()
+        .map(|prefix| serde_json::json!({ "prefix": prefix }))
+        .collect();
+    for object in objects {
+        entries.push(serde_json::to_value(object)?);
+    }
+    writeln!(out, "{}", serde_json::to_string_pretty(&entries)?)?;
+    Ok(())
+}
+
+/// Width of the `--long` storage class column, fitting INTELLIGENT_TIERING
+const STORAGE_CLASS_WIDTH: usize = 19;
+
+/// Width of the `--versions` version ID column, fitting AWS's 32-character IDs
+const VERSION_ID_WIDTH: usize = 32;
+
+/// Width of the `--show-etag` column in `--long` output: a single-part MD5
+/// ETag; multipart ETags run over and push the key along
+const ETAG_WIDTH: usize = 32;
+
+/// Column alignment for `render_table`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+}
+
+/// Optional columns shown by `--long` and `--output table`
+#[derive(Debug, Clone, Copy, Default)]
+struct ExtraColumns<'a> {
+    owner: bool,
+    /// Public-access flags per key, present when `--show-acl` is set
+    acl: Option<&'a HashMap<String, bool>>,
+    etag: bool,
+}
+
+impl ExtraColumns<'_> {
+    /// The ETag without its surrounding quotes
+    fn etag_cell(&self, obj: &EnhancedObjectInfo) -> Option<String> {
+        self.etag.then(|| {
+            obj.etag
+                .as_deref()
+                .map(|etag| etag.trim_matches('"').to_string())
+                .unwrap_or_else(|| "-".to_string())
+        })
+    }
+
+    fn owner_cell(&self, obj: &EnhancedObjectInfo) -> Option<String> {
+        self.owner
+            .then(|| obj.owner.clone().unwrap_or_else(|| "-".to_string()))
+    }
+
+    fn acl_cell(&self, obj: &EnhancedObjectInfo) -> Option<String> {
+        self.acl.map(|flags| match flags.get(&obj.key) {
+            Some(true) => "PUBLIC".to_string(),
+            Some(false) => "private".to_string(),
+            None => "?".to_string(),
+        })
+    }
+}
+
+/// Table headers, alignments and rows for `--output table`
+struct Table {
+    headers: Vec<&'static str>,
+    align: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+/// Build the `--output table` rows for prefixes and objects
+fn build_table(
+    prefixes: &[String],
+    objects: &[EnhancedObjectInfo],
+    human_readable: bool,
+    extra: ExtraColumns,
+) -> Table {
+    let mut headers = vec!["DATE", "SIZE", "STORAGE CLASS"];
+    let mut align = vec![Align::Left, Align::Right, Align::Left];
+    if extra.etag {
+        headers.push("ETAG");
+        align.push(Align::Left);
+    }
+    if extra.owner {
+        headers.push("OWNER");
+        align.push(Align::Left);
+    }
+    if extra.acl.is_some() {
+        headers.push("ACL");
+        align.push(Align::Left);
+    }
+    headers.push("KEY");
+    align.push(Align::Left);
+
+    let mut rows = Vec::with_capacity(prefixes.len() + objects.len());
+
+    for prefix in prefixes {
+        let mut row = vec![String::new(); headers.len()];
+        row[1] = "DIR".to_string();
+        row[headers.len() - 1] = format!("{prefix}/");
+        rows.push(row);
+    }
+
+    for obj in objects {
+        let modified = obj
+            .modified
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let size = if human_readable {
+            format_size(obj.size)
+        } else {
+            obj.size.to_string()
+        };
+        let storage_class = storage_class_or_default(obj).to_string();
+
+        let mut row = vec![modified, size, storage_class];
+        row.extend(extra.etag_cell(obj));
+        row.extend(extra.owner_cell(obj));
+        row.extend(extra.acl_cell(obj));
+        row.push(obj.key.clone());
+        rows.push(row);
+    }
+
+    Table {
+        headers,
+        align,
+        rows,
+    }
+}
+
+/// Render rows into lines with column widths computed from the displayed cells.
+/// The last column is never padded so long keys don't produce trailing spaces.
+fn render_table(table: &Table) -> Vec<String> {
+    let columns = table.headers.len();
+    let mut widths: Vec<usize> = table.headers.iter().map(|h| h.chars().count()).collect();
+    for row in &table.rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render_row = |cells: &[&str]| -> String {
+        let mut line = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i > 0 {
+                line.push_str("  ");
+            }
+            if i == columns - 1 {
+                line.push_str(cell);
+            } else {
+                match table.align[i] {
+                    Align::Left => line.push_str(&format!("{:<width$}", cell, width = widths[i])),
+                    Align::Right => line.push_str(&format!("{:>width$}", cell, width = widths[i])),
+                }
+            }
+        }
+        line
+    };
+
+    let mut lines = Vec::with_capacity(table.rows.len() + 1);
+    lines.push(render_row(&table.headers));
+    for row in &table.rows {
+        let cells: Vec<&str> = row.iter().map(|cell| cell.as_str()).collect();
+        lines.push(render_row(&cells));
+    }
+    lines
+}
+
+fn print_table(
+    out: &mut dyn Write,
+    prefixes: &[String],
+    objects: &[EnhancedObjectInfo],
+    human_readable: bool,
+    extra: ExtraColumns,
+) -> std::io::Result<()> {
+    let table = build_table(prefixes, objects, human_readable, extra);
+    let lines = render_table(&table);
+
+    for (i, line) in lines.iter().enumerate() {
+        if i == 0 {
+            writeln!(out, "{}", line.bold())?;
+        } else if i <= prefixes.len() {
+            writeln!(out, "{}", line.blue())?;
+        } else {
+            writeln!(out, "{line}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Grantee URIs that make an object readable outside the owning account
+const PUBLIC_GRANTEE_URIS: &[&str] = &[
+    "http://acs.amazonaws.com/groups/global/AllUsers",
+    "http://acs.amazonaws.com/groups/global/AuthenticatedUsers",
+];
+
+/// Check whether any grant exposes the object to everyone
+fn has_public_grant(grants: &[Grant]) -> bool {
+    grants.iter().any(|grant| {
+        let public_grantee = grant
+            .grantee()
+            .and_then(|grantee| grantee.uri())
+            .is_some_and(|uri| PUBLIC_GRANTEE_URIS.contains(&uri));
+        let readable = matches!(
+            grant.permission(),
+            Some(Permission::Read) | Some(Permission::FullControl)
+        );
+        public_grantee && readable
+    })
+}
+
+/// Fetch object ACLs with bounded concurrency and flag publicly readable keys.
+/// Keys whose ACL can't be read are left out of the map.
+async fn fetch_public_acl_flags(
+    config: &Config,
+    bucket: &str,
+    objects: &[EnhancedObjectInfo],
+) -> HashMap<String, bool> {
+    use futures::stream::{self, StreamExt};
+
+    stream::iter(objects.iter().map(|obj| obj.key.clone()))
+        .map(|key| async move {
+            let response = config
+                .client
+                .get_object_acl()
+                .bucket(bucket)
+                .key(&key)
+                .send()
+                .await;
+            match response {
+                Ok(acl) => Some((key, has_public_grant(acl.grants()))),
+                Err(e) => {
+                    log::debug!("Failed to read ACL for {key}: {e}");
+                    None
+                }
+            }
+        })
+        .buffer_unordered(ACL_CONCURRENCY)
+        .filter_map(|flag| async move { flag })
+        .collect()
+        .await
+}
+
+/// Whether listing left out a field that `--enrich` can fill from HeadObject
+fn needs_enrichment(obj: &EnhancedObjectInfo) -> bool {
+    obj.created.is_none() || obj.modified.is_none() || obj.storage_class.is_none()
+}
+
+/// `--enrich`: HeadObject each incomplete object so date and storage class
+/// filters see real values instead of dropping the row
+async fn enrich_objects(config: &Config, bucket: &str, objects: &mut [EnhancedObjectInfo]) {
+    use futures::stream::{self, StreamExt};
+
+    let pending: Vec<usize> = (0..objects.len())
+        .filter(|&index| needs_enrichment(&objects[index]))
+        .collect();
+    if pending.is_empty() {
+        return;
+    }
+
+    warn!(
+        "--enrich: sending {} HeadObject request(s) to s3://{bucket}; each one is billed as a separate request",
+        pending.len()
+    );
+
+    let keys: &[EnhancedObjectInfo] = objects;
+    let heads: Vec<(usize, HeadMetadata)> = stream::iter(pending)
+        .map(|index| async move {
+            let key = &keys[index].key;
+            let response = config
+                .client
+                .head_object()
+                .bucket(bucket)
+                .key(key)
+                .send()
+                .await;
+            match response {
+                Ok(head) => Some((
+                    index,
+                    HeadMetadata {
+                        last_modified: head.last_modified().and_then(|dt| {
+                            DateTime::<Utc>::from_timestamp(dt.secs(), dt.subsec_nanos())
+                        }),
+                        storage_class: head.storage_class().map(|sc| sc.as_str().to_string()),
+                        etag: head.e_tag().map(|tag| tag.to_string()),
+                    },
+                )),
+                Err(e) => {
+                    log::debug!("Failed to enrich {key}: {e}");
+                    None
+                }
+            }
+        })
+        .buffer_unordered(ENRICH_CONCURRENCY)
+        .filter_map(|head| async move { head })
+        .collect()
+        .await;
+
+    for (index, head) in heads {
+        apply_head_metadata(&mut objects[index], head);
+    }
+}
+
+/// The HeadObject fields `--enrich` copies into a listing row
+#[derive(Debug, Default)]
+struct HeadMetadata {
+    last_modified: Option<DateTime<Utc>>,
+    storage_class: Option<String>,
+    etag: Option<String>,
+}
+
+/// Fill only the fields the listing left empty. HeadObject omits the storage
+/// class header for STANDARD objects, so its absence means STANDARD.
+fn apply_head_metadata(obj: &mut EnhancedObjectInfo, head: HeadMetadata) {
+    if obj.created.is_none() {
+        obj.created = head.last_modified;
+    }
+    if obj.modified.is_none() {
+        obj.modified = head.last_modified;
+    }
+    if obj.storage_class.is_none() {
+        obj.storage_class = Some(head.storage_class.unwrap_or_else(|| "STANDARD".to_string()));
+    }
+    if obj.etag.is_none() {
+        obj.etag = head.etag;
+    }
 }
 
 fn format_size(size: i64) -> String {
@@ -483,26 +1531,29 @@ fn build_filter_config(
     modified_before: Option<&str>,
     min_size: Option<&str>,
     max_size: Option<&str>,
+    extensions: &[String],
+    storage_classes: &[String],
     max_results: Option<usize>,
     head: Option<usize>,
     tail: Option<usize>,
     sort_by: Option<&str>,
     reverse: bool,
+    now: DateTime<Utc>,
 ) -> Result<FilterConfig> {
     let mut config = FilterConfig::default();
 
     // Parse date filters
     if let Some(date_str) = created_after {
-        config.created_after = Some(parse_date_filter(date_str)?);
+        config.created_after = Some(parse_date_filter(date_str, now)?);
     }
     if let Some(date_str) = created_before {
-        config.created_before = Some(parse_date_filter(date_str)?);
+        config.created_before = Some(parse_date_filter(date_str, now)?);
     }
     if let Some(date_str) = modified_after {
-        config.modified_after = Some(parse_date_filter(date_str)?);
+        config.modified_after = Some(parse_date_filter(date_str, now)?);
     }
     if let Some(date_str) = modified_before {
-        config.modified_before = Some(parse_date_filter(date_str)?);
+        config.modified_before = Some(parse_date_filter(date_str, now)?);
     }
 
     // Parse size filters
@@ -513,6 +1564,12 @@ fn build_filter_config(
         config.max_size = Some(parse_size_filter(size_str)?);
     }
 
+    config.extensions = extensions.to_vec();
+    config.storage_classes = storage_classes
+        .iter()
+        .map(|class| Ok(parse_storage_class(class)?.as_str().to_string()))
+        .collect::<Result<_>>()?;
+
     // Set result limits
     config.max_results = max_results;
     config.head = head;
@@ -545,6 +1602,10 @@ fn convert_to_enhanced_object_info(object: &Object, _bucket_name: &str) -> Enhan
     // Extract additional metadata
     let storage_class = object.storage_class().map(|sc| sc.as_str().to_string());
     let etag = object.e_tag().map(|tag| tag.to_string());
+    let owner = object
+        .owner()
+        .and_then(|owner| owner.display_name().or(owner.id()))
+        .map(|owner| owner.to_string());
 
     EnhancedObjectInfo {
         key,
@@ -553,6 +1614,9 @@ fn convert_to_enhanced_object_info(object: &Object, _bucket_name: &str) -> Enhan
         modified,
         storage_class,
         etag,
+        owner,
+        version_id: None,
+        delete_marker: false,
     }
 }
 
@@ -577,7 +1641,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -588,23 +1661,8 @@ mod tests {
         let result = execute(
             &config,
             Some("s3://test-bucket"),
-            false,
-            false,
-            false,
-            false,
-            None,
-            "info",
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
+            &LsOptions::default(),
+            &mut Vec::new(),
         )
         .await;
 
@@ -619,23 +1677,8 @@ mod tests {
         let result = execute(
             &config,
             Some("s3://test-bucket/prefix/"),
-            false,
-            false,
-            false,
-            false,
-            None,
-            "info",
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
+            &LsOptions::default(),
+            &mut Vec::new(),
         )
         .await;
 
@@ -650,23 +1693,11 @@ mod tests {
         let result = execute(
             &config,
             Some("s3://test-bucket"),
-            false,
-            true,
-            false,
-            false,
-            None,
-            "info",
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
+            &LsOptions {
+                recursive: true,
+                ..Default::default()
+            },
+            &mut Vec::new(),
         )
         .await;
 
@@ -681,23 +1712,11 @@ mod tests {
         let result = execute(
             &config,
             Some("s3://test-bucket"),
-            true,
-            false,
-            false,
-            false,
-            None,
-            "info",
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
+            &LsOptions {
+                long: true,
+                ..Default::default()
+            },
+            &mut Vec::new(),
         )
         .await;
 
@@ -712,23 +1731,11 @@ mod tests {
         let result = execute(
             &config,
             Some("s3://test-bucket"),
-            false,
-            false,
-            true,
-            false,
-            None,
-            "info",
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
+            &LsOptions {
+                human_readable: true,
+                ..Default::default()
+            },
+            &mut Vec::new(),
         )
         .await;
 
@@ -743,23 +1750,11 @@ mod tests {
         let result = execute(
             &config,
             Some("s3://test-bucket"),
-            false,
-            false,
-            false,
-            true,
-            None,
-            "info",
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
+            &LsOptions {
+                summarize: true,
+                ..Default::default()
+            },
+            &mut Vec::new(),
         )
         .await;
 
@@ -774,23 +1769,14 @@ mod tests {
         let result = execute(
             &config,
             Some("s3://test-bucket/prefix/"),
-            true,
-            true,
-            true,
-            true,
-            None,
-            "info",
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            false,
+            &LsOptions {
+                long: true,
+                recursive: true,
+                human_readable: true,
+                summarize: true,
+                ..Default::default()
+            },
+            &mut Vec::new(),
         )
         .await;
 
@@ -802,11 +1788,7 @@ mod tests {
     async fn test_execute_no_path() {
         let config = create_mock_config();
 
-        let result = execute(
-            &config, None, false, false, false, false, None, "info", None, None, None, None, None,
-            None, None, None, None, None, false,
-        )
-        .await;
+        let result = execute(&config, None, &LsOptions::default(), &mut Vec::new()).await;
 
         // Will fail due to no AWS connection, but tests the routing
         assert!(result.is_err());
@@ -875,16 +1857,45 @@ mod tests {
 
     #[test]
     fn test_print_long_format_with_mock_object() {
-        // We can't easily test print_long_format since it prints to stdout
-        // and we'd need to mock AWS SDK types. The execute tests above
-        // cover the code paths that call print_long_format.
+        let obj = table_object("logs/app.log", 2048, Some("GLACIER"));
+        let extra = ExtraColumns {
+            owner: true,
+            acl: None,
+            etag: false,
+        };
 
-        // Test that format_size works correctly for the sizes that would be used
-        let test_sizes = vec![0, 1024, 1048576, 1073741824];
-        for size in test_sizes {
-            let formatted = format_size(size);
-            assert!(!formatted.is_empty());
-        }
+        let mut out = Vec::new();
+        print_enhanced_long_format(&mut out, &obj, false, extra).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "        2048 2024-01-15 10:30:00 GLACIER             logs/app.log owner=-\n"
+        );
+
+        let mut out = Vec::new();
+        print_enhanced_long_format(&mut out, &obj, true, extra).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("       2.0KB "));
+    }
+
+    #[test]
+    fn test_long_format_etag_and_default_storage_class() {
+        let mut obj = table_object("a.txt", 5, None);
+        obj.etag = Some("\"5d41402abc4b2a76b9719d911017c592\"".to_string());
+        let extra = ExtraColumns {
+            etag: true,
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_enhanced_long_format(&mut out, &obj, false, extra).unwrap();
+        print_long_format_prefix(&mut out, "logs/", extra).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[0],
+            "           5 2024-01-15 10:30:00 STANDARD            5d41402abc4b2a76b9719d911017c592 a.txt"
+        );
+        // Prefixes line up with the keys
+        assert_eq!(lines[1].find("logs/"), lines[0].find("a.txt"));
     }
 
     #[test]
@@ -925,4 +1936,544 @@ mod tests {
             );
         }
     }
+
+    fn table_object(key: &str, size: i64, storage_class: Option<&str>) -> EnhancedObjectInfo {
+        EnhancedObjectInfo {
+            key: key.to_string(),
+            size,
+            created: None,
+            modified: DateTime::parse_from_rfc3339("2024-01-15T10:30:00Z")
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc)),
+            storage_class: storage_class.map(|s| s.to_string()),
+            etag: None,
+            owner: None,
+            version_id: None,
+            delete_marker: false,
+        }
+    }
+
+    #[test]
+    fn test_print_json() {
+        let mut object = table_object("logs/app.log", 2048, Some("GLACIER"));
+        object.etag = Some("\"abc123\"".to_string());
+
+        let mut out = Vec::new();
+        print_json(&mut out, &["logs/archive/".to_string()], &[object]).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                { "prefix": "logs/archive/" },
+                {
+                    "key": "logs/app.log",
+                    "size": 2048,
+                    "last_modified": "2024-01-15T10:30:00Z",
+                    "storage_class": "GLACIER",
+                    "etag": "\"abc123\""
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_version_rows() {
+        let version = ObjectVersion::builder()
+            .key("logs/app.log")
+            .size(2048)
+            .version_id("3HL4kqtJlcpXroDTDmJ")
+            .build();
+        let row = version_row(&version);
+        assert_eq!(row.version_id.as_deref(), Some("3HL4kqtJlcpXroDTDmJ"));
+        assert_eq!(row.size, 2048);
+        assert!(!row.delete_marker);
+
+        let marker = DeleteMarkerEntry::builder()
+            .key("logs/app.log")
+            .version_id("null")
+            .build();
+        let row = delete_marker_row(&marker);
+        assert!(row.delete_marker);
+        assert_eq!(row.size, 0);
+    }
+
+    #[test]
+    fn test_print_version_line() {
+        let mut version = table_object("logs/app.log", 2048, None);
+        version.version_id = Some("v2".to_string());
+        let mut marker = table_object("logs/app.log", 0, None);
+        marker.version_id = Some("v3".to_string());
+        marker.delete_marker = true;
+
+        let mut out = Vec::new();
+        print_version_line(&mut out, &marker, false, false).unwrap();
+        print_version_line(&mut out, &version, true, false).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "{:<32} logs/app.log (delete marker)\n        2048 2024-01-15 10:30:00 {:<32} logs/app.log\n",
+                "v3", "v2"
+            )
+        );
+    }
+
+    #[test]
+    fn test_print_json_includes_versions() {
+        let mut version = table_object("logs/app.log", 2048, Some("STANDARD"));
+        version.version_id = Some("v2".to_string());
+        let mut marker = table_object("logs/app.log", 0, None);
+        marker.version_id = Some("v3".to_string());
+        marker.delete_marker = true;
+
+        let mut out = Vec::new();
+        print_json(&mut out, &[], &[marker, version]).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value[0]["version_id"], "v3");
+        assert_eq!(value[0]["delete_marker"], true);
+        assert_eq!(value[1]["version_id"], "v2");
+        assert!(value[1].get("delete_marker").is_none());
+    }
+
+    #[test]
+    fn test_render_table_aligns_columns() {
+        let prefixes = vec!["logs".to_string()];
+        let objects = vec![
+            table_object("a.txt", 5, Some("STANDARD")),
+            table_object("big.bin", 1234567, Some("GLACIER")),
+            table_object("c.txt", 42, None),
+        ];
+
+        let table = build_table(&prefixes, &objects, false, ExtraColumns::default());
+        let lines = render_table(&table);
+
+        assert_eq!(
+            lines,
+            vec![
+                "DATE                    SIZE  STORAGE CLASS  KEY",
+                "                         DIR                 logs/",
+                "2024-01-15 10:30:00        5  STANDARD       a.txt",
+                "2024-01-15 10:30:00  1234567  GLACIER        big.bin",
+                "2024-01-15 10:30:00       42  STANDARD       c.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_table_human_readable_sizes() {
+        let objects = vec![
+            table_object("small", 512, None),
+            table_object("large", 1536, None),
+        ];
+
+        let table = build_table(&[], &objects, true, ExtraColumns::default());
+        assert_eq!(table.rows[0][1], "512B");
+        assert_eq!(table.rows[1][1], "1.5KB");
+
+        let lines = render_table(&table);
+        // Sizes are right-aligned under the SIZE header
+        assert!(lines[1].contains("  512B  "));
+        assert!(lines[2].contains(" 1.5KB  "));
+    }
+
+    #[test]
+    fn test_render_table_owner_and_acl_columns() {
+        let mut owned = table_object("a.txt", 5, None);
+        owned.owner = Some("alice".to_string());
+        let objects = vec![owned, table_object("b.txt", 7, None)];
+        let acl = HashMap::from([("a.txt".to_string(), true)]);
+
+        let table = build_table(
+            &[],
+            &objects,
+            false,
+            ExtraColumns {
+                owner: true,
+                acl: Some(&acl),
+                etag: false,
+            },
+        );
+
+        assert_eq!(
+            table.headers,
+            vec!["DATE", "SIZE", "STORAGE CLASS", "OWNER", "ACL", "KEY"]
+        );
+        assert_eq!(table.rows[0][3..], ["alice", "PUBLIC", "a.txt"]);
+        // Missing owner and unreadable ACL fall back to placeholders
+        assert_eq!(table.rows[1][3..], ["-", "?", "b.txt"]);
+    }
+
+    #[test]
+    fn test_has_public_grant() {
+        use aws_sdk_s3::types::{Grantee, Type};
+
+        let grant = |grantee: Grantee, permission: Permission| {
+            Grant::builder()
+                .grantee(grantee)
+                .permission(permission)
+                .build()
+        };
+        let group = |uri: &str| {
+            Grantee::builder()
+                .r#type(Type::Group)
+                .uri(uri)
+                .build()
+                .unwrap()
+        };
+        let owner = Grantee::builder()
+            .r#type(Type::CanonicalUser)
+            .id("owner-id")
+            .build()
+            .unwrap();
+
+        let private = vec![grant(owner.clone(), Permission::FullControl)];
+        assert!(!has_public_grant(&private));
+
+        let public_read = vec![
+            grant(owner, Permission::FullControl),
+            grant(
+                group("http://acs.amazonaws.com/groups/global/AllUsers"),
+                Permission::Read,
+            ),
+        ];
+        assert!(has_public_grant(&public_read));
+
+        // Write-only grants don't expose object contents
+        let public_write = vec![grant(
+            group("http://acs.amazonaws.com/groups/global/AllUsers"),
+            Permission::Write,
+        )];
+        assert!(!has_public_grant(&public_write));
+
+        let log_delivery = vec![grant(
+            group("http://acs.amazonaws.com/groups/s3/LogDelivery"),
+            Permission::Read,
+        )];
+        assert!(!has_public_grant(&log_delivery));
+    }
+
+    #[test]
+    fn test_build_filter_config_storage_classes() {
+        let build = |classes: &[&str]| {
+            let classes: Vec<String> = classes.iter().map(|c| c.to_string()).collect();
+            build_filter_config(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                &[],
+                &classes,
+                None,
+                None,
+                None,
+                None,
+                false,
+                Utc::now(),
+            )
+        };
+
+        let config = build(&["standard", " GLACIER "]).unwrap();
+        assert_eq!(config.storage_classes, vec!["STANDARD", "GLACIER"]);
+        assert!(build(&["FROZEN"])
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown storage class"));
+    }
+
+    #[test]
+    fn test_merge_in_key_order_matches_serial_listing() {
+        let keys = |objects: &[EnhancedObjectInfo]| -> Vec<String> {
+            objects.iter().map(|obj| obj.key.clone()).collect()
+        };
+        let top_level = vec![table_object("a.txt", 1, None), table_object("c", 1, None)];
+        // Prefixes complete out of order under buffer_unordered
+        let nested = vec![
+            vec![table_object("b/2", 1, None), table_object("b/10", 1, None)],
+            vec![table_object("a/x", 1, None)],
+        ];
+
+        let merged = merge_in_key_order(top_level, nested);
+        // '.' sorts before '/', exactly as S3 orders keys
+        assert_eq!(keys(&merged), vec!["a.txt", "a/x", "b/10", "b/2", "c"]);
+    }
+
+    #[test]
+    fn test_synthesize_prefixes_from_recursive_keys() {
+        let keys = vec![
+            "logs/2024/01/app.log",
+            "logs/2024/02/app.log",
+            "logs/readme.txt",
+            "data/raw/a.csv",
+            "top.txt",
+        ];
+
+        assert_eq!(
+            synthesize_prefixes(&keys, ""),
+            vec![
+                "data/",
+                "data/raw/",
+                "logs/",
+                "logs/2024/",
+                "logs/2024/01/",
+                "logs/2024/02/",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_synthesize_prefixes_under_base_prefix() {
+        let keys = vec!["logs/2024/01/app.log", "logs/readme.txt", "logs/2024/x"];
+
+        assert_eq!(
+            synthesize_prefixes(&keys, "logs/"),
+            vec!["logs/2024/", "logs/2024/01/"]
+        );
+        // Keys outside the base prefix and leaf objects add nothing
+        assert!(synthesize_prefixes(&["other/a/b", "logs"], "logs/").is_empty());
+    }
+
+    #[test]
+    fn test_apply_head_metadata_fills_missing_fields() {
+        let modified = DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut obj = EnhancedObjectInfo {
+            key: "data/report.csv".to_string(),
+            size: 10,
+            created: None,
+            modified: None,
+            storage_class: None,
+            etag: Some("\"listed\"".to_string()),
+            owner: None,
+            version_id: None,
+            delete_marker: false,
+        };
+        assert!(needs_enrichment(&obj));
+
+        apply_head_metadata(
+            &mut obj,
+            HeadMetadata {
+                last_modified: Some(modified),
+                storage_class: None,
+                etag: Some("\"head\"".to_string()),
+            },
+        );
+
+        assert_eq!(obj.created, Some(modified));
+        assert_eq!(obj.modified, Some(modified));
+        // HeadObject leaves out the header for STANDARD objects
+        assert_eq!(obj.storage_class.as_deref(), Some("STANDARD"));
+        // Values from the listing are kept
+        assert_eq!(obj.etag.as_deref(), Some("\"listed\""));
+        assert!(!needs_enrichment(&obj));
+
+        // Now filterable by creation date instead of silently excluded
+        let filter = FilterConfig {
+            created_after: Some(modified - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(passes_filters(&obj, &filter));
+    }
+
+    #[test]
+    fn test_apply_head_metadata_keeps_listed_values() {
+        let listed = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut obj = EnhancedObjectInfo {
+            key: "a".to_string(),
+            size: 1,
+            created: Some(listed),
+            modified: Some(listed),
+            storage_class: Some("GLACIER".to_string()),
+            etag: None,
+            owner: None,
+            version_id: None,
+            delete_marker: false,
+        };
+        assert!(!needs_enrichment(&obj));
+
+        apply_head_metadata(
+            &mut obj,
+            HeadMetadata {
+                last_modified: Some(listed + chrono::Duration::days(1)),
+                storage_class: Some("STANDARD_IA".to_string()),
+                etag: None,
+            },
+        );
+
+        assert_eq!(obj.created, Some(listed));
+        assert_eq!(obj.storage_class.as_deref(), Some("GLACIER"));
+    }
+
+    #[tokio::test]
+    async fn test_listing_advances_metrics() {
+        use crate::otel::GLOBAL_METRICS;
+        use std::sync::atomic::Ordering;
+
+        let config = crate::commands::test_client::canned_config(|_, _| {
+            (
+                200,
+                "<ListBucketResult>\
+                   <Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                   <Contents><Key>report.csv</Key><Size>42</Size>\
+                   <LastModified>2024-01-01T00:00:00.000Z</LastModified></Contents>\
+                 </ListBucketResult>"
+                    .to_string(),
+            )
+        });
+        let lists = GLOBAL_METRICS.lists_total.load(Ordering::Relaxed);
+        let mut out = Vec::new();
+
+        execute(
+            &config,
+            Some("s3://bucket"),
+            &LsOptions::default(),
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert!(String::from_utf8(out).unwrap().contains("report.csv"));
+        assert!(GLOBAL_METRICS.lists_total.load(Ordering::Relaxed) > lists);
+    }
+
+    #[tokio::test]
+    async fn test_folders_only_skips_acl_lookups() {
+        let (config, requests) = crate::commands::test_client::recording_config(|_, _| {
+            (
+                200,
+                "<ListBucketResult>\
+                   <Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                   <Contents><Key>logs/a.log</Key><Size>42</Size>\
+                   <LastModified>2024-01-01T00:00:00.000Z</LastModified></Contents>\
+                 </ListBucketResult>"
+                    .to_string(),
+            )
+        });
+        let mut out = Vec::new();
+
+        execute(
+            &config,
+            Some("s3://bucket"),
+            &LsOptions {
+                long: true,
+                recursive: true,
+                folders_only: true,
+                show_acl: true,
+                ..Default::default()
+            },
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert!(String::from_utf8(out).unwrap().contains("logs/"));
+        // Only the listing: no GetObjectAcl for objects folders-only hides
+        let requests = requests.lock().unwrap();
+        assert!(
+            requests.iter().all(|request| !request.uri.contains("acl")),
+            "{requests:?}"
+        );
+    }
+
+    fn object_at(key: &str, modified: &str) -> EnhancedObjectInfo {
+        EnhancedObjectInfo {
+            key: key.to_string(),
+            size: 0,
+            created: None,
+            modified: Some(modified.parse().unwrap()),
+            storage_class: None,
+            etag: None,
+            owner: None,
+            version_id: None,
+            delete_marker: false,
+        }
+    }
+
+    #[test]
+    fn test_follow_watermark() {
+        let mut watermark = FollowWatermark::default();
+        let first = object_at("a.log", "2024-01-01T00:00:00Z");
+        assert!(watermark.is_new(&first));
+        watermark.observe(&first);
+        assert!(!watermark.is_new(&first));
+
+        // Same second, different key
+        assert!(watermark.is_new(&object_at("b.log", "2024-01-01T00:00:00Z")));
+        // Overwritten in place
+        assert!(watermark.is_new(&object_at("a.log", "2024-01-01T00:00:05Z")));
+        let mut replaced = first.clone();
+        replaced.etag = Some("\"new\"".to_string());
+        assert!(watermark.is_new(&replaced));
+
+        // A multipart upload started before the newest object but completed
+        // after it was printed is still reported
+        let later = object_at("c.log", "2024-01-01T00:10:00Z");
+        watermark.observe(&later);
+        assert!(watermark.is_new(&object_at("big.bin", "2024-01-01T00:05:00Z")));
+
+        // Beyond the lookback window everything counts as seen
+        assert!(!watermark.is_new(&object_at("old.log", "2023-12-30T00:00:00Z")));
+        watermark.observe(&object_at("d.log", "2024-01-03T00:00:00Z"));
+        assert!(!watermark.seen.contains_key("a.log"));
+        assert!(!watermark.is_new(&first));
+    }
+
+    #[tokio::test]
+    async fn test_poll_new_objects() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let polls = Arc::new(AtomicUsize::new(0));
+        let counter = polls.clone();
+        let config = crate::commands::test_client::canned_config(move |_, _| {
+            let contents = match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => {
+                    "<Contents><Key>logs/a.log</Key><Size>1</Size>\
+                      <LastModified>2024-01-01T00:00:00.000Z</LastModified></Contents>"
+                }
+                _ => {
+                    "<Contents><Key>logs/a.log</Key><Size>1</Size>\
+                      <LastModified>2024-01-01T00:00:00.000Z</LastModified></Contents>\
+                      <Contents><Key>logs/c.log</Key><Size>1</Size>\
+                      <LastModified>2024-01-01T00:01:00.000Z</LastModified></Contents>\
+                      <Contents><Key>logs/b.log</Key><Size>1</Size>\
+                      <LastModified>2024-01-01T00:00:00.000Z</LastModified></Contents>"
+                }
+            };
+            (
+                200,
+                format!(
+                    "<ListBucketResult><Name>logs</Name><IsTruncated>false</IsTruncated>\
+                     {contents}</ListBucketResult>"
+                ),
+            )
+        });
+        let listing = FollowListing {
+            bucket: "logs",
+            prefix: "logs/",
+            recursive: true,
+            show_owner: false,
+        };
+        let mut watermark = FollowWatermark::default();
+
+        let seeded = poll_new_objects(&config, &listing, &mut watermark)
+            .await
+            .unwrap();
+        assert_eq!(seeded.len(), 1);
+
+        let keys: Vec<String> = poll_new_objects(&config, &listing, &mut watermark)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|obj| obj.key)
+            .collect();
+        assert_eq!(keys, vec!["logs/b.log", "logs/c.log"]);
+
+        assert!(poll_new_objects(&config, &listing, &mut watermark)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
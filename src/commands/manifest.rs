@@ -0,0 +1,397 @@
+use anyhow::Result;
+use aws_sdk_s3::primitives::ByteStream;
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use tokio::io::AsyncReadExt;
+
+use crate::commands::s3_uri::S3Uri;
+use crate::config::Config;
+
+/// SHA-256 sums loaded from a `sha256sum`-style manifest (`--checksum-manifest`)
+#[derive(Debug, Default)]
+pub struct ChecksumManifest {
+    entries: HashMap<String, String>,
+}
+
+impl ChecksumManifest {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read checksum manifest {path}: {e}"))?;
+        Self::parse(&contents).map_err(|e| anyhow::anyhow!("{path}: {e}"))
+    }
+
+    /// Parse `<hash>  <relative-path>` lines; a `*` before the path (binary
+    /// mode), blank lines and `#` comments are accepted
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut entries = HashMap::new();
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim_end();
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (hash, path) = line
+                .split_once(char::is_whitespace)
+                .map(|(hash, rest)| (hash, rest.trim_start().trim_start_matches('*')))
+                .filter(|(hash, path)| {
+                    hash.len() == 64
+                        && hash.chars().all(|c| c.is_ascii_hexdigit())
+                        && !path.is_empty()
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!("line {}: expected '<sha256>  <path>'", number + 1)
+                })?;
+
+            entries.insert(normalize_path(path), hash.to_lowercase());
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn expected(&self, relative_path: &str) -> Option<&str> {
+        self.entries
+            .get(&normalize_path(relative_path))
+            .map(|hash| hash.as_str())
+    }
+
+    /// Manifest key for a single-file transfer: the path as given when the
+    /// manifest lists it, otherwise just the file name
+    pub fn single_file_key(&self, path: &str) -> String {
+        if self.expected(path).is_some() {
+            return normalize_path(path);
+        }
+        Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| normalize_path(path))
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+/// Result of checking one transferred file against the manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestOutcome {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    Missing,
+}
+
+/// Checks transferred files against a manifest and collects a per-file report
+#[derive(Debug)]
+pub struct ManifestVerifier {
+    manifest: ChecksumManifest,
+    results: Mutex<Vec<(String, ManifestOutcome)>>,
+}
+
+impl ManifestVerifier {
+    pub fn new(manifest: ChecksumManifest) -> Self {
+        Self {
+            manifest,
+            results: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn manifest(&self) -> &ChecksumManifest {
+        &self.manifest
+    }
+
+    /// Hash a local file and record how it compares with its manifest entry
+    pub async fn verify_file(&self, relative_path: &str, local_path: &Path) -> Result<()> {
+        let actual = sha256_file(local_path).await?;
+        self.check(relative_path, &actual);
+        Ok(())
+    }
+
+    fn check(&self, relative_path: &str, actual: &str) -> ManifestOutcome {
+        let outcome = match self.manifest.expected(relative_path) {
+            None => ManifestOutcome::Missing,
+            Some(expected) if expected.eq_ignore_ascii_case(actual) => ManifestOutcome::Pass,
+            Some(expected) => ManifestOutcome::Mismatch {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        };
+
+        self.results
+            .lock()
+            .unwrap()
+            .push((normalize_path(relative_path), outcome.clone()));
+        outcome
+    }
+
+    /// Print the pass/fail table and fail if any file mismatched or had no entry
+    pub fn finish(&self) -> Result<()> {
+        let results = self.results.lock().unwrap();
+        let width = results
+            .iter()
+            .map(|(path, _)| path.len())
+            .max()
+            .unwrap_or(0)
+            .max("PATH".len());
+
+        println!("{:<width$}  RESULT", "PATH");
+        let mut failures = 0;
+        for (path, outcome) in results.iter() {
+            let result = match outcome {
+                ManifestOutcome::Pass => "PASS".to_string(),
+                ManifestOutcome::Mismatch { expected, actual } => {
+                    format!("FAIL (expected {expected}, got {actual})")
+                }
+                ManifestOutcome::Missing => "FAIL (no manifest entry)".to_string(),
+            };
+            if *outcome != ManifestOutcome::Pass {
+                failures += 1;
+            }
+            println!("{path:<width$}  {result}");
+        }
+
+        if failures > 0 {
+            return Err(anyhow::anyhow!(
+                "Checksum manifest verification failed for {failures} of {} file(s)",
+                results.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// One object written by a recursive upload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WrittenObject {
+    pub key: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Serialize)]
+struct CompletionDocument<'a> {
+    completed_at: DateTime<Utc>,
+    total_objects: usize,
+    total_bytes: u64,
+    objects: &'a [WrittenObject],
+}
+
+/// JSON completion marker listing every key a recursive upload wrote
+/// (`--write-manifest`), in the spirit of Hadoop's `_SUCCESS` file. It is only
+/// uploaded once every transfer has succeeded.
+#[derive(Debug)]
+pub struct CompletionManifest {
+    target: S3Uri,
+    objects: Mutex<Vec<WrittenObject>>,
+}
+
+impl CompletionManifest {
+    /// `key` is placed under the destination prefix unless it is a full s3:// URI
+    pub fn new(dest: &str, key: &str) -> Result<Self> {
+        let target = if key.starts_with("s3://") {
+            S3Uri::parse(key)?
+        } else {
+            let dest = S3Uri::parse(dest)?;
+            let prefix = dest.key_or_empty().trim_end_matches('/');
+            let key = key.trim_start_matches('/');
+            S3Uri {
+                key: Some(if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{prefix}/{key}")
+                }),
+                bucket: dest.bucket,
+            }
+        };
+
+        if target.key_or_empty().is_empty() || target.key_or_empty().ends_with('/') {
+            return Err(anyhow::anyhow!(
+                "--write-manifest needs an object key, e.g. _SUCCESS"
+            ));
+        }
+
+        Ok(Self {
+            target,
+            objects: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Hash an uploaded local file and add its key to the listing
+    pub async fn record(&self, key: &str, local_path: &Path, size: u64) -> Result<()> {
+        let sha256 = sha256_file(local_path).await?;
+        self.objects.lock().unwrap().push(WrittenObject {
+            key: key.to_string(),
+            size,
+            sha256,
+        });
+        Ok(())
+    }
+
+    fn to_json(&self, completed_at: DateTime<Utc>) -> Result<String> {
+        let mut objects = self.objects.lock().unwrap().clone();
+        objects.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let document = CompletionDocument {
+            completed_at,
+            total_objects: objects.len(),
+            total_bytes: objects.iter().map(|obj| obj.size).sum(),
+            objects: &objects,
+        };
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    /// Upload the marker; call only after every transfer has succeeded
+    pub async fn upload(&self, config: &Config) -> Result<()> {
+        let body = self.to_json(Utc::now())?;
+        let uri = self.target.to_string();
+
+        config
+            .client
+            .put_object()
+            .bucket(&self.target.bucket)
+            .key(self.target.key_or_empty())
+            .content_type("application/json")
+            .body(ByteStream::from(body.into_bytes()))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to write completion manifest {uri}: {e}"))?;
+
+        info!("Wrote completion manifest {uri}");
+        Ok(())
+    }
+}
+
+/// Hex SHA-256 of a local file, read in chunks
+pub async fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to open {} for hashing: {e}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = ChecksumManifest::parse(&format!(
+            "# release artifacts\n{HELLO_SHA256}  ./dist/app.tar.gz\n\n{} *notes.txt\n",
+            "A".repeat(64)
+        ))
+        .unwrap();
+
+        assert_eq!(manifest.expected("dist/app.tar.gz"), Some(HELLO_SHA256));
+        assert_eq!(
+            manifest.expected("notes.txt").map(str::to_string),
+            Some("a".repeat(64))
+        );
+        assert_eq!(manifest.expected("other"), None);
+
+        assert!(ChecksumManifest::parse("deadbeef  file\n").is_err());
+        assert!(ChecksumManifest::parse(HELLO_SHA256).is_err());
+    }
+
+    #[test]
+    fn test_single_file_key() {
+        let manifest =
+            ChecksumManifest::parse(&format!("{HELLO_SHA256}  dist/app.tar.gz\n")).unwrap();
+
+        assert_eq!(
+            manifest.single_file_key("./dist/app.tar.gz"),
+            "dist/app.tar.gz"
+        );
+        assert_eq!(
+            manifest.single_file_key("/tmp/out/app.tar.gz"),
+            "app.tar.gz"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verifier_reports_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hello.txt");
+        std::fs::write(&file, b"hello").unwrap();
+
+        let manifest = ChecksumManifest::parse(&format!(
+            "{HELLO_SHA256}  hello.txt\n{}  other.txt\n",
+            "0".repeat(64)
+        ))
+        .unwrap();
+        let verifier = ManifestVerifier::new(manifest);
+
+        verifier.verify_file("hello.txt", &file).await.unwrap();
+        assert!(verifier.finish().is_ok());
+
+        assert_eq!(
+            verifier.check("other.txt", HELLO_SHA256),
+            ManifestOutcome::Mismatch {
+                expected: "0".repeat(64),
+                actual: HELLO_SHA256.to_string(),
+            }
+        );
+        assert_eq!(
+            verifier.check("new.txt", HELLO_SHA256),
+            ManifestOutcome::Missing
+        );
+        assert!(verifier.finish().is_err());
+    }
+
+    #[test]
+    fn test_completion_manifest_key_placement() {
+        let manifest = CompletionManifest::new("s3://bucket/releases/v1/", "_SUCCESS").unwrap();
+        assert_eq!(manifest.target.bucket, "bucket");
+        assert_eq!(manifest.target.key_or_empty(), "releases/v1/_SUCCESS");
+
+        let manifest = CompletionManifest::new("s3://bucket", "done.json").unwrap();
+        assert_eq!(manifest.target.key_or_empty(), "done.json");
+
+        let manifest =
+            CompletionManifest::new("s3://bucket/data", "s3://markers/run-1.json").unwrap();
+        assert_eq!(manifest.target.bucket, "markers");
+        assert_eq!(manifest.target.key_or_empty(), "run-1.json");
+
+        assert!(CompletionManifest::new("s3://bucket/data", "").is_err());
+        assert!(CompletionManifest::new("./local", "_SUCCESS").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_completion_manifest_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hello.txt");
+        std::fs::write(&file, b"hello").unwrap();
+
+        let manifest = CompletionManifest::new("s3://bucket/out", "_SUCCESS").unwrap();
+        manifest.record("out/z.txt", &file, 5).await.unwrap();
+        manifest.record("out/a.txt", &file, 5).await.unwrap();
+
+        let completed_at = DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let json: serde_json::Value =
+            serde_json::from_str(&manifest.to_json(completed_at).unwrap()).unwrap();
+
+        assert_eq!(json["completed_at"], "2024-05-01T10:00:00Z");
+        assert_eq!(json["total_objects"], 2);
+        assert_eq!(json["total_bytes"], 10);
+        assert_eq!(json["objects"][0]["key"], "out/a.txt");
+        assert_eq!(json["objects"][1]["key"], "out/z.txt");
+        assert_eq!(json["objects"][0]["sha256"], HELLO_SHA256);
+    }
+}
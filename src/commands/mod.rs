@@ -1,18 +1,36 @@
 pub mod bucket;
+pub mod cat;
 pub mod config;
 pub mod cp;
+pub mod decompress;
 pub mod du;
+pub mod exists;
+pub mod find_dupes;
 pub mod get;
 pub mod head_object;
 pub mod ls;
+pub mod manifest;
+pub mod mv;
+pub mod paginate;
+pub mod partial;
 pub mod presign;
+pub mod restore;
+pub mod retry;
 pub mod rm;
 pub mod s3_uri;
+pub mod staging;
 pub mod sync;
+pub mod tag;
+#[cfg(test)]
+pub mod test_client;
 pub mod upload;
+pub mod verify;
+pub mod version;
+pub mod wait;
 
-use crate::args::{Args, Commands};
+use crate::args::{Args, BucketCommands, Commands, TagCommands};
 use crate::config::Config;
+use crate::progress::ProgressDisplay;
 use anyhow::Result;
 
 /// Execute the appropriate command based on CLI arguments
@@ -25,40 +43,67 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
             human_readable,
             summarize,
             pattern,
+            ignore_case,
             created_after,
             created_before,
             modified_after,
             modified_before,
             min_size,
             max_size,
+            extension,
+            storage_class,
             max_results,
             head,
             tail,
             sort_by,
             reverse,
+            folders_only,
+            files_only,
+            show_owner,
+            show_acl,
+            show_etag,
+            enrich,
+            count,
+            versions,
+            parallel,
+            follow,
+            interval,
         } => {
-            ls::execute(
-                config,
-                path.as_deref(),
-                *long,
-                *recursive,
-                *human_readable,
-                *summarize,
-                pattern.as_deref(),
-                &args.debug,
-                created_after.as_deref(),
-                created_before.as_deref(),
-                modified_after.as_deref(),
-                modified_before.as_deref(),
-                min_size.as_deref(),
-                max_size.as_deref(),
-                *max_results,
-                *head,
-                *tail,
-                sort_by.as_deref(),
-                *reverse,
-            )
-            .await
+            let options = ls::LsOptions {
+                long: *long,
+                recursive: *recursive,
+                human_readable: *human_readable,
+                summarize: *summarize,
+                pattern: pattern.clone(),
+                ignore_case: *ignore_case,
+                debug_level: args.debug.clone(),
+                created_after: created_after.clone(),
+                created_before: created_before.clone(),
+                modified_after: modified_after.clone(),
+                modified_before: modified_before.clone(),
+                min_size: min_size.clone(),
+                max_size: max_size.clone(),
+                extensions: extension.clone(),
+                storage_classes: storage_class.clone(),
+                max_results: *max_results,
+                head: *head,
+                tail: *tail,
+                sort_by: sort_by.clone(),
+                reverse: *reverse,
+                output: args.output,
+                folders_only: *folders_only,
+                files_only: *files_only,
+                show_owner: *show_owner,
+                show_acl: *show_acl,
+                show_etag: *show_etag,
+                enrich: *enrich,
+                count: *count,
+                versions: *versions,
+                parallel: *parallel,
+                follow: *follow,
+                interval: *interval,
+            };
+            ls::execute(config, path.as_deref(), &options, &mut std::io::stdout()).await
         }
         Commands::Cp {
             source,
@@ -69,7 +114,47 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
             force,
             include,
             exclude,
+            exclude_dir,
+            content_type,
+            metadata,
+            tags,
+            decompress,
+            checksum_manifest,
+            write_manifest,
+            multipart_threshold,
+            part_size,
+            storage_class,
+            checksum_algorithm,
+            quiet,
         } => {
+            let transfer_options = cp::TransferOptions {
+                content_type: content_type.clone(),
+                decompress: *decompress,
+                progress: ProgressDisplay::detect(args.progress, args.no_progress || *quiet),
+                temp_dir: staging::resolve_download_temp_dir(
+                    args.temp_dir.as_deref(),
+                    source,
+                    dest,
+                )?,
+                checksum_manifest: load_checksum_manifest(checksum_manifest.as_deref())?,
+                write_manifest: completion_manifest(source, dest, write_manifest.as_deref())?,
+                metadata: cp::parse_metadata(metadata)?,
+                tagging: Some(tag::parse_tags(tags)?)
+                    .filter(|tags| !tags.is_empty())
+                    .map(|tags| tag::tagging_header(&tags)),
+                multipart: upload::MultipartSettings::from_args(
+                    multipart_threshold.as_deref(),
+                    part_size.as_deref(),
+                )?,
+                storage_class: storage_class
+                    .as_deref()
+                    .map(cp::parse_storage_class)
+                    .transpose()?,
+                checksum_algorithm: checksum_algorithm.map(cp::sdk_checksum_algorithm),
+                exclude_dirs: exclude_dir.clone(),
+                quiet: *quiet,
+                ..Default::default()
+            };
             cp::execute(
                 config,
                 source,
@@ -80,6 +165,7 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
                 *force,
                 include.as_deref(),
                 exclude.as_deref(),
+                &transfer_options,
             )
             .await
         }
@@ -88,10 +174,41 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
             dest,
             delete,
             dryrun,
-            max_concurrent: _,
+            max_concurrent,
             include,
             exclude,
+            size_only,
+            exact_timestamps,
+            max_delete,
+            max_delete_percent,
+            verify,
+            checksum_manifest,
+            write_manifest,
+            content_type,
+            metadata,
+            compare_metadata,
+            checksum,
+            quiet,
         } => {
+            let sync_options = sync::SyncOptions {
+                max_delete: *max_delete,
+                max_delete_percent: *max_delete_percent,
+                verify: *verify,
+                progress: ProgressDisplay::detect(args.progress, args.no_progress || *quiet),
+                temp_dir: staging::resolve_download_temp_dir(
+                    args.temp_dir.as_deref(),
+                    source,
+                    dest,
+                )?,
+                checksum_manifest: load_checksum_manifest(checksum_manifest.as_deref())?,
+                write_manifest: completion_manifest(source, dest, write_manifest.as_deref())?,
+                content_type: content_type.clone(),
+                metadata: cp::parse_metadata(metadata)?,
+                compare_metadata: *compare_metadata,
+                max_concurrent: *max_concurrent,
+                checksum: *checksum,
+                quiet: *quiet,
+            };
             sync::execute(
                 config,
                 source,
@@ -100,8 +217,38 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
                 *delete,
                 exclude.as_deref(),
                 include.as_deref(),
-                false,
-                false,
+                *size_only,
+                *exact_timestamps,
+                &sync_options,
+            )
+            .await
+        }
+        Commands::Mv {
+            source,
+            dest,
+            recursive,
+            dryrun,
+            include,
+            exclude,
+        } => {
+            let transfer_options = cp::TransferOptions {
+                progress: ProgressDisplay::detect(args.progress, args.no_progress),
+                temp_dir: staging::resolve_download_temp_dir(
+                    args.temp_dir.as_deref(),
+                    source,
+                    dest,
+                )?,
+                ..Default::default()
+            };
+            mv::execute(
+                config,
+                source,
+                dest,
+                *recursive,
+                *dryrun,
+                include.as_deref(),
+                exclude.as_deref(),
+                &transfer_options,
             )
             .await
         }
@@ -111,6 +258,10 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
             dryrun,
             include,
             exclude,
+            ignore_locked,
+            yes,
+            quiet,
+            version_id,
         } => {
             rm::execute(
                 config,
@@ -120,6 +271,11 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
                 false,
                 include.as_deref(),
                 exclude.as_deref(),
+                *ignore_locked,
+                *yes,
+                *quiet,
+                version_id.as_deref(),
+                &mut std::io::stdout(),
             )
             .await
         }
@@ -129,7 +285,7 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
             } else {
                 s3_uri
             };
-            bucket::create_bucket(config, bucket_name, None).await
+            bucket::create_bucket(config, bucket_name, Some(&args.region)).await
         }
         Commands::Rb {
             s3_uri,
@@ -137,11 +293,19 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
             all,
             confirm,
             pattern,
+            ignore_case,
         } => {
             if *all {
                 bucket::delete_all_buckets(config, *force, *confirm).await
             } else if let Some(pattern_str) = pattern {
-                bucket::delete_buckets_by_pattern(config, pattern_str, *force, *confirm).await
+                bucket::delete_buckets_by_pattern(
+                    config,
+                    pattern_str,
+                    *ignore_case,
+                    *force,
+                    *confirm,
+                )
+                .await
             } else if let Some(uri) = s3_uri {
                 let bucket_name = if let Some(stripped) = uri.strip_prefix("s3://") {
                     stripped // Remove "s3://" prefix
@@ -153,25 +317,176 @@ pub async fn execute_command(args: &Args, config: &Config) -> Result<()> {
                 anyhow::bail!("Either provide a bucket URI, use --all flag to delete all buckets, or use --pattern to delete buckets matching a wildcard pattern")
             }
         }
-        Commands::Presign { s3_uri, expires_in } => {
-            presign::execute(config, s3_uri, *expires_in, None).await
+        Commands::Bucket { command } => match command {
+            BucketCommands::CleanMultipart {
+                s3_uri,
+                older_than,
+                dryrun,
+            } => bucket::clean_multipart(config, s3_uri, older_than.as_deref(), *dryrun).await,
+            BucketCommands::Versioning { action, s3_uri } => {
+                bucket::versioning(config, s3_uri, *action).await
+            }
+        },
+        Commands::Presign {
+            s3_uri,
+            expires_in,
+            method,
+            content_type,
+            recursive,
+            keys_from_file,
+        } => {
+            // The header would be signed into the URL, and S3 would then
+            // reject anyone using the URL without sending it themselves
+            if args.expected_bucket_owner.is_some() {
+                return Err(anyhow::anyhow!(
+                    "--expected-bucket-owner can't be used with presign: the URL would only \
+                     work for clients that send the x-amz-expected-bucket-owner header"
+                ));
+            }
+            presign::execute(
+                config,
+                s3_uri,
+                *expires_in,
+                Some(method.as_str()),
+                content_type.as_deref(),
+                *recursive,
+                keys_from_file.as_deref(),
+            )
+            .await
         }
-        Commands::HeadObject { bucket, key } => {
+        Commands::HeadObject {
+            bucket,
+            key,
+            preview_bytes,
+            preview_tail,
+            checksum,
+        } => {
             let s3_uri = format!("s3://{bucket}/{key}");
-            head_object::execute(config, &s3_uri).await
+            head_object::execute(
+                config,
+                &s3_uri,
+                *preview_bytes,
+                *preview_tail,
+                *checksum,
+                args.output,
+                &mut std::io::stdout(),
+            )
+            .await
+        }
+        Commands::Exists { s3_uri, quiet } => exists::execute(config, s3_uri, *quiet).await,
+        Commands::Wait {
+            condition,
+            s3_uri,
+            wait_timeout,
+            interval,
+        } => wait::execute(config, s3_uri, *condition, *wait_timeout, *interval).await,
+        Commands::Tag { command } => match command {
+            TagCommands::Get { s3_uri } => tag::get(config, s3_uri, &mut std::io::stdout()).await,
+            TagCommands::Set { s3_uri, tags } => tag::set(config, s3_uri, tags).await,
+            TagCommands::Remove { s3_uri } => tag::remove(config, s3_uri).await,
+        },
+        Commands::Restore {
+            s3_uri,
+            days,
+            tier,
+            recursive,
+        } => restore::execute(config, s3_uri, *days, *tier, *recursive).await,
+        Commands::Cat {
+            s3_uri,
+            range,
+            follow,
+            interval,
+            decompress,
+        } => {
+            cat::execute(
+                config,
+                s3_uri,
+                range.as_deref(),
+                *follow,
+                *interval,
+                *decompress,
+            )
+            .await
         }
         Commands::Du {
             s3_uri,
             human_readable,
             summarize,
-        } => du::execute(config, s3_uri, *human_readable, *summarize, None).await,
-        Commands::Config { command } => config::execute(command.clone()).await,
+        } => {
+            du::execute(
+                config,
+                s3_uri,
+                *human_readable,
+                *summarize,
+                None,
+                args.output,
+                &mut std::io::stdout(),
+            )
+            .await
+        }
+        Commands::FindDupes {
+            s3_uri,
+            min_size,
+            human_readable,
+        } => {
+            find_dupes::execute(
+                config,
+                s3_uri,
+                min_size.as_deref(),
+                *human_readable,
+                &mut std::io::stdout(),
+            )
+            .await
+        }
+        Commands::Verify { s3_uri, local_path } => {
+            verify::execute(config, s3_uri, local_path).await
+        }
+        Commands::Config { command } => {
+            let profile = crate::config::active_profile(args.profile.as_deref());
+            config::execute(command.clone(), &profile).await
+        }
+        Commands::Version { json } => version::execute(*json).await,
     }
 }
 
+/// Load `--checksum-manifest` up front so a bad manifest fails before any transfer
+fn load_checksum_manifest(
+    path: Option<&str>,
+) -> Result<Option<std::sync::Arc<manifest::ManifestVerifier>>> {
+    path.map(|path| {
+        let manifest = manifest::ChecksumManifest::load(path)?;
+        Ok(std::sync::Arc::new(manifest::ManifestVerifier::new(
+            manifest,
+        )))
+    })
+    .transpose()
+}
+
+/// Set up `--write-manifest`, which only makes sense for local-to-S3 uploads
+fn completion_manifest(
+    source: &str,
+    dest: &str,
+    key: Option<&str>,
+) -> Result<Option<std::sync::Arc<manifest::CompletionManifest>>> {
+    key.map(|key| {
+        if s3_uri::is_s3_uri(source) || source == cp::STDIN_SOURCE || !s3_uri::is_s3_uri(dest) {
+            return Err(anyhow::anyhow!(
+                "--write-manifest is only supported when uploading a local directory to S3"
+            ));
+        }
+        Ok(std::sync::Arc::new(manifest::CompletionManifest::new(
+            dest, key,
+        )?))
+    })
+    .transpose()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::args::{
+        ColorChoice, OutputFormat, PresignMethod, ProgressMode, RestoreTier, WaitCondition,
+    };
     use aws_sdk_s3::Client;
     use std::sync::Arc;
 
@@ -193,7 +508,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -204,7 +528,34 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Ls {
                 path: Some("s3://test-bucket".to_string()),
                 long: false,
@@ -212,17 +563,31 @@ mod tests {
                 human_readable: false,
                 summarize: false,
                 pattern: None,
+                ignore_case: false,
                 created_after: None,
                 created_before: None,
                 modified_after: None,
                 modified_before: None,
                 min_size: None,
                 max_size: None,
+                extension: Vec::new(),
+                storage_class: Vec::new(),
                 max_results: None,
                 head: None,
                 tail: None,
                 sort_by: None,
                 reverse: false,
+                folders_only: false,
+                files_only: false,
+                show_owner: false,
+                show_acl: false,
+                show_etag: false,
+                enrich: false,
+                count: false,
+                versions: false,
+                parallel: None,
+                follow: false,
+                interval: 5,
             },
         };
 
@@ -239,7 +604,34 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Cp {
                 source: "./test".to_string(),
                 dest: "s3://bucket/test".to_string(),
@@ -249,6 +641,18 @@ mod tests {
                 force: false,
                 include: None,
                 exclude: None,
+                exclude_dir: Vec::new(),
+                content_type: None,
+                metadata: Vec::new(),
+                tags: Vec::new(),
+                decompress: false,
+                checksum_manifest: None,
+                write_manifest: None,
+                multipart_threshold: None,
+                part_size: None,
+                storage_class: None,
+                checksum_algorithm: None,
+                quiet: false,
             },
         };
 
@@ -264,7 +668,34 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Sync {
                 source: ".".to_string(), // Use current directory which exists
                 dest: "s3://bucket/test".to_string(),
@@ -273,6 +704,18 @@ mod tests {
                 max_concurrent: 4,
                 include: None,
                 exclude: None,
+                size_only: false,
+                exact_timestamps: false,
+                max_delete: None,
+                max_delete_percent: None,
+                verify: false,
+                checksum_manifest: None,
+                write_manifest: None,
+                content_type: None,
+                metadata: Vec::new(),
+                compare_metadata: false,
+                checksum: false,
+                quiet: false,
             },
         };
 
@@ -289,13 +732,44 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Rm {
                 s3_uri: "s3://bucket/file".to_string(),
                 recursive: false,
                 dryrun: true,
                 include: None,
                 exclude: None,
+                ignore_locked: true,
+                yes: false,
+                quiet: false,
+                version_id: None,
             },
         };
 
@@ -310,7 +784,34 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Mb {
                 s3_uri: "s3://new-bucket".to_string(),
             },
@@ -328,13 +829,41 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Rb {
                 s3_uri: Some("s3://bucket".to_string()),
                 force: false,
                 all: false,
                 confirm: false,
                 pattern: None,
+                ignore_case: false,
             },
         };
 
@@ -350,18 +879,101 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Presign {
                 s3_uri: "s3://bucket/file".to_string(),
                 expires_in: 3600,
+                method: PresignMethod::Get,
+                content_type: None,
+                recursive: false,
+                keys_from_file: None,
             },
         };
 
         let result = execute_command(&args, &config).await;
-        // Presign is a placeholder, should succeed
+        // Signing is local, so this succeeds without credentials or a network
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_presign_rejects_expected_bucket_owner() {
+        let config = create_mock_config();
+        let args = Args {
+            debug: "info".to_string(),
+            endpoint: None,
+            region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
+            timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: Some("111122223333".to_string()),
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
+            command: Commands::Presign {
+                s3_uri: "s3://bucket/file".to_string(),
+                expires_in: 3600,
+                method: PresignMethod::Get,
+                content_type: None,
+                recursive: false,
+                keys_from_file: None,
+            },
+        };
+
+        let result = execute_command(&args, &config).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--expected-bucket-owner can't be used with presign"));
+    }
+
     #[tokio::test]
     async fn test_execute_head_object_command() {
         let config = create_mock_config();
@@ -369,10 +981,40 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::HeadObject {
                 bucket: "test-bucket".to_string(),
                 key: "test-key".to_string(),
+                preview_bytes: None,
+                preview_tail: false,
+                checksum: false,
             },
         };
 
@@ -388,7 +1030,34 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Du {
                 s3_uri: "s3://bucket/path".to_string(),
                 human_readable: true,
@@ -408,7 +1077,34 @@ mod tests {
             debug: "info".to_string(),
             endpoint: None,
             region: "us-east-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Config { command: None },
         };
 
@@ -431,17 +1127,31 @@ mod tests {
                 human_readable: false,
                 summarize: false,
                 pattern: None,
+                ignore_case: false,
                 created_after: None,
                 created_before: None,
                 modified_after: None,
                 modified_before: None,
                 min_size: None,
                 max_size: None,
+                extension: Vec::new(),
+                storage_class: Vec::new(),
                 max_results: None,
                 head: None,
                 tail: None,
                 sort_by: None,
                 reverse: false,
+                folders_only: false,
+                files_only: false,
+                show_owner: false,
+                show_acl: false,
+                show_etag: false,
+                enrich: false,
+                count: false,
+                versions: false,
+                parallel: None,
+                follow: false,
+                interval: 5,
             },
             Commands::Cp {
                 source: "src".to_string(),
@@ -452,6 +1162,18 @@ mod tests {
                 force: false,
                 include: None,
                 exclude: None,
+                exclude_dir: Vec::new(),
+                content_type: None,
+                metadata: Vec::new(),
+                tags: Vec::new(),
+                decompress: false,
+                checksum_manifest: None,
+                write_manifest: None,
+                multipart_threshold: None,
+                part_size: None,
+                storage_class: None,
+                checksum_algorithm: None,
+                quiet: false,
             },
             Commands::Sync {
                 source: "src".to_string(),
@@ -461,6 +1183,26 @@ mod tests {
                 max_concurrent: 1,
                 include: None,
                 exclude: None,
+                size_only: false,
+                exact_timestamps: false,
+                max_delete: None,
+                max_delete_percent: None,
+                verify: false,
+                checksum_manifest: None,
+                write_manifest: None,
+                content_type: None,
+                metadata: Vec::new(),
+                compare_metadata: false,
+                checksum: false,
+                quiet: false,
+            },
+            Commands::Mv {
+                source: "s3://bucket/a".to_string(),
+                dest: "s3://bucket/b".to_string(),
+                recursive: false,
+                dryrun: false,
+                include: None,
+                exclude: None,
             },
             Commands::Rm {
                 s3_uri: "s3://bucket/key".to_string(),
@@ -468,6 +1210,10 @@ mod tests {
                 dryrun: false,
                 include: None,
                 exclude: None,
+                ignore_locked: true,
+                yes: false,
+                quiet: false,
+                version_id: None,
             },
             Commands::Mb {
                 s3_uri: "s3://bucket".to_string(),
@@ -478,24 +1224,92 @@ mod tests {
                 all: false,
                 confirm: false,
                 pattern: None,
+                ignore_case: false,
+            },
+            Commands::Bucket {
+                command: BucketCommands::CleanMultipart {
+                    s3_uri: "s3://bucket".to_string(),
+                    older_than: None,
+                    dryrun: true,
+                },
             },
             Commands::Presign {
                 s3_uri: "s3://bucket/key".to_string(),
                 expires_in: 3600,
+                method: PresignMethod::Get,
+                content_type: None,
+                recursive: false,
+                keys_from_file: None,
             },
             Commands::HeadObject {
                 bucket: "bucket".to_string(),
                 key: "key".to_string(),
+                preview_bytes: None,
+                preview_tail: false,
+                checksum: false,
+            },
+            Commands::Exists {
+                s3_uri: "s3://bucket/key".to_string(),
+                quiet: false,
+            },
+            Commands::Wait {
+                condition: WaitCondition::Exists,
+                s3_uri: "s3://bucket/key".to_string(),
+                wait_timeout: 300,
+                interval: 5,
+            },
+            Commands::Tag {
+                command: TagCommands::Get {
+                    s3_uri: "s3://bucket/key".to_string(),
+                },
+            },
+            Commands::Restore {
+                s3_uri: "s3://bucket/key".to_string(),
+                days: 7,
+                tier: RestoreTier::Standard,
+                recursive: false,
+            },
+            Commands::Cat {
+                s3_uri: "s3://bucket/key".to_string(),
+                range: None,
+                follow: false,
+                interval: 2,
+                decompress: false,
             },
             Commands::Du {
                 s3_uri: "s3://bucket".to_string(),
                 human_readable: false,
                 summarize: false,
             },
+            Commands::FindDupes {
+                s3_uri: "s3://bucket".to_string(),
+                min_size: None,
+                human_readable: false,
+            },
+            Commands::Verify {
+                s3_uri: "s3://bucket/backup".to_string(),
+                local_path: "./backup".to_string(),
+            },
             Commands::Config { command: None },
+            Commands::Version { json: false },
         ];
 
         // If this compiles, all command variants are properly structured
-        assert_eq!(commands.len(), 10);
+        assert_eq!(commands.len(), 20);
+    }
+
+    #[test]
+    fn test_completion_manifest_requires_local_to_s3() {
+        assert!(completion_manifest("./dist", "s3://bucket/out", None)
+            .unwrap()
+            .is_none());
+        assert!(
+            completion_manifest("./dist", "s3://bucket/out", Some("_SUCCESS"))
+                .unwrap()
+                .is_some()
+        );
+        assert!(completion_manifest("s3://bucket/out", "./dist", Some("_SUCCESS")).is_err());
+        assert!(completion_manifest("s3://a/x", "s3://b/y", Some("_SUCCESS")).is_err());
+        assert!(completion_manifest("-", "s3://bucket/out", Some("_SUCCESS")).is_err());
     }
 }
@@ -0,0 +1,387 @@
+use anyhow::Result;
+use log::info;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::commands::cp::{self, TransferOptions};
+use crate::commands::paginate::list_objects_v2_resumable;
+use crate::commands::partial::PartialFailures;
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::config::Config;
+use crate::utils::wildcard_match;
+
+/// One side of a move: a local file or an S3 object
+#[derive(Debug, Clone, PartialEq)]
+enum Location {
+    Local(PathBuf),
+    S3(S3Uri),
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Local(path) => write!(f, "{}", path.display()),
+            Location::S3(uri) => write!(f, "{uri}"),
+        }
+    }
+}
+
+/// A single source → destination pair
+#[derive(Debug, PartialEq)]
+struct Move {
+    source: Location,
+    dest: Location,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    config: &Config,
+    source: &str,
+    dest: &str,
+    recursive: bool,
+    dryrun: bool,
+    include: Option<&str>,
+    exclude: Option<&str>,
+    options: &TransferOptions,
+) -> Result<()> {
+    let start_time = Instant::now();
+
+    if !is_s3_uri(source) && !is_s3_uri(dest) {
+        return Err(anyhow::anyhow!(
+            "Local to local move not supported. Use standard mv command."
+        ));
+    }
+
+    let moves = plan_moves(config, source, dest, recursive, include, exclude).await?;
+    if moves.is_empty() {
+        info!("Nothing to move from {source}");
+        return Ok(());
+    }
+
+    if dryrun {
+        for mv in &moves {
+            println!("(dryrun) move: {} to {}", mv.source, mv.dest);
+        }
+        return Ok(());
+    }
+
+    let mut failures = PartialFailures::new("mv");
+    failures.add_attempted(moves.len());
+
+    for mv in &moves {
+        // The source is only removed once its copy has landed
+        let result = match transfer(config, mv, options).await {
+            Ok(()) => remove_source(config, &mv.source).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => println!("move: {} to {}", mv.source, mv.dest),
+            Err(e) => {
+                eprintln!("move failed: {} to {} ({e})", mv.source, mv.dest);
+                failures.record(&mv.source.to_string(), &e.to_string());
+            }
+        }
+    }
+
+    {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS.operations_total.add(
+            (moves.len() - failures.failed()) as u64,
+            &[KeyValue::new("operation", "mv")],
+        );
+        OTEL_INSTRUMENTS.operation_duration.record(
+            start_time.elapsed().as_millis() as f64 / 1000.0,
+            &[KeyValue::new("operation", "mv")],
+        );
+    }
+
+    failures.into_result()
+}
+
+/// Work out every source → destination pair, applying --include/--exclude
+/// to paths relative to the source
+async fn plan_moves(
+    config: &Config,
+    source: &str,
+    dest: &str,
+    recursive: bool,
+    include: Option<&str>,
+    exclude: Option<&str>,
+) -> Result<Vec<Move>> {
+    let mut moves = Vec::new();
+
+    if !recursive {
+        let source = parse_location(source)?;
+        let name = match &source {
+            Location::Local(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            Location::S3(uri) => uri
+                .key_or_empty()
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        };
+        if name.is_empty() {
+            return Err(anyhow::anyhow!(
+                "mv needs a file or object as its source; use --recursive to move a directory or prefix"
+            ));
+        }
+        if is_selected(&name, include, exclude) {
+            let dest = single_destination(dest, &name)?;
+            moves.push(Move { source, dest });
+        }
+    } else if is_s3_uri(source) {
+        let source_uri = S3Uri::parse(source)?;
+        let prefix = source_uri.key_or_empty().to_string();
+
+        let mut request = config.client.list_objects_v2().bucket(&source_uri.bucket);
+        if !prefix.is_empty() {
+            request = request.prefix(&prefix);
+        }
+
+        let mut keys = Vec::new();
+        list_objects_v2_resumable(config, request, |response| {
+            keys.extend(
+                response
+                    .contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|object| object.key),
+            );
+            Ok(())
+        })
+        .await?;
+
+        for key in keys {
+            let relative = key[prefix.len()..].trim_start_matches('/').to_string();
+            // Skip "directory" markers; there is nothing to copy into a file
+            if relative.is_empty() || relative.ends_with('/') {
+                continue;
+            }
+            if is_selected(&relative, include, exclude) {
+                moves.push(Move {
+                    source: Location::S3(S3Uri {
+                        bucket: source_uri.bucket.clone(),
+                        key: Some(key),
+                    }),
+                    dest: join_destination(dest, &relative)?,
+                });
+            }
+        }
+    } else {
+        for entry in walkdir::WalkDir::new(source).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry
+                .path()
+                .strip_prefix(source)?
+                .to_string_lossy()
+                .replace(std::path::MAIN_SEPARATOR, "/");
+            if is_selected(&relative, include, exclude) {
+                moves.push(Move {
+                    source: Location::Local(entry.into_path()),
+                    dest: join_destination(dest, &relative)?,
+                });
+            }
+        }
+    }
+
+    if let Some(mv) = moves.iter().find(|mv| mv.source == mv.dest) {
+        return Err(anyhow::anyhow!(
+            "Refusing to move {} onto itself",
+            mv.source
+        ));
+    }
+
+    Ok(moves)
+}
+
+fn parse_location(location: &str) -> Result<Location> {
+    if is_s3_uri(location) {
+        Ok(Location::S3(S3Uri::parse(location)?))
+    } else {
+        Ok(Location::Local(PathBuf::from(location)))
+    }
+}
+
+/// Destination for a single move: a trailing `/`, a bare bucket or an
+/// existing local directory keeps the source's file name
+fn single_destination(dest: &str, name: &str) -> Result<Location> {
+    let into_directory = dest.ends_with('/')
+        || (is_s3_uri(dest) && S3Uri::parse(dest)?.key.is_none())
+        || (!is_s3_uri(dest) && Path::new(dest).is_dir());
+    if into_directory {
+        join_destination(dest, name)
+    } else {
+        parse_location(dest)
+    }
+}
+
+/// `relative` placed under the destination prefix or directory
+fn join_destination(dest: &str, relative: &str) -> Result<Location> {
+    if is_s3_uri(dest) {
+        let uri = S3Uri::parse(dest)?;
+        let key = match uri.key_or_empty().trim_end_matches('/') {
+            "" => relative.to_string(),
+            prefix => format!("{prefix}/{relative}"),
+        };
+        Ok(Location::S3(S3Uri {
+            bucket: uri.bucket,
+            key: Some(key),
+        }))
+    } else {
+        Ok(Location::Local(Path::new(dest).join(relative)))
+    }
+}
+
+/// --include keeps only matching paths; --exclude then drops matches
+fn is_selected(relative: &str, include: Option<&str>, exclude: Option<&str>) -> bool {
+    include.is_none_or(|pattern| wildcard_match(pattern, relative))
+        && !exclude.is_some_and(|pattern| wildcard_match(pattern, relative))
+}
+
+async fn transfer(config: &Config, mv: &Move, options: &TransferOptions) -> Result<()> {
+    match (&mv.source, &mv.dest) {
+        (Location::Local(path), Location::S3(uri)) => {
+            cp::upload_file_to_s3(config, &path.to_string_lossy(), uri, options).await
+        }
+        (Location::S3(uri), Location::Local(path)) => {
+            cp::download_file_from_s3(config, uri, &path.to_string_lossy(), options).await
+        }
+        (Location::S3(source), Location::S3(dest)) => {
+            cp::copy_object(config, source, dest, options).await
+        }
+        (Location::Local(_), Location::Local(_)) => Err(anyhow::anyhow!(
+            "Local to local move not supported. Use standard mv command."
+        )),
+    }
+}
+
+async fn remove_source(config: &Config, source: &Location) -> Result<()> {
+    match source {
+        Location::Local(path) => Ok(tokio::fs::remove_file(path).await?),
+        Location::S3(uri) => {
+            config
+                .client
+                .delete_object()
+                .bucket(&uri.bucket)
+                .key(uri.key_or_empty())
+                .send()
+                .await?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_client::canned_config;
+
+    fn s3(uri: &str) -> Location {
+        Location::S3(S3Uri::parse(uri).unwrap())
+    }
+
+    #[test]
+    fn test_single_destination() {
+        assert_eq!(
+            single_destination("s3://bucket/dir/", "a.txt").unwrap(),
+            s3("s3://bucket/dir/a.txt")
+        );
+        assert_eq!(
+            single_destination("s3://bucket", "a.txt").unwrap(),
+            s3("s3://bucket/a.txt")
+        );
+        assert_eq!(
+            single_destination("s3://bucket/renamed.txt", "a.txt").unwrap(),
+            s3("s3://bucket/renamed.txt")
+        );
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().to_str().unwrap();
+        assert_eq!(
+            single_destination(dir, "a.txt").unwrap(),
+            Location::Local(temp_dir.path().join("a.txt"))
+        );
+    }
+
+    #[test]
+    fn test_is_selected() {
+        assert!(is_selected("logs/a.log", None, None));
+        assert!(is_selected("logs/a.log", Some("*.log"), None));
+        assert!(!is_selected("logs/a.txt", Some("*.log"), None));
+        assert!(!is_selected("logs/a.tmp", None, Some("*.tmp")));
+        assert!(!is_selected("a.log", Some("*.log"), Some("a.*")));
+    }
+
+    #[tokio::test]
+    async fn test_plan_local_directory_with_filters() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        std::fs::write(temp_dir.path().join("a.log"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("b.tmp"), "b").unwrap();
+        std::fs::write(temp_dir.path().join("sub/c.log"), "c").unwrap();
+
+        let config = canned_config(|_, _| (404, String::new()));
+        let moves = plan_moves(
+            &config,
+            temp_dir.path().to_str().unwrap(),
+            "s3://bucket/archive/",
+            true,
+            None,
+            Some("*.tmp"),
+        )
+        .await
+        .unwrap();
+
+        let dests: Vec<String> = moves.iter().map(|mv| mv.dest.to_string()).collect();
+        assert_eq!(
+            dests,
+            vec!["s3://bucket/archive/a.log", "s3://bucket/archive/sub/c.log"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_move_onto_itself_is_rejected() {
+        let config = canned_config(|_, _| (404, String::new()));
+        let result = plan_moves(
+            &config,
+            "s3://bucket/a.txt",
+            "s3://bucket/a.txt",
+            false,
+            None,
+            None,
+        )
+        .await;
+        assert!(result.unwrap_err().to_string().contains("onto itself"));
+    }
+
+    #[tokio::test]
+    async fn test_dryrun_leaves_source_in_place() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("a.txt");
+        std::fs::write(&file, "data").unwrap();
+
+        let config = canned_config(|_, _| (404, String::new()));
+        execute(
+            &config,
+            file.to_str().unwrap(),
+            "s3://bucket/",
+            false,
+            true,
+            None,
+            None,
+            &TransferOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert!(file.exists());
+    }
+}
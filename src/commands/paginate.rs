@@ -0,0 +1,231 @@
+use anyhow::Result;
+use aws_sdk_s3::operation::list_objects_v2::builders::ListObjectsV2FluentBuilder;
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Output;
+use std::future::Future;
+
+use crate::config::Config;
+use crate::events::EventSink;
+
+/// Error codes returned when temporary credentials lapse between requests
+const CREDENTIAL_EXPIRY_CODES: &[&str] = &[
+    "ExpiredToken",
+    "ExpiredTokenException",
+    "TokenRefreshRequired",
+    "RequestExpired",
+    "InvalidToken",
+];
+
+/// How many times a single page may be retried after a credential refresh
+const MAX_CREDENTIAL_REFRESHES: usize = 2;
+
+/// Check whether an error was caused by expired temporary credentials
+pub fn is_credential_expiry_error(err: &anyhow::Error) -> bool {
+    let details = format!("{err:?}");
+    CREDENTIAL_EXPIRY_CODES
+        .iter()
+        .any(|code| details.contains(code))
+}
+
+/// Drive a paginated listing, keeping the continuation token so that a
+/// credential expiry between pages resumes from the failed page instead of
+/// restarting the whole listing.
+///
+/// `fetch` receives the continuation token for the page to load and returns the
+/// page together with the next token (`None` when the listing is complete).
+/// Refreshes are only attempted when `refreshable` is set, since static keys
+/// can't be renewed by retrying. Each retry is reported to `events`.
+pub async fn paginate_resumable<T, Fetch, Fut, OnPage>(
+    refreshable: bool,
+    events: &dyn EventSink,
+    mut fetch: Fetch,
+    mut on_page: OnPage,
+) -> Result<()>
+where
+    Fetch: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(T, Option<String>)>>,
+    OnPage: FnMut(T) -> Result<()>,
+{
+    let mut continuation_token: Option<String> = None;
+    let mut refreshes = 0;
+
+    loop {
+        match fetch(continuation_token.clone()).await {
+            Ok((page, next_token)) => {
+                refreshes = 0;
+                on_page(page)?;
+
+                match next_token {
+                    Some(token) => continuation_token = Some(token),
+                    None => return Ok(()),
+                }
+            }
+            Err(e)
+                if refreshable
+                    && refreshes < MAX_CREDENTIAL_REFRESHES
+                    && is_credential_expiry_error(&e) =>
+            {
+                refreshes += 1;
+                // The SDK identity cache re-resolves expired credentials on the
+                // next request, so retrying the same page picks up fresh ones.
+                events.on_retry(
+                    "list",
+                    refreshes,
+                    "credentials expired mid-listing, refreshing and resuming",
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Run a ListObjectsV2 request across all pages, resuming after credential expiry
+pub async fn list_objects_v2_resumable<OnPage>(
+    config: &Config,
+    request: ListObjectsV2FluentBuilder,
+    on_page: OnPage,
+) -> Result<()>
+where
+    OnPage: FnMut(ListObjectsV2Output) -> Result<()>,
+{
+    paginate_resumable(
+        config.refreshable_credentials,
+        config.events.as_ref(),
+        |token| {
+            let mut req = request.clone();
+            if let Some(token) = token {
+                req = req.continuation_token(token);
+            }
+            async move {
+                let response = req.send().await?;
+                let next_token = if response.is_truncated().unwrap_or(false) {
+                    response.next_continuation_token().map(|s| s.to_string())
+                } else {
+                    None
+                };
+                Ok((response, next_token))
+            }
+        },
+        on_page,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::NoopEventSink;
+    use std::cell::RefCell;
+    use std::sync::Mutex;
+
+    /// Records retry attempts
+    #[derive(Default)]
+    struct RetryRecorder {
+        attempts: Mutex<Vec<usize>>,
+    }
+
+    impl EventSink for RetryRecorder {
+        fn on_retry(&self, _operation: &str, attempt: usize, _reason: &str) {
+            self.attempts.lock().unwrap().push(attempt);
+        }
+    }
+
+    #[test]
+    fn test_is_credential_expiry_error() {
+        assert!(is_credential_expiry_error(&anyhow::anyhow!(
+            "service error: code: Some(\"ExpiredToken\")"
+        )));
+        assert!(is_credential_expiry_error(&anyhow::anyhow!(
+            "RequestExpired: request has expired"
+        )));
+        assert!(!is_credential_expiry_error(&anyhow::anyhow!(
+            "AccessDenied"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_resumes_after_mid_pagination_auth_failure() {
+        let requested = RefCell::new(Vec::new());
+        let failed_once = RefCell::new(false);
+        let mut pages = Vec::new();
+        let events = RetryRecorder::default();
+
+        let result = paginate_resumable(
+            true,
+            &events,
+            |token: Option<String>| {
+                requested.borrow_mut().push(token.clone());
+                let fail = token.as_deref() == Some("page-2") && !*failed_once.borrow();
+                if fail {
+                    *failed_once.borrow_mut() = true;
+                }
+                async move {
+                    if fail {
+                        anyhow::bail!("service error: ExpiredToken");
+                    }
+                    match token.as_deref() {
+                        None => Ok(("a", Some("page-2".to_string()))),
+                        Some("page-2") => Ok(("b", Some("page-3".to_string()))),
+                        _ => Ok(("c", None)),
+                    }
+                }
+            },
+            |page| {
+                pages.push(page);
+                Ok(())
+            },
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(pages, vec!["a", "b", "c"]);
+        assert_eq!(*events.attempts.lock().unwrap(), vec![1]);
+        // The failed page is retried with the same token rather than restarting
+        assert_eq!(
+            *requested.borrow(),
+            vec![
+                None,
+                Some("page-2".to_string()),
+                Some("page-2".to_string()),
+                Some("page-3".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_static_credentials_do_not_retry() {
+        let mut calls = 0;
+
+        let result = paginate_resumable(
+            false,
+            &NoopEventSink,
+            |_token: Option<String>| {
+                calls += 1;
+                async { Err::<((), Option<String>), _>(anyhow::anyhow!("ExpiredToken")) }
+            },
+            |_page| Ok(()),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_repeated_expiry() {
+        let mut calls = 0;
+
+        let result = paginate_resumable(
+            true,
+            &NoopEventSink,
+            |_token: Option<String>| {
+                calls += 1;
+                async { Err::<((), Option<String>), _>(anyhow::anyhow!("ExpiredToken")) }
+            },
+            |_page| Ok(()),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, MAX_CREDENTIAL_REFRESHES + 1);
+    }
+}
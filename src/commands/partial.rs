@@ -0,0 +1,154 @@
+use anyhow::Result;
+
+/// How many individual failure messages make it into the aggregated error
+const REPORTED_FAILURES: usize = 3;
+
+/// Per-item failures from a batch operation (DeleteObjects, multipart) that
+/// keeps going after an item fails and reports the failed subset at the end
+#[derive(Debug)]
+pub struct PartialFailures {
+    operation: &'static str,
+    attempted: usize,
+    failures: Vec<String>,
+}
+
+impl PartialFailures {
+    pub fn new(operation: &'static str) -> Self {
+        Self {
+            operation,
+            attempted: 0,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Count items sent in a batch, whether or not they succeed
+    pub fn add_attempted(&mut self, count: usize) {
+        self.attempted += count;
+    }
+
+    /// Record one failed item; it is classified like any other error
+    pub fn record(&mut self, item: &str, message: &str) {
+        use crate::otel::OTEL_INSTRUMENTS;
+
+        let failure = format!("{item}: {message}");
+        OTEL_INSTRUMENTS.record_error_with_type(&failure);
+        self.failures.push(failure);
+    }
+
+    /// Record the per-key errors a DeleteObjects response reports alongside
+    /// the keys it did delete
+    pub fn record_delete_errors(&mut self, bucket: &str, errors: &[aws_sdk_s3::types::Error]) {
+        for error in errors {
+            let item = format!("s3://{bucket}/{}", error.key().unwrap_or(""));
+            let message = match (error.code(), error.message()) {
+                (Some(code), Some(message)) => format!("{code}: {message}"),
+                (Some(code), None) => code.to_string(),
+                (None, Some(message)) => message.to_string(),
+                (None, None) => "unknown error".to_string(),
+            };
+            eprintln!("delete failed: {item} ({message})");
+            self.record(&item, &message);
+        }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// `Ok` when every item succeeded, otherwise one error summarising how
+    /// many of the attempted items failed with the first few messages
+    pub fn into_result(self) -> Result<()> {
+        if self.failures.is_empty() {
+            return Ok(());
+        }
+
+        {
+            use crate::otel::OTEL_INSTRUMENTS;
+            use opentelemetry::KeyValue;
+
+            OTEL_INSTRUMENTS.partial_failures_total.add(
+                self.failures.len() as u64,
+                &[KeyValue::new("operation", self.operation)],
+            );
+        }
+
+        let mut summary = self
+            .failures
+            .iter()
+            .take(REPORTED_FAILURES)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("; ");
+        if self.failures.len() > REPORTED_FAILURES {
+            summary.push_str(&format!(
+                " (and {} more)",
+                self.failures.len() - REPORTED_FAILURES
+            ));
+        }
+
+        Err(anyhow::anyhow!(
+            "{}: {} of {} item(s) failed: {summary}",
+            self.operation,
+            self.failures.len(),
+            self.attempted.max(self.failures.len())
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_failures_is_ok() {
+        let mut failures = PartialFailures::new("delete_objects");
+        failures.add_attempted(10);
+        assert_eq!(failures.failed(), 0);
+        assert!(failures.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_aggregated_error_lists_first_failures() {
+        let mut failures = PartialFailures::new("delete_objects");
+        failures.add_attempted(1000);
+        for i in 0..5 {
+            failures.record(&format!("s3://b/k{i}"), "AccessDenied: Access Denied");
+        }
+        assert_eq!(failures.failed(), 5);
+
+        let message = failures.into_result().unwrap_err().to_string();
+        assert_eq!(
+            message,
+            "delete_objects: 5 of 1000 item(s) failed: \
+             s3://b/k0: AccessDenied: Access Denied; \
+             s3://b/k1: AccessDenied: Access Denied; \
+             s3://b/k2: AccessDenied: Access Denied (and 2 more)"
+        );
+    }
+
+    #[test]
+    fn test_record_delete_errors() {
+        let errors = vec![
+            aws_sdk_s3::types::Error::builder()
+                .key("logs/a.log")
+                .code("AccessDenied")
+                .message("Access Denied")
+                .build(),
+            aws_sdk_s3::types::Error::builder()
+                .key("logs/b.log")
+                .build(),
+        ];
+
+        let mut failures = PartialFailures::new("delete_objects");
+        failures.add_attempted(3);
+        failures.record_delete_errors("bucket", &errors);
+
+        let message = failures.into_result().unwrap_err().to_string();
+        assert_eq!(
+            message,
+            "delete_objects: 2 of 3 item(s) failed: \
+             s3://bucket/logs/a.log: AccessDenied: Access Denied; \
+             s3://bucket/logs/b.log: unknown error"
+        );
+    }
+}
@@ -2,14 +2,24 @@ use anyhow::Result;
 use log::info;
 use std::time::Instant;
 
+use crate::commands::paginate::list_objects_v2_resumable;
 use crate::commands::s3_uri::{is_s3_uri, S3Uri};
 use crate::config::Config;
 
+/// Maximum URLs signed concurrently in batch mode
+const PRESIGN_BATCH_CONCURRENCY: usize = 32;
+
+/// Longest expiry S3 accepts for a SigV4 presigned URL (7 days)
+const MAX_EXPIRES_IN_SECS: u64 = 604_800;
+
 pub async fn execute(
     config: &Config,
     s3_uri: &str,
     expires_in: u64,
     method: Option<&str>,
+    content_type: Option<&str>,
+    recursive: bool,
+    keys_from_file: Option<&str>,
 ) -> Result<()> {
     let start_time = Instant::now();
 
@@ -20,6 +30,26 @@ pub async fn execute(
     }
 
     let uri = S3Uri::parse(s3_uri)?;
+    let method = method.unwrap_or("GET");
+    validate_expires_in(expires_in)?;
+
+    if content_type.is_some() && !method.eq_ignore_ascii_case("PUT") {
+        return Err(anyhow::anyhow!(
+            "--content-type only applies to --method put"
+        ));
+    }
+
+    if recursive || keys_from_file.is_some() {
+        return execute_batch(
+            config,
+            &uri,
+            expires_in,
+            method,
+            content_type,
+            keys_from_file,
+        )
+        .await;
+    }
 
     if uri.key.is_none() || uri.key_or_empty().is_empty() {
         return Err(anyhow::anyhow!(
@@ -29,17 +59,9 @@ pub async fn execute(
 
     info!("Generating presigned URL for: {s3_uri}");
 
-    let method = method.unwrap_or("GET");
-
-    let result = match method.to_uppercase().as_str() {
-        "GET" => generate_get_presigned_url(config, &uri, expires_in).await,
-        "PUT" => generate_put_presigned_url(config, &uri, expires_in).await,
-        "DELETE" => generate_delete_presigned_url(config, &uri, expires_in).await,
-        _ => Err(anyhow::anyhow!(
-            "Unsupported HTTP method: {}. Supported methods: GET, PUT, DELETE",
-            method
-        )),
-    };
+    let result = presign_uri(config, &uri, expires_in, method, content_type)
+        .await
+        .map(|url| println!("{url}"));
 
     // Record presign operation using proper OTEL SDK
     match result {
@@ -80,11 +102,155 @@ pub async fn execute(
     }
 }
 
+/// Reject expiries S3 would refuse before any URL is signed
+fn validate_expires_in(expires_in: u64) -> Result<()> {
+    if expires_in == 0 || expires_in > MAX_EXPIRES_IN_SECS {
+        return Err(anyhow::anyhow!(
+            "--expires-in must be between 1 and {MAX_EXPIRES_IN_SECS} seconds (7 days), got {expires_in}"
+        ));
+    }
+    Ok(())
+}
+
+/// Sign a single object URI with the requested HTTP method
+async fn presign_uri(
+    config: &Config,
+    uri: &S3Uri,
+    expires_in: u64,
+    method: &str,
+    content_type: Option<&str>,
+) -> Result<String> {
+    match method.to_uppercase().as_str() {
+        "GET" => generate_get_presigned_url(config, uri, expires_in).await,
+        "PUT" => generate_put_presigned_url(config, uri, expires_in, content_type).await,
+        "DELETE" => generate_delete_presigned_url(config, uri, expires_in).await,
+        _ => Err(anyhow::anyhow!(
+            "Unsupported HTTP method: {}. Supported methods: GET, PUT, DELETE",
+            method
+        )),
+    }
+}
+
+/// Presign every object under a prefix (`--recursive`) or every key listed in
+/// a file (`--keys-from-file`), printing one `key\turl` line per object
+async fn execute_batch(
+    config: &Config,
+    uri: &S3Uri,
+    expires_in: u64,
+    method: &str,
+    content_type: Option<&str>,
+    keys_from_file: Option<&str>,
+) -> Result<()> {
+    use futures::stream::{self, StreamExt, TryStreamExt};
+
+    let start_time = Instant::now();
+
+    let targets = match keys_from_file {
+        Some(path) => {
+            let contents = tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to read keys file {path}: {e}"))?;
+            parse_keys_file(&contents, &uri.bucket)?
+        }
+        None => list_prefix_objects(config, uri).await?,
+    };
+
+    info!(
+        "Generating {} presigned URLs in s3://{}",
+        targets.len(),
+        uri.bucket
+    );
+
+    // Signing is local, so a generous bound keeps ordering cheap without
+    // spawning one future per object for huge prefixes
+    let mut signed = stream::iter(targets.iter())
+        .map(|target| async move {
+            presign_uri(config, target, expires_in, method, content_type)
+                .await
+                .map(|url| (target.key_or_empty(), url))
+        })
+        .buffered(PRESIGN_BATCH_CONCURRENCY);
+
+    let mut count = 0u64;
+    while let Some((key, url)) = signed.try_next().await? {
+        println!("{key}\t{url}");
+        count += 1;
+    }
+
+    {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        let operation_type = format!("presign_batch_{}", method.to_lowercase());
+
+        OTEL_INSTRUMENTS
+            .operations_total
+            .add(count, &[KeyValue::new("operation", operation_type.clone())]);
+
+        let duration_seconds = start_time.elapsed().as_millis() as f64 / 1000.0;
+        OTEL_INSTRUMENTS.operation_duration.record(
+            duration_seconds,
+            &[KeyValue::new("operation", operation_type)],
+        );
+    }
+
+    Ok(())
+}
+
+/// List every object under the URI's prefix for `--recursive`
+async fn list_prefix_objects(config: &Config, uri: &S3Uri) -> Result<Vec<S3Uri>> {
+    let mut request = config.client.list_objects_v2().bucket(&uri.bucket);
+    if !uri.key_or_empty().is_empty() {
+        request = request.prefix(uri.key_or_empty());
+    }
+
+    let mut targets = Vec::new();
+    list_objects_v2_resumable(config, request, |response| {
+        for object in response.contents.unwrap_or_default() {
+            // Skip zero-byte "directory" markers
+            if let Some(key) = object.key.filter(|key| !key.ends_with('/')) {
+                targets.push(S3Uri {
+                    bucket: uri.bucket.clone(),
+                    key: Some(key),
+                });
+            }
+        }
+        Ok(())
+    })
+    .await?;
+
+    Ok(targets)
+}
+
+/// Parse a `--keys-from-file` list: one key per line, either a bare key in the
+/// command's bucket or a full `s3://` URI. Blank lines and `#` comments are skipped.
+fn parse_keys_file(contents: &str, default_bucket: &str) -> Result<Vec<S3Uri>> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            if is_s3_uri(line) {
+                let uri = S3Uri::parse(line)?;
+                if uri.key_or_empty().is_empty() {
+                    return Err(anyhow::anyhow!("Keys file entry {line} has no object key"));
+                }
+                Ok(uri)
+            } else {
+                Ok(S3Uri {
+                    bucket: default_bucket.to_string(),
+                    key: Some(line.to_string()),
+                })
+            }
+        })
+        .collect()
+}
+
 async fn generate_get_presigned_url(
     config: &Config,
     s3_uri: &S3Uri,
     expires_in: u64,
-) -> Result<()> {
+) -> Result<String> {
     let start_time = Instant::now();
     let expiration = std::time::Duration::from_secs(expires_in);
 
@@ -119,8 +285,7 @@ async fn generate_get_presigned_url(
                 );
             }
 
-            println!("{}", presigned_request.uri());
-            Ok(())
+            Ok(presigned_request.uri().to_string())
         }
         Err(e) => {
             // Record error using proper OTEL SDK
@@ -139,11 +304,14 @@ async fn generate_get_presigned_url(
     }
 }
 
+/// A Content-Type becomes a signed header, so the uploader has to send the
+/// same value for the URL to be accepted
 async fn generate_put_presigned_url(
     config: &Config,
     s3_uri: &S3Uri,
     expires_in: u64,
-) -> Result<()> {
+    content_type: Option<&str>,
+) -> Result<String> {
     let start_time = Instant::now();
     let expiration = std::time::Duration::from_secs(expires_in);
 
@@ -152,6 +320,7 @@ async fn generate_put_presigned_url(
         .put_object()
         .bucket(&s3_uri.bucket)
         .key(s3_uri.key_or_empty())
+        .set_content_type(content_type.map(str::to_string))
         .presigned(aws_sdk_s3::presigning::PresigningConfig::expires_in(
             expiration,
         )?)
@@ -178,8 +347,7 @@ async fn generate_put_presigned_url(
                 );
             }
 
-            println!("{}", presigned_request.uri());
-            Ok(())
+            Ok(presigned_request.uri().to_string())
         }
         Err(e) => {
             // Record error using proper OTEL SDK
@@ -202,7 +370,7 @@ async fn generate_delete_presigned_url(
     config: &Config,
     s3_uri: &S3Uri,
     expires_in: u64,
-) -> Result<()> {
+) -> Result<String> {
     let start_time = Instant::now();
     let expiration = std::time::Duration::from_secs(expires_in);
 
@@ -237,8 +405,7 @@ async fn generate_delete_presigned_url(
                 );
             }
 
-            println!("{}", presigned_request.uri());
-            Ok(())
+            Ok(presigned_request.uri().to_string())
         }
         Err(e) => {
             // Record error using proper OTEL SDK
@@ -268,6 +435,13 @@ mod tests {
             aws_sdk_s3::config::Builder::new()
                 .region(aws_config::Region::new("us-east-1"))
                 .behavior_version(aws_config::BehaviorVersion::latest())
+                .credentials_provider(aws_sdk_s3::config::Credentials::new(
+                    "AKIDEXAMPLE",
+                    "secret",
+                    None,
+                    None,
+                    "test",
+                ))
                 .build(),
         ));
 
@@ -278,7 +452,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -286,7 +469,16 @@ mod tests {
     async fn test_execute_non_s3_uri() {
         let config = create_mock_config();
 
-        let result = execute(&config, "/local/path/file.txt", 3600, None).await;
+        let result = execute(
+            &config,
+            "/local/path/file.txt",
+            3600,
+            None,
+            None,
+            false,
+            None,
+        )
+        .await;
 
         assert!(result.is_err());
         assert!(result
@@ -301,7 +493,7 @@ mod tests {
 
         let result = execute(
             &config, "s3://", // invalid S3 URI
-            3600, None,
+            3600, None, None, false, None,
         )
         .await;
 
@@ -312,7 +504,16 @@ mod tests {
     async fn test_execute_get_method() {
         let config = create_mock_config();
 
-        let result = execute(&config, "s3://bucket/file.txt", 3600, Some("GET")).await;
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            3600,
+            Some("GET"),
+            None,
+            false,
+            None,
+        )
+        .await;
 
         // Presign works with mock clients, so this should succeed
         assert!(result.is_ok());
@@ -322,7 +523,16 @@ mod tests {
     async fn test_execute_put_method() {
         let config = create_mock_config();
 
-        let result = execute(&config, "s3://bucket/file.txt", 3600, Some("PUT")).await;
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            3600,
+            Some("PUT"),
+            None,
+            false,
+            None,
+        )
+        .await;
 
         // Presign works with mock clients, so this should succeed
         assert!(result.is_ok());
@@ -332,7 +542,16 @@ mod tests {
     async fn test_execute_delete_method() {
         let config = create_mock_config();
 
-        let result = execute(&config, "s3://bucket/file.txt", 3600, Some("DELETE")).await;
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            3600,
+            Some("DELETE"),
+            None,
+            false,
+            None,
+        )
+        .await;
 
         // Presign works with mock clients, so this should succeed
         assert!(result.is_ok());
@@ -343,7 +562,16 @@ mod tests {
         let config = create_mock_config();
 
         // Test with no method specified (should default to GET)
-        let result = execute(&config, "s3://bucket/file.txt", 3600, None).await;
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            3600,
+            None,
+            None,
+            false,
+            None,
+        )
+        .await;
 
         // Presign works with mock clients, so this should succeed
         assert!(result.is_ok());
@@ -357,7 +585,10 @@ mod tests {
             &config,
             "s3://bucket/file.txt",
             3600,
-            Some("POST"), // unsupported method
+            Some("POST"),
+            None, // unsupported method
+            false,
+            None,
         )
         .await;
 
@@ -373,7 +604,16 @@ mod tests {
         let config = create_mock_config();
 
         // Test with lowercase method
-        let result = execute(&config, "s3://bucket/file.txt", 3600, Some("get")).await;
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            3600,
+            Some("get"),
+            None,
+            false,
+            None,
+        )
+        .await;
 
         // Presign works with mock clients, so this should succeed
         assert!(result.is_ok());
@@ -401,7 +641,58 @@ mod tests {
         };
 
         // Presign works with mock clients, so this should succeed
-        let result = generate_put_presigned_url(&config, &s3_uri, 3600).await;
+        let result = generate_put_presigned_url(&config, &s3_uri, 3600, None).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_put_presigned_url_signs_content_type() {
+        let config = create_mock_config();
+        let s3_uri = S3Uri {
+            bucket: "test-bucket".to_string(),
+            key: Some("upload.csv".to_string()),
+        };
+
+        let url = generate_put_presigned_url(&config, &s3_uri, 3600, Some("text/csv"))
+            .await
+            .unwrap();
+        assert!(url.contains("X-Amz-SignedHeaders=content-type%3Bhost"));
+
+        let url = generate_put_presigned_url(&config, &s3_uri, 3600, None)
+            .await
+            .unwrap();
+        assert!(url.contains("X-Amz-SignedHeaders=host"));
+    }
+
+    #[tokio::test]
+    async fn test_content_type_requires_put() {
+        let config = create_mock_config();
+
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            3600,
+            Some("GET"),
+            Some("text/plain"),
+            false,
+            None,
+        )
+        .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--content-type only applies to --method put"));
+
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            3600,
+            Some("put"),
+            Some("text/plain"),
+            false,
+            None,
+        )
+        .await;
         assert!(result.is_ok());
     }
 
@@ -418,6 +709,53 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_execute_requires_key() {
+        let config = create_mock_config();
+
+        for uri in ["s3://bucket", "s3://bucket/"] {
+            let result = execute(&config, uri, 3600, None, None, false, None).await;
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("requires a specific object key"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_out_of_range_expiry() {
+        let config = create_mock_config();
+
+        for expires_in in [0, MAX_EXPIRES_IN_SECS + 1] {
+            let result = execute(
+                &config,
+                "s3://bucket/file.txt",
+                expires_in,
+                None,
+                None,
+                false,
+                None,
+            )
+            .await;
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("--expires-in must be between 1 and 604800 seconds"));
+        }
+
+        let result = execute(
+            &config,
+            "s3://bucket/file.txt",
+            MAX_EXPIRES_IN_SECS,
+            None,
+            None,
+            false,
+            None,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_expiration_duration() {
         let expires_in = 3600u64;
@@ -436,4 +774,46 @@ mod tests {
             assert!(matches!(normalized.as_str(), "GET" | "PUT" | "DELETE"));
         }
     }
+
+    #[test]
+    fn test_parse_keys_file() {
+        let contents = "# links for the release\nreports/q1.pdf\n\n  reports/q2.pdf  \ns3://other-bucket/shared/readme.txt\n";
+        let targets = parse_keys_file(contents, "my-bucket").unwrap();
+
+        let pairs: Vec<(&str, &str)> = targets
+            .iter()
+            .map(|uri| (uri.bucket.as_str(), uri.key_or_empty()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("my-bucket", "reports/q1.pdf"),
+                ("my-bucket", "reports/q2.pdf"),
+                ("other-bucket", "shared/readme.txt"),
+            ]
+        );
+
+        assert!(parse_keys_file("s3://other-bucket\n", "my-bucket").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_keys_from_file() {
+        let config = create_mock_config();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let keys_path = temp_dir.path().join("keys.txt");
+        std::fs::write(&keys_path, "a.txt\nb.txt\n").unwrap();
+
+        // A bucket-only URI is fine in batch mode
+        let result = execute(
+            &config,
+            "s3://bucket",
+            3600,
+            None,
+            None,
+            false,
+            keys_path.to_str(),
+        )
+        .await;
+        assert!(result.is_ok());
+    }
 }
@@ -0,0 +1,221 @@
+use anyhow::Result;
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::types::{GlacierJobParameters, ObjectStorageClass, RestoreRequest, Tier};
+use log::info;
+use std::time::Instant;
+
+use crate::args::RestoreTier;
+use crate::commands::paginate::list_objects_v2_resumable;
+use crate::commands::partial::PartialFailures;
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::config::Config;
+
+/// Error code S3 returns when a restore for the object is already running
+const RESTORE_IN_PROGRESS_CODE: &str = "RestoreAlreadyInProgress";
+
+/// What happened to a single restore request
+#[derive(Debug, PartialEq, Eq)]
+enum RestoreOutcome {
+    Initiated,
+    AlreadyInProgress,
+}
+
+/// Running totals for the end-of-run report
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RestoreSummary {
+    initiated: usize,
+    in_progress: usize,
+}
+
+impl RestoreSummary {
+    fn add(&mut self, outcome: &RestoreOutcome) {
+        match outcome {
+            RestoreOutcome::Initiated => self.initiated += 1,
+            RestoreOutcome::AlreadyInProgress => self.in_progress += 1,
+        }
+    }
+
+    fn report(&self) -> String {
+        format!(
+            "Restore initiated for {} object(s), {} already in progress",
+            self.initiated, self.in_progress
+        )
+    }
+}
+
+pub async fn execute(
+    config: &Config,
+    s3_uri: &str,
+    days: i32,
+    tier: RestoreTier,
+    recursive: bool,
+) -> Result<()> {
+    let start_time = Instant::now();
+
+    if !is_s3_uri(s3_uri) {
+        return Err(anyhow::anyhow!(
+            "restore command only works with S3 URIs (s3://...)"
+        ));
+    }
+    if days < 1 {
+        return Err(anyhow::anyhow!("--days must be at least 1, got {days}"));
+    }
+
+    let uri = S3Uri::parse(s3_uri)?;
+
+    let keys = if recursive {
+        list_archived_keys(config, &uri).await?
+    } else {
+        if uri.key_or_empty().is_empty() {
+            return Err(anyhow::anyhow!(
+                "restore requires a specific object key, or --recursive for a prefix"
+            ));
+        }
+        vec![uri.key_or_empty().to_string()]
+    };
+
+    info!(
+        "Requesting {} restore of {} object(s) in s3://{} for {days} day(s)",
+        sdk_tier(tier).as_str(),
+        keys.len(),
+        uri.bucket
+    );
+
+    let mut summary = RestoreSummary::default();
+    let mut failures = PartialFailures::new("restore");
+    failures.add_attempted(keys.len());
+
+    for key in &keys {
+        let object = format!("s3://{}/{key}", uri.bucket);
+        match restore_object(config, &uri.bucket, key, days, tier).await {
+            Ok(outcome) => {
+                match outcome {
+                    RestoreOutcome::Initiated => println!("restore: {object}"),
+                    RestoreOutcome::AlreadyInProgress => {
+                        println!("restore: {object} (already in progress)")
+                    }
+                }
+                summary.add(&outcome);
+            }
+            Err(e) => {
+                eprintln!("restore failed: {object} ({e})");
+                failures.record(&object, &e.to_string());
+            }
+        }
+    }
+
+    if recursive {
+        println!("{}", summary.report());
+    }
+
+    {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS.operations_total.add(
+            summary.initiated as u64,
+            &[KeyValue::new("operation", "restore")],
+        );
+        OTEL_INSTRUMENTS.operation_duration.record(
+            start_time.elapsed().as_millis() as f64 / 1000.0,
+            &[KeyValue::new("operation", "restore")],
+        );
+    }
+
+    failures.into_result()
+}
+
+/// Archived objects under the prefix; GLACIER_IR and the other classes are
+/// readable without a restore, so they are skipped
+async fn list_archived_keys(config: &Config, uri: &S3Uri) -> Result<Vec<String>> {
+    let mut request = config.client.list_objects_v2().bucket(&uri.bucket);
+    if !uri.key_or_empty().is_empty() {
+        request = request.prefix(uri.key_or_empty());
+    }
+
+    let mut keys = Vec::new();
+    list_objects_v2_resumable(config, request, |response| {
+        for object in response.contents.unwrap_or_default() {
+            let archived = matches!(
+                object.storage_class(),
+                Some(ObjectStorageClass::Glacier | ObjectStorageClass::DeepArchive)
+            );
+            if let Some(key) = object.key.filter(|_| archived) {
+                keys.push(key);
+            }
+        }
+        Ok(())
+    })
+    .await?;
+
+    Ok(keys)
+}
+
+async fn restore_object(
+    config: &Config,
+    bucket: &str,
+    key: &str,
+    days: i32,
+    tier: RestoreTier,
+) -> Result<RestoreOutcome> {
+    let request = RestoreRequest::builder()
+        .days(days)
+        .glacier_job_parameters(
+            GlacierJobParameters::builder()
+                .tier(sdk_tier(tier))
+                .build()?,
+        )
+        .build();
+
+    match config
+        .client
+        .restore_object()
+        .bucket(bucket)
+        .key(key)
+        .restore_request(request)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(RestoreOutcome::Initiated),
+        Err(e) if e.code() == Some(RESTORE_IN_PROGRESS_CODE) => {
+            Ok(RestoreOutcome::AlreadyInProgress)
+        }
+        Err(e) => Err(match (e.code(), e.message()) {
+            (Some(code), Some(message)) => anyhow::anyhow!("{code}: {message}"),
+            (Some(code), None) => anyhow::anyhow!("{code}"),
+            _ => anyhow::anyhow!("{e}"),
+        }),
+    }
+}
+
+fn sdk_tier(tier: RestoreTier) -> Tier {
+    match tier {
+        RestoreTier::Standard => Tier::Standard,
+        RestoreTier::Expedited => Tier::Expedited,
+        RestoreTier::Bulk => Tier::Bulk,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_summary() {
+        let mut summary = RestoreSummary::default();
+        summary.add(&RestoreOutcome::Initiated);
+        summary.add(&RestoreOutcome::Initiated);
+        summary.add(&RestoreOutcome::AlreadyInProgress);
+        assert_eq!(
+            summary.report(),
+            "Restore initiated for 2 object(s), 1 already in progress"
+        );
+    }
+
+    #[test]
+    fn test_sdk_tier() {
+        assert_eq!(sdk_tier(RestoreTier::Standard), Tier::Standard);
+        assert_eq!(sdk_tier(RestoreTier::Expedited), Tier::Expedited);
+        assert_eq!(sdk_tier(RestoreTier::Bulk), Tier::Bulk);
+    }
+}
@@ -0,0 +1,202 @@
+use aws_sdk_s3::config::interceptors::BeforeTransmitInterceptorContextRef;
+use aws_sdk_s3::config::retry::RetryConfig;
+use aws_sdk_s3::config::{ConfigBag, Intercept, RuntimeComponents};
+use aws_sdk_s3::error::BoxError;
+use aws_smithy_runtime_api::client::orchestrator::Metadata;
+use aws_smithy_runtime_api::client::retries::RequestAttempts;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::events::EventSink;
+
+/// Retry settings for transient S3 errors (`--max-retries`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; zero disables retrying
+    pub max_retries: usize,
+    /// Delay before the first retry, doubled for each one after
+    pub base_delay: Duration,
+    /// Upper bound on a single delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+
+    /// The SDK retry strategy every S3 request runs under: throttles, 5xx
+    /// and connection failures are retried with jittered exponential
+    /// backoff, up to `max_retries` times after the first attempt
+    pub fn sdk_retry_config(&self) -> RetryConfig {
+        if self.max_retries == 0 {
+            return RetryConfig::disabled();
+        }
+        RetryConfig::standard()
+            .with_max_attempts(u32::try_from(self.max_retries + 1).unwrap_or(u32::MAX))
+            .with_initial_backoff(self.base_delay)
+            .with_max_backoff(self.max_delay)
+    }
+}
+
+/// Reports each SDK retry to the event sink and counts it as a service
+/// error, so dashboards show retry pressure
+pub struct RetryInterceptor {
+    events: Arc<dyn EventSink>,
+}
+
+impl RetryInterceptor {
+    pub fn new(events: Arc<dyn EventSink>) -> Self {
+        Self { events }
+    }
+}
+
+impl std::fmt::Debug for RetryInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RetryInterceptor")
+    }
+}
+
+impl Intercept for RetryInterceptor {
+    fn name(&self) -> &'static str {
+        "RetryInterceptor"
+    }
+
+    fn read_before_attempt(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let attempt = cfg
+            .load::<RequestAttempts>()
+            .map_or(1, |attempts| attempts.attempts());
+        if attempt > 1 {
+            let operation = cfg
+                .load::<Metadata>()
+                .map_or("request", |metadata| metadata.name());
+            record_retry(operation);
+            self.events
+                .on_retry(operation, attempt as usize - 1, "transient S3 error");
+        }
+        Ok(())
+    }
+}
+
+fn record_retry(operation: &str) {
+    use crate::otel::{GLOBAL_METRICS, OTEL_INSTRUMENTS};
+    use opentelemetry::KeyValue;
+    use std::sync::atomic::Ordering;
+
+    GLOBAL_METRICS
+        .errors_service
+        .fetch_add(1, Ordering::Relaxed);
+    OTEL_INSTRUMENTS
+        .errors_service
+        .add(1, &[KeyValue::new("operation", operation.to_string())]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_client::retrying_config;
+    use aws_sdk_s3::primitives::ByteStream;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// Records retry attempts
+    #[derive(Default)]
+    struct RetryRecorder {
+        attempts: Mutex<Vec<(String, usize)>>,
+    }
+
+    impl EventSink for RetryRecorder {
+        fn on_retry(&self, operation: &str, attempt: usize, _reason: &str) {
+            self.attempts
+                .lock()
+                .unwrap()
+                .push((operation.to_string(), attempt));
+        }
+    }
+
+    const SLOW_DOWN: &str =
+        "<Error><Code>SlowDown</Code><Message>Reduce your request rate</Message></Error>";
+
+    #[test]
+    fn test_sdk_retry_config() {
+        assert_eq!(RetryPolicy::new(0).sdk_retry_config().max_attempts(), 1);
+        let config = RetryPolicy::new(3).sdk_retry_config();
+        assert_eq!(config.max_attempts(), 4);
+        assert_eq!(config.initial_backoff(), Duration::from_millis(100));
+        assert_eq!(config.max_backoff(), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_transient_put_error_is_retried() {
+        let events = Arc::new(RetryRecorder::default());
+        let puts = Arc::new(AtomicUsize::new(0));
+        let counter = puts.clone();
+        let (config, requests) = retrying_config(3, events.clone(), move |method, _| {
+            match (method, counter.fetch_add(1, Ordering::SeqCst)) {
+                ("PUT", 0) => (503, SLOW_DOWN.to_string()),
+                _ => (200, String::new()),
+            }
+        });
+
+        config
+            .client
+            .put_object()
+            .bucket("b")
+            .key("k")
+            .body(ByteStream::from_static(b"payload"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        assert_eq!(
+            *events.attempts.lock().unwrap(),
+            vec![("PutObject".to_string(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_retries_bounds_requests() {
+        for max_retries in [0, 2] {
+            let events = Arc::new(RetryRecorder::default());
+            let (config, requests) = retrying_config(max_retries, events.clone(), |_, _| {
+                (503, SLOW_DOWN.to_string())
+            });
+
+            let result = config.client.get_object().bucket("b").key("k").send().await;
+
+            assert!(result.is_err());
+            assert_eq!(requests.lock().unwrap().len(), max_retries + 1);
+            assert_eq!(events.attempts.lock().unwrap().len(), max_retries);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_are_not_retried() {
+        let events = Arc::new(RetryRecorder::default());
+        let (config, requests) = retrying_config(3, events.clone(), |_, _| {
+            (403, "<Error><Code>AccessDenied</Code></Error>".to_string())
+        });
+
+        let result = config.client.get_object().bucket("b").key("k").send().await;
+
+        assert!(result.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(events.attempts.lock().unwrap().is_empty());
+    }
+}
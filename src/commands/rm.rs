@@ -1,12 +1,28 @@
 use anyhow::Result;
+use aws_sdk_s3::types::{ObjectLockEnabled, ObjectLockLegalHoldStatus, ObjectLockMode};
 use base64::{engine::general_purpose::STANDARD as b64, Engine as _};
-use log::info;
+use futures::stream::{self, StreamExt};
+use log::{debug, info};
 use md5;
-use std::time::Instant;
+use std::collections::HashSet;
+use std::io::{IsTerminal, Write};
+use std::time::{Instant, SystemTime};
 
+use crate::commands::paginate::list_objects_v2_resumable;
+use crate::commands::partial::PartialFailures;
 use crate::commands::s3_uri::{is_s3_uri, S3Uri};
 use crate::config::Config;
 
+/// Concurrent head-object lookups when checking a page of keys for object lock
+const LOCK_CHECK_CONCURRENCY: usize = 16;
+
+/// DeleteObjects accepts at most this many keys per request
+const MAX_DELETE_BATCH: usize = 1000;
+
+/// Matched keys listed before asking to confirm a recursive delete
+const CONFIRM_SAMPLE_SIZE: usize = 10;
+
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     config: &Config,
     path: &str,
@@ -15,6 +31,11 @@ pub async fn execute(
     force: bool,
     include: Option<&str>,
     exclude: Option<&str>,
+    ignore_locked: bool,
+    yes: bool,
+    quiet: bool,
+    version_id: Option<&str>,
+    out: &mut dyn Write,
 ) -> Result<()> {
     let start_time = Instant::now();
 
@@ -25,10 +46,17 @@ pub async fn execute(
     }
 
     let s3_uri = S3Uri::parse(path)?;
+    let target = delete_target(&s3_uri, version_id);
+
+    if version_id.is_some() && s3_uri.key_or_empty().is_empty() {
+        return Err(anyhow::anyhow!(
+            "--version-id needs an object key (s3://bucket/key)"
+        ));
+    }
 
     if dryrun {
         info!("[DRY RUN] Would delete {path}");
-        return Ok(());
+        return dryrun_delete(config, &s3_uri, recursive, &target, quiet, out).await;
     }
 
     let result = if s3_uri.key.is_none() || s3_uri.key_or_empty().is_empty() {
@@ -36,24 +64,27 @@ pub async fn execute(
         if !force {
             return Err(anyhow::anyhow!("To delete a bucket, use --force flag"));
         }
-        delete_bucket(config, &s3_uri.bucket, recursive).await
+        delete_bucket(config, &s3_uri.bucket, recursive, quiet).await
     } else {
         // Deleting specific object(s)
         if recursive {
-            delete_objects_recursive(config, &s3_uri, include, exclude).await
+            // Scripts without a terminal can't answer, so they keep the old behaviour
+            if !yes && std::io::stdin().is_terminal() {
+                confirm_recursive_delete(config, &s3_uri).await?;
+            }
+            delete_objects_recursive(config, &s3_uri, include, exclude, ignore_locked, quiet).await
         } else {
-            delete_single_object(config, &s3_uri).await
+            delete_single_object(config, &s3_uri, version_id, ignore_locked).await
         }
     };
 
     match result {
-        Ok(_) => {
+        Ok(deleted) => {
             let duration = start_time.elapsed();
 
             // Record rm operation using proper OTEL SDK
             {
-                use crate::otel::OTEL_INSTRUMENTS;
-                use opentelemetry::KeyValue;
+                use crate::otel::{GLOBAL_METRICS, OTEL_INSTRUMENTS};
 
                 let operation_type = if s3_uri.key.is_none() || s3_uri.key_or_empty().is_empty() {
                     "rm_bucket"
@@ -63,18 +94,21 @@ pub async fn execute(
                     "rm_single"
                 };
 
-                OTEL_INSTRUMENTS
-                    .operations_total
-                    .add(1, &[KeyValue::new("operation", operation_type)]);
-
-                let duration_seconds = duration.as_millis() as f64 / 1000.0;
-                OTEL_INSTRUMENTS.operation_duration.record(
-                    duration_seconds,
-                    &[KeyValue::new("operation", operation_type)],
+                let duration_ms = duration.as_millis() as u64;
+                OTEL_INSTRUMENTS.record_delete(
+                    operation_type,
+                    deleted,
+                    duration_ms,
+                    config.otel.bucket_label(&s3_uri.bucket),
                 );
+                GLOBAL_METRICS.record_delete(deleted, duration_ms).await;
             }
 
-            println!("delete: s3://{}/{}", s3_uri.bucket, s3_uri.key_or_empty());
+            // Quiet runs already printed their one summary line, and a
+            // locked object skipped under --ignore-locked deletes nothing
+            if !quiet && deleted > 0 {
+                writeln!(out, "delete: {target}")?;
+            }
 
             // Transparent du call for real-time bucket analytics
             let bucket_uri = format!("s3://{}", s3_uri.bucket);
@@ -96,19 +130,147 @@ pub async fn execute(
     }
 }
 
-async fn delete_single_object(config: &Config, s3_uri: &S3Uri) -> Result<()> {
-    let start_time = Instant::now();
-    info!(
-        "Deleting object: s3://{}/{}",
-        s3_uri.bucket,
-        s3_uri.key_or_empty()
+/// Report what rm would delete: each object, or under `quiet` only how many
+async fn dryrun_delete(
+    config: &Config,
+    s3_uri: &S3Uri,
+    recursive: bool,
+    target: &str,
+    quiet: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let targets = if recursive || s3_uri.key_or_empty().is_empty() {
+        let request = config
+            .client
+            .list_objects_v2()
+            .bucket(&s3_uri.bucket)
+            .prefix(s3_uri.key_or_empty());
+        let mut targets = Vec::new();
+        list_objects_v2_resumable(config, request, |response| {
+            targets.extend(
+                response
+                    .contents()
+                    .iter()
+                    .filter_map(|object| object.key())
+                    .map(|key| format!("s3://{}/{key}", s3_uri.bucket)),
+            );
+            Ok(())
+        })
+        .await?;
+        targets
+    } else {
+        vec![target.to_string()]
+    };
+
+    if quiet {
+        writeln!(out, "(dryrun) would delete {} object(s)", targets.len())?;
+    } else {
+        for target in &targets {
+            writeln!(out, "(dryrun) delete: {target}")?;
+        }
+    }
+    Ok(())
+}
+
+/// List what a recursive delete would match and ask before going ahead
+async fn confirm_recursive_delete(config: &Config, s3_uri: &S3Uri) -> Result<()> {
+    let request = config
+        .client
+        .list_objects_v2()
+        .bucket(&s3_uri.bucket)
+        .prefix(s3_uri.key_or_empty());
+
+    let mut sample = Vec::new();
+    let mut total = 0usize;
+    list_objects_v2_resumable(config, request, |response| {
+        for key in response.contents().iter().filter_map(|object| object.key()) {
+            if sample.len() < CONFIRM_SAMPLE_SIZE {
+                sample.push(key.to_string());
+            }
+            total += 1;
+        }
+        Ok(())
+    })
+    .await?;
+
+    if total == 0 {
+        return Ok(());
+    }
+
+    eprint!(
+        "{}Proceed? [y/N] ",
+        confirmation_prompt(&s3_uri.bucket, &sample, total)
     );
+    std::io::stderr().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    if is_confirmed(&answer) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Aborted, nothing was deleted (pass --yes to skip this prompt)"
+        ))
+    }
+}
+
+fn confirmation_prompt(bucket: &str, sample: &[String], total: usize) -> String {
+    let mut prompt = format!("The following {total} object(s) will be deleted:\n");
+    for key in sample {
+        prompt.push_str(&format!("  s3://{bucket}/{key}\n"));
+    }
+    if total > sample.len() {
+        prompt.push_str(&format!("  ... and {} more\n", total - sample.len()));
+    }
+    prompt
+}
+
+fn is_confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// The object being deleted, with the version when one was picked
+fn delete_target(s3_uri: &S3Uri, version_id: Option<&str>) -> String {
+    match version_id {
+        Some(version_id) => format!("{s3_uri} (version {version_id})"),
+        None => s3_uri.to_string(),
+    }
+}
+
+/// Delete one object, or with `version_id` permanently delete that version
+/// rather than adding a delete marker. Returns how many objects were
+/// deleted: 1, or 0 when `ignore_locked` skipped a locked object
+async fn delete_single_object(
+    config: &Config,
+    s3_uri: &S3Uri,
+    version_id: Option<&str>,
+    ignore_locked: bool,
+) -> Result<u64> {
+    let start_time = Instant::now();
+    let uri = delete_target(s3_uri, version_id);
+    info!("Deleting object: {uri}");
+
+    if bucket_has_object_lock(config, &s3_uri.bucket).await {
+        let key = s3_uri.key_or_empty().to_string();
+        if let Some(reason) =
+            object_lock_protection(config, &s3_uri.bucket, &key, version_id).await?
+        {
+            if !ignore_locked {
+                return Err(anyhow::anyhow!(
+                    "Cannot delete {uri}: protected by {reason}"
+                ));
+            }
+            eprintln!("skip: {uri} (protected by {reason})");
+            return Ok(0);
+        }
+    }
 
     let result = config
         .client
         .delete_object()
         .bucket(&s3_uri.bucket)
         .key(s3_uri.key_or_empty())
+        .set_version_id(version_id.map(str::to_string))
         .send()
         .await;
 
@@ -121,10 +283,6 @@ async fn delete_single_object(config: &Config, s3_uri: &S3Uri) -> Result<()> {
                 use crate::otel::OTEL_INSTRUMENTS;
                 use opentelemetry::KeyValue;
 
-                OTEL_INSTRUMENTS
-                    .files_deleted_total
-                    .add(1, &[KeyValue::new("operation", "delete_single")]);
-
                 let duration_seconds = duration.as_millis() as f64 / 1000.0;
                 OTEL_INSTRUMENTS.operation_duration.record(
                     duration_seconds,
@@ -132,25 +290,18 @@ async fn delete_single_object(config: &Config, s3_uri: &S3Uri) -> Result<()> {
                 );
             }
 
-            println!("delete: s3://{}/{}", s3_uri.bucket, s3_uri.key_or_empty());
-
             // Transparent du call for real-time bucket analytics
             let bucket_uri = format!("s3://{}", s3_uri.bucket);
             call_transparent_du(config, &bucket_uri).await;
 
-            Ok(())
+            Ok(1)
         }
         Err(e) => {
             // Record error using proper OTEL SDK
             {
                 use crate::otel::OTEL_INSTRUMENTS;
 
-                let error_msg = format!(
-                    "Failed to delete single object s3://{}/{}: {}",
-                    s3_uri.bucket,
-                    s3_uri.key_or_empty(),
-                    e
-                );
+                let error_msg = format!("Failed to delete single object {uri}: {e}");
                 OTEL_INSTRUMENTS.record_error_with_type(&error_msg);
             }
 
@@ -164,7 +315,9 @@ async fn delete_objects_recursive(
     s3_uri: &S3Uri,
     _include: Option<&str>,
     _exclude: Option<&str>,
-) -> Result<()> {
+    ignore_locked: bool,
+    quiet: bool,
+) -> Result<u64> {
     let start_time = Instant::now();
     info!(
         "Recursively deleting objects with prefix: s3://{}/{}",
@@ -174,6 +327,9 @@ async fn delete_objects_recursive(
 
     let mut continuation_token: Option<String> = None;
     let mut deleted_count = 0;
+    let mut skipped_locked = 0u64;
+    let mut failures = PartialFailures::new("delete_objects");
+    let lock_enabled = bucket_has_object_lock(config, &s3_uri.bucket).await;
 
     let result: anyhow::Result<()> = async {
         loop {
@@ -191,85 +347,40 @@ async fn delete_objects_recursive(
             let response = list_request.send().await?;
 
             if let Some(objects) = response.contents {
-                // Collect object keys for batch deletion
-                let mut objects_to_delete = Vec::new();
-
-                for object in objects {
-                    if let Some(key) = object.key {
-                        objects_to_delete.push(
-                            aws_sdk_s3::types::ObjectIdentifier::builder()
-                                .key(&key)
-                                .build()
-                                .map_err(|e| {
-                                    anyhow::anyhow!("Failed to build object identifier: {}", e)
-                                })?,
-                        );
-                        println!("delete: s3://{}/{}", s3_uri.bucket, key);
-                        deleted_count += 1;
-                    }
-                }
+                let mut keys: Vec<String> = objects.into_iter().filter_map(|o| o.key).collect();
 
-                // Perform batch deletion if we have objects to delete
-                if !objects_to_delete.is_empty() {
-                    let delete_request = aws_sdk_s3::types::Delete::builder()
-                        .set_objects(Some(objects_to_delete.clone()))
-                        .build()
-                        .map_err(|e| anyhow::anyhow!("Failed to build delete request: {}", e))?;
-
-                    // For MinIO compatibility, compute and add Content-MD5 header
-                    // MinIO requires this header for batch deletion operations
-                    let result = config
-                        .client
-                        .delete_objects()
-                        .bucket(&s3_uri.bucket)
-                        .delete(delete_request.clone())
-                        .customize()
-                        .mutate_request(|req| {
-                            // For MinIO compatibility, we need to add Content-MD5 header
-                            // Get the request body bytes if available
-                            let payload_xml = if let Some(body_bytes) = req.body().bytes() {
-                                body_bytes.to_vec()
-                            } else {
-                                // Fallback: compute MD5 of empty body
-                                Vec::new()
-                            };
-
-                            // Compute MD5 hash of the payload and base64 encode it
-                            let md5_hash = md5::compute(&payload_xml);
-                            let md5_b64 = b64.encode(md5_hash.as_ref());
-
-                            // Add the Content-MD5 header
-                            req.headers_mut().insert("Content-MD5", md5_b64);
-                        })
-                        .send()
+                if lock_enabled {
+                    let protections: Vec<Result<Option<String>>> = stream::iter(keys.iter())
+                        .map(|key| object_lock_protection(config, &s3_uri.bucket, key, None))
+                        .buffered(LOCK_CHECK_CONCURRENCY)
+                        .collect()
                         .await;
 
-                    match result {
-                        Ok(_) => {
-                            // Batch deletion succeeded with Content-MD5 header
-                        },
-                        Err(e) if e.to_string().contains("MissingContentMD5") => {
-                            info!("Batch deletion failed with MissingContentMD5, falling back to individual deletions");
-                            // Fall back to individual object deletion when batch fails
-                            for obj in &objects_to_delete {
-                                let key = obj.key();
-                                if !key.is_empty() {
-                                    config
-                                        .client
-                                        .delete_object()
-                                        .bucket(&s3_uri.bucket)
-                                        .key(key)
-                                        .send()
-                                        .await?;
-
-                                    println!("delete: s3://{}/{}", s3_uri.bucket, key);
-                                }
+                    let mut unlocked = Vec::with_capacity(keys.len());
+                    for (key, protection) in keys.into_iter().zip(protections) {
+                        match protection? {
+                            Some(reason) if !ignore_locked => {
+                                return Err(anyhow::anyhow!(
+                                    "Cannot delete s3://{}/{key}: protected by {reason}",
+                                    s3_uri.bucket
+                                ));
+                            }
+                            Some(reason) => {
+                                eprintln!(
+                                    "skip: s3://{}/{key} (protected by {reason})",
+                                    s3_uri.bucket
+                                );
+                                skipped_locked += 1;
                             }
-                        },
-                        Err(e) => {
-                            return Err(e.into());
+                            None => unlocked.push(key),
                         }
                     }
+                    keys = unlocked;
+                }
+
+                for batch in keys.chunks(MAX_DELETE_BATCH) {
+                    deleted_count +=
+                        delete_batch(config, &s3_uri.bucket, batch, &mut failures, quiet).await?;
                 }
             }
 
@@ -293,11 +404,6 @@ async fn delete_objects_recursive(
                 use crate::otel::OTEL_INSTRUMENTS;
                 use opentelemetry::KeyValue;
 
-                OTEL_INSTRUMENTS.files_deleted_total.add(
-                    deleted_count,
-                    &[KeyValue::new("operation", "delete_recursive")],
-                );
-
                 let duration_seconds = duration.as_millis() as f64 / 1000.0;
                 OTEL_INSTRUMENTS.operation_duration.record(
                     duration_seconds,
@@ -306,12 +412,25 @@ async fn delete_objects_recursive(
             }
 
             info!("Successfully deleted {deleted_count} objects");
+            if quiet {
+                println!(
+                    "delete: {deleted_count} object(s) from s3://{}/{}",
+                    s3_uri.bucket,
+                    s3_uri.key_or_empty()
+                );
+            }
+            if skipped_locked > 0 {
+                eprintln!(
+                    "Skipped {skipped_locked} object(s) protected by object lock retention or legal hold"
+                );
+            }
 
             // Transparent du call for real-time bucket analytics
             let bucket_uri = format!("s3://{}", s3_uri.bucket);
             call_transparent_du(config, &bucket_uri).await;
 
-            Ok(())
+            // Each failed key was already classified as it came back
+            failures.into_result().map(|()| deleted_count)
         }
         Err(e) => {
             // Record error using proper OTEL SDK
@@ -332,11 +451,116 @@ async fn delete_objects_recursive(
     }
 }
 
-async fn delete_bucket(config: &Config, bucket_name: &str, force_empty: bool) -> Result<()> {
+/// Delete up to `MAX_DELETE_BATCH` keys with one DeleteObjects request,
+/// returning how many were deleted. Keys S3 refuses are recorded as failures;
+/// servers that insist on Content-MD5 and still reject the batch get one
+/// DeleteObject per key instead.
+async fn delete_batch(
+    config: &Config,
+    bucket: &str,
+    keys: &[String],
+    failures: &mut PartialFailures,
+    quiet: bool,
+) -> Result<u64> {
+    let objects_to_delete = keys
+        .iter()
+        .map(|key| {
+            aws_sdk_s3::types::ObjectIdentifier::builder()
+                .key(key)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build object identifier: {}", e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let delete_request = aws_sdk_s3::types::Delete::builder()
+        .set_objects(Some(objects_to_delete))
+        .quiet(true)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build delete request: {}", e))?;
+
+    // For MinIO compatibility, compute and add Content-MD5 header
+    // MinIO requires this header for batch deletion operations
+    let result = config
+        .client
+        .delete_objects()
+        .bucket(bucket)
+        .delete(delete_request)
+        .customize()
+        .mutate_request(|req| {
+            // Get the request body bytes if available
+            let payload_xml = if let Some(body_bytes) = req.body().bytes() {
+                body_bytes.to_vec()
+            } else {
+                // Fallback: compute MD5 of empty body
+                Vec::new()
+            };
+
+            // Compute MD5 hash of the payload and base64 encode it
+            let md5_hash = md5::compute(&payload_xml);
+            let md5_b64 = b64.encode(md5_hash.as_ref());
+
+            // Add the Content-MD5 header
+            req.headers_mut().insert("Content-MD5", md5_b64);
+        })
+        .send()
+        .await;
+
+    failures.add_attempted(keys.len());
+    match result {
+        Ok(output) => {
+            // Quiet mode only reports the keys S3 refused; the rest were deleted
+            failures.record_delete_errors(bucket, output.errors());
+            let refused: HashSet<&str> = output.errors().iter().filter_map(|e| e.key()).collect();
+            let mut deleted = 0;
+            for key in keys.iter().filter(|key| !refused.contains(key.as_str())) {
+                if !quiet {
+                    println!("delete: s3://{bucket}/{key}");
+                }
+                deleted += 1;
+            }
+            Ok(deleted)
+        }
+        Err(e) if e.to_string().contains("MissingContentMD5") => {
+            info!("Batch deletion failed with MissingContentMD5, falling back to individual deletions");
+            let mut deleted = 0;
+            for key in keys {
+                match config
+                    .client
+                    .delete_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .send()
+                    .await
+                {
+                    Ok(_) => {
+                        if !quiet {
+                            println!("delete: s3://{bucket}/{key}");
+                        }
+                        deleted += 1;
+                    }
+                    Err(e) => {
+                        let item = format!("s3://{bucket}/{key}");
+                        eprintln!("delete failed: {item} ({e})");
+                        failures.record(&item, &e.to_string());
+                    }
+                }
+            }
+            Ok(deleted)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn delete_bucket(
+    config: &Config,
+    bucket_name: &str,
+    force_empty: bool,
+    quiet: bool,
+) -> Result<u64> {
     let start_time = Instant::now();
     info!("Deleting bucket: {bucket_name}");
 
-    let result: anyhow::Result<()> = async {
+    let result: anyhow::Result<u64> = async {
+        let mut deleted = 0;
         if force_empty {
             // First, delete all objects in the bucket
             let s3_uri = S3Uri {
@@ -344,7 +568,8 @@ async fn delete_bucket(config: &Config, bucket_name: &str, force_empty: bool) ->
                 key: None,
             };
 
-            delete_objects_recursive(config, &s3_uri, None, None).await?;
+            // Locked objects would keep the bucket from being removed, so fail fast
+            deleted = delete_objects_recursive(config, &s3_uri, None, None, false, quiet).await?;
 
             // Also delete all object versions and delete markers (for versioned buckets)
             delete_all_versions(config, bucket_name).await?;
@@ -358,12 +583,12 @@ async fn delete_bucket(config: &Config, bucket_name: &str, force_empty: bool) ->
             .send()
             .await?;
 
-        Ok(())
+        Ok(deleted)
     }
     .await;
 
     match result {
-        Ok(_) => {
+        Ok(deleted) => {
             let duration = start_time.elapsed();
 
             // Record bucket deletion using proper OTEL SDK
@@ -388,7 +613,7 @@ async fn delete_bucket(config: &Config, bucket_name: &str, force_empty: bool) ->
             let bucket_uri = format!("s3://{bucket_name}");
             call_transparent_du(config, &bucket_uri).await;
 
-            Ok(())
+            Ok(deleted)
         }
         Err(e) => {
             // Record error using proper OTEL SDK
@@ -404,11 +629,97 @@ async fn delete_bucket(config: &Config, bucket_name: &str, force_empty: bool) ->
     }
 }
 
+/// Whether object lock is enabled on the bucket; buckets without a lock
+/// configuration (or that refuse the lookup) skip the per-object checks
+async fn bucket_has_object_lock(config: &Config, bucket: &str) -> bool {
+    match config
+        .client
+        .get_object_lock_configuration()
+        .bucket(bucket)
+        .send()
+        .await
+    {
+        Ok(response) => response
+            .object_lock_configuration()
+            .and_then(|lock| lock.object_lock_enabled())
+            .is_some_and(|enabled| *enabled == ObjectLockEnabled::Enabled),
+        Err(e) => {
+            debug!("No object lock configuration for {bucket}: {e}");
+            false
+        }
+    }
+}
+
+/// Look up an object's (or one version's) retention and legal hold via
+/// head-object. A missing object has nothing protecting it, so the delete
+/// goes ahead and stays idempotent like a plain DeleteObject.
+async fn object_lock_protection(
+    config: &Config,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+) -> Result<Option<String>> {
+    let result = config
+        .client
+        .head_object()
+        .bucket(bucket)
+        .key(key)
+        .set_version_id(version_id.map(str::to_string))
+        .send()
+        .await;
+    let head = match result {
+        Ok(head) => head,
+        Err(e) if e.raw_response().map(|response| response.status().as_u16()) == Some(404) => {
+            debug!("s3://{bucket}/{key} not found; nothing to protect");
+            return Ok(None);
+        }
+        Err(e) => {
+            return Err(anyhow::anyhow!(
+                "Failed to check object lock on s3://{bucket}/{key}: {e}"
+            ))
+        }
+    };
+
+    Ok(lock_protection(
+        head.object_lock_mode(),
+        head.object_lock_retain_until_date()
+            .and_then(|date| SystemTime::try_from(*date).ok()),
+        head.object_lock_legal_hold_status(),
+        SystemTime::now(),
+    ))
+}
+
+/// Describe what keeps an object from being deleted, if anything: a legal hold,
+/// or a retention period that hasn't expired yet
+fn lock_protection(
+    mode: Option<&ObjectLockMode>,
+    retain_until: Option<SystemTime>,
+    legal_hold: Option<&ObjectLockLegalHoldStatus>,
+    now: SystemTime,
+) -> Option<String> {
+    if legal_hold == Some(&ObjectLockLegalHoldStatus::On) {
+        return Some("legal hold".to_string());
+    }
+
+    let retain_until = retain_until.filter(|until| *until > now)?;
+    let mode = match mode {
+        Some(ObjectLockMode::Compliance) => "compliance",
+        Some(ObjectLockMode::Governance) => "governance",
+        _ => return None,
+    };
+    let until = chrono::DateTime::<chrono::Utc>::from(retain_until);
+    Some(format!(
+        "{mode} retention until {}",
+        until.format("%Y-%m-%d %H:%M:%S UTC")
+    ))
+}
+
 async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
     info!("Deleting all versions and delete markers in bucket: {bucket_name}");
 
     let mut key_marker: Option<String> = None;
     let mut version_id_marker: Option<String> = None;
+    let mut failures = PartialFailures::new("delete_objects");
 
     loop {
         let mut list_request = config.client.list_object_versions().bucket(bucket_name);
@@ -461,6 +772,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
 
         // Perform batch deletion if we have objects to delete
         if !objects_to_delete.is_empty() {
+            failures.add_attempted(objects_to_delete.len());
             let delete_request = aws_sdk_s3::types::Delete::builder()
                 .set_objects(Some(objects_to_delete))
                 .build()
@@ -468,7 +780,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
 
             // For MinIO compatibility, compute and add Content-MD5 header
             // MinIO requires this header for batch deletion operations
-            config
+            let output = config
                 .client
                 .delete_objects()
                 .bucket(bucket_name)
@@ -493,6 +805,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
                 })
                 .send()
                 .await?;
+            failures.record_delete_errors(bucket_name, output.errors());
         }
 
         // Check if there are more versions to delete
@@ -504,7 +817,7 @@ async fn delete_all_versions(config: &Config, bucket_name: &str) -> Result<()> {
         }
     }
 
-    Ok(())
+    failures.into_result()
 }
 
 // Add transparent du call for real-time bucket analytics
@@ -555,7 +868,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -571,6 +893,11 @@ mod tests {
             false,
             None,
             None,
+            true,
+            true,
+            false,
+            None,
+            &mut Vec::new(),
         )
         .await;
 
@@ -584,6 +911,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_dry_run() {
         let config = create_mock_config();
+        let mut out = Vec::new();
 
         let result = execute(
             &config,
@@ -593,10 +921,55 @@ mod tests {
             false,
             None,
             None,
+            true,
+            true,
+            false,
+            None,
+            &mut out,
         )
         .await;
 
         assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "(dryrun) delete: s3://bucket/file.txt\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quiet_dry_run_prints_count() {
+        let config = crate::commands::test_client::canned_config(|method, _| match method {
+            "GET" => (
+                200,
+                "<ListBucketResult><Contents><Key>logs/a.log</Key></Contents>\
+                 <Contents><Key>logs/b.log</Key></Contents></ListBucketResult>"
+                    .to_string(),
+            ),
+            _ => panic!("dry run sent a {method} request"),
+        });
+        let mut out = Vec::new();
+
+        execute(
+            &config,
+            "s3://bucket/logs/",
+            true,
+            true,
+            false,
+            None,
+            None,
+            true,
+            true,
+            true,
+            None,
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "(dryrun) would delete 2 object(s)\n"
+        );
     }
 
     #[tokio::test]
@@ -611,6 +984,11 @@ mod tests {
             false, // no force flag
             None,
             None,
+            true,
+            true,
+            false,
+            None,
+            &mut Vec::new(),
         )
         .await;
 
@@ -633,6 +1011,11 @@ mod tests {
             true, // force flag
             None,
             None,
+            true,
+            true,
+            false,
+            None,
+            &mut Vec::new(),
         )
         .await;
 
@@ -652,6 +1035,11 @@ mod tests {
             false,
             None,
             None,
+            true,
+            true,
+            false,
+            None,
+            &mut Vec::new(),
         )
         .await;
 
@@ -671,6 +1059,11 @@ mod tests {
             false,
             None,
             None,
+            true,
+            true,
+            false,
+            None,
+            &mut Vec::new(),
         )
         .await;
 
@@ -683,8 +1076,18 @@ mod tests {
         let config = create_mock_config();
 
         let result = execute(
-            &config, "s3://", // invalid S3 URI
-            false, false, false, None, None,
+            &config,
+            "s3://", // invalid S3 URI
+            false,
+            false,
+            false,
+            None,
+            None,
+            true,
+            true,
+            false,
+            None,
+            &mut Vec::new(),
         )
         .await;
 
@@ -721,7 +1124,7 @@ mod tests {
         };
 
         // This will fail due to no real AWS connection, but tests the function structure
-        let result = delete_single_object(&config, &s3_uri).await;
+        let result = delete_single_object(&config, &s3_uri, None, true).await;
         assert!(result.is_err());
     }
 
@@ -734,7 +1137,7 @@ mod tests {
         };
 
         // This will fail due to no real AWS connection, but tests the function structure
-        let result = delete_objects_recursive(&config, &s3_uri, None, None).await;
+        let result = delete_objects_recursive(&config, &s3_uri, None, None, true, false).await;
         assert!(result.is_err());
     }
 
@@ -743,7 +1146,7 @@ mod tests {
         let config = create_mock_config();
 
         // This will fail due to no real AWS connection, but tests the function structure
-        let result = delete_bucket(&config, "test-bucket", true).await;
+        let result = delete_bucket(&config, "test-bucket", true, false).await;
         assert!(result.is_err());
     }
 
@@ -755,4 +1158,199 @@ mod tests {
         let result = delete_all_versions(&config, "test-bucket").await;
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_delete_target() {
+        let s3_uri = S3Uri::parse("s3://bucket/key.txt").unwrap();
+        assert_eq!(delete_target(&s3_uri, None), "s3://bucket/key.txt");
+        assert_eq!(
+            delete_target(&s3_uri, Some("abc123")),
+            "s3://bucket/key.txt (version abc123)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version_id_needs_object_key() {
+        let config = create_mock_config();
+        let result = execute(
+            &config,
+            "s3://bucket",
+            false,
+            false,
+            true,
+            None,
+            None,
+            true,
+            true,
+            false,
+            Some("abc123"),
+            &mut Vec::new(),
+        )
+        .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--version-id needs an object key"));
+    }
+
+    #[test]
+    fn test_confirmation_prompt() {
+        let sample: Vec<String> = (0..CONFIRM_SAMPLE_SIZE)
+            .map(|i| format!("logs/{i}.log"))
+            .collect();
+        let prompt = confirmation_prompt("bucket", &sample, 25);
+
+        assert!(prompt.starts_with("The following 25 object(s) will be deleted:\n"));
+        assert!(prompt.contains("  s3://bucket/logs/0.log\n"));
+        assert!(prompt.contains("  s3://bucket/logs/9.log\n"));
+        assert!(prompt.ends_with("  ... and 15 more\n"));
+
+        let prompt = confirmation_prompt("bucket", &sample[..2], 2);
+        assert!(!prompt.contains("more"));
+    }
+
+    #[test]
+    fn test_is_confirmed() {
+        assert!(is_confirmed("y\n"));
+        assert!(is_confirmed(" YES "));
+        assert!(!is_confirmed("\n"));
+        assert!(!is_confirmed("n"));
+        assert!(!is_confirmed("yep"));
+    }
+
+    #[test]
+    fn test_lock_protection() {
+        let now = SystemTime::now();
+        let later = now + std::time::Duration::from_secs(3600);
+        let earlier = now - std::time::Duration::from_secs(3600);
+
+        assert!(lock_protection(None, None, None, now).is_none());
+        assert_eq!(
+            lock_protection(None, None, Some(&ObjectLockLegalHoldStatus::On), now).as_deref(),
+            Some("legal hold")
+        );
+        assert!(lock_protection(None, None, Some(&ObjectLockLegalHoldStatus::Off), now).is_none());
+
+        let compliance =
+            lock_protection(Some(&ObjectLockMode::Compliance), Some(later), None, now).unwrap();
+        assert!(compliance.starts_with("compliance retention until "));
+        assert!(
+            lock_protection(Some(&ObjectLockMode::Governance), Some(later), None, now)
+                .unwrap()
+                .starts_with("governance retention")
+        );
+
+        // Expired retention no longer protects the object
+        assert!(
+            lock_protection(Some(&ObjectLockMode::Compliance), Some(earlier), None, now).is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_advances_metrics() {
+        use crate::otel::GLOBAL_METRICS;
+        use std::sync::atomic::Ordering;
+
+        // DeleteObject succeeds; the object lock lookup and transparent du find nothing
+        let config = crate::commands::test_client::canned_config(|method, _| match method {
+            "DELETE" => (204, String::new()),
+            _ => (404, "<Error><Code>NoSuchKey</Code></Error>".to_string()),
+        });
+        let deletes = GLOBAL_METRICS.deletes_total.load(Ordering::Relaxed);
+        let files = GLOBAL_METRICS.files_deleted_total.load(Ordering::Relaxed);
+
+        execute(
+            &config,
+            "s3://bucket/file.txt",
+            false,
+            false,
+            false,
+            None,
+            None,
+            false,
+            true,
+            true,
+            None,
+            &mut Vec::new(),
+        )
+        .await
+        .unwrap();
+
+        assert!(GLOBAL_METRICS.deletes_total.load(Ordering::Relaxed) > deletes);
+        assert!(GLOBAL_METRICS.files_deleted_total.load(Ordering::Relaxed) > files);
+    }
+
+    #[tokio::test]
+    async fn test_delete_missing_key_on_locked_bucket() {
+        // Object lock is on, the key is already gone, and DeleteObject
+        // succeeds as it does for any missing key
+        let config = crate::commands::test_client::canned_config(|method, uri| match method {
+            "GET" if uri.contains("object-lock") => (
+                200,
+                "<ObjectLockConfiguration><ObjectLockEnabled>Enabled</ObjectLockEnabled>\
+                 </ObjectLockConfiguration>"
+                    .to_string(),
+            ),
+            "DELETE" => (204, String::new()),
+            _ => (404, String::new()),
+        });
+
+        execute(
+            &config,
+            "s3://bucket/gone.txt",
+            false,
+            false,
+            false,
+            None,
+            None,
+            false,
+            true,
+            true,
+            None,
+            &mut Vec::new(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_skipped_locked_object_is_not_reported_deleted() {
+        // Object lock is on and the object is under a legal hold
+        let (config, _) = crate::commands::test_client::header_config(|method, uri| match method {
+            "GET" if uri.contains("object-lock") => (
+                200,
+                Vec::new(),
+                "<ObjectLockConfiguration><ObjectLockEnabled>Enabled</ObjectLockEnabled>\
+                 </ObjectLockConfiguration>"
+                    .to_string(),
+            ),
+            "HEAD" => (
+                200,
+                vec![("x-amz-object-lock-legal-hold", "ON".to_string())],
+                String::new(),
+            ),
+            "DELETE" => panic!("deleted a locked object: {uri}"),
+            _ => (404, Vec::new(), String::new()),
+        });
+        let mut out = Vec::new();
+
+        execute(
+            &config,
+            "s3://bucket/held.txt",
+            false,
+            false,
+            false,
+            None,
+            None,
+            true,
+            true,
+            false,
+            None,
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert!(out.is_empty(), "{}", String::from_utf8_lossy(&out));
+    }
 }
@@ -2,7 +2,7 @@ use anyhow::{anyhow, Result};
 use std::fmt;
 
 /// Represents a parsed S3 URI
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct S3Uri {
     pub bucket: String,
     pub key: Option<String>,
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use log::debug;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::fs;
+
+use crate::commands::s3_uri::is_s3_uri;
+
+/// Environment variable equivalent of `--temp-dir`
+pub const TMPDIR_ENV: &str = "OBSCTL_TMPDIR";
+
+/// Resolve the staging directory from `--temp-dir`, falling back to
+/// `OBSCTL_TMPDIR`, and check up front that it is a writable directory.
+/// `None` means temp files sit beside their targets.
+pub fn resolve_temp_dir(flag: Option<&str>) -> Result<Option<PathBuf>> {
+    let dir = match flag {
+        Some(dir) => dir.to_string(),
+        None => match std::env::var(TMPDIR_ENV) {
+            Ok(dir) if !dir.is_empty() => dir,
+            _ => return Ok(None),
+        },
+    };
+
+    let path = PathBuf::from(&dir);
+    if !path.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Temp directory {dir} does not exist or is not a directory"
+        ));
+    }
+
+    let probe = path.join(format!(".obsctl-write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"")
+        .map_err(|e| anyhow::anyhow!("Temp directory {dir} is not writable: {e}"))?;
+    let _ = std::fs::remove_file(&probe);
+
+    Ok(Some(path))
+}
+
+/// `resolve_temp_dir` for a transfer from `source` to `dest`. Only downloads
+/// stage files, so uploads and S3-to-S3 copies skip the lookup and a bad
+/// `OBSCTL_TMPDIR` can't fail them.
+pub fn resolve_download_temp_dir(
+    flag: Option<&str>,
+    source: &str,
+    dest: &str,
+) -> Result<Option<PathBuf>> {
+    if is_s3_uri(source) && !is_s3_uri(dest) {
+        resolve_temp_dir(flag)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Per-process sequence that keeps concurrent staged names apart
+static STAGING_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Where to stage a download before moving it onto `target`. Every call
+/// yields a fresh name, so concurrent downloads of same-named keys sharing
+/// one temp dir never write to the same file.
+pub fn staging_path(target: &Path, temp_dir: Option<&Path>) -> PathBuf {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "download".to_string());
+    let seq = STAGING_SEQ.fetch_add(1, Ordering::Relaxed);
+    let staged_name = format!(".{file_name}.obsctl-{}-{seq}.tmp", std::process::id());
+
+    match temp_dir {
+        Some(dir) => dir.join(staged_name),
+        None => target
+            .parent()
+            .map(|parent| parent.join(&staged_name))
+            .unwrap_or_else(|| PathBuf::from(&staged_name)),
+    }
+}
+
+/// Whether two existing paths live on the same filesystem, when that can be told
+pub fn same_filesystem(a: &Path, b: &Path) -> Option<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let a = std::fs::metadata(a).ok()?;
+        let b = std::fs::metadata(b).ok()?;
+        Some(a.dev() == b.dev())
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (a, b);
+        None
+    }
+}
+
+/// Move a fully written staged file onto its target. The rename is atomic on
+/// the same filesystem; across filesystems it degrades to copy and remove.
+pub async fn commit(staged: &Path, target: &Path) -> Result<()> {
+    if fs::rename(staged, target).await.is_ok() {
+        return Ok(());
+    }
+
+    debug!(
+        "Rename of {} failed, copying into place instead",
+        staged.display()
+    );
+    let copied = fs::copy(staged, target).await;
+    let _ = fs::remove_file(staged).await;
+    copied
+        .map_err(|e| anyhow::anyhow!("Failed to move download into {}: {e}", target.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_staging_path_placement() {
+        let target = Path::new("/data/out/report.csv");
+
+        let beside = staging_path(target, None);
+        assert_eq!(beside.parent(), Some(Path::new("/data/out")));
+        let name = beside.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with(".report.csv.obsctl-") && name.ends_with(".tmp"));
+
+        let redirected = staging_path(target, Some(Path::new("/scratch")));
+        assert_eq!(redirected.parent(), Some(Path::new("/scratch")));
+    }
+
+    #[tokio::test]
+    async fn test_same_basename_targets_stage_separately() {
+        let scratch = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let first = out.path().join("a/x.csv");
+        let second = out.path().join("b/x.csv");
+        std::fs::create_dir_all(first.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(second.parent().unwrap()).unwrap();
+
+        let staged_first = staging_path(&first, Some(scratch.path()));
+        let staged_second = staging_path(&second, Some(scratch.path()));
+        assert_ne!(staged_first, staged_second);
+
+        std::fs::write(&staged_first, b"first").unwrap();
+        std::fs::write(&staged_second, b"second").unwrap();
+        commit(&staged_first, &first).await.unwrap();
+        commit(&staged_second, &second).await.unwrap();
+
+        assert_eq!(std::fs::read(&first).unwrap(), b"first");
+        assert_eq!(std::fs::read(&second).unwrap(), b"second");
+    }
+
+    #[test]
+    fn test_resolve_temp_dir_validates() {
+        let dir = tempfile::tempdir().unwrap();
+        let resolved = resolve_temp_dir(dir.path().to_str()).unwrap();
+        assert_eq!(resolved.as_deref(), Some(dir.path()));
+
+        let missing = dir.path().join("missing");
+        assert!(resolve_temp_dir(missing.to_str()).is_err());
+    }
+
+    #[test]
+    fn test_temp_dir_only_resolved_for_downloads() {
+        let missing = tempfile::tempdir().unwrap().path().join("missing");
+        let missing = missing.to_str();
+
+        assert!(resolve_download_temp_dir(missing, "s3://b/k", "./k").is_err());
+        assert!(resolve_download_temp_dir(missing, "./k", "s3://b/k")
+            .unwrap()
+            .is_none());
+        assert!(resolve_download_temp_dir(missing, "s3://a/k", "s3://b/k")
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_commit_moves_staged_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("final.txt");
+        let staged = staging_path(&target, None);
+        std::fs::write(&staged, b"payload").unwrap();
+
+        commit(&staged, &target).await.unwrap();
+
+        assert_eq!(std::fs::read(&target).unwrap(), b"payload");
+        assert!(!staged.exists());
+    }
+}
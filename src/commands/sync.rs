@@ -1,15 +1,101 @@
 use anyhow::Result;
-use log::info;
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use futures::stream::{self, Stream, StreamExt};
+use indicatif::HumanBytes;
+use log::{debug, info, warn};
 use std::collections::HashMap;
-use std::path::Path;
-use std::time::Instant;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 use tokio::fs;
 use walkdir::WalkDir;
 
 use crate::commands::cp;
 use crate::commands::du;
+use crate::commands::manifest::{CompletionManifest, ManifestVerifier};
 use crate::commands::s3_uri::{is_s3_uri, S3Uri};
 use crate::config::Config;
+use crate::progress::{ProgressDisplay, TransferProgress};
+
+/// Maximum concurrent HeadObject requests for `--compare-metadata`
+const METADATA_CHECK_CONCURRENCY: usize = 16;
+
+/// Maximum files hashed at once for `--checksum`
+const CHECKSUM_CONCURRENCY: usize = 8;
+
+/// User metadata key (`x-amz-meta-checksum`) holding a hex MD5 of the content,
+/// written by multipart uploads and consulted when the ETag isn't one
+pub const CHECKSUM_METADATA_KEY: &str = "checksum";
+
+/// Optional sync behaviour beyond the aws-cli compatible flags
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// Abort before deleting when more than this many deletions are planned
+    pub max_delete: Option<usize>,
+    /// Abort before deleting when more than this percentage of the destination would go
+    pub max_delete_percent: Option<f64>,
+    /// Re-list both sides after the sync and fail on any missing or mismatched files
+    pub verify: bool,
+    /// Progress display for the transfer phase
+    pub progress: ProgressDisplay,
+    /// Directory for staging downloads (`--temp-dir`)
+    pub temp_dir: Option<PathBuf>,
+    /// Check each transferred local file against a manifest (`--checksum-manifest`)
+    pub checksum_manifest: Option<Arc<ManifestVerifier>>,
+    /// Completion marker written after a successful upload sync (`--write-manifest`)
+    pub write_manifest: Option<Arc<CompletionManifest>>,
+    /// Content-Type set on uploaded objects
+    pub content_type: Option<String>,
+    /// User metadata (`x-amz-meta-*`) set on uploaded objects
+    pub metadata: HashMap<String, String>,
+    /// HeadObject unchanged objects and fix drifted Content-Type or user metadata
+    pub compare_metadata: bool,
+    /// Maximum number of files transferred at once (`--max-concurrent`)
+    pub max_concurrent: usize,
+    /// Hash same-size files and transfer those whose content differs (`--checksum`)
+    pub checksum: bool,
+    /// Print a summary line instead of one line per file (`--quiet`)
+    pub quiet: bool,
+}
+
+/// What a `--dryrun` sync would do, printed as a block after the per-file lines
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DryRunSummary {
+    transfers: usize,
+    transfer_bytes: u64,
+    deletes: usize,
+    delete_bytes: u64,
+    metadata_updates: usize,
+    unchanged: usize,
+}
+
+impl DryRunSummary {
+    /// `action` is the transfer verb: "upload" or "download"
+    fn report(&self, action: &str) -> String {
+        let mut report = format!(
+            "Dry run summary:\n  {:<10} {} file(s), {}\n  {:<10} {} file(s), {}\n",
+            format!("{action}:"),
+            self.transfers,
+            HumanBytes(self.transfer_bytes),
+            "delete:",
+            self.deletes,
+            HumanBytes(self.delete_bytes),
+        );
+        if self.metadata_updates > 0 {
+            report.push_str(&format!(
+                "  {:<10} {} update(s)\n",
+                "metadata:", self.metadata_updates
+            ));
+        }
+        report.push_str(&format!(
+            "  {:<10} {} file(s)",
+            "unchanged:", self.unchanged
+        ));
+        report
+    }
+}
 
 #[allow(clippy::too_many_arguments)]
 pub async fn execute(
@@ -22,9 +108,16 @@ pub async fn execute(
     include: Option<&str>,
     size_only: bool,
     exact_timestamps: bool,
+    options: &SyncOptions,
 ) -> Result<()> {
     info!("Syncing from {source} to {dest}");
 
+    if source == cp::STDIN_SOURCE {
+        return Err(anyhow::anyhow!(
+            "sync cannot read from stdin; use 'obsctl cp - s3://bucket/key' to upload a stream"
+        ));
+    }
+
     if dryrun {
         info!("[DRY RUN] Would sync from {source} to {dest}");
     }
@@ -45,6 +138,7 @@ pub async fn execute(
                 include,
                 size_only,
                 exact_timestamps,
+                options,
             )
             .await
         }
@@ -60,6 +154,7 @@ pub async fn execute(
                 include,
                 size_only,
                 exact_timestamps,
+                options,
             )
             .await
         }
@@ -75,6 +170,7 @@ pub async fn execute(
                 include,
                 size_only,
                 exact_timestamps,
+                options,
             )
             .await
         }
@@ -97,22 +193,63 @@ async fn sync_local_to_s3(
     _exclude: Option<&str>,
     _include: Option<&str>,
     size_only: bool,
-    _exact_timestamps: bool,
+    exact_timestamps: bool,
+    options: &SyncOptions,
 ) -> Result<()> {
     let start_time = Instant::now();
     let dest_uri = S3Uri::parse(dest)?;
 
+    // An upload always leaves the object newer than its file, so as in aws s3
+    // sync --exact-timestamps only applies to downloads
+    if exact_timestamps && !size_only {
+        warn!("--exact-timestamps only applies when syncing from S3 to local");
+    }
+    let comparison = Comparison::from_flags(size_only, false);
+
     // Build map of local files
     let local_files = scan_local_directory(source)?;
 
     // Build map of S3 objects
     let s3_objects = scan_s3_objects(config, &dest_uri).await?;
 
+    // Work out deletions before transferring anything so the safety threshold
+    // can abort the whole sync untouched
+    let mut keys_to_delete: Vec<String> = Vec::new();
+    if delete {
+        for s3_key in s3_objects.keys() {
+            // Calculate what the local relative path would be
+            let local_relative_path = if dest_uri.key_or_empty().is_empty() {
+                s3_key.clone()
+            } else {
+                s3_key
+                    .strip_prefix(&format!(
+                        "{}/",
+                        dest_uri.key_or_empty().trim_end_matches('/')
+                    ))
+                    .unwrap_or(s3_key)
+                    .to_string()
+            };
+
+            if !local_files.contains_key(&local_relative_path) {
+                keys_to_delete.push(s3_key.clone());
+            }
+        }
+        keys_to_delete.sort();
+
+        let planned: Vec<String> = keys_to_delete
+            .iter()
+            .map(|key| format!("s3://{}/{}", dest_uri.bucket, key))
+            .collect();
+        check_delete_threshold(&planned, s3_objects.len(), options)?;
+    }
+
     let mut upload_count = 0;
     let mut delete_count = 0;
     let mut total_upload_bytes = 0u64;
 
-    // Compare and upload files that are new or different
+    // Compare and collect files that are new or different
+    let mut uploads: Vec<(String, String, u64)> = Vec::new();
+    let mut unchanged: Vec<(String, String, u64)> = Vec::new();
     for (relative_path, local_file) in &local_files {
         let s3_key = if dest_uri.key_or_empty().is_empty() {
             relative_path.clone()
@@ -124,82 +261,209 @@ async fn sync_local_to_s3(
             )
         };
 
-        let should_upload = match s3_objects.get(&s3_key) {
-            Some(s3_object) => {
-                // File exists in S3, check if we need to update
-                if size_only {
-                    local_file.size != s3_object.size
-                } else {
-                    // For now, just compare sizes (timestamp comparison would require more complex logic)
-                    local_file.size != s3_object.size
-                }
-            }
-            None => {
-                // File doesn't exist in S3, need to upload
-                true
+        let local_path = format!("{}/{}", source.trim_end_matches('/'), relative_path);
+        if needs_transfer(local_file, s3_objects.get(&s3_key), comparison) {
+            let s3_dest = format!("s3://{}/{}", dest_uri.bucket, s3_key);
+            uploads.push((local_path, s3_dest, local_file.size as u64));
+        } else {
+            unchanged.push((local_path, s3_key, local_file.size as u64));
+        }
+    }
+
+    if options.checksum {
+        let (changed, same) =
+            split_checksum_mismatches(config, &dest_uri.bucket, unchanged, &s3_objects).await?;
+        for (local_path, s3_key, size) in changed {
+            uploads.push((
+                local_path,
+                format!("s3://{}/{}", dest_uri.bucket, s3_key),
+                size,
+            ));
+        }
+        unchanged = same;
+    }
+
+    // Same-size objects can still carry stale metadata: small ones are fixed
+    // with a server-side copy, the rest are uploaded again
+    let upload_options = cp::TransferOptions {
+        content_type: options.content_type.clone(),
+        metadata: options.metadata.clone(),
+        ..Default::default()
+    };
+    let mut metadata_updates: Vec<(String, String, HeadObjectOutput)> = Vec::new();
+    if options.compare_metadata {
+        let drifted =
+            find_metadata_drift(config, &dest_uri.bucket, &unchanged, &upload_options).await?;
+        for (index, head) in drifted {
+            let (local_path, s3_key, size) = &unchanged[index];
+            // CopyObject can't rewrite objects this large in place; re-upload them
+            if *size > cp::MAX_COPY_OBJECT_SIZE {
+                let s3_dest = format!("s3://{}/{}", dest_uri.bucket, s3_key);
+                uploads.push((local_path.clone(), s3_dest, *size));
+            } else {
+                metadata_updates.push((s3_key.clone(), local_path.clone(), head));
             }
-        };
+        }
+        metadata_updates.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+    uploads.sort();
 
-        if should_upload {
-            let local_path = format!("{}/{}", source.trim_end_matches('/'), relative_path);
-            let s3_dest = format!("s3://{}/{}", dest_uri.bucket, s3_key);
+    let upload_bytes: u64 = uploads.iter().map(|(_, _, size)| size).sum();
+    let mut tracker = if dryrun {
+        TransferProgress::new(ProgressDisplay::None, 0, 0)
+    } else {
+        TransferProgress::new(options.progress, uploads.len() as u64, upload_bytes)
+    };
 
-            if dryrun {
+    if dryrun {
+        for (local_path, s3_dest, size) in &uploads {
+            if !options.quiet {
                 println!("(dryrun) upload: {local_path} to {s3_dest}");
-            } else {
-                println!("upload: {local_path} to {s3_dest}");
-                cp::execute(
-                    config,
-                    &local_path,
-                    &s3_dest,
-                    false,
-                    false,
-                    1,
-                    false,
-                    None,
-                    None,
-                )
-                .await?;
             }
             upload_count += 1;
-            total_upload_bytes += local_file.size as u64;
+            total_upload_bytes += size;
+        }
+    } else {
+        let transfer_options = cp::TransferOptions {
+            file_bars: tracker.file_bars(),
+            ..upload_options.clone()
+        };
+        let source_prefix = format!("{}/", source.trim_end_matches('/'));
+        let bucket_prefix = format!("s3://{}/", dest_uri.bucket);
+
+        let mut transfers = pin!(bounded_transfers(
+            &uploads,
+            options.max_concurrent,
+            |(local_path, s3_dest, size)| {
+                let transfer_options = &transfer_options;
+                let source_prefix = &source_prefix;
+                let bucket_prefix = &bucket_prefix;
+                async move {
+                    cp::execute(
+                        config,
+                        local_path,
+                        s3_dest,
+                        false,
+                        false,
+                        1,
+                        false,
+                        None,
+                        None,
+                        transfer_options,
+                    )
+                    .await?;
+                    if let Some(verifier) = &options.checksum_manifest {
+                        let relative = local_path
+                            .strip_prefix(source_prefix.as_str())
+                            .unwrap_or(local_path);
+                        verifier
+                            .verify_file(relative, Path::new(local_path))
+                            .await?;
+                    }
+                    if let Some(manifest) = &options.write_manifest {
+                        let key = s3_dest
+                            .strip_prefix(bucket_prefix.as_str())
+                            .unwrap_or(s3_dest);
+                        manifest.record(key, Path::new(local_path), *size).await?;
+                    }
+                    Ok(())
+                }
+            },
+        ));
+
+        while let Some(((local_path, s3_dest, size), result)) = transfers.next().await {
+            if let Err(e) = result {
+                tracker.eprintln(&format!("upload failed: {local_path}: {e}"));
+                return Err(e);
+            }
+            if !options.quiet {
+                tracker.println(&format!("upload: {local_path} to {s3_dest}"));
+            }
+            tracker.file_done(*size);
+            upload_count += 1;
+            total_upload_bytes += size;
         }
     }
+    tracker.finish();
+    if let (false, Some(verifier)) = (dryrun, &options.checksum_manifest) {
+        verifier.finish()?;
+    }
 
-    // Delete files from S3 that don't exist locally (if --delete flag is set)
-    if delete {
-        for s3_key in s3_objects.keys() {
-            // Calculate what the local relative path would be
-            let local_relative_path = if dest_uri.key_or_empty().is_empty() {
-                s3_key.clone()
-            } else {
-                s3_key
-                    .strip_prefix(&format!(
-                        "{}/",
-                        dest_uri.key_or_empty().trim_end_matches('/')
-                    ))
-                    .unwrap_or(s3_key)
-                    .to_string()
-            };
+    let dry_run_summary = DryRunSummary {
+        transfers: uploads.len(),
+        transfer_bytes: upload_bytes,
+        deletes: keys_to_delete.len(),
+        delete_bytes: keys_to_delete
+            .iter()
+            .filter_map(|key| s3_objects.get(key))
+            .map(|object| object.size as u64)
+            .sum(),
+        metadata_updates: metadata_updates.len(),
+        unchanged: local_files.len() - uploads.len() - metadata_updates.len(),
+    };
 
-            if !local_files.contains_key(&local_relative_path) {
-                let s3_path = format!("s3://{}/{}", dest_uri.bucket, s3_key);
+    for (s3_key, local_path, head) in &metadata_updates {
+        let s3_path = format!("s3://{}/{}", dest_uri.bucket, s3_key);
 
-                if dryrun {
-                    println!("(dryrun) delete: {s3_path}");
+        if dryrun {
+            if !options.quiet {
+                println!("(dryrun) update metadata: {s3_path}");
+            }
+        } else {
+            if !options.quiet {
+                println!("update metadata: {s3_path}");
+            }
+            // REPLACE drops every header not sent with the copy, so the ones
+            // sync doesn't manage, and the storage class, come from the HEAD
+            config
+                .client
+                .copy_object()
+                .copy_source(cp::copy_source(&dest_uri.bucket, s3_key))
+                .bucket(&dest_uri.bucket)
+                .key(s3_key)
+                .metadata_directive(aws_sdk_s3::types::MetadataDirective::Replace)
+                .set_content_type(
+                    upload_options
+                        .content_type_for(local_path)
+                        .or_else(|| head.content_type().map(str::to_string)),
+                )
+                .set_content_encoding(head.content_encoding().map(str::to_string))
+                .set_content_disposition(head.content_disposition().map(str::to_string))
+                .set_content_language(head.content_language().map(str::to_string))
+                .set_cache_control(head.cache_control().map(str::to_string))
+                .set_metadata(if options.metadata.is_empty() {
+                    head.metadata().cloned()
                 } else {
-                    println!("delete: {s3_path}");
-                    config
-                        .client
-                        .delete_object()
-                        .bucket(&dest_uri.bucket)
-                        .key(s3_key)
-                        .send()
-                        .await?;
-                }
-                delete_count += 1;
+                    Some(keep_stored_checksum(options.metadata.clone(), head))
+                })
+                .set_storage_class(head.storage_class().cloned())
+                .send()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to update metadata on {s3_path}: {e}"))?;
+        }
+    }
+
+    // Delete files from S3 that don't exist locally (if --delete flag is set)
+    for s3_key in &keys_to_delete {
+        let s3_path = format!("s3://{}/{}", dest_uri.bucket, s3_key);
+
+        if dryrun {
+            if !options.quiet {
+                println!("(dryrun) delete: {s3_path}");
             }
+        } else {
+            if !options.quiet {
+                println!("delete: {s3_path}");
+            }
+            config
+                .client
+                .delete_object()
+                .bucket(&dest_uri.bucket)
+                .key(s3_key)
+                .send()
+                .await?;
         }
+        delete_count += 1;
     }
 
     let duration = start_time.elapsed();
@@ -235,6 +499,46 @@ async fn sync_local_to_s3(
     }
 
     info!("Sync completed: {upload_count} uploads, {delete_count} deletes");
+    if options.quiet {
+        println!(
+            "{}",
+            quiet_summary(
+                dryrun,
+                "upload",
+                upload_count,
+                metadata_updates.len(),
+                delete_count
+            )
+        );
+    } else if dryrun {
+        println!("{}", dry_run_summary.report("upload"));
+    }
+
+    if options.verify && !dryrun {
+        let local_files = scan_local_directory(source)?;
+        let objects = scan_s3_objects(config, &dest_uri).await?;
+        let s3_objects = relative_to_prefix(objects.clone(), dest_uri.key_or_empty());
+        let mut discrepancies = find_discrepancies(&local_files, &s3_objects, comparison);
+        if options.checksum {
+            let candidates = same_size_pairs(&local_files, &s3_objects)
+                .map(|(relative_path, size)| {
+                    (
+                        format!("{}/{}", source.trim_end_matches('/'), relative_path),
+                        key_under_prefix(dest_uri.key_or_empty(), relative_path),
+                        size,
+                    )
+                })
+                .collect();
+            discrepancies.extend(
+                checksum_discrepancies(config, &dest_uri.bucket, candidates, &objects).await?,
+            );
+        }
+        report_verification(&discrepancies)?;
+    }
+
+    if let (false, Some(manifest)) = (dryrun, &options.write_manifest) {
+        manifest.upload(config).await?;
+    }
 
     // Transparent du call for real-time bucket analytics
     if !dryrun && upload_count > 0 {
@@ -255,10 +559,12 @@ async fn sync_s3_to_local(
     _exclude: Option<&str>,
     _include: Option<&str>,
     size_only: bool,
-    _exact_timestamps: bool,
+    exact_timestamps: bool,
+    options: &SyncOptions,
 ) -> Result<()> {
     let start_time = Instant::now();
     let source_uri = S3Uri::parse(source)?;
+    let comparison = Comparison::from_flags(size_only, exact_timestamps);
 
     // Build map of S3 objects
     let s3_objects = scan_s3_objects(config, &source_uri).await?;
@@ -270,11 +576,39 @@ async fn sync_s3_to_local(
         HashMap::new()
     };
 
+    // Work out deletions before transferring anything so the safety threshold
+    // can abort the whole sync untouched
+    let mut paths_to_delete: Vec<String> = Vec::new();
+    let mut delete_bytes = 0u64;
+    if delete {
+        for local_relative_path in local_files.keys() {
+            let s3_key = if source_uri.key_or_empty().is_empty() {
+                local_relative_path.clone()
+            } else {
+                format!(
+                    "{}/{}",
+                    source_uri.key_or_empty().trim_end_matches('/'),
+                    local_relative_path
+                )
+            };
+
+            if !s3_objects.contains_key(&s3_key) {
+                paths_to_delete.push(format!("{dest}/{local_relative_path}"));
+                delete_bytes += local_files[local_relative_path].size as u64;
+            }
+        }
+        paths_to_delete.sort();
+
+        check_delete_threshold(&paths_to_delete, local_files.len(), options)?;
+    }
+
     let mut download_count = 0;
     let mut delete_count = 0;
     let mut total_download_bytes = 0u64;
 
-    // Compare and download files that are new or different
+    // Compare and collect files that are new or different
+    let mut downloads: Vec<(String, String, u64)> = Vec::new();
+    let mut unchanged: Vec<(String, String, u64)> = Vec::new();
     for (s3_key, s3_object) in &s3_objects {
         let local_relative_path = if source_uri.key_or_empty().is_empty() {
             s3_key.clone()
@@ -288,73 +622,123 @@ async fn sync_s3_to_local(
                 .to_string()
         };
 
-        let should_download = match local_files.get(&local_relative_path) {
-            Some(local_file) => {
-                // File exists locally, check if we need to update
-                if size_only {
-                    local_file.size != s3_object.size
-                } else {
-                    // For now, just compare sizes
-                    local_file.size != s3_object.size
-                }
-            }
-            None => {
-                // File doesn't exist locally, need to download
-                true
-            }
-        };
-
-        if should_download {
+        let local_dest = format!("{}/{}", dest.trim_end_matches('/'), local_relative_path);
+        if needs_transfer(s3_object, local_files.get(&local_relative_path), comparison) {
             let s3_source = format!("s3://{}/{}", source_uri.bucket, s3_key);
-            let local_dest = format!("{}/{}", dest.trim_end_matches('/'), local_relative_path);
+            downloads.push((s3_source, local_dest, s3_object.size as u64));
+        } else if options.checksum {
+            unchanged.push((local_dest, s3_key.clone(), s3_object.size as u64));
+        }
+    }
+
+    if options.checksum {
+        let (changed, _) =
+            split_checksum_mismatches(config, &source_uri.bucket, unchanged, &s3_objects).await?;
+        for (local_dest, s3_key, size) in changed {
+            downloads.push((
+                format!("s3://{}/{}", source_uri.bucket, s3_key),
+                local_dest,
+                size,
+            ));
+        }
+    }
+    downloads.sort();
+
+    let download_bytes: u64 = downloads.iter().map(|(_, _, size)| size).sum();
+    let mut tracker = if dryrun {
+        TransferProgress::new(ProgressDisplay::None, 0, 0)
+    } else {
+        TransferProgress::new(options.progress, downloads.len() as u64, download_bytes)
+    };
 
-            if dryrun {
+    if dryrun {
+        for (s3_source, local_dest, size) in &downloads {
+            if !options.quiet {
                 println!("(dryrun) download: {s3_source} to {local_dest}");
-            } else {
-                println!("download: {s3_source} to {local_dest}");
-                cp::execute(
-                    config,
-                    &s3_source,
-                    &local_dest,
-                    false,
-                    false,
-                    1,
-                    false,
-                    None,
-                    None,
-                )
-                .await?;
             }
             download_count += 1;
-            total_download_bytes += s3_object.size as u64;
+            total_download_bytes += size;
         }
+    } else {
+        let transfer_options = cp::TransferOptions {
+            temp_dir: options.temp_dir.clone(),
+            file_bars: tracker.file_bars(),
+            ..Default::default()
+        };
+        let dest_prefix = format!("{}/", dest.trim_end_matches('/'));
+        let bucket_prefix = format!("s3://{}/", source_uri.bucket);
+
+        let mut transfers = pin!(bounded_transfers(
+            &downloads,
+            options.max_concurrent,
+            |(s3_source, local_dest, _)| {
+                let transfer_options = &transfer_options;
+                let dest_prefix = &dest_prefix;
+                let bucket_prefix = &bucket_prefix;
+                let s3_objects = &s3_objects;
+                async move {
+                    cp::execute(
+                        config,
+                        s3_source,
+                        local_dest,
+                        false,
+                        false,
+                        1,
+                        false,
+                        None,
+                        None,
+                        transfer_options,
+                    )
+                    .await?;
+                    let modified = s3_source
+                        .strip_prefix(bucket_prefix.as_str())
+                        .and_then(|key| s3_objects.get(key))
+                        .and_then(|object| object.modified);
+                    preserve_modified_time(Path::new(local_dest), modified)?;
+                    if let Some(verifier) = &options.checksum_manifest {
+                        let relative = local_dest
+                            .strip_prefix(dest_prefix.as_str())
+                            .unwrap_or(local_dest);
+                        verifier
+                            .verify_file(relative, Path::new(local_dest))
+                            .await?;
+                    }
+                    Ok(())
+                }
+            },
+        ));
+
+        while let Some(((s3_source, local_dest, size), result)) = transfers.next().await {
+            if let Err(e) = result {
+                tracker.eprintln(&format!("download failed: {s3_source}: {e}"));
+                return Err(e);
+            }
+            if !options.quiet {
+                tracker.println(&format!("download: {s3_source} to {local_dest}"));
+            }
+            tracker.file_done(*size);
+            download_count += 1;
+            total_download_bytes += size;
+        }
+    }
+    tracker.finish();
+    if let (false, Some(verifier)) = (dryrun, &options.checksum_manifest) {
+        verifier.finish()?;
     }
 
     // Delete local files that don't exist in S3 (if --delete flag is set)
-    if delete {
-        for local_relative_path in local_files.keys() {
-            let s3_key = if source_uri.key_or_empty().is_empty() {
-                local_relative_path.clone()
-            } else {
-                format!(
-                    "{}/{}",
-                    source_uri.key_or_empty().trim_end_matches('/'),
-                    local_relative_path
-                )
-            };
-
-            if !s3_objects.contains_key(&s3_key) {
-                let local_path = format!("{dest}/{local_relative_path}");
-
-                if dryrun {
-                    println!("(dryrun) delete: {local_path}");
-                } else {
-                    println!("delete: {local_path}");
-                    fs::remove_file(&local_path).await?;
-                }
-                delete_count += 1;
+    for local_path in &paths_to_delete {
+        if dryrun {
+            if !options.quiet {
+                println!("(dryrun) delete: {local_path}");
+            }
+        } else {
+            if !options.quiet {
+                println!("delete: {local_path}");
             }
+            fs::remove_file(local_path).await?;
         }
+        delete_count += 1;
     }
 
     let duration = start_time.elapsed();
@@ -392,6 +776,44 @@ async fn sync_s3_to_local(
     }
 
     info!("Sync completed: {download_count} downloads, {delete_count} deletes");
+    if options.quiet {
+        println!(
+            "{}",
+            quiet_summary(dryrun, "download", download_count, 0, delete_count)
+        );
+    } else if dryrun {
+        let summary = DryRunSummary {
+            transfers: downloads.len(),
+            transfer_bytes: downloads.iter().map(|(_, _, size)| size).sum(),
+            deletes: paths_to_delete.len(),
+            delete_bytes,
+            metadata_updates: 0,
+            unchanged: s3_objects.len() - downloads.len(),
+        };
+        println!("{}", summary.report("download"));
+    }
+
+    if options.verify && !dryrun {
+        let objects = scan_s3_objects(config, &source_uri).await?;
+        let s3_objects = relative_to_prefix(objects.clone(), source_uri.key_or_empty());
+        let local_files = scan_local_directory(dest)?;
+        let mut discrepancies = find_discrepancies(&s3_objects, &local_files, comparison);
+        if options.checksum {
+            let candidates = same_size_pairs(&s3_objects, &local_files)
+                .map(|(relative_path, size)| {
+                    (
+                        format!("{}/{}", dest.trim_end_matches('/'), relative_path),
+                        key_under_prefix(source_uri.key_or_empty(), relative_path),
+                        size,
+                    )
+                })
+                .collect();
+            discrepancies.extend(
+                checksum_discrepancies(config, &source_uri.bucket, candidates, &objects).await?,
+            );
+        }
+        report_verification(&discrepancies)?;
+    }
 
     // Transparent du call for real-time bucket analytics
     if !dryrun && download_count > 0 {
@@ -402,6 +824,452 @@ async fn sync_s3_to_local(
     Ok(())
 }
 
+/// Run `transfer` over every item with at most `limit` in flight, yielding
+/// each item with its result as it completes. Dropping the stream early
+/// cancels whatever is still running.
+fn bounded_transfers<'a, T, F, Fut>(
+    items: &'a [T],
+    limit: usize,
+    transfer: F,
+) -> impl Stream<Item = (&'a T, Result<()>)> + 'a
+where
+    F: Fn(&'a T) -> Fut + 'a,
+    Fut: Future<Output = Result<()>> + 'a,
+{
+    stream::iter(items)
+        .map(move |item| {
+            let transfer = transfer(item);
+            async move { (item, transfer.await) }
+        })
+        .buffer_unordered(limit.max(1))
+}
+
+/// The one line `--quiet` prints in place of the per-file output; under
+/// `--dryrun` it counts what would have happened
+fn quiet_summary(
+    dryrun: bool,
+    direction: &str,
+    transferred: u64,
+    metadata_updates: usize,
+    deleted: u64,
+) -> String {
+    let mut summary = if dryrun {
+        format!("(dryrun) sync: {transferred} file(s) to {direction}")
+    } else {
+        format!("sync: {transferred} file(s) {direction}ed")
+    };
+    if metadata_updates > 0 {
+        summary.push_str(&format!(", {metadata_updates} metadata update(s)"));
+    }
+    if dryrun {
+        summary.push_str(&format!(", {deleted} to delete"));
+    } else {
+        summary.push_str(&format!(", {deleted} deleted"));
+    }
+    summary
+}
+
+/// Refuse to continue when the planned deletions exceed `--max-delete` or
+/// `--max-delete-percent`, listing what would have been deleted
+fn check_delete_threshold(
+    planned: &[String],
+    dest_total: usize,
+    options: &SyncOptions,
+) -> Result<()> {
+    let count = planned.len();
+    let percent = if dest_total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / dest_total as f64
+    };
+
+    let exceeded = match (options.max_delete, options.max_delete_percent) {
+        (Some(max), _) if count > max => Some(format!("--max-delete {max}")),
+        (_, Some(max_percent)) if percent > max_percent => {
+            Some(format!("--max-delete-percent {max_percent}"))
+        }
+        _ => None,
+    };
+
+    if let Some(limit) = exceeded {
+        eprintln!("Sync aborted: {count} deletions planned ({percent:.1}% of {dest_total} destination files) exceeds {limit}");
+        eprintln!("Would have deleted:");
+        for path in planned {
+            eprintln!("  {path}");
+        }
+        return Err(anyhow::anyhow!(
+            "Refusing to delete {} of {} destination files: exceeds {}. Check the source path, or raise the limit to proceed",
+            count,
+            dest_total,
+            limit
+        ));
+    }
+
+    Ok(())
+}
+
+/// Content-Type and user metadata an upload sets, for `--compare-metadata`
+#[derive(Debug, Clone, Default)]
+struct UploadMetadata {
+    content_type: Option<String>,
+    metadata: HashMap<String, String>,
+}
+
+impl UploadMetadata {
+    /// What uploading `local_path` with `options` would set, including a
+    /// Content-Type detected from the file extension
+    fn for_upload(options: &cp::TransferOptions, local_path: &str) -> Self {
+        UploadMetadata {
+            content_type: options.content_type_for(local_path),
+            metadata: options.metadata.clone(),
+        }
+    }
+
+    /// Whether an object's current headers differ from what an upload would
+    /// write. Content-Type and user metadata only count when the upload sets
+    /// them; user metadata must then match exactly, with keys compared
+    /// case-insensitively.
+    fn differs_from(
+        &self,
+        content_type: Option<&str>,
+        metadata: Option<&HashMap<String, String>>,
+    ) -> bool {
+        if let Some(expected) = &self.content_type {
+            if content_type != Some(expected.as_str()) {
+                return true;
+            }
+        }
+
+        if self.metadata.is_empty() {
+            return false;
+        }
+        // The stored content MD5 isn't part of what the user asked for
+        let current: HashMap<String, &str> = metadata
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.to_lowercase(), value.as_str()))
+            .filter(|(key, _)| key != CHECKSUM_METADATA_KEY)
+            .collect();
+        let expected: HashMap<String, &str> = self
+            .metadata
+            .iter()
+            .map(|(key, value)| (key.to_lowercase(), value.as_str()))
+            .collect();
+        current != expected
+    }
+}
+
+/// HeadObject each `(local path, key, size)` candidate and return the indexes,
+/// with their HeadObject responses, whose metadata no longer matches what the
+/// upload would set
+async fn find_metadata_drift(
+    config: &Config,
+    bucket: &str,
+    candidates: &[(String, String, u64)],
+    upload_options: &cp::TransferOptions,
+) -> Result<Vec<(usize, HeadObjectOutput)>> {
+    let checks: Vec<Result<Option<(usize, HeadObjectOutput)>>> =
+        stream::iter(candidates.iter().enumerate())
+            .map(|(index, (local_path, key, _))| async move {
+                let desired = UploadMetadata::for_upload(upload_options, local_path);
+                let head = config
+                    .client
+                    .head_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to read metadata of s3://{bucket}/{key}: {e}")
+                    })?;
+                Ok(desired
+                    .differs_from(head.content_type(), head.metadata())
+                    .then_some((index, head)))
+            })
+            .buffer_unordered(METADATA_CHECK_CONCURRENCY)
+            .collect()
+            .await;
+
+    let mut drifted = Vec::new();
+    for check in checks {
+        drifted.extend(check?);
+    }
+    Ok(drifted)
+}
+
+/// A `(local path, key, size)` pair whose contents may be compared
+type Candidate = (String, String, u64);
+
+/// Split same-size `(local path, key, size)` pairs into those whose local MD5
+/// differs from the object's and those that match. Objects with no MD5 to
+/// compare against (multipart ETags without a stored checksum) keep the size
+/// and timestamp decision and count as matching.
+async fn split_checksum_mismatches(
+    config: &Config,
+    bucket: &str,
+    candidates: Vec<Candidate>,
+    objects: &HashMap<String, FileInfo>,
+) -> Result<(Vec<Candidate>, Vec<Candidate>)> {
+    let checks: Vec<Result<(bool, Candidate)>> = stream::iter(candidates)
+        .map(|candidate| async move {
+            let (local_path, key, _) = &candidate;
+            let etag = objects.get(key).and_then(|object| object.etag.as_deref());
+            let Some(remote) = remote_md5(config, bucket, key, etag).await? else {
+                debug!(
+                    "s3://{bucket}/{key} has a multipart ETag and no stored checksum; comparing size and modification time"
+                );
+                return Ok((false, candidate));
+            };
+            let local = md5_file(Path::new(local_path)).await?;
+            Ok((local != remote, candidate))
+        })
+        .buffer_unordered(CHECKSUM_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut changed = Vec::new();
+    let mut same = Vec::new();
+    for check in checks {
+        match check? {
+            (true, candidate) => changed.push(candidate),
+            (false, candidate) => same.push(candidate),
+        }
+    }
+    changed.sort();
+    same.sort();
+    Ok((changed, same))
+}
+
+/// Hex MD5 of an object's content: its ETag when that is a plain MD5, else
+/// an `x-amz-meta-checksum` stored at upload time. SSE-KMS ETags also look
+/// like MD5s but aren't, which only ever causes an extra transfer.
+pub async fn remote_md5(
+    config: &Config,
+    bucket: &str,
+    key: &str,
+    etag: Option<&str>,
+) -> Result<Option<String>> {
+    if let Some(md5) = etag.and_then(plain_md5) {
+        return Ok(Some(md5));
+    }
+
+    let head = config
+        .client
+        .head_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read metadata of s3://{bucket}/{key}: {e}"))?;
+    Ok(metadata_md5(&head))
+}
+
+/// The `x-amz-meta-checksum` MD5 stored with the object at upload time
+pub fn metadata_md5(head: &HeadObjectOutput) -> Option<String> {
+    head.metadata()
+        .and_then(|metadata| metadata.get(CHECKSUM_METADATA_KEY))
+        .and_then(|checksum| plain_md5(checksum))
+}
+
+/// `metadata` plus the content MD5 stored with an object, which still holds
+/// when only the object's metadata is being replaced
+pub fn keep_stored_checksum(
+    mut metadata: HashMap<String, String>,
+    head: &HeadObjectOutput,
+) -> HashMap<String, String> {
+    if let Some(md5) = metadata_md5(head) {
+        metadata
+            .entry(CHECKSUM_METADATA_KEY.to_string())
+            .or_insert(md5);
+    }
+    metadata
+}
+
+/// A quoted or bare 32-digit hex MD5, lowercased; `None` for multipart ETags
+/// (`<md5>-<parts>`) and anything else
+pub fn plain_md5(value: &str) -> Option<String> {
+    let value = value.trim_matches('"');
+    (value.len() == 32 && value.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| value.to_ascii_lowercase())
+}
+
+pub async fn md5_file(path: &Path) -> Result<String> {
+    use tokio::io::AsyncReadExt;
+
+    let mut file = fs::File::open(path)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to open {} for hashing: {e}", path.display()))?;
+    let mut context = md5::Context::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        context.consume(&buffer[..read]);
+    }
+    Ok(format!("{:x}", context.compute()))
+}
+
+/// How a file present on both sides is judged up to date, following aws s3 sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    /// Transfer when the sizes differ or the source was modified later
+    SizeAndNewer,
+    /// `--size-only`: transfer only when the sizes differ
+    SizeOnly,
+    /// `--exact-timestamps`: transfer when the sizes or modification times differ
+    ExactTimestamps,
+}
+
+impl Comparison {
+    /// `--size-only` wins when both flags are given
+    fn from_flags(size_only: bool, exact_timestamps: bool) -> Self {
+        match (size_only, exact_timestamps) {
+            (true, _) => Comparison::SizeOnly,
+            (false, true) => Comparison::ExactTimestamps,
+            (false, false) => Comparison::SizeAndNewer,
+        }
+    }
+}
+
+/// Decide whether a source file must be transferred to bring the destination up to date.
+/// Timestamps are compared in whole seconds, the precision S3 reports, and are
+/// skipped when either side has none.
+fn needs_transfer(source: &FileInfo, dest: Option<&FileInfo>, comparison: Comparison) -> bool {
+    let Some(dest) = dest else {
+        // Missing at the destination
+        return true;
+    };
+    if source.size != dest.size {
+        return true;
+    }
+
+    let seconds = |modified: Option<SystemTime>| {
+        modified
+            .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_secs())
+    };
+    match (comparison, seconds(source.modified), seconds(dest.modified)) {
+        (Comparison::SizeOnly, _, _) => false,
+        (Comparison::SizeAndNewer, Some(source), Some(dest)) => source > dest,
+        (Comparison::ExactTimestamps, Some(source), Some(dest)) => source != dest,
+        (_, _, _) => false,
+    }
+}
+
+/// Give a downloaded file the object's LastModified, as aws s3 sync does, so
+/// the next run sees it as unchanged under either timestamp comparison
+fn preserve_modified_time(path: &Path, modified: Option<SystemTime>) -> Result<()> {
+    if let Some(modified) = modified {
+        std::fs::File::options()
+            .write(true)
+            .open(path)?
+            .set_modified(modified)?;
+    }
+    Ok(())
+}
+
+/// Re-key S3 listings relative to the sync prefix so they line up with local paths
+pub fn relative_to_prefix(
+    objects: HashMap<String, FileInfo>,
+    prefix: &str,
+) -> HashMap<String, FileInfo> {
+    if prefix.is_empty() {
+        return objects;
+    }
+
+    let prefix = format!("{}/", prefix.trim_end_matches('/'));
+    objects
+        .into_iter()
+        .map(|(key, info)| match key.strip_prefix(&prefix) {
+            Some(relative) => (relative.to_string(), info),
+            None => (key, info),
+        })
+        .collect()
+}
+
+/// Every source file that is missing at the destination after a sync, or
+/// that the sync's own `comparison` would still transfer
+fn find_discrepancies(
+    source: &HashMap<String, FileInfo>,
+    dest: &HashMap<String, FileInfo>,
+    comparison: Comparison,
+) -> Vec<String> {
+    let mut discrepancies: Vec<String> = source
+        .iter()
+        .filter_map(|(path, source_file)| match dest.get(path) {
+            None => Some(format!("missing: {path}")),
+            Some(dest_file) if source_file.size != dest_file.size => Some(format!(
+                "size mismatch: {path} (source {} bytes, destination {} bytes)",
+                source_file.size, dest_file.size
+            )),
+            Some(dest_file) if needs_transfer(source_file, Some(dest_file), comparison) => {
+                Some(format!("modification time mismatch: {path}"))
+            }
+            Some(_) => None,
+        })
+        .collect();
+    discrepancies.sort();
+    discrepancies
+}
+
+/// Relative paths present on both sides with the same size, and that size;
+/// the only pairs `--checksum` has left to compare after `find_discrepancies`
+fn same_size_pairs<'a>(
+    source: &'a HashMap<String, FileInfo>,
+    dest: &'a HashMap<String, FileInfo>,
+) -> impl Iterator<Item = (&'a String, u64)> {
+    source.iter().filter_map(|(path, source_file)| {
+        dest.get(path)
+            .filter(|dest_file| dest_file.size == source_file.size)
+            .map(|_| (path, source_file.size as u64))
+    })
+}
+
+/// Object key for a path relative to the sync prefix
+fn key_under_prefix(prefix: &str, relative_path: &str) -> String {
+    if prefix.is_empty() {
+        relative_path.to_string()
+    } else {
+        format!("{}/{}", prefix.trim_end_matches('/'), relative_path)
+    }
+}
+
+/// `--verify --checksum`: same-size `(local path, key, size)` pairs whose
+/// contents differ, as discrepancies
+async fn checksum_discrepancies(
+    config: &Config,
+    bucket: &str,
+    candidates: Vec<Candidate>,
+    objects: &HashMap<String, FileInfo>,
+) -> Result<Vec<String>> {
+    let (changed, _) = split_checksum_mismatches(config, bucket, candidates, objects).await?;
+    Ok(changed
+        .into_iter()
+        .map(|(local_path, key, _)| {
+            format!("checksum mismatch: {local_path} (s3://{bucket}/{key})")
+        })
+        .collect())
+}
+
+/// Print the `--verify` outcome and fail the sync when anything didn't land
+fn report_verification(discrepancies: &[String]) -> Result<()> {
+    if discrepancies.is_empty() {
+        println!("verify: all files present and matching at destination");
+        return Ok(());
+    }
+
+    eprintln!("verify: {} discrepancies found", discrepancies.len());
+    for discrepancy in discrepancies {
+        eprintln!("  {discrepancy}");
+    }
+    Err(anyhow::anyhow!(
+        "Sync verification failed: {} files missing or mismatched at destination",
+        discrepancies.len()
+    ))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn sync_s3_to_s3(
     _config: &Config,
@@ -413,6 +1281,7 @@ async fn sync_s3_to_s3(
     _include: Option<&str>,
     _size_only: bool,
     _exact_timestamps: bool,
+    _options: &SyncOptions,
 ) -> Result<()> {
     // S3 to S3 sync is more complex and less commonly used
     // For now, return an error suggesting to use cp with --recursive
@@ -422,13 +1291,14 @@ async fn sync_s3_to_s3(
 }
 
 #[derive(Debug, Clone)]
-struct FileInfo {
-    size: i64,
-    #[allow(dead_code)] // TODO: Use for timestamp-based sync comparison
-    modified: Option<std::time::SystemTime>,
+pub struct FileInfo {
+    pub size: i64,
+    /// ETag from the listing; local files have none
+    pub etag: Option<String>,
+    pub modified: Option<std::time::SystemTime>,
 }
 
-fn scan_local_directory(dir_path: &str) -> Result<HashMap<String, FileInfo>> {
+pub fn scan_local_directory(dir_path: &str) -> Result<HashMap<String, FileInfo>> {
     let mut files = HashMap::new();
     let base_path = Path::new(dir_path);
 
@@ -451,6 +1321,7 @@ fn scan_local_directory(dir_path: &str) -> Result<HashMap<String, FileInfo>> {
                 relative_path,
                 FileInfo {
                     size: metadata.len() as i64,
+                    etag: None,
                     modified: metadata.modified().ok(),
                 },
             );
@@ -460,7 +1331,7 @@ fn scan_local_directory(dir_path: &str) -> Result<HashMap<String, FileInfo>> {
     Ok(files)
 }
 
-async fn scan_s3_objects(config: &Config, s3_uri: &S3Uri) -> Result<HashMap<String, FileInfo>> {
+pub async fn scan_s3_objects(config: &Config, s3_uri: &S3Uri) -> Result<HashMap<String, FileInfo>> {
     let mut objects = HashMap::new();
 
     let mut list_request = config.client.list_objects_v2().bucket(&s3_uri.bucket);
@@ -489,7 +1360,14 @@ async fn scan_s3_objects(config: &Config, s3_uri: &S3Uri) -> Result<HashMap<Stri
                             .checked_add(std::time::Duration::from_secs(timestamp as u64))
                     });
 
-                    objects.insert(key, FileInfo { size, modified });
+                    objects.insert(
+                        key,
+                        FileInfo {
+                            size,
+                            etag: object.e_tag,
+                            modified,
+                        },
+                    );
                 }
             }
         }
@@ -559,7 +1437,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -578,6 +1465,7 @@ mod tests {
             None,  // include
             false, // size_only
             false, // exact_timestamps
+            &SyncOptions::default(),
         )
         .await;
 
@@ -602,6 +1490,7 @@ mod tests {
             None,  // include
             false, // size_only
             false, // exact_timestamps
+            &SyncOptions::default(),
         )
         .await;
 
@@ -624,6 +1513,7 @@ mod tests {
             None,  // include
             false, // size_only
             false, // exact_timestamps
+            &SyncOptions::default(),
         )
         .await;
 
@@ -634,6 +1524,30 @@ mod tests {
             .contains("Local to local sync not supported"));
     }
 
+    #[tokio::test]
+    async fn test_execute_rejects_stdin_source() {
+        let config = create_mock_config();
+
+        let result = execute(
+            &config,
+            "-",
+            "s3://bucket/prefix/",
+            false, // dryrun
+            false, // delete
+            None,  // exclude
+            None,  // include
+            false, // size_only
+            false, // exact_timestamps
+            &SyncOptions::default(),
+        )
+        .await;
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("cannot read from stdin"));
+    }
+
     #[test]
     fn test_scan_local_directory() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -679,6 +1593,7 @@ mod tests {
     fn test_file_info_debug() {
         let file_info = FileInfo {
             size: 1024,
+            etag: None,
             modified: None,
         };
 
@@ -690,6 +1605,7 @@ mod tests {
     fn test_file_info_clone() {
         let file_info = FileInfo {
             size: 1024,
+            etag: None,
             modified: None,
         };
 
@@ -762,6 +1678,7 @@ mod tests {
 
         let file_info = FileInfo {
             size: metadata.len() as i64,
+            etag: None,
             modified,
         };
 
@@ -780,4 +1697,512 @@ mod tests {
         let files = result.unwrap();
         assert_eq!(files.len(), 0);
     }
+
+    #[test]
+    fn test_check_delete_threshold() {
+        let planned: Vec<String> = (0..5).map(|i| format!("s3://b/file{i}")).collect();
+
+        // Off by default
+        assert!(check_delete_threshold(&planned, 10, &SyncOptions::default()).is_ok());
+
+        let count_limit = SyncOptions {
+            max_delete: Some(4),
+            ..Default::default()
+        };
+        let err = check_delete_threshold(&planned, 10, &count_limit).unwrap_err();
+        assert!(err.to_string().contains("--max-delete 4"));
+
+        let at_limit = SyncOptions {
+            max_delete: Some(5),
+            ..Default::default()
+        };
+        assert!(check_delete_threshold(&planned, 10, &at_limit).is_ok());
+
+        let percent_limit = SyncOptions {
+            max_delete_percent: Some(25.0),
+            ..Default::default()
+        };
+        let err = check_delete_threshold(&planned, 10, &percent_limit).unwrap_err();
+        assert!(err.to_string().contains("--max-delete-percent 25"));
+        assert!(check_delete_threshold(&planned, 100, &percent_limit).is_ok());
+    }
+
+    fn file(size: i64) -> FileInfo {
+        FileInfo {
+            size,
+            etag: None,
+            modified: None,
+        }
+    }
+
+    #[test]
+    fn test_find_discrepancies() {
+        let source = HashMap::from([
+            ("same.txt".to_string(), file(10)),
+            ("changed.txt".to_string(), file(20)),
+            ("missing.txt".to_string(), file(30)),
+        ]);
+        let dest = HashMap::from([
+            ("same.txt".to_string(), file(10)),
+            ("changed.txt".to_string(), file(21)),
+            // Extra destination files aren't a verification failure
+            ("extra.txt".to_string(), file(5)),
+        ]);
+
+        assert_eq!(
+            find_discrepancies(&source, &dest, Comparison::SizeOnly),
+            vec![
+                "missing: missing.txt".to_string(),
+                "size mismatch: changed.txt (source 20 bytes, destination 21 bytes)".to_string(),
+            ]
+        );
+        assert!(
+            report_verification(&find_discrepancies(&source, &dest, Comparison::SizeOnly)).is_err()
+        );
+        assert!(report_verification(&[]).is_ok());
+
+        // The sync's own comparison decides whether timestamps count
+        let now = SystemTime::now();
+        let source = HashMap::from([(
+            "a.txt".to_string(),
+            FileInfo {
+                modified: Some(now),
+                ..file(10)
+            },
+        )]);
+        let dest = HashMap::from([(
+            "a.txt".to_string(),
+            FileInfo {
+                modified: Some(now - std::time::Duration::from_secs(60)),
+                ..file(10)
+            },
+        )]);
+        assert!(find_discrepancies(&source, &dest, Comparison::SizeOnly).is_empty());
+        assert_eq!(
+            find_discrepancies(&source, &dest, Comparison::ExactTimestamps),
+            vec!["modification time mismatch: a.txt".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_checksum_catches_same_size_changes() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.txt"), b"new!").unwrap();
+
+        // The object has the same size and is newer, but its ETag is the MD5
+        // of other content; the listing still shows it after the upload
+        let config = crate::commands::test_client::canned_config(|method, uri| match method {
+            "GET" if uri.contains("list-type=2") => (
+                200,
+                "<ListBucketResult>\
+                   <Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                   <Contents><Key>a.txt</Key><Size>4</Size>\
+                   <ETag>&quot;5d41402abc4b2a76b9719d911017c592&quot;</ETag>\
+                   <LastModified>2099-01-01T00:00:00.000Z</LastModified></Contents>\
+                 </ListBucketResult>"
+                    .to_string(),
+            ),
+            _ => (200, String::new()),
+        });
+        let options = SyncOptions {
+            verify: true,
+            checksum: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let error = sync_local_to_s3(
+            &config,
+            dir.path().to_str().unwrap(),
+            "s3://bucket/",
+            false,
+            false,
+            None,
+            None,
+            false,
+            false,
+            &options,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().contains("Sync verification failed"));
+    }
+
+    #[test]
+    fn test_upload_metadata_drift() {
+        let desired = UploadMetadata {
+            content_type: Some("text/html".to_string()),
+            metadata: HashMap::from([("owner".to_string(), "web".to_string())]),
+        };
+        let current = HashMap::from([("Owner".to_string(), "web".to_string())]);
+
+        assert!(!desired.differs_from(Some("text/html"), Some(&current)));
+        assert!(desired.differs_from(Some("binary/octet-stream"), Some(&current)));
+        assert!(desired.differs_from(Some("text/html"), None));
+
+        let stale = HashMap::from([
+            ("owner".to_string(), "web".to_string()),
+            ("build".to_string(), "41".to_string()),
+        ]);
+        assert!(desired.differs_from(Some("text/html"), Some(&stale)));
+
+        // Without a Content-Type or user metadata to set, nothing is compared
+        let plain = UploadMetadata::default();
+        assert!(!plain.differs_from(Some("binary/octet-stream"), None));
+        assert!(!plain.differs_from(None, Some(&current)));
+
+        // The content MD5 stored by multipart uploads isn't drift
+        let stored = HashMap::from([
+            ("owner".to_string(), "web".to_string()),
+            (
+                CHECKSUM_METADATA_KEY.to_string(),
+                "5d41402abc4b2a76b9719d911017c592".to_string(),
+            ),
+        ]);
+        assert!(!desired.differs_from(Some("text/html"), Some(&stored)));
+    }
+
+    #[tokio::test]
+    async fn test_compare_metadata_uses_detected_content_type() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("index.html"), b"<p/>").unwrap();
+
+        // The object matches by size and is newer, but was stored without a
+        // Content-Type; the one detected from `.html` must count as drift
+        let (config, requests) =
+            crate::commands::test_client::recording_config(|method, uri| match method {
+                "GET" if uri.contains("list-type=2") => (
+                    200,
+                    "<ListBucketResult>\
+                       <Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                       <Contents><Key>index.html</Key><Size>4</Size>\
+                       <LastModified>2099-01-01T00:00:00.000Z</LastModified></Contents>\
+                     </ListBucketResult>"
+                        .to_string(),
+                ),
+                "PUT" => (200, "<CopyObjectResult></CopyObjectResult>".to_string()),
+                _ => (200, String::new()),
+            });
+        let options = SyncOptions {
+            compare_metadata: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        sync_local_to_s3(
+            &config,
+            dir.path().to_str().unwrap(),
+            "s3://bucket/",
+            false,
+            false,
+            None,
+            None,
+            false,
+            false,
+            &options,
+        )
+        .await
+        .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let copy = requests
+            .iter()
+            .find(|request| request.headers.contains_key("x-amz-copy-source"))
+            .expect("metadata update copy");
+        assert_eq!(
+            copy.headers.get("content-type").map(String::as_str),
+            Some("text/html")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metadata_update_keeps_object_headers() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("my report.html"), b"<p/>").unwrap();
+
+        // Stored without a Content-Type, but with headers, user metadata and
+        // a storage class the metadata refresh must not drop
+        let (config, requests) =
+            crate::commands::test_client::header_config(|method, uri| match method {
+                "GET" if uri.contains("list-type=2") => (
+                    200,
+                    Vec::new(),
+                    "<ListBucketResult>\
+                       <Name>bucket</Name><IsTruncated>false</IsTruncated>\
+                       <Contents><Key>my report.html</Key><Size>4</Size>\
+                       <LastModified>2099-01-01T00:00:00.000Z</LastModified></Contents>\
+                     </ListBucketResult>"
+                        .to_string(),
+                ),
+                "HEAD" => (
+                    200,
+                    vec![
+                        ("cache-control", "max-age=60".to_string()),
+                        ("content-encoding", "gzip".to_string()),
+                        ("x-amz-meta-owner", "web".to_string()),
+                        ("x-amz-storage-class", "STANDARD_IA".to_string()),
+                    ],
+                    String::new(),
+                ),
+                "PUT" => (
+                    200,
+                    Vec::new(),
+                    "<CopyObjectResult></CopyObjectResult>".to_string(),
+                ),
+                _ => (200, Vec::new(), String::new()),
+            });
+        let options = SyncOptions {
+            compare_metadata: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        sync_local_to_s3(
+            &config,
+            dir.path().to_str().unwrap(),
+            "s3://bucket/",
+            false,
+            false,
+            None,
+            None,
+            false,
+            false,
+            &options,
+        )
+        .await
+        .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let copy = requests
+            .iter()
+            .find(|request| request.headers.contains_key("x-amz-copy-source"))
+            .expect("metadata update copy");
+        let header = |name: &str| copy.headers.get(name).map(String::as_str);
+        assert_eq!(header("x-amz-copy-source"), Some("bucket/my%20report.html"));
+        assert_eq!(header("content-type"), Some("text/html"));
+        assert_eq!(header("cache-control"), Some("max-age=60"));
+        assert_eq!(header("content-encoding"), Some("gzip"));
+        assert_eq!(header("x-amz-meta-owner"), Some("web"));
+        assert_eq!(header("x-amz-storage-class"), Some("STANDARD_IA"));
+    }
+
+    #[test]
+    fn test_relative_to_prefix() {
+        let objects = HashMap::from([
+            ("backups/a.txt".to_string(), file(1)),
+            ("backups/dir/b.txt".to_string(), file(2)),
+        ]);
+
+        let relative = relative_to_prefix(objects.clone(), "backups/");
+        assert!(relative.contains_key("a.txt"));
+        assert!(relative.contains_key("dir/b.txt"));
+
+        assert_eq!(relative_to_prefix(objects, "").len(), 2);
+    }
+
+    #[test]
+    fn test_comparison_from_flags() {
+        assert_eq!(
+            Comparison::from_flags(false, false),
+            Comparison::SizeAndNewer
+        );
+        assert_eq!(Comparison::from_flags(true, false), Comparison::SizeOnly);
+        assert_eq!(
+            Comparison::from_flags(false, true),
+            Comparison::ExactTimestamps
+        );
+        // --size-only wins when both are given
+        assert_eq!(Comparison::from_flags(true, true), Comparison::SizeOnly);
+    }
+
+    #[test]
+    fn test_needs_transfer_comparisons() {
+        let at = |size: i64, secs: u64| FileInfo {
+            size,
+            etag: None,
+            modified: Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs)),
+        };
+
+        // (source, destination, size-and-newer, size-only, exact-timestamps)
+        let cases = [
+            (at(10, 100), None, true, true, true),
+            (at(10, 100), Some(at(11, 100)), true, true, true),
+            (at(10, 100), Some(at(10, 100)), false, false, false),
+            (at(10, 200), Some(at(10, 100)), true, false, true),
+            (at(10, 100), Some(at(10, 200)), false, false, true),
+            // Sub-second differences don't count; S3 reports whole seconds
+            (
+                FileInfo {
+                    modified: Some(
+                        SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(100_500),
+                    ),
+                    ..at(10, 0)
+                },
+                Some(at(10, 100)),
+                false,
+                false,
+                false,
+            ),
+            // Without timestamps only the size is compared
+            (file(10), Some(at(10, 100)), false, false, false),
+        ];
+
+        for (source, dest, newer, size_only, exact) in cases {
+            let dest = dest.as_ref();
+            assert_eq!(
+                needs_transfer(&source, dest, Comparison::SizeAndNewer),
+                newer,
+                "size-and-newer: {source:?} vs {dest:?}"
+            );
+            assert_eq!(
+                needs_transfer(&source, dest, Comparison::SizeOnly),
+                size_only,
+                "size-only: {source:?} vs {dest:?}"
+            );
+            assert_eq!(
+                needs_transfer(&source, dest, Comparison::ExactTimestamps),
+                exact,
+                "exact-timestamps: {source:?} vs {dest:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_preserve_modified_time() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("a.txt");
+        std::fs::write(&path, "data").unwrap();
+
+        let modified = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        preserve_modified_time(&path, Some(modified)).unwrap();
+        assert_eq!(path.metadata().unwrap().modified().unwrap(), modified);
+    }
+
+    #[test]
+    fn test_quiet_summary() {
+        assert_eq!(
+            quiet_summary(false, "upload", 3, 1, 2),
+            "sync: 3 file(s) uploaded, 1 metadata update(s), 2 deleted"
+        );
+        assert_eq!(
+            quiet_summary(true, "download", 4, 0, 1),
+            "(dryrun) sync: 4 file(s) to download, 1 to delete"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_summary_report() {
+        let summary = DryRunSummary {
+            transfers: 3,
+            transfer_bytes: 3 * 1024 * 1024,
+            deletes: 2,
+            delete_bytes: 300,
+            metadata_updates: 0,
+            unchanged: 10,
+        };
+        assert_eq!(
+            summary.report("upload"),
+            "Dry run summary:\n  upload:    3 file(s), 3.00 MiB\n  delete:    2 file(s), 300 B\n  unchanged: 10 file(s)"
+        );
+
+        let with_metadata = DryRunSummary {
+            metadata_updates: 4,
+            ..DryRunSummary::default()
+        };
+        assert!(with_metadata.report("download").contains(
+            "  download:  0 file(s), 0 B\n  delete:    0 file(s), 0 B\n  metadata:  4 update(s)\n"
+        ));
+    }
+
+    #[test]
+    fn test_plain_md5() {
+        assert_eq!(
+            plain_md5("\"5D41402ABC4B2A76B9719D911017C592\"").as_deref(),
+            Some("5d41402abc4b2a76b9719d911017c592")
+        );
+        assert_eq!(plain_md5("5d41402abc4b2a76b9719d911017c592-3"), None);
+        assert_eq!(plain_md5("not-an-md5"), None);
+    }
+
+    #[tokio::test]
+    async fn test_split_checksum_mismatches() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let same = temp_dir.path().join("same.txt");
+        let edited = temp_dir.path().join("edited.txt");
+        std::fs::write(&same, "hello").unwrap();
+        std::fs::write(&edited, "jello").unwrap();
+        assert_eq!(
+            md5_file(&same).await.unwrap(),
+            "5d41402abc4b2a76b9719d911017c592"
+        );
+
+        // Both objects hold "hello"; only the edited local file differs
+        let object = FileInfo {
+            size: 5,
+            etag: Some("\"5d41402abc4b2a76b9719d911017c592\"".to_string()),
+            modified: None,
+        };
+        let objects = HashMap::from([
+            ("same.txt".to_string(), object.clone()),
+            ("edited.txt".to_string(), object),
+        ]);
+        let candidates = vec![
+            (
+                same.to_string_lossy().to_string(),
+                "same.txt".to_string(),
+                5,
+            ),
+            (
+                edited.to_string_lossy().to_string(),
+                "edited.txt".to_string(),
+                5,
+            ),
+        ];
+
+        let config = create_mock_config();
+        let (changed, unchanged) =
+            split_checksum_mismatches(&config, "bucket", candidates, &objects)
+                .await
+                .unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].1, "edited.txt");
+        assert_eq!(unchanged.len(), 1);
+        assert_eq!(unchanged[0].1, "same.txt");
+    }
+
+    #[tokio::test]
+    async fn test_bounded_transfers_limits_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<usize> = (0..20).collect();
+
+        let completed: Vec<usize> = bounded_transfers(&items, 3, |_| async {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .map(|(item, result)| {
+            result.unwrap();
+            *item
+        })
+        .collect()
+        .await;
+
+        assert_eq!(completed.len(), 20);
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_transfers_zero_limit_runs_serially() {
+        let items = vec!["a", "b"];
+        let results: Vec<_> = bounded_transfers(&items, 0, |_| async { Ok(()) })
+            .collect()
+            .await;
+        assert_eq!(results.len(), 2);
+    }
 }
@@ -0,0 +1,221 @@
+use anyhow::Result;
+use aws_sdk_s3::types::{Tag, Tagging};
+use log::info;
+use std::io::Write;
+use std::time::Instant;
+
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::config::Config;
+
+/// Most tags S3 allows on one object
+const MAX_TAGS: usize = 10;
+
+/// S3 limits on tag key and value length, in characters
+const MAX_TAG_KEY_LEN: usize = 128;
+const MAX_TAG_VALUE_LEN: usize = 256;
+
+/// Parse `KEY=VALUE` pairs for an object's tag set, keeping their order.
+/// Values may be empty; keys may not repeat.
+pub fn parse_tags(pairs: &[String]) -> Result<Vec<(String, String)>> {
+    if pairs.len() > MAX_TAGS {
+        return Err(anyhow::anyhow!(
+            "Too many tags ({}): S3 allows at most {MAX_TAGS} per object",
+            pairs.len()
+        ));
+    }
+
+    let mut tags: Vec<(String, String)> = Vec::new();
+    for pair in pairs {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid tag '{pair}': expected KEY=VALUE"))?;
+        if key.is_empty() || key.chars().count() > MAX_TAG_KEY_LEN {
+            return Err(anyhow::anyhow!(
+                "Invalid tag key '{key}': must be 1 to {MAX_TAG_KEY_LEN} characters"
+            ));
+        }
+        if value.chars().count() > MAX_TAG_VALUE_LEN {
+            return Err(anyhow::anyhow!(
+                "Invalid tag value for '{key}': longer than {MAX_TAG_VALUE_LEN} characters"
+            ));
+        }
+        if tags.iter().any(|(existing, _)| existing == key) {
+            return Err(anyhow::anyhow!("Duplicate tag key '{key}'"));
+        }
+        tags.push((key.to_string(), value.to_string()));
+    }
+    Ok(tags)
+}
+
+/// Tag set in the URL query form PutObject's `x-amz-tagging` header takes
+/// (`k1=v1&k2=v2`), with keys and values percent-encoded
+pub fn tagging_header(tags: &[(String, String)]) -> String {
+    tags.iter()
+        .map(|(key, value)| format!("{}={}", url_encode(key), url_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Percent-encode everything outside the RFC 3986 unreserved set
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Print an object's tags, one `KEY=VALUE` per line
+pub async fn get(config: &Config, s3_uri: &str, out: &mut dyn Write) -> Result<()> {
+    let start_time = Instant::now();
+    let uri = object_uri(s3_uri)?;
+
+    let response = config
+        .client
+        .get_object_tagging()
+        .bucket(&uri.bucket)
+        .key(uri.key_or_empty())
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get tags for {uri}: {e}"))?;
+
+    for tag in response.tag_set() {
+        writeln!(out, "{}={}", tag.key(), tag.value())?;
+    }
+
+    record_metrics("get_tags", start_time);
+    Ok(())
+}
+
+/// Replace an object's whole tag set
+pub async fn set(config: &Config, s3_uri: &str, pairs: &[String]) -> Result<()> {
+    let start_time = Instant::now();
+    let uri = object_uri(s3_uri)?;
+    let tags = parse_tags(pairs)?;
+
+    let tag_set = tags
+        .iter()
+        .map(|(key, value)| Tag::builder().key(key).value(value).build())
+        .collect::<Result<Vec<_>, _>>()?;
+    let tagging = Tagging::builder().set_tag_set(Some(tag_set)).build()?;
+
+    info!("Setting {} tag(s) on {uri}", tags.len());
+    config
+        .client
+        .put_object_tagging()
+        .bucket(&uri.bucket)
+        .key(uri.key_or_empty())
+        .tagging(tagging)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to set tags on {uri}: {e}"))?;
+
+    println!("tag: {uri} ({} tag(s))", tags.len());
+    record_metrics("put_tags", start_time);
+    Ok(())
+}
+
+/// Remove every tag from an object
+pub async fn remove(config: &Config, s3_uri: &str) -> Result<()> {
+    let start_time = Instant::now();
+    let uri = object_uri(s3_uri)?;
+
+    config
+        .client
+        .delete_object_tagging()
+        .bucket(&uri.bucket)
+        .key(uri.key_or_empty())
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to remove tags from {uri}: {e}"))?;
+
+    println!("untag: {uri}");
+    record_metrics("delete_tags", start_time);
+    Ok(())
+}
+
+/// Tags belong to objects, so the URI must name a key
+fn object_uri(s3_uri: &str) -> Result<S3Uri> {
+    if !is_s3_uri(s3_uri) {
+        return Err(anyhow::anyhow!(
+            "tag command only works with S3 URIs (s3://...)"
+        ));
+    }
+    let uri = S3Uri::parse(s3_uri)?;
+    if uri.key_or_empty().is_empty() {
+        return Err(anyhow::anyhow!(
+            "tag needs an object key (s3://bucket/key), not a bucket"
+        ));
+    }
+    Ok(uri)
+}
+
+fn record_metrics(operation: &'static str, start_time: Instant) {
+    use crate::otel::OTEL_INSTRUMENTS;
+    use opentelemetry::KeyValue;
+
+    OTEL_INSTRUMENTS
+        .operations_total
+        .add(1, &[KeyValue::new("operation", operation)]);
+    OTEL_INSTRUMENTS.operation_duration.record(
+        start_time.elapsed().as_millis() as f64 / 1000.0,
+        &[KeyValue::new("operation", operation)],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let tags = parse_tags(&pairs(&["env=prod", "note=a=b", "empty="])).unwrap();
+        assert_eq!(
+            tags,
+            vec![
+                ("env".to_string(), "prod".to_string()),
+                ("note".to_string(), "a=b".to_string()),
+                ("empty".to_string(), String::new()),
+            ]
+        );
+
+        assert!(parse_tags(&pairs(&["no-separator"])).is_err());
+        assert!(parse_tags(&pairs(&["=value"])).is_err());
+        assert!(parse_tags(&pairs(&["env=a", "env=b"]))
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate"));
+
+        let too_many: Vec<String> = (0..11).map(|i| format!("k{i}=v")).collect();
+        assert!(parse_tags(&too_many).is_err());
+        let long_key = format!("{}=v", "k".repeat(129));
+        assert!(parse_tags(&[long_key]).is_err());
+    }
+
+    #[test]
+    fn test_tagging_header_is_url_encoded() {
+        let tags = parse_tags(&pairs(&["team=data eng", "path=a/b&c", "plain=ok-1.2_~"])).unwrap();
+        assert_eq!(
+            tagging_header(&tags),
+            "team=data%20eng&path=a%2Fb%26c&plain=ok-1.2_~"
+        );
+        assert_eq!(url_encode("café"), "caf%C3%A9");
+    }
+
+    #[test]
+    fn test_object_uri_requires_key() {
+        assert!(object_uri("s3://bucket/key").is_ok());
+        assert!(object_uri("s3://bucket").is_err());
+        assert!(object_uri("s3://bucket/").is_err());
+        assert!(object_uri("/local/file").is_err());
+    }
+}
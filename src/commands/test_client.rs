@@ -0,0 +1,165 @@
+//! Canned-response S3 client for command tests that need requests to succeed
+
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::Client;
+use aws_smithy_runtime_api::client::http::{
+    http_client_fn, HttpConnector, HttpConnectorFuture, SharedHttpClient, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::http::{Response, StatusCode};
+use aws_smithy_types::body::SdkBody;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::commands::retry::{RetryInterceptor, RetryPolicy};
+use crate::config::{Config, OtelConfig};
+use crate::events::{EventSink, NoopEventSink};
+
+type Responder = dyn Fn(&str, &str) -> CannedResponse + Send + Sync;
+
+/// Status, headers and body sent back for one request
+pub type CannedResponse = (u16, Vec<(&'static str, String)>, String);
+
+/// A request the canned client received, with lowercased header names
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub uri: String,
+    pub headers: HashMap<String, String>,
+}
+
+/// Requests seen by a `recording_config` client, in the order they were sent
+pub type RecordedRequests = Arc<Mutex<Vec<RecordedRequest>>>;
+
+/// Answers every request with `responder(method, uri)`
+#[derive(Clone)]
+struct CannedConnector {
+    responder: Arc<Responder>,
+    requests: RecordedRequests,
+}
+
+impl std::fmt::Debug for CannedConnector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CannedConnector")
+    }
+}
+
+impl HttpConnector for CannedConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: request.method().to_string(),
+            uri: request.uri().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| (name.to_lowercase(), value.to_string()))
+                .collect(),
+        });
+        let (status, headers, body) = (self.responder)(request.method(), request.uri());
+        let mut response = Response::new(
+            StatusCode::try_from(status).expect("valid status code"),
+            SdkBody::from(body),
+        );
+        for (name, value) in headers {
+            response.headers_mut().insert(name, value);
+        }
+        HttpConnectorFuture::ready(Ok(response))
+    }
+}
+
+/// Config whose client never touches the network: each request gets the
+/// status and body `responder` returns for its method and URI
+pub fn canned_config(
+    responder: impl Fn(&str, &str) -> (u16, String) + Send + Sync + 'static,
+) -> Config {
+    recording_config(responder).0
+}
+
+/// `recording_config` whose responses can also carry headers, for metadata
+/// that S3 only returns in headers (HEAD, object lock status)
+pub fn header_config(
+    responder: impl Fn(&str, &str) -> CannedResponse + Send + Sync + 'static,
+) -> (Config, RecordedRequests) {
+    build_config(RetryPolicy::new(0), Arc::new(NoopEventSink), responder)
+}
+
+/// `canned_config` that also hands back every request the client sent
+pub fn recording_config(
+    responder: impl Fn(&str, &str) -> (u16, String) + Send + Sync + 'static,
+) -> (Config, RecordedRequests) {
+    build_config(
+        RetryPolicy::new(0),
+        Arc::new(NoopEventSink),
+        without_headers(responder),
+    )
+}
+
+/// `recording_config` whose client retries transient errors like the CLI's,
+/// with millisecond backoff, reporting retries to `events`
+pub fn retrying_config(
+    max_retries: usize,
+    events: Arc<dyn EventSink>,
+    responder: impl Fn(&str, &str) -> (u16, String) + Send + Sync + 'static,
+) -> (Config, RecordedRequests) {
+    let policy = RetryPolicy {
+        max_retries,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(5),
+    };
+    build_config(policy, events, without_headers(responder))
+}
+
+fn without_headers(
+    responder: impl Fn(&str, &str) -> (u16, String) + Send + Sync + 'static,
+) -> impl Fn(&str, &str) -> CannedResponse + Send + Sync + 'static {
+    move |method, uri| {
+        let (status, body) = responder(method, uri);
+        (status, Vec::new(), body)
+    }
+}
+
+/// The canned HTTP client on its own, for SDK clients other than S3's
+pub fn recording_http_client(
+    responder: impl Fn(&str, &str) -> CannedResponse + Send + Sync + 'static,
+) -> (SharedHttpClient, RecordedRequests) {
+    let requests = RecordedRequests::default();
+    let connector = CannedConnector {
+        responder: Arc::new(responder),
+        requests: requests.clone(),
+    };
+    let http_client = http_client_fn(move |_, _| SharedHttpConnector::new(connector.clone()));
+    (http_client, requests)
+}
+
+fn build_config(
+    retry: RetryPolicy,
+    events: Arc<dyn EventSink>,
+    responder: impl Fn(&str, &str) -> CannedResponse + Send + Sync + 'static,
+) -> (Config, RecordedRequests) {
+    let (http_client, requests) = recording_http_client(responder);
+
+    let client = Client::from_conf(
+        aws_sdk_s3::config::Builder::new()
+            .region(Region::new("us-east-1"))
+            .behavior_version(BehaviorVersion::latest())
+            .credentials_provider(Credentials::new("AKID", "SECRET", None, None, "test"))
+            .http_client(http_client)
+            .retry_config(retry.sdk_retry_config())
+            .interceptor(RetryInterceptor::new(events.clone()))
+            .build(),
+    );
+
+    let config = Config {
+        client: Arc::new(client),
+        otel: OtelConfig {
+            read_operations: true,
+            ..OtelConfig::default()
+        },
+        refreshable_credentials: false,
+        events,
+        retry,
+        bandwidth: None,
+    };
+    (config, requests)
+}
@@ -1,10 +1,300 @@
 use anyhow::Result;
+use aws_sdk_s3::operation::upload_part::UploadPartOutput;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
 use log::info;
-use std::time::Instant;
-
-use crate::commands::cp;
-use crate::commands::s3_uri::is_s3_uri;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt, ReadBuf};
+
+use crate::commands::cp::{self, TransferOptions};
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::commands::sync;
 use crate::config::Config;
+use crate::progress::FileProgress;
+
+/// Smallest part S3 accepts (except for the last one)
+pub const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Largest part S3 accepts
+const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Most parts S3 accepts in one multipart upload
+const MAX_UPLOAD_PARTS: u64 = 10_000;
+
+/// Maximum concurrent UploadPart requests per file
+const UPLOAD_PART_CONCURRENCY: usize = 4;
+
+/// When single-file uploads switch from PutObject to a multipart upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MultipartSettings {
+    /// Files of at least this many bytes are uploaded in parts
+    pub threshold: u64,
+    /// Bytes per part, grown when a file would need more than 10,000 parts
+    pub part_size: u64,
+}
+
+impl Default for MultipartSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 100 * 1024 * 1024,
+            part_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl MultipartSettings {
+    /// Build from `--multipart-threshold` / `--part-size` (e.g. `64MiB`, `1GB`)
+    pub fn from_args(threshold: Option<&str>, part_size: Option<&str>) -> Result<Self> {
+        use crate::filtering::parse_size_filter;
+
+        let defaults = Self::default();
+        let threshold = match threshold {
+            Some(value) => parse_size_filter(value)? as u64,
+            None => defaults.threshold,
+        };
+        let part_size = match part_size {
+            Some(value) => parse_size_filter(value)? as u64,
+            None => defaults.part_size,
+        };
+        if !(MIN_PART_SIZE..=MAX_PART_SIZE).contains(&part_size) {
+            return Err(anyhow::anyhow!(
+                "--part-size must be between 5MiB and 5GiB, got {part_size} bytes"
+            ));
+        }
+        Ok(Self {
+            threshold,
+            part_size,
+        })
+    }
+
+    pub fn applies_to(&self, file_size: u64) -> bool {
+        file_size >= self.threshold
+    }
+
+    /// Byte ranges (part number, offset, length) covering a file of `size` bytes
+    pub fn part_ranges(&self, size: u64) -> Vec<(i32, u64, u64)> {
+        let part_size = self.part_size.max(size.div_ceil(MAX_UPLOAD_PARTS));
+        (0..size.div_ceil(part_size).max(1))
+            .map(|i| {
+                let offset = i * part_size;
+                (i as i32 + 1, offset, part_size.min(size - offset))
+            })
+            .collect()
+    }
+}
+
+/// Token bucket shared by every transfer in a run, capping their combined
+/// throughput at `--max-bandwidth`. The bucket holds one second of traffic,
+/// so short bursts go through at full speed before pacing kicks in.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    bytes_per_second: f64,
+    bucket: Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl BandwidthLimiter {
+    pub fn new(bytes_per_second: u64) -> Self {
+        let bytes_per_second = bytes_per_second.max(1) as f64;
+        Self {
+            bytes_per_second,
+            bucket: Mutex::new(TokenBucket {
+                tokens: bytes_per_second,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take `bytes` from the bucket, returning how long the caller must wait
+    /// for the shortfall to refill
+    fn reserve(&self, bytes: usize) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let refill = now.duration_since(bucket.last_refill).as_secs_f64() * self.bytes_per_second;
+        bucket.tokens = (bucket.tokens + refill).min(self.bytes_per_second) - bytes as f64;
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.bytes_per_second)
+        }
+    }
+}
+
+/// Reader paced by a [`BandwidthLimiter`]; without one it passes reads
+/// straight through
+pub struct ThrottledReader<R> {
+    inner: R,
+    limiter: Option<Arc<BandwidthLimiter>>,
+    delay: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ThrottledReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        if let Some(delay) = self.delay.as_mut() {
+            ready!(delay.as_mut().poll(cx));
+            self.delay = None;
+        }
+
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+
+        // Bytes already read are handed over now; the wait is paid before
+        // the next read
+        let read = buf.filled().len() - before;
+        if let Some(limiter) = &self.limiter {
+            let wait = limiter.reserve(read);
+            if !wait.is_zero() {
+                self.delay = Some(Box::pin(tokio::time::sleep(wait)));
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Wrap a file, stdin or response body so reads respect `--max-bandwidth`
+pub fn throttle<R: AsyncRead + Unpin>(config: &Config, reader: R) -> ThrottledReader<R> {
+    ThrottledReader {
+        inner: reader,
+        limiter: config.bandwidth.clone(),
+        delay: None,
+    }
+}
+
+/// Read a whole local file for a PutObject body, paced by `--max-bandwidth`
+pub async fn read_file(config: &Config, path: &Path) -> Result<Vec<u8>> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut body = Vec::with_capacity(file.metadata().await?.len() as usize);
+    throttle(config, file).read_to_end(&mut body).await?;
+    Ok(body)
+}
+
+/// The CompleteMultipartUpload entry for an uploaded part. Uploads created
+/// with a checksum algorithm must list each part's checksum as well as its ETag.
+pub fn completed_part(part_number: i32, response: &UploadPartOutput) -> CompletedPart {
+    CompletedPart::builder()
+        .part_number(part_number)
+        .set_e_tag(response.e_tag().map(str::to_string))
+        .set_checksum_crc32(response.checksum_crc32().map(str::to_string))
+        .set_checksum_crc32_c(response.checksum_crc32_c().map(str::to_string))
+        .set_checksum_crc64_nvme(response.checksum_crc64_nvme().map(str::to_string))
+        .set_checksum_sha1(response.checksum_sha1().map(str::to_string))
+        .set_checksum_sha256(response.checksum_sha256().map(str::to_string))
+        .build()
+}
+
+/// Upload a local file with CreateMultipartUpload and concurrent UploadPart
+/// requests, each reading its own slice of the file, storing its MD5 as
+/// `x-amz-meta-checksum`. A failed upload is aborted so its parts don't linger.
+pub async fn upload_file_multipart(
+    config: &Config,
+    local_path: &Path,
+    s3_uri: &S3Uri,
+    file_size: u64,
+    options: &TransferOptions,
+    progress: &FileProgress,
+) -> Result<()> {
+    use futures::stream::{self, StreamExt, TryStreamExt};
+
+    let key = s3_uri.key_or_empty();
+    let request = config
+        .client
+        .create_multipart_upload()
+        .bucket(&s3_uri.bucket)
+        .key(key)
+        .set_content_type(options.content_type_for(&local_path.to_string_lossy()))
+        .set_tagging(options.tagging.clone())
+        .set_storage_class(options.storage_class.clone())
+        .set_checksum_algorithm(options.checksum_algorithm.clone());
+    // A multipart ETag isn't an MD5 of the content, so the MD5 is stored
+    // with the object for `sync --checksum` and `verify` to compare against
+    let mut metadata = options.metadata.clone();
+    metadata
+        .entry(sync::CHECKSUM_METADATA_KEY.to_string())
+        .or_insert(sync::md5_file(local_path).await?);
+    let created = request.set_metadata(Some(metadata)).send().await?;
+    let upload_id = created
+        .upload_id()
+        .ok_or_else(|| anyhow::anyhow!("No upload ID returned for {}", s3_uri))?
+        .to_string();
+
+    let ranges = options.multipart.part_ranges(file_size);
+    info!(
+        "Uploading {} to {s3_uri} in {} parts",
+        local_path.display(),
+        ranges.len()
+    );
+
+    let result: Result<()> = async {
+        let mut parts: Vec<CompletedPart> = stream::iter(ranges)
+            .map(|(part_number, offset, length)| {
+                let upload_id = &upload_id;
+                async move {
+                    let mut file = tokio::fs::File::open(local_path).await?;
+                    file.seek(std::io::SeekFrom::Start(offset)).await?;
+                    let mut buffer = vec![0u8; length as usize];
+                    throttle(config, file).read_exact(&mut buffer).await?;
+
+                    let response = config
+                        .client
+                        .upload_part()
+                        .bucket(&s3_uri.bucket)
+                        .key(key)
+                        .upload_id(upload_id)
+                        .part_number(part_number)
+                        .set_checksum_algorithm(options.checksum_algorithm.clone())
+                        .body(ByteStream::from(buffer))
+                        .send()
+                        .await?;
+                    progress.inc(length);
+                    Ok::<_, anyhow::Error>(completed_part(part_number, &response))
+                }
+            })
+            .buffer_unordered(UPLOAD_PART_CONCURRENCY)
+            .try_collect()
+            .await?;
+        parts.sort_by_key(|part| part.part_number());
+
+        config
+            .client
+            .complete_multipart_upload()
+            .bucket(&s3_uri.bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await?;
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        if let Err(cleanup) = cp::abort_multipart_upload(config, s3_uri, &upload_id).await {
+            return Err(anyhow::anyhow!("{e}; {cleanup}"));
+        }
+        return Err(e);
+    }
+    Ok(())
+}
 
 pub async fn execute(
     config: &Config,
@@ -36,9 +326,16 @@ pub async fn execute(
 
     // Use the cp command to perform the actual upload
     let result = cp::execute(
-        config, local_path, &dest, recursive, false, // dryrun = false
+        config,
+        local_path,
+        &dest,
+        recursive,
+        false, // dryrun = false
         1,     // max_concurrent = 1 (upload is typically single-threaded)
-        force, include, exclude,
+        force,
+        include,
+        exclude,
+        &TransferOptions::default(),
     )
     .await;
 
@@ -88,6 +385,7 @@ pub async fn execute(
 mod tests {
     use super::*;
     use aws_sdk_s3::Client;
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     fn create_mock_config() -> Config {
@@ -105,7 +403,16 @@ mod tests {
                 endpoint: None,
                 service_name: "obsctl-test".to_string(),
                 service_version: crate::get_service_version(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
             },
+            refreshable_credentials: false,
+            events: Arc::new(crate::events::NoopEventSink),
+            retry: crate::commands::retry::RetryPolicy::default(),
+            bandwidth: None,
         }
     }
 
@@ -296,6 +603,84 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_bandwidth_limiter_reserve() {
+        let limiter = BandwidthLimiter::new(1000);
+        // A full bucket absorbs one second of traffic without waiting
+        assert_eq!(limiter.reserve(1000), Duration::ZERO);
+        let wait = limiter.reserve(500);
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_throttled_reader_paces_reads() {
+        let mut config = create_mock_config();
+        config.bandwidth = Some(Arc::new(BandwidthLimiter::new(100_000)));
+        let data = vec![7u8; 120_000];
+
+        let start = Instant::now();
+        let mut read = Vec::new();
+        throttle(&config, data.as_slice())
+            .read_to_end(&mut read)
+            .await
+            .unwrap();
+
+        assert_eq!(read, data);
+        // 20,000 bytes past the initial burst at 100,000 bytes/s
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn test_unthrottled_reader_passes_through() {
+        let config = create_mock_config();
+        let mut read = Vec::new();
+        throttle(&config, b"payload".as_slice())
+            .read_to_end(&mut read)
+            .await
+            .unwrap();
+        assert_eq!(read, b"payload");
+    }
+
+    #[test]
+    fn test_multipart_settings() {
+        const MIB: u64 = 1024 * 1024;
+
+        let defaults = MultipartSettings::from_args(None, None).unwrap();
+        assert_eq!(defaults, MultipartSettings::default());
+        assert!(!defaults.applies_to(100 * MIB - 1));
+        assert!(defaults.applies_to(100 * MIB));
+
+        let custom = MultipartSettings::from_args(Some("8MiB"), Some("5MiB")).unwrap();
+        assert_eq!(custom.threshold, 8 * MIB);
+        assert_eq!(custom.part_size, 5 * MIB);
+
+        assert!(MultipartSettings::from_args(None, Some("1MiB")).is_err());
+        assert!(MultipartSettings::from_args(None, Some("6GiB")).is_err());
+    }
+
+    #[test]
+    fn test_part_ranges() {
+        const MIB: u64 = 1024 * 1024;
+        let settings = MultipartSettings::default();
+
+        let ranges = settings.part_ranges(40 * MIB);
+        assert_eq!(
+            ranges,
+            vec![
+                (1, 0, 16 * MIB),
+                (2, 16 * MIB, 16 * MIB),
+                (3, 32 * MIB, 8 * MIB)
+            ]
+        );
+
+        // 1 TiB at 16 MiB would need 65536 parts, so parts grow to fit
+        let size = 1024 * 1024 * MIB;
+        let ranges = settings.part_ranges(size);
+        assert!(ranges.len() as u64 <= MAX_UPLOAD_PARTS);
+        let (_, offset, length) = *ranges.last().unwrap();
+        assert_eq!(offset + length, size);
+    }
+
     #[test]
     fn test_s3_uri_validation() {
         // Test S3 URI validation logic
@@ -384,4 +769,70 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_completed_part_carries_checksums() {
+        let response = UploadPartOutput::builder()
+            .e_tag("\"abc\"")
+            .checksum_crc32_c("4waSgw==")
+            .build();
+        let part = completed_part(2, &response);
+        assert_eq!(part.part_number(), Some(2));
+        assert_eq!(part.e_tag(), Some("\"abc\""));
+        assert_eq!(part.checksum_crc32_c(), Some("4waSgw=="));
+        assert_eq!(part.checksum_sha256(), None);
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_stores_md5() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.bin");
+        std::fs::write(&path, b"hello").unwrap();
+
+        let (config, requests) =
+            crate::commands::test_client::recording_config(|method, uri| match method {
+                "POST" if uri.contains("uploads") => (
+                    200,
+                    "<InitiateMultipartUploadResult><UploadId>u1</UploadId>\
+                     </InitiateMultipartUploadResult>"
+                        .to_string(),
+                ),
+                "POST" => (
+                    200,
+                    "<CompleteMultipartUploadResult></CompleteMultipartUploadResult>".to_string(),
+                ),
+                _ => (200, String::new()),
+            });
+        let options = TransferOptions {
+            metadata: HashMap::from([("owner".to_string(), "web".to_string())]),
+            ..Default::default()
+        };
+        let progress = options.file_bars.start("big.bin", Some(5));
+
+        upload_file_multipart(
+            &config,
+            &path,
+            &S3Uri::parse("s3://bucket/big.bin").unwrap(),
+            5,
+            &options,
+            &progress,
+        )
+        .await
+        .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let create = &requests[0];
+        assert!(create.uri.contains("uploads"));
+        assert_eq!(
+            create
+                .headers
+                .get("x-amz-meta-checksum")
+                .map(String::as_str),
+            Some("5d41402abc4b2a76b9719d911017c592")
+        );
+        assert_eq!(
+            create.headers.get("x-amz-meta-owner").map(String::as_str),
+            Some("web")
+        );
+    }
 }
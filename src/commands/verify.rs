@@ -0,0 +1,613 @@
+use anyhow::Result;
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::types::ChecksumMode;
+use aws_smithy_checksums::ChecksumAlgorithm;
+use base64::{engine::general_purpose::STANDARD as b64, Engine as _};
+use futures::stream::{self, StreamExt};
+use log::{debug, info};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::io::AsyncReadExt;
+
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::commands::sync::{self, FileInfo};
+use crate::commands::upload::MultipartSettings;
+use crate::config::Config;
+
+/// Maximum files hashed at once
+const VERIFY_CONCURRENCY: usize = 8;
+
+/// One way the local side and S3 disagree
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Discrepancy {
+    /// Local file with no object
+    Missing(String),
+    /// Object with no local file
+    Extra(String),
+    /// Present on both sides with different sizes
+    SizeMismatch {
+        path: String,
+        local: i64,
+        remote: i64,
+    },
+    /// Same size, different content
+    ChecksumMismatch(String),
+}
+
+impl std::fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Discrepancy::Missing(path) => write!(f, "missing: {path}"),
+            Discrepancy::Extra(path) => write!(f, "extra: {path}"),
+            Discrepancy::SizeMismatch {
+                path,
+                local,
+                remote,
+            } => write!(
+                f,
+                "differs: {path} (local {local} bytes, S3 {remote} bytes)"
+            ),
+            Discrepancy::ChecksumMismatch(path) => write!(f, "differs: {path} (checksum)"),
+        }
+    }
+}
+
+/// A path present on both sides with the same size, awaiting a checksum
+#[derive(Debug, PartialEq)]
+struct SameSize {
+    relative: String,
+    local_path: PathBuf,
+    key: String,
+    etag: Option<String>,
+    size: u64,
+}
+
+/// Compare a local file or directory against S3 by size and checksum without
+/// transferring anything. Fails when anything is missing, extra or different.
+pub async fn execute(config: &Config, s3_uri: &str, local_path: &str) -> Result<()> {
+    let start_time = Instant::now();
+
+    if !is_s3_uri(s3_uri) {
+        return Err(anyhow::anyhow!(
+            "verify command only works with S3 URIs (s3://...)"
+        ));
+    }
+    let uri = S3Uri::parse(s3_uri)?;
+    let path = Path::new(local_path);
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Local path {local_path} does not exist"));
+    }
+
+    // Both sides keyed by the path relative to the local root. `target` is the
+    // object key for a single file, or the key prefix for a directory.
+    let single_file = path.is_file();
+    let (local_files, remote_objects, target) = if single_file {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let key = match uri.key_or_empty() {
+            "" => name.clone(),
+            prefix if prefix.ends_with('/') => format!("{prefix}{name}"),
+            key => key.to_string(),
+        };
+        let local = HashMap::from([(
+            name.clone(),
+            FileInfo {
+                size: path.metadata()?.len() as i64,
+                etag: None,
+                modified: None,
+            },
+        )]);
+        let object_uri = S3Uri {
+            bucket: uri.bucket.clone(),
+            key: Some(key.clone()),
+        };
+        let remote: HashMap<String, FileInfo> = sync::scan_s3_objects(config, &object_uri)
+            .await?
+            .into_iter()
+            .filter(|(listed, _)| *listed == key)
+            .map(|(_, info)| (name.clone(), info))
+            .collect();
+        (local, remote, key)
+    } else {
+        let prefix = match uri.key_or_empty().trim_end_matches('/') {
+            "" => String::new(),
+            prefix => format!("{prefix}/"),
+        };
+        let prefix_uri = S3Uri {
+            bucket: uri.bucket.clone(),
+            key: Some(prefix.clone()),
+        };
+        let remote =
+            sync::relative_to_prefix(sync::scan_s3_objects(config, &prefix_uri).await?, &prefix)
+                .into_iter()
+                // Zero-byte "directory" markers have no local counterpart
+                .filter(|(relative, _)| !relative.is_empty() && !relative.ends_with('/'))
+                .collect();
+        (sync::scan_local_directory(local_path)?, remote, prefix)
+    };
+    // Local path and object key for a relative path
+    let locate = |relative: &str| {
+        if single_file {
+            (path.to_path_buf(), target.clone())
+        } else {
+            (path.join(relative), format!("{target}{relative}"))
+        }
+    };
+
+    info!(
+        "Verifying {} local file(s) against {} object(s) in s3://{}/{target}",
+        local_files.len(),
+        remote_objects.len(),
+        uri.bucket
+    );
+
+    let (mut discrepancies, same_size) = compare_listings(&local_files, &remote_objects, locate);
+
+    let checks: Vec<Result<(String, Option<bool>)>> = stream::iter(same_size)
+        .map(|candidate| async {
+            let matches = content_matches(config, &uri.bucket, &candidate).await?;
+            Ok((candidate.relative, matches))
+        })
+        .buffer_unordered(VERIFY_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut size_only = 0;
+    for check in checks {
+        match check? {
+            (_, Some(true)) => {}
+            (relative, Some(false)) => discrepancies.push(Discrepancy::ChecksumMismatch(relative)),
+            (relative, None) => {
+                debug!("{relative}: no checksum to compare against; verified by size only");
+                size_only += 1;
+            }
+        }
+    }
+    discrepancies.sort_by(|a, b| discrepancy_path(a).cmp(discrepancy_path(b)));
+
+    for discrepancy in &discrepancies {
+        println!("{discrepancy}");
+    }
+    println!("{}", summary(local_files.len(), size_only, &discrepancies));
+
+    {
+        use crate::otel::OTEL_INSTRUMENTS;
+        use opentelemetry::KeyValue;
+
+        OTEL_INSTRUMENTS
+            .operations_total
+            .add(1, &[KeyValue::new("operation", "verify")]);
+        OTEL_INSTRUMENTS.operation_duration.record(
+            start_time.elapsed().as_millis() as f64 / 1000.0,
+            &[KeyValue::new("operation", "verify")],
+        );
+    }
+
+    if discrepancies.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Verification failed: {} discrepancies between {local_path} and {s3_uri}",
+            discrepancies.len()
+        ))
+    }
+}
+
+/// Missing, extra and different-size paths, plus the same-size pairs whose
+/// contents still need comparing
+fn compare_listings(
+    local: &HashMap<String, FileInfo>,
+    remote: &HashMap<String, FileInfo>,
+    locate: impl Fn(&str) -> (PathBuf, String),
+) -> (Vec<Discrepancy>, Vec<SameSize>) {
+    let mut discrepancies = Vec::new();
+    let mut same_size = Vec::new();
+
+    for (relative, local_file) in local {
+        match remote.get(relative) {
+            None => discrepancies.push(Discrepancy::Missing(relative.clone())),
+            Some(object) if object.size != local_file.size => {
+                discrepancies.push(Discrepancy::SizeMismatch {
+                    path: relative.clone(),
+                    local: local_file.size,
+                    remote: object.size,
+                })
+            }
+            Some(object) => {
+                let (local_path, key) = locate(relative);
+                same_size.push(SameSize {
+                    relative: relative.clone(),
+                    local_path,
+                    key,
+                    etag: object.etag.clone(),
+                    size: object.size as u64,
+                });
+            }
+        }
+    }
+    discrepancies.extend(
+        remote
+            .keys()
+            .filter(|relative| !local.contains_key(*relative))
+            .map(|relative| Discrepancy::Extra(relative.clone())),
+    );
+
+    discrepancies.sort();
+    same_size.sort_by(|a, b| a.relative.cmp(&b.relative));
+    (discrepancies, same_size)
+}
+
+/// Whether the local file has the object's content: `Some` when there is a
+/// checksum to compare, `None` when only the size could be checked
+async fn content_matches(config: &Config, bucket: &str, file: &SameSize) -> Result<Option<bool>> {
+    let etag = file.etag.as_deref();
+    if let Some(remote) = etag.and_then(sync::plain_md5) {
+        return Ok(Some(sync::md5_file(&file.local_path).await? == remote));
+    }
+
+    // Otherwise look for a checksum stored with the object: an additional
+    // checksum (`cp --checksum-algorithm`) or an MD5 in its metadata
+    let head = config
+        .client
+        .head_object()
+        .bucket(bucket)
+        .key(&file.key)
+        .checksum_mode(ChecksumMode::Enabled)
+        .send()
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!("Failed to read metadata of s3://{bucket}/{}: {e}", file.key)
+        })?;
+    if let Some(remote) = RemoteChecksum::from_head(&head) {
+        if let Some(local) = remote.local_equivalent(&file.local_path, file.size).await? {
+            return Ok(Some(local == remote.value));
+        }
+    }
+    if let Some(remote) = sync::metadata_md5(&head) {
+        return Ok(Some(sync::md5_file(&file.local_path).await? == remote));
+    }
+
+    // A multipart ETag can only be reproduced with the part size it was
+    // uploaded with; obsctl's default is tried when the part count agrees
+    let Some(parts) = etag.and_then(multipart_part_count) else {
+        return Ok(None);
+    };
+    let settings = MultipartSettings::default();
+    if settings.part_ranges(file.size).len() != parts {
+        return Ok(None);
+    }
+    let local = multipart_etag(&file.local_path, file.size, &settings).await?;
+    Ok(Some(
+        Some(local.as_str()) == etag.map(|etag| etag.trim_matches('"')),
+    ))
+}
+
+/// An additional checksum S3 stores for the object, as HeadObject returns it
+#[derive(Debug, PartialEq)]
+struct RemoteChecksum {
+    algorithm: ChecksumAlgorithm,
+    /// Base64; composite checksums of multipart uploads end in `-<parts>`
+    value: String,
+}
+
+impl RemoteChecksum {
+    /// The strongest checksum the object carries
+    fn from_head(head: &HeadObjectOutput) -> Option<Self> {
+        [
+            (ChecksumAlgorithm::Sha256, head.checksum_sha256()),
+            (ChecksumAlgorithm::Sha1, head.checksum_sha1()),
+            (ChecksumAlgorithm::Crc64Nvme, head.checksum_crc64_nvme()),
+            (ChecksumAlgorithm::Crc32c, head.checksum_crc32_c()),
+            (ChecksumAlgorithm::Crc32, head.checksum_crc32()),
+        ]
+        .into_iter()
+        .find_map(|(algorithm, value)| {
+            value.map(|value| Self {
+                algorithm,
+                value: value.to_string(),
+            })
+        })
+    }
+
+    /// Part count of a composite checksum
+    fn parts(&self) -> Option<usize> {
+        self.value.rsplit_once('-')?.1.parse().ok()
+    }
+
+    /// The same checksum computed over the local file. Like multipart ETags,
+    /// a composite checksum depends on the part size, so it is only
+    /// reproduced when obsctl's default part size gives the same part count.
+    async fn local_equivalent(&self, path: &Path, size: u64) -> Result<Option<String>> {
+        let Some(parts) = self.parts() else {
+            let digest = range_checksums(path, self.algorithm, &[(1, 0, size)]).await?;
+            return Ok(Some(b64.encode(&digest[0])));
+        };
+
+        let ranges = MultipartSettings::default().part_ranges(size);
+        if ranges.len() != parts {
+            return Ok(None);
+        }
+        let mut composite = self.algorithm.into_impl();
+        for digest in range_checksums(path, self.algorithm, &ranges).await? {
+            composite.update(&digest);
+        }
+        Ok(Some(format!(
+            "{}-{parts}",
+            b64.encode(composite.finalize())
+        )))
+    }
+}
+
+/// The raw checksum of each `(part, offset, length)` range of the file, read
+/// in order
+async fn range_checksums(
+    path: &Path,
+    algorithm: ChecksumAlgorithm,
+    ranges: &[(i32, u64, u64)],
+) -> Result<Vec<Vec<u8>>> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to open {} for hashing: {e}", path.display()))?;
+    let mut digests = Vec::with_capacity(ranges.len());
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    for (_, _, length) in ranges {
+        let mut checksum = algorithm.into_impl();
+        let mut remaining = *length;
+        while remaining > 0 {
+            let want = remaining.min(buffer.len() as u64) as usize;
+            let read = file.read(&mut buffer[..want]).await?;
+            if read == 0 {
+                return Err(anyhow::anyhow!(
+                    "{} changed size while being hashed",
+                    path.display()
+                ));
+            }
+            checksum.update(&buffer[..read]);
+            remaining -= read as u64;
+        }
+        digests.push(checksum.finalize().to_vec());
+    }
+
+    Ok(digests)
+}
+
+/// Part count of a multipart ETag (`"<md5>-<parts>"`)
+fn multipart_part_count(etag: &str) -> Option<usize> {
+    let (md5, parts) = etag.trim_matches('"').split_once('-')?;
+    (md5.len() == 32 && md5.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| parts.parse().ok())
+        .flatten()
+}
+
+/// The ETag S3 gives a multipart upload of the file: the MD5 of the
+/// concatenated part MD5s, followed by the part count
+async fn multipart_etag(path: &Path, size: u64, settings: &MultipartSettings) -> Result<String> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to open {} for hashing: {e}", path.display()))?;
+    let ranges = settings.part_ranges(size);
+    let mut digests = Vec::with_capacity(ranges.len() * 16);
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    for (_, _, length) in &ranges {
+        let mut context = md5::Context::new();
+        let mut remaining = *length;
+        while remaining > 0 {
+            let want = remaining.min(buffer.len() as u64) as usize;
+            let read = file.read(&mut buffer[..want]).await?;
+            if read == 0 {
+                return Err(anyhow::anyhow!(
+                    "{} changed size while being hashed",
+                    path.display()
+                ));
+            }
+            context.consume(&buffer[..read]);
+            remaining -= read as u64;
+        }
+        digests.extend_from_slice(&context.compute().0);
+    }
+
+    Ok(format!("{:x}-{}", md5::compute(&digests), ranges.len()))
+}
+
+fn discrepancy_path(discrepancy: &Discrepancy) -> &str {
+    match discrepancy {
+        Discrepancy::Missing(path)
+        | Discrepancy::Extra(path)
+        | Discrepancy::ChecksumMismatch(path)
+        | Discrepancy::SizeMismatch { path, .. } => path,
+    }
+}
+
+fn summary(checked: usize, size_only: usize, discrepancies: &[Discrepancy]) -> String {
+    let count =
+        |wanted: fn(&Discrepancy) -> bool| discrepancies.iter().filter(|d| wanted(d)).count();
+    let mut summary = format!(
+        "verify: {checked} local file(s) checked, {} missing, {} extra, {} differing",
+        count(|d| matches!(d, Discrepancy::Missing(_))),
+        count(|d| matches!(d, Discrepancy::Extra(_))),
+        count(|d| {
+            matches!(
+                d,
+                Discrepancy::SizeMismatch { .. } | Discrepancy::ChecksumMismatch(_)
+            )
+        }),
+    );
+    if size_only > 0 {
+        summary.push_str(&format!(" ({size_only} compared by size only)"));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(size: i64, etag: Option<&str>) -> FileInfo {
+        FileInfo {
+            size,
+            etag: etag.map(str::to_string),
+            modified: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_listings() {
+        let local = HashMap::from([
+            ("same.txt".to_string(), file(10, None)),
+            ("grown.txt".to_string(), file(20, None)),
+            ("only-local.txt".to_string(), file(5, None)),
+        ]);
+        let remote = HashMap::from([
+            ("same.txt".to_string(), file(10, Some("\"etag\""))),
+            ("grown.txt".to_string(), file(15, None)),
+            ("only-remote.txt".to_string(), file(5, None)),
+        ]);
+
+        let (discrepancies, same_size) = compare_listings(&local, &remote, |relative| {
+            (
+                Path::new("/backup").join(relative),
+                format!("data/{relative}"),
+            )
+        });
+        assert_eq!(
+            discrepancies,
+            vec![
+                Discrepancy::Missing("only-local.txt".to_string()),
+                Discrepancy::Extra("only-remote.txt".to_string()),
+                Discrepancy::SizeMismatch {
+                    path: "grown.txt".to_string(),
+                    local: 20,
+                    remote: 15,
+                },
+            ]
+        );
+        assert_eq!(
+            same_size,
+            vec![SameSize {
+                relative: "same.txt".to_string(),
+                local_path: PathBuf::from("/backup/same.txt"),
+                key: "data/same.txt".to_string(),
+                etag: Some("\"etag\"".to_string()),
+                size: 10,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_summary_and_display() {
+        let discrepancies = vec![
+            Discrepancy::Missing("a".to_string()),
+            Discrepancy::ChecksumMismatch("b".to_string()),
+            Discrepancy::SizeMismatch {
+                path: "c".to_string(),
+                local: 2,
+                remote: 1,
+            },
+        ];
+        assert_eq!(
+            summary(5, 1, &discrepancies),
+            "verify: 5 local file(s) checked, 1 missing, 0 extra, 2 differing (1 compared by size only)"
+        );
+        assert_eq!(discrepancies[1].to_string(), "differs: b (checksum)");
+        assert_eq!(
+            discrepancies[2].to_string(),
+            "differs: c (local 2 bytes, S3 1 bytes)"
+        );
+    }
+
+    #[test]
+    fn test_multipart_part_count() {
+        assert_eq!(
+            multipart_part_count("\"d41d8cd98f00b204e9800998ecf8427e-12\""),
+            Some(12)
+        );
+        assert_eq!(
+            multipart_part_count("d41d8cd98f00b204e9800998ecf8427e"),
+            None
+        );
+        assert_eq!(multipart_part_count("not-an-etag"), None);
+    }
+
+    #[tokio::test]
+    async fn test_multipart_etag() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("data.bin");
+        std::fs::write(&path, b"abcdefghij").unwrap();
+
+        let settings = MultipartSettings {
+            threshold: 0,
+            part_size: 4,
+        };
+        let mut digests = Vec::new();
+        for part in [&b"abcd"[..], b"efgh", b"ij"] {
+            digests.extend_from_slice(&md5::compute(part).0);
+        }
+        assert_eq!(
+            multipart_etag(&path, 10, &settings).await.unwrap(),
+            format!("{:x}-3", md5::compute(&digests))
+        );
+        assert!(multipart_etag(&path, 20, &settings).await.is_err());
+    }
+
+    #[test]
+    fn test_remote_checksum_from_head() {
+        let head = HeadObjectOutput::builder()
+            .checksum_crc32_c("4waSgw==")
+            .checksum_sha256("ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=")
+            .build();
+        let remote = RemoteChecksum::from_head(&head).unwrap();
+        assert_eq!(remote.algorithm, ChecksumAlgorithm::Sha256);
+        assert_eq!(remote.parts(), None);
+
+        let head = HeadObjectOutput::builder()
+            .checksum_crc32_c("pzJFoA==-3")
+            .build();
+        assert_eq!(RemoteChecksum::from_head(&head).unwrap().parts(), Some(3));
+        assert!(RemoteChecksum::from_head(&HeadObjectOutput::builder().build()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_remote_checksum_local_equivalent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("data.bin");
+        std::fs::write(&path, b"123456789").unwrap();
+
+        let full = RemoteChecksum {
+            algorithm: ChecksumAlgorithm::Crc32c,
+            value: "4waSgw==".to_string(),
+        };
+        assert_eq!(
+            full.local_equivalent(&path, 9).await.unwrap().as_deref(),
+            Some("4waSgw==")
+        );
+
+        // A checksum of the part checksums, here from a one-part upload
+        let composite = RemoteChecksum {
+            algorithm: ChecksumAlgorithm::Crc32c,
+            value: "pzJFoA==-1".to_string(),
+        };
+        assert_eq!(
+            composite
+                .local_equivalent(&path, 9)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("pzJFoA==-1")
+        );
+
+        // Uploaded with a part size that can't be reproduced
+        let other_part_size = RemoteChecksum {
+            algorithm: ChecksumAlgorithm::Crc32c,
+            value: "pzJFoA==-3".to_string(),
+        };
+        assert_eq!(
+            other_part_size.local_equivalent(&path, 9).await.unwrap(),
+            None
+        );
+    }
+}
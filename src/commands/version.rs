@@ -0,0 +1,92 @@
+use anyhow::Result;
+use serde::Serialize;
+
+/// Build metadata embedded by build.rs
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    pub rustc_version: &'static str,
+    pub target: &'static str,
+    pub profile: &'static str,
+    pub features: Vec<&'static str>,
+}
+
+impl BuildInfo {
+    /// Build information for the running binary
+    pub fn current() -> Self {
+        Self {
+            version: crate::get_full_version(),
+            git_commit: env!("OBSCTL_GIT_COMMIT"),
+            rustc_version: env!("OBSCTL_RUSTC_VERSION"),
+            target: env!("OBSCTL_TARGET"),
+            profile: env!("OBSCTL_BUILD_PROFILE"),
+            features: env!("OBSCTL_FEATURES")
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .collect(),
+        }
+    }
+}
+
+pub async fn execute(json: bool) -> Result<()> {
+    let info = BuildInfo::current();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else {
+        println!("obsctl {}", info.version);
+        println!("commit:   {}", info.git_commit);
+        println!("rustc:    {}", info.rustc_version);
+        println!("target:   {}", info.target);
+        println!("profile:  {}", info.profile);
+        println!(
+            "features: {}",
+            if info.features.is_empty() {
+                "none".to_string()
+            } else {
+                info.features.join(", ")
+            }
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_current() {
+        let info = BuildInfo::current();
+
+        assert_eq!(info.version, crate::VERSION);
+        assert!(!info.git_commit.is_empty());
+        assert!(!info.rustc_version.is_empty());
+        assert!(!info.target.is_empty());
+    }
+
+    #[test]
+    fn test_build_info_json_fields() {
+        let value = serde_json::to_value(BuildInfo::current()).unwrap();
+
+        for field in [
+            "version",
+            "git_commit",
+            "rustc_version",
+            "target",
+            "profile",
+            "features",
+        ] {
+            assert!(value.get(field).is_some(), "missing field {field}");
+        }
+        assert!(value["features"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_execute_json() {
+        assert!(execute(true).await.is_ok());
+        assert!(execute(false).await.is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use log::{debug, info};
+use std::time::{Duration, Instant};
+
+use crate::args::WaitCondition;
+use crate::commands::exists::{self, Presence, EXIT_ACCESS_DENIED, EXIT_ERROR};
+use crate::config::Config;
+use crate::errors::ExitCodeError;
+
+/// The condition didn't hold before `--wait-timeout` ran out
+pub const EXIT_TIMED_OUT: i32 = 1;
+
+/// Whether a poll's answer is the state being waited for
+fn satisfied(condition: WaitCondition, presence: Presence) -> bool {
+    matches!(
+        (condition, presence),
+        (WaitCondition::Exists, Presence::Exists) | (WaitCondition::NotExists, Presence::NotFound)
+    )
+}
+
+/// Poll the target every `interval` seconds until `condition` holds, for at
+/// most `timeout` seconds (0 for no limit). Transient failures are retried on
+/// the next poll; access denied won't change by waiting, so it ends the wait.
+pub async fn execute(
+    config: &Config,
+    s3_uri: &str,
+    condition: WaitCondition,
+    timeout: u64,
+    interval: u64,
+) -> Result<()> {
+    let fail = |code: i32, message: String| -> anyhow::Error {
+        ExitCodeError {
+            code,
+            message: Some(message),
+        }
+        .into()
+    };
+
+    let uri = exists::parse_target("wait", s3_uri).map_err(|e| fail(EXIT_ERROR, e.to_string()))?;
+    let start_time = Instant::now();
+    let deadline = (timeout > 0).then(|| start_time + Duration::from_secs(timeout));
+    let interval = Duration::from_secs(interval.max(1));
+    let mut attempts = 0u64;
+
+    loop {
+        attempts += 1;
+        let poll_start = Instant::now();
+        let (presence, error) = exists::check(config, &uri).await;
+        {
+            use opentelemetry::KeyValue;
+
+            crate::otel::record_read_operation(
+                &config.otel,
+                &[KeyValue::new("operation", "wait_poll")],
+                poll_start.elapsed(),
+            );
+        }
+        debug!("wait {uri}: poll {attempts}: {presence:?}");
+
+        if satisfied(condition, presence) {
+            info!(
+                "{uri} {} after {attempts} poll(s) ({:?})",
+                match condition {
+                    WaitCondition::Exists => "exists",
+                    WaitCondition::NotExists => "is gone",
+                },
+                start_time.elapsed()
+            );
+            return Ok(());
+        }
+        let last_error = match presence {
+            Presence::AccessDenied => {
+                let message = format!("access denied to {uri}");
+                crate::otel::OTEL_INSTRUMENTS.record_error_with_type(&message);
+                return Err(fail(EXIT_ACCESS_DENIED, message));
+            }
+            Presence::Failed => error,
+            Presence::Exists | Presence::NotFound => None,
+        };
+
+        let pause = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    let mut message = format!(
+                        "Timed out after {timeout}s waiting for {uri} to {} ({attempts} polls)",
+                        match condition {
+                            WaitCondition::Exists => "exist",
+                            WaitCondition::NotExists => "disappear",
+                        }
+                    );
+                    if let Some(e) = last_error {
+                        message.push_str(&format!("; last error: {e:#}"));
+                    }
+                    crate::otel::OTEL_INSTRUMENTS.record_error_with_type(&message);
+                    return Err(fail(EXIT_TIMED_OUT, message));
+                }
+                interval.min(remaining)
+            }
+            None => interval,
+        };
+        tokio::time::sleep(pause).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_client::canned_config;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_satisfied() {
+        assert!(satisfied(WaitCondition::Exists, Presence::Exists));
+        assert!(satisfied(WaitCondition::NotExists, Presence::NotFound));
+        assert!(!satisfied(WaitCondition::Exists, Presence::NotFound));
+        assert!(!satisfied(WaitCondition::NotExists, Presence::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_waits_until_the_object_appears() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let counter = polls.clone();
+        let config = canned_config(move |_, _| {
+            let status = if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                404
+            } else {
+                200
+            };
+            (status, String::new())
+        });
+
+        execute(
+            &config,
+            "s3://bucket/_SUCCESS",
+            WaitCondition::Exists,
+            30,
+            1,
+        )
+        .await
+        .unwrap();
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_times_out() {
+        let config = canned_config(|_, _| (200, String::new()));
+
+        let err = execute(&config, "s3://bucket/lock", WaitCondition::NotExists, 1, 1)
+            .await
+            .unwrap_err();
+        let exit = err.downcast::<ExitCodeError>().unwrap();
+        assert_eq!(exit.code, EXIT_TIMED_OUT);
+        assert!(exit.message.unwrap().contains("to disappear"));
+    }
+
+    #[tokio::test]
+    async fn test_access_denied_ends_the_wait() {
+        let config = canned_config(|_, _| (403, String::new()));
+
+        let err = execute(&config, "s3://bucket/key", WaitCondition::Exists, 0, 1)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ExitCodeError>().unwrap().code,
+            EXIT_ACCESS_DENIED
+        );
+    }
+}
@@ -1,13 +1,34 @@
 use anyhow::Result;
-use aws_config::{meta::region::RegionProviderChain, Region};
+use aws_config::sts::AssumeRoleProvider;
+use aws_config::{meta::region::RegionProviderChain, Region, SdkConfig};
+use aws_sdk_s3::config::interceptors::{
+    BeforeTransmitInterceptorContextMut, FinalizerInterceptorContextRef,
+};
+use aws_sdk_s3::config::timeout::TimeoutConfig;
+use aws_sdk_s3::config::{
+    ConfigBag, Intercept, ProvideCredentials, RequestChecksumCalculation, RuntimeComponents,
+    SharedCredentialsProvider, SharedHttpClient,
+};
+use aws_sdk_s3::error::BoxError;
 use aws_sdk_s3::Client;
+use aws_smithy_runtime_api::client::orchestrator::Metadata;
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-
-use crate::args::Args;
+use std::time::Duration;
+
+use crate::args::{AddressingStyle, Args, BucketCommands, Commands, SignatureVersion, TagCommands};
+use crate::commands::retry::{RetryInterceptor, RetryPolicy};
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+use crate::commands::upload::BandwidthLimiter;
+use crate::events::{CliEventSink, EventSink};
+use crate::filtering::parse_rate;
+use crate::otel::OtlpProtocol;
+use crate::proxy::ProxyConfig;
+use crate::sigv2::SigV2Interceptor;
+use crate::tls;
 
 #[derive(Debug, Clone)]
 pub struct OtelConfig {
@@ -15,6 +36,25 @@ pub struct OtelConfig {
     pub endpoint: Option<String>,
     pub service_name: String,
     pub service_version: String,
+    /// Record metrics for read-only commands (ls, du, head-object); off by
+    /// default so frequent listings don't swamp the dashboards
+    pub read_operations: bool,
+    /// `deployment.environment` resource attribute; "development" when unset
+    pub environment: Option<String>,
+    /// Extra resource attributes from `OTEL_RESOURCE_ATTRIBUTES`
+    pub resource_attributes: Vec<(String, String)>,
+    /// Label transfer metrics with the bucket name; turn off where thousands
+    /// of buckets would blow up metric cardinality
+    pub bucket_labels: bool,
+    /// OTLP transport; detected from the endpoint when unset
+    pub protocol: Option<OtlpProtocol>,
+}
+
+impl OtelConfig {
+    /// `bucket` as a metric label, or `None` when bucket labels are off
+    pub fn bucket_label<'a>(&self, bucket: &'a str) -> Option<&'a str> {
+        self.bucket_labels.then_some(bucket)
+    }
 }
 
 impl Default for OtelConfig {
@@ -24,6 +64,11 @@ impl Default for OtelConfig {
             endpoint: None,
             service_name: "obsctl".to_string(),
             service_version: env!("CARGO_PKG_VERSION").to_string(),
+            read_operations: false,
+            environment: None,
+            resource_attributes: Vec::new(),
+            bucket_labels: true,
+            protocol: None,
         }
     }
 }
@@ -31,92 +76,776 @@ impl Default for OtelConfig {
 pub struct Config {
     pub client: Arc<Client>,
     pub otel: OtelConfig,
+    /// Credentials come from a provider that can renew them (assume-role, SSO, web identity)
+    pub refreshable_credentials: bool,
+    /// Receives transfer, retry and error events from the commands
+    pub events: Arc<dyn EventSink>,
+    /// Retries for transient S3 errors, applied to every request (`--max-retries`)
+    pub retry: RetryPolicy,
+    /// Shared throughput cap for transfers (`--max-bandwidth`)
+    pub bandwidth: Option<Arc<BandwidthLimiter>>,
 }
 
 impl Config {
     pub async fn new(args: &Args) -> Result<Self> {
         // Read AWS config files first
         let aws_config = read_aws_config_files()?;
+        let profile = active_profile(args.profile.as_deref());
 
         // Set up AWS environment variables (config file values first, then env overrides)
-        setup_aws_environment(&aws_config, &args.debug)?;
+        setup_aws_environment(&aws_config, &profile, &args.debug)?;
 
         let region_provider =
             RegionProviderChain::first_try(Some(Region::new(args.region.clone())))
                 .or_default_provider()
                 .or_else(Region::new("ru-moscow-1"));
 
-        let shared_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-            .region(region_provider)
-            .load()
-            .await;
+        if args.no_verify_ssl {
+            eprintln!(
+                "WARNING: TLS certificate verification is disabled (--no-verify-ssl). \
+                 Connections can be intercepted; use this for development only, \
+                 never against production endpoints."
+            );
+        }
+        let ca_bundle = if args.no_verify_ssl {
+            None
+        } else {
+            resolve_ca_bundle(
+                args.ca_bundle.as_deref(),
+                env::var("AWS_CA_BUNDLE").ok(),
+                &aws_config,
+                &profile,
+            )
+        };
+        let proxy = ProxyConfig::resolve(args.proxy.as_deref())?;
+        let http_client = tls::build_http_client(
+            ca_bundle.as_deref().map(Path::new),
+            args.no_verify_ssl,
+            proxy,
+        )?;
+
+        let shared_config = load_shared_config(region_provider, &profile, http_client).await;
+
+        // Profiles with role_arn + source_profile are chained by the SDK's
+        // profile provider; --role-arn assumes a role on top of whatever
+        // credentials that chain resolved
+        let shared_config = match &args.role_arn {
+            Some(role_arn) => {
+                validate_role_arn(role_arn)?;
+                let provider =
+                    assume_role_provider(&shared_config, role_arn, args.external_id.as_deref())
+                        .await;
+                shared_config
+                    .into_builder()
+                    .credentials_provider(SharedCredentialsProvider::new(provider))
+                    .build()
+            }
+            None => shared_config,
+        };
+
+        // Configure OTEL from config file and environment
+        let mut otel = configure_otel(&aws_config, &profile)?;
+        if args.otel_read_ops {
+            otel.read_operations = true;
+        }
+        if args.otel_no_bucket_labels {
+            otel.bucket_labels = false;
+        }
+
+        let events: Arc<dyn EventSink> = Arc::new(CliEventSink::new(otel.bucket_labels));
+        let retry = RetryPolicy::new(args.max_retries);
 
         let mut s3_config_builder = aws_sdk_s3::config::Builder::from(&shared_config);
 
         // CRITICAL FIX: Handle endpoint from multiple sources with proper priority
-        // Priority: 1) CLI --endpoint flag, 2) AWS_ENDPOINT_URL env var, 3) config file
+        // Priority: 1) CLI --endpoint flag, 2) per-bucket [endpoints] mapping,
+        // 3) AWS_ENDPOINT_URL env var, 4) config file
+        let bucket_endpoints = load_bucket_endpoints(&aws_config)?;
+        let bucket_endpoint =
+            command_bucket(&args.command).and_then(|bucket| bucket_endpoints.get(&bucket).cloned());
         let endpoint_url = args
             .endpoint
             .clone()
+            .or(bucket_endpoint)
             .or_else(|| std::env::var("AWS_ENDPOINT_URL").ok())
             .or_else(|| {
-                let profile =
-                    std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
                 aws_config
                     .get(&profile)
                     .and_then(|profile_config| profile_config.get("endpoint_url"))
                     .cloned()
             });
 
+        let addressing_style =
+            resolve_addressing_style(args.addressing_style, &aws_config, &profile)?;
+        let signature_version =
+            resolve_signature_version(args.signature_version, &aws_config, &profile)?;
+        s3_config_builder = s3_config_builder.force_path_style(use_path_style(
+            addressing_style,
+            signature_version,
+            endpoint_url.is_some(),
+        )?);
+
         if let Some(endpoint) = endpoint_url {
+            validate_endpoint_url(&endpoint)?;
+            s3_config_builder = s3_config_builder.endpoint_url(endpoint);
+        }
+
+        if let Some(account_id) = &args.expected_bucket_owner {
+            validate_account_id(account_id)?;
+            s3_config_builder = s3_config_builder.interceptor(ExpectedBucketOwnerInterceptor {
+                account_id: account_id.clone(),
+            });
+        }
+
+        if args.sse_c_key.is_some() || args.sse_c_copy_source_key.is_some() {
+            s3_config_builder = s3_config_builder.interceptor(SseCustomerKeyInterceptor::new(
+                args.sse_c_key.as_deref(),
+                args.sse_c_copy_source_key.as_deref(),
+                args.sse_c_algorithm.as_deref(),
+            )?);
+        }
+
+        if signature_version == SignatureVersion::V2 {
+            // SigV2 re-signs the request after the SDK's SigV4 signer, so it
+            // needs the resolved keys up front. Presigned URLs stay SigV4.
+            let provider = shared_config
+                .credentials_provider()
+                .ok_or_else(|| anyhow::anyhow!("Signature v2 requires AWS credentials"))?;
+            let credentials = provider.provide_credentials().await.map_err(|e| {
+                anyhow::anyhow!("Failed to resolve credentials for signature v2: {e}")
+            })?;
             s3_config_builder = s3_config_builder
-                .endpoint_url(endpoint)
-                .force_path_style(true); // Required for MinIO and other S3-compatible services
+                .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+                .interceptor(sigv2_interceptor(&credentials)?);
         }
 
+        s3_config_builder = s3_config_builder
+            .timeout_config(build_timeout_config(
+                args.timeout,
+                args.connect_timeout,
+                args.read_timeout,
+            ))
+            .interceptor(TimeoutInterceptor)
+            .retry_config(retry.sdk_retry_config())
+            .interceptor(RetryInterceptor::new(events.clone()));
+
         let s3_config = s3_config_builder.build();
         let client = Arc::new(Client::from_conf(s3_config));
 
-        // Configure OTEL from config file and environment
-        let otel = configure_otel(&aws_config)?;
+        let refreshable_credentials =
+            args.role_arn.is_some() || uses_refreshable_credentials(&aws_config, &profile);
+
+        let bandwidth = args
+            .max_bandwidth
+            .as_deref()
+            .map(parse_rate)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid --max-bandwidth: {e}"))?
+            .map(|rate| Arc::new(BandwidthLimiter::new(rate)));
+
+        Ok(Config {
+            client,
+            otel,
+            refreshable_credentials,
+            events,
+            retry,
+            bandwidth,
+        })
+    }
+}
+
+/// `--timeout` bounds each request attempt and is the default connect
+/// timeout; `--read-timeout` is opt-in. Zero turns a timeout off.
+fn build_timeout_config(
+    timeout: u64,
+    connect_timeout: Option<u64>,
+    read_timeout: Option<u64>,
+) -> TimeoutConfig {
+    let seconds = |value: u64| (value > 0).then(|| Duration::from_secs(value));
+
+    let mut builder = TimeoutConfig::builder();
+    builder
+        .set_operation_attempt_timeout(seconds(timeout))
+        .set_connect_timeout(seconds(connect_timeout.unwrap_or(timeout)))
+        .set_read_timeout(read_timeout.and_then(seconds));
+    builder.build()
+}
+
+/// Operations whose duration grows with the object size; a short per-request
+/// timeout would cut large transfers off, so they're exempt from it
+const TRANSFER_OPERATIONS: &[&str] = &[
+    "GetObject",
+    "PutObject",
+    "UploadPart",
+    "CopyObject",
+    "UploadPartCopy",
+    "CompleteMultipartUpload",
+];
+
+/// Lifts the per-request and first-byte timeouts for transfers (downloads
+/// keep the first-byte one) and counts requests that end in a timeout
+#[derive(Debug)]
+struct TimeoutInterceptor;
+
+impl Intercept for TimeoutInterceptor {
+    fn name(&self) -> &'static str {
+        "TimeoutInterceptor"
+    }
+
+    fn modify_before_retry_loop(
+        &self,
+        _context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let operation = cfg
+            .load::<Metadata>()
+            .map(|metadata| metadata.name().to_string());
+        let Some(operation) = operation.filter(|name| TRANSFER_OPERATIONS.contains(&name.as_str()))
+        else {
+            return Ok(());
+        };
+
+        if let Some(timeouts) = cfg.load::<TimeoutConfig>() {
+            let mut relaxed = timeouts.to_builder().disable_operation_attempt_timeout();
+            if operation != "GetObject" {
+                relaxed = relaxed.disable_read_timeout();
+            }
+            cfg.interceptor_state().store_put(relaxed.build());
+        }
+        Ok(())
+    }
 
-        Ok(Config { client, otel })
+    fn read_after_execution(
+        &self,
+        context: &FinalizerInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if let Some(Err(err)) = context.output_or_error() {
+            let connector_timeout = err
+                .as_connector_error()
+                .is_some_and(|connector_error| connector_error.is_timeout());
+            if err.is_timeout_error() || connector_timeout {
+                crate::otel::OTEL_INSTRUMENTS.record_timeout();
+            }
+        }
+        Ok(())
     }
 }
 
-/// Read AWS configuration files (~/.aws/config and ~/.aws/credentials)
-fn read_aws_config_files() -> Result<HashMap<String, HashMap<String, String>>> {
-    let mut config = HashMap::new();
+/// Operations that aren't scoped to an existing bucket and reject or ignore
+/// the expected-owner header
+const ACCOUNT_SCOPED_OPERATIONS: &[&str] = &["ListBuckets", "CreateBucket"];
+
+/// Sends `x-amz-expected-bucket-owner` on every bucket request so S3 rejects
+/// calls against a bucket owned by a different account (`--expected-bucket-owner`)
+#[derive(Debug)]
+struct ExpectedBucketOwnerInterceptor {
+    account_id: String,
+}
+
+impl Intercept for ExpectedBucketOwnerInterceptor {
+    fn name(&self) -> &'static str {
+        "ExpectedBucketOwnerInterceptor"
+    }
 
-    // Check if AWS_CONFIG_FILE is set to a specific file
-    if let Ok(config_file_path) = std::env::var("AWS_CONFIG_FILE") {
-        let config_file = PathBuf::from(config_file_path);
-        if config_file.exists() {
-            let config_content = fs::read_to_string(&config_file)?;
-            parse_aws_config_file(&config_content, &mut config)?;
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let operation = cfg.load::<Metadata>().map(|metadata| metadata.name());
+        if operation.is_some_and(|name| ACCOUNT_SCOPED_OPERATIONS.contains(&name)) {
+            return Ok(());
         }
+
+        context
+            .request_mut()
+            .headers_mut()
+            .insert("x-amz-expected-bucket-owner", self.account_id.clone());
+        Ok(())
+    }
+}
+
+/// Operations that read or write SSE-C encrypted object data
+const SSE_C_OPERATIONS: &[&str] = &[
+    "GetObject",
+    "HeadObject",
+    "PutObject",
+    "CreateMultipartUpload",
+    "UploadPart",
+    "CompleteMultipartUpload",
+    "CopyObject",
+    "UploadPartCopy",
+];
+
+/// Copies that read an SSE-C source, which takes its own copy-source key
+const SSE_C_COPY_OPERATIONS: &[&str] = &["CopyObject", "UploadPartCopy"];
+
+/// A validated SSE-C key with the MD5 S3 checks it against
+#[derive(Debug)]
+struct SseCustomerKey {
+    algorithm: String,
+    key: String,
+    key_md5: String,
+}
+
+impl SseCustomerKey {
+    /// Validate the base64 key given with `flag` and precompute its MD5
+    fn new(flag: &str, key: &str, algorithm: Option<&str>) -> Result<Self> {
+        use base64::{engine::general_purpose::STANDARD as b64, Engine as _};
+
+        let algorithm = algorithm.unwrap_or("AES256");
+        if algorithm != "AES256" {
+            return Err(anyhow::anyhow!(
+                "Unsupported --sse-c-algorithm '{algorithm}': S3 only supports AES256"
+            ));
+        }
+
+        let raw = b64
+            .decode(key.trim())
+            .map_err(|e| anyhow::anyhow!("Invalid {flag}: expected a base64-encoded key ({e})"))?;
+        if raw.len() != 32 {
+            return Err(anyhow::anyhow!(
+                "Invalid {flag}: AES256 needs a 32-byte key, got {} bytes",
+                raw.len()
+            ));
+        }
+
+        Ok(Self {
+            algorithm: algorithm.to_string(),
+            key: b64.encode(&raw),
+            key_md5: b64.encode(md5::compute(&raw).as_ref()),
+        })
+    }
+
+    fn headers(&self, prefix: &str) -> [(String, String); 3] {
+        [
+            (
+                format!("{prefix}server-side-encryption-customer-algorithm"),
+                self.algorithm.clone(),
+            ),
+            (
+                format!("{prefix}server-side-encryption-customer-key"),
+                self.key.clone(),
+            ),
+            (
+                format!("{prefix}server-side-encryption-customer-key-md5"),
+                self.key_md5.clone(),
+            ),
+        ]
+    }
+}
+
+/// Sends the customer-provided key headers (`--sse-c-key`) on object
+/// requests, and the copy-source ones (`--sse-c-copy-source-key`) on copies.
+/// The two are separate because S3 rejects copy-source key headers for a
+/// source that isn't SSE-C encrypted.
+#[derive(Debug)]
+struct SseCustomerKeyInterceptor {
+    key: Option<SseCustomerKey>,
+    copy_source_key: Option<SseCustomerKey>,
+}
+
+impl SseCustomerKeyInterceptor {
+    fn new(
+        key: Option<&str>,
+        copy_source_key: Option<&str>,
+        algorithm: Option<&str>,
+    ) -> Result<Self> {
+        Ok(Self {
+            key: key
+                .map(|key| SseCustomerKey::new("--sse-c-key", key, algorithm))
+                .transpose()?,
+            copy_source_key: copy_source_key
+                .map(|key| SseCustomerKey::new("--sse-c-copy-source-key", key, algorithm))
+                .transpose()?,
+        })
+    }
+
+    fn headers(&self, operation: &str) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if !SSE_C_OPERATIONS.contains(&operation) {
+            return headers;
+        }
+
+        if let Some(key) = &self.key {
+            headers.extend(key.headers("x-amz-"));
+        }
+        if let Some(key) = self
+            .copy_source_key
+            .as_ref()
+            .filter(|_| SSE_C_COPY_OPERATIONS.contains(&operation))
+        {
+            headers.extend(key.headers("x-amz-copy-source-"));
+        }
+        headers
+    }
+}
+
+impl Intercept for SseCustomerKeyInterceptor {
+    fn name(&self) -> &'static str {
+        "SseCustomerKeyInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(operation) = cfg.load::<Metadata>().map(|metadata| metadata.name()) else {
+            return Ok(());
+        };
+
+        let request_headers = context.request_mut().headers_mut();
+        for (name, value) in self.headers(operation) {
+            request_headers.insert(name, value);
+        }
+        Ok(())
+    }
+}
+
+/// AWS account IDs are exactly 12 digits
+fn validate_account_id(account_id: &str) -> Result<()> {
+    if account_id.len() == 12 && account_id.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Invalid --expected-bucket-owner '{account_id}': expected a 12-digit AWS account ID"
+        ))
+    }
+}
+
+/// IAM role ARNs look like `arn:aws:iam::123456789012:role/name` (or another
+/// partition); catch typos before STS answers with a generic validation error
+fn validate_role_arn(role_arn: &str) -> Result<()> {
+    let parts: Vec<&str> = role_arn.splitn(6, ':').collect();
+    let valid = parts.len() == 6
+        && parts[0] == "arn"
+        && !parts[1].is_empty()
+        && parts[2] == "iam"
+        && parts[5].starts_with("role/")
+        && parts[5].len() > "role/".len();
+
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Invalid --role-arn '{role_arn}': expected an IAM role ARN like \
+             arn:aws:iam::123456789012:role/name"
+        ))
+    }
+}
+
+/// STS AssumeRole credentials for `--role-arn`, signed with the base
+/// credentials in `shared_config`. The SDK renews them before they expire.
+/// The SigV2 signer keeps the keys it was built with for the whole run, so
+/// temporary credentials (`--role-arn`, SSO, instance roles) would go stale
+/// when they expire; only long-term access keys are accepted
+fn sigv2_interceptor(credentials: &aws_sdk_s3::config::Credentials) -> Result<SigV2Interceptor> {
+    if credentials.session_token().is_some() || credentials.expiry().is_some() {
+        anyhow::bail!(
+            "Signature v2 needs long-term access keys, but the resolved credentials \
+             are temporary (they carry a session token or expiry). Use static \
+             aws_access_key_id/aws_secret_access_key, or signature v4."
+        );
+    }
+    Ok(SigV2Interceptor::new(
+        credentials.access_key_id().to_string(),
+        credentials.secret_access_key().to_string(),
+    ))
+}
+
+/// Load the SDK's shared config. The custom HTTP client goes on it so the
+/// credential providers (STS for `--role-arn`, SSO, IMDS) honour
+/// `--ca-bundle`, `--proxy` and `--no-verify-ssl` like S3 requests do
+async fn load_shared_config(
+    region_provider: RegionProviderChain,
+    profile: &str,
+    http_client: Option<SharedHttpClient>,
+) -> SdkConfig {
+    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(region_provider)
+        .profile_name(profile);
+    if let Some(http_client) = http_client {
+        loader = loader.http_client(http_client);
+    }
+    loader.load().await
+}
+
+async fn assume_role_provider(
+    shared_config: &SdkConfig,
+    role_arn: &str,
+    external_id: Option<&str>,
+) -> AssumeRoleProvider {
+    let mut builder = AssumeRoleProvider::builder(role_arn)
+        .session_name(format!("obsctl-{}", chrono::Utc::now().timestamp()))
+        .configure(shared_config);
+    if let Some(external_id) = external_id {
+        builder = builder.external_id(external_id);
+    }
+    builder.build().await
+}
+
+/// S3 endpoints must be absolute http(s) URLs; anything else only fails
+/// later as an unhelpful connection error
+pub fn validate_endpoint_url(endpoint: &str) -> Result<()> {
+    let problem = if endpoint.trim() != endpoint {
+        Some("leading or trailing whitespace".to_string())
+    } else if !endpoint.contains("://") {
+        // "host:port" would otherwise parse with the host as its scheme
+        Some("missing scheme".to_string())
     } else {
-        // Get AWS config directory
-        let aws_dir = get_aws_config_dir()?;
+        match url::Url::parse(endpoint) {
+            Ok(url) if !matches!(url.scheme(), "http" | "https") => {
+                Some(format!("unsupported scheme '{}'", url.scheme()))
+            }
+            Ok(url) if url.host_str().is_none_or(str::is_empty) => Some("no host".to_string()),
+            Ok(_) => None,
+            Err(e) => Some(e.to_string()),
+        }
+    };
+
+    match problem {
+        None => Ok(()),
+        Some(problem) => Err(anyhow::anyhow!(
+            "Invalid endpoint URL '{endpoint}': {problem}. Use a full URL with an http:// or \
+             https:// scheme, e.g. https://s3.example.com or http://localhost:9000"
+        )),
+    }
+}
+
+/// CA bundle from `--ca-bundle`, then `AWS_CA_BUNDLE`, then the profile's
+/// `ca_bundle` key, as the AWS CLI resolves it
+fn resolve_ca_bundle(
+    flag: Option<&str>,
+    env_bundle: Option<String>,
+    aws_config: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> Option<String> {
+    flag.map(str::to_string)
+        .or(env_bundle.filter(|path| !path.is_empty()))
+        .or_else(|| {
+            aws_config
+                .get(profile)
+                .and_then(|profile_config| profile_config.get("ca_bundle"))
+                .cloned()
+        })
+}
+
+/// Pick the addressing style from `--addressing-style`, falling back to the
+/// profile's `addressing_style` key (the AWS CLI's `s3` sub-setting), then auto
+fn resolve_addressing_style(
+    flag: Option<AddressingStyle>,
+    aws_config: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> Result<AddressingStyle> {
+    if let Some(style) = flag {
+        return Ok(style);
+    }
+
+    match aws_config
+        .get(profile)
+        .and_then(|profile_config| profile_config.get("addressing_style"))
+    {
+        Some(value) => AddressingStyle::from_config_value(value).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid addressing_style '{value}' in profile {profile}: expected auto, path or virtual"
+            )
+        }),
+        None => Ok(AddressingStyle::Auto),
+    }
+}
+
+/// Whether requests put the bucket in the path. Path style is what MinIO and
+/// most S3-compatible services expect, so `auto` picks it for custom
+/// endpoints. SigV2 signs the bucket as part of the path, so it always
+/// uses path style and rejects an explicit `virtual`.
+fn use_path_style(
+    style: AddressingStyle,
+    signature: SignatureVersion,
+    custom_endpoint: bool,
+) -> Result<bool> {
+    match (style, signature) {
+        (AddressingStyle::Virtual, SignatureVersion::V2) => Err(anyhow::anyhow!(
+            "Virtual-hosted addressing can't be used with signature v2; \
+             use --addressing-style path or auto"
+        )),
+        (_, SignatureVersion::V2) | (AddressingStyle::Path, _) => Ok(true),
+        (AddressingStyle::Virtual, _) => Ok(false),
+        (AddressingStyle::Auto, _) => Ok(custom_endpoint),
+    }
+}
+
+/// Pick the signing version from `--signature-version`, falling back to the
+/// profile's `signature_version` key (v2/s3 or v4/s3v4), then SigV4
+fn resolve_signature_version(
+    flag: Option<SignatureVersion>,
+    aws_config: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> Result<SignatureVersion> {
+    if let Some(version) = flag {
+        return Ok(version);
+    }
+
+    match aws_config
+        .get(profile)
+        .and_then(|profile_config| profile_config.get("signature_version"))
+    {
+        Some(value) => SignatureVersion::from_config_value(value).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid signature_version '{value}' in profile {profile}: expected v2 or v4"
+            )
+        }),
+        None => Ok(SignatureVersion::V4),
+    }
+}
+
+/// Load per-bucket endpoint overrides from the `[endpoints]` section of the AWS
+/// config files or the dedicated ~/.aws/endpoints file (which takes precedence)
+fn load_bucket_endpoints(
+    aws_config: &HashMap<String, HashMap<String, String>>,
+) -> Result<HashMap<String, String>> {
+    let mut endpoints = aws_config.get("endpoints").cloned().unwrap_or_default();
+
+    let endpoints_file = get_aws_config_dir()?.join("endpoints");
+    if endpoints_file.exists() {
+        let content = fs::read_to_string(&endpoints_file)?;
+        let mut file_config = HashMap::new();
+        parse_aws_config_file(&content, &mut file_config)?;
+        if let Some(section) = file_config.remove("endpoints") {
+            endpoints.extend(section);
+        }
+    }
+
+    Ok(endpoints)
+}
 
-        // Read ~/.aws/config
-        let config_file = aws_dir.join("config");
-        if config_file.exists() {
-            let config_content = fs::read_to_string(&config_file)?;
-            parse_aws_config_file(&config_content, &mut config)?;
+/// Find the bucket a command operates on so its endpoint can be resolved
+/// before the client is built. For transfers the first S3 URI wins.
+fn command_bucket(command: &Commands) -> Option<String> {
+    let uris: Vec<&str> = match command {
+        Commands::Ls { path, .. } => path.iter().map(|p| p.as_str()).collect(),
+        Commands::Cp { source, dest, .. }
+        | Commands::Sync { source, dest, .. }
+        | Commands::Mv { source, dest, .. } => {
+            vec![source.as_str(), dest.as_str()]
+        }
+        Commands::Rm { s3_uri, .. }
+        | Commands::Mb { s3_uri }
+        | Commands::Presign { s3_uri, .. }
+        | Commands::Restore { s3_uri, .. }
+        | Commands::Cat { s3_uri, .. }
+        | Commands::Exists { s3_uri, .. }
+        | Commands::Wait { s3_uri, .. }
+        | Commands::Du { s3_uri, .. }
+        | Commands::FindDupes { s3_uri, .. }
+        | Commands::Verify { s3_uri, .. } => vec![s3_uri.as_str()],
+        Commands::Rb { s3_uri, .. } => s3_uri.iter().map(|u| u.as_str()).collect(),
+        Commands::HeadObject { bucket, .. } => return Some(bucket.clone()),
+        Commands::Bucket {
+            command:
+                BucketCommands::CleanMultipart { s3_uri, .. }
+                | BucketCommands::Versioning { s3_uri, .. },
+        } => vec![s3_uri.as_str()],
+        Commands::Tag {
+            command:
+                TagCommands::Get { s3_uri }
+                | TagCommands::Set { s3_uri, .. }
+                | TagCommands::Remove { s3_uri },
+        } => vec![s3_uri.as_str()],
+        _ => Vec::new(),
+    };
+
+    uris.into_iter().find_map(|uri| {
+        if is_s3_uri(uri) {
+            S3Uri::parse(uri).ok().map(|parsed| parsed.bucket)
+        } else if matches!(
+            command,
+            Commands::Ls { .. } | Commands::Mb { .. } | Commands::Rb { .. }
+        ) {
+            // ls/mb/rb also accept a bare bucket name
+            uri.split('/')
+                .next()
+                .filter(|b| !b.is_empty())
+                .map(|b| b.to_string())
+        } else {
+            None
         }
+    })
+}
+
+/// Check whether the active profile resolves to temporary credentials that the
+/// SDK can renew, as opposed to static keys
+fn uses_refreshable_credentials(
+    aws_config: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> bool {
+    const REFRESHABLE_KEYS: &[&str] = &[
+        "role_arn",
+        "sso_session",
+        "sso_start_url",
+        "credential_process",
+        "web_identity_token_file",
+    ];
+
+    let profile_refreshable = aws_config.get(profile).is_some_and(|profile_config| {
+        REFRESHABLE_KEYS
+            .iter()
+            .any(|key| profile_config.contains_key(*key))
+    });
+
+    profile_refreshable
+        || std::env::var("AWS_WEB_IDENTITY_TOKEN_FILE").is_ok()
+        || std::env::var("AWS_CONTAINER_CREDENTIALS_FULL_URI").is_ok()
+        || std::env::var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI").is_ok()
+}
+
+/// Read AWS configuration files (~/.aws/config and ~/.aws/credentials)
+fn read_aws_config_files() -> Result<HashMap<String, HashMap<String, String>>> {
+    let mut config = HashMap::new();
+
+    let aws_config_file = std::env::var("AWS_CONFIG_FILE").ok();
+    let shared_credentials_file = std::env::var("AWS_SHARED_CREDENTIALS_FILE").ok();
+    let aws_dir = get_aws_config_dir()?;
 
-        // Read ~/.aws/credentials
-        let credentials_file = aws_dir.join("credentials");
-        if credentials_file.exists() {
-            let credentials_content = fs::read_to_string(&credentials_file)?;
-            parse_aws_config_file(&credentials_content, &mut config)?;
+    // AWS_CONFIG_FILE points at a specific file, otherwise ~/.aws/config
+    let config_file = aws_config_file
+        .as_deref()
+        .map_or_else(|| aws_dir.join("config"), PathBuf::from);
+    let credentials_file = credentials_file_path(
+        aws_config_file.as_deref(),
+        shared_credentials_file.as_deref(),
+        &aws_dir,
+    );
+
+    for file in std::iter::once(config_file).chain(credentials_file) {
+        if file.exists() {
+            let content = fs::read_to_string(&file)?;
+            parse_aws_config_file(&content, &mut config)?;
         }
     }
 
     Ok(config)
 }
 
+/// Credentials file to read: `AWS_SHARED_CREDENTIALS_FILE`, else
+/// ~/.aws/credentials, which a bare `AWS_CONFIG_FILE` has always skipped
+fn credentials_file_path(
+    aws_config_file: Option<&str>,
+    shared_credentials_file: Option<&str>,
+    aws_dir: &Path,
+) -> Option<PathBuf> {
+    match (shared_credentials_file, aws_config_file) {
+        (Some(path), _) => Some(PathBuf::from(path)),
+        (None, None) => Some(aws_dir.join("credentials")),
+        (None, Some(_)) => None,
+    }
+}
+
 /// Get the AWS configuration directory path
 fn get_aws_config_dir() -> Result<PathBuf> {
     if let Ok(aws_config_file) = std::env::var("AWS_CONFIG_FILE") {
@@ -182,15 +911,20 @@ fn parse_aws_config_file(
     Ok(())
 }
 
+/// Profile to read settings from: `--profile`, then `AWS_PROFILE`, then "default"
+pub fn active_profile(flag: Option<&str>) -> String {
+    flag.map(str::to_string)
+        .or_else(|| std::env::var("AWS_PROFILE").ok())
+        .unwrap_or_else(|| "default".to_string())
+}
+
 /// Set up AWS environment variables from config files and CLI args
 fn setup_aws_environment(
     aws_config: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
     debug_level: &str,
 ) -> Result<()> {
-    // Get the profile to use (default to "default")
-    let profile = std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
-
-    if let Some(profile_config) = aws_config.get(&profile) {
+    if let Some(profile_config) = aws_config.get(profile) {
         // Set AWS credentials if not already set by environment
         if std::env::var("AWS_ACCESS_KEY_ID").is_err() {
             if let Some(access_key) = profile_config.get("aws_access_key_id") {
@@ -235,7 +969,10 @@ fn setup_aws_environment(
 }
 
 /// Configure OpenTelemetry from config files and environment
-fn configure_otel(aws_config: &HashMap<String, HashMap<String, String>>) -> Result<OtelConfig> {
+fn configure_otel(
+    aws_config: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> Result<OtelConfig> {
     let mut otel_config = OtelConfig::default();
 
     // First, check for dedicated ~/.aws/otel file
@@ -263,14 +1000,27 @@ fn configure_otel(aws_config: &HashMap<String, HashMap<String, String>>) -> Resu
             if let Some(service_name) = otel_section.get("service_name") {
                 otel_config.service_name = service_name.clone();
             }
+
+            if let Some(read_ops_str) = otel_section.get("read_operations") {
+                otel_config.read_operations = read_ops_str.to_lowercase() == "true";
+            }
+
+            if let Some(environment) = otel_section.get("environment") {
+                otel_config.environment = Some(environment.clone());
+            }
+
+            if let Some(bucket_labels_str) = otel_section.get("bucket_labels") {
+                otel_config.bucket_labels = bucket_labels_str.to_lowercase() == "true";
+            }
+
+            if let Some(protocol) = otel_section.get("protocol") {
+                otel_config.protocol = Some(OtlpProtocol::parse(protocol)?);
+            }
         }
     }
 
-    // Get the profile to use (default to "default")
-    let profile = std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
-
     // Check for OTEL configuration in AWS config file (can override otel file)
-    if let Some(profile_config) = aws_config.get(&profile) {
+    if let Some(profile_config) = aws_config.get(profile) {
         // Check if OTEL is enabled in config file
         if let Some(enabled_str) = profile_config.get("otel_enabled") {
             otel_config.enabled = enabled_str.to_lowercase() == "true";
@@ -285,6 +1035,26 @@ fn configure_otel(aws_config: &HashMap<String, HashMap<String, String>>) -> Resu
         if let Some(service_name) = profile_config.get("otel_service_name") {
             otel_config.service_name = service_name.clone();
         }
+
+        // Whether ls, du and head-object record metrics
+        if let Some(read_ops_str) = profile_config.get("otel_read_operations") {
+            otel_config.read_operations = read_ops_str.to_lowercase() == "true";
+        }
+
+        // Get deployment environment (staging, production, ...) from config file
+        if let Some(environment) = profile_config.get("otel_environment") {
+            otel_config.environment = Some(environment.clone());
+        }
+
+        // Whether transfer metrics carry a bucket label
+        if let Some(bucket_labels_str) = profile_config.get("otel_bucket_labels") {
+            otel_config.bucket_labels = bucket_labels_str.to_lowercase() == "true";
+        }
+
+        // Get OTLP transport (grpc or http/protobuf) from config file
+        if let Some(protocol) = profile_config.get("otel_protocol") {
+            otel_config.protocol = Some(OtlpProtocol::parse(protocol)?);
+        }
     }
 
     // Environment variables override everything
@@ -300,13 +1070,48 @@ fn configure_otel(aws_config: &HashMap<String, HashMap<String, String>>) -> Resu
         otel_config.service_name = service_name;
     }
 
+    if let Ok(protocol) = std::env::var("OTEL_EXPORTER_OTLP_PROTOCOL") {
+        otel_config.protocol = Some(
+            OtlpProtocol::parse(&protocol)
+                .map_err(|e| anyhow::anyhow!("Invalid OTEL_EXPORTER_OTLP_PROTOCOL: {e}"))?,
+        );
+    }
+
+    if let Ok(environment) = std::env::var("OTEL_DEPLOYMENT_ENVIRONMENT") {
+        otel_config.environment = Some(environment);
+    }
+
+    if let Ok(attributes) = std::env::var("OTEL_RESOURCE_ATTRIBUTES") {
+        otel_config.resource_attributes = parse_resource_attributes(&attributes);
+    }
+
     Ok(otel_config)
 }
 
+/// Parse `OTEL_RESOURCE_ATTRIBUTES` (`key=value,key2=value2`). Entries
+/// without a key or `=` are skipped with a warning rather than failing startup.
+fn parse_resource_attributes(value: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    for entry in value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        match entry.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                attributes.push((key.trim().to_string(), value.trim().to_string()));
+            }
+            _ => log::warn!("Ignoring malformed OTEL_RESOURCE_ATTRIBUTES entry '{entry}'"),
+        }
+    }
+    attributes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::args::{Args, Commands};
+    use crate::args::{ColorChoice, OutputFormat, ProgressMode};
+    use clap::Parser;
 
     #[test]
     fn test_parse_aws_config_file() {
@@ -439,14 +1244,14 @@ key_with_empty_value =
         default_profile.insert("region".to_string(), "eu-central-1".to_string());
         aws_config.insert("default".to_string(), default_profile);
 
-        let result = setup_aws_environment(&aws_config, "debug");
+        let result = setup_aws_environment(&aws_config, "default", "debug");
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_setup_aws_environment_missing_profile() {
         let aws_config = HashMap::new(); // No profiles
-        let result = setup_aws_environment(&aws_config, "info");
+        let result = setup_aws_environment(&aws_config, "default", "info");
 
         // Should succeed even with missing profile
         assert!(result.is_ok());
@@ -468,7 +1273,7 @@ key_with_empty_value =
         );
         aws_config.insert("default".to_string(), default_profile);
 
-        let otel_config = configure_otel(&aws_config).unwrap();
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
 
         // Should use config file values
         assert!(otel_config.enabled);
@@ -476,6 +1281,111 @@ key_with_empty_value =
         assert_eq!(otel_config.service_name, "config-service");
     }
 
+    #[test]
+    fn test_parse_resource_attributes() {
+        assert_eq!(
+            parse_resource_attributes("team=storage, region = eu ,,note=a=b"),
+            vec![
+                ("team".to_string(), "storage".to_string()),
+                ("region".to_string(), "eu".to_string()),
+                ("note".to_string(), "a=b".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_resource_attributes("novalue,=orphan,ok="),
+            vec![("ok".to_string(), String::new())]
+        );
+        assert!(parse_resource_attributes("").is_empty());
+    }
+
+    #[test]
+    fn test_configure_otel_environment_from_profile() {
+        let mut aws_config = HashMap::new();
+        let mut default_profile = HashMap::new();
+        default_profile.insert("otel_environment".to_string(), "staging".to_string());
+        aws_config.insert("default".to_string(), default_profile);
+
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
+        assert_eq!(otel_config.environment.as_deref(), Some("staging"));
+    }
+
+    #[test]
+    fn test_active_profile_flag_wins() {
+        assert_eq!(active_profile(Some("staging")), "staging");
+    }
+
+    #[test]
+    fn test_configure_otel_reads_selected_profile() {
+        let mut aws_config = HashMap::new();
+        let mut default_profile = HashMap::new();
+        default_profile.insert("otel_service_name".to_string(), "default-svc".to_string());
+        aws_config.insert("default".to_string(), default_profile);
+        let mut staging_profile = HashMap::new();
+        staging_profile.insert("otel_service_name".to_string(), "staging-svc".to_string());
+        aws_config.insert("staging".to_string(), staging_profile);
+
+        assert_eq!(
+            configure_otel(&aws_config, "staging").unwrap().service_name,
+            "staging-svc"
+        );
+        assert_eq!(
+            configure_otel(&aws_config, "default").unwrap().service_name,
+            "default-svc"
+        );
+    }
+
+    #[test]
+    fn test_configure_otel_protocol_from_profile() {
+        let mut aws_config = HashMap::new();
+        let mut default_profile = HashMap::new();
+        default_profile.insert("otel_protocol".to_string(), "http/protobuf".to_string());
+        aws_config.insert("default".to_string(), default_profile);
+        assert_eq!(
+            configure_otel(&aws_config, "default").unwrap().protocol,
+            Some(OtlpProtocol::HttpProtobuf)
+        );
+
+        aws_config
+            .get_mut("default")
+            .unwrap()
+            .insert("otel_protocol".to_string(), "carrier-pigeon".to_string());
+        assert!(configure_otel(&aws_config, "default").is_err());
+    }
+
+    #[test]
+    fn test_configure_otel_bucket_labels_opt_out() {
+        assert!(
+            configure_otel(&HashMap::new(), "default")
+                .unwrap()
+                .bucket_labels
+        );
+
+        let mut aws_config = HashMap::new();
+        let mut default_profile = HashMap::new();
+        default_profile.insert("otel_bucket_labels".to_string(), "false".to_string());
+        aws_config.insert("default".to_string(), default_profile);
+
+        assert!(
+            !configure_otel(&aws_config, "default")
+                .unwrap()
+                .bucket_labels
+        );
+    }
+
+    #[test]
+    fn test_configure_otel_read_operations() {
+        let otel_config = configure_otel(&HashMap::new(), "default").unwrap();
+        assert!(!otel_config.read_operations);
+
+        let mut aws_config = HashMap::new();
+        let mut default_profile = HashMap::new();
+        default_profile.insert("otel_read_operations".to_string(), "true".to_string());
+        aws_config.insert("default".to_string(), default_profile);
+
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
+        assert!(otel_config.read_operations);
+    }
+
     #[test]
     fn test_configure_otel_case_insensitive() {
         let mut aws_config = HashMap::new();
@@ -483,7 +1393,7 @@ key_with_empty_value =
         default_profile.insert("otel_enabled".to_string(), "TRUE".to_string());
         aws_config.insert("default".to_string(), default_profile);
 
-        let otel_config = configure_otel(&aws_config).unwrap();
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
         assert!(otel_config.enabled);
 
         // Test false case
@@ -492,7 +1402,7 @@ key_with_empty_value =
         default_profile.insert("otel_enabled".to_string(), "FALSE".to_string());
         aws_config.insert("default".to_string(), default_profile);
 
-        let otel_config = configure_otel(&aws_config).unwrap();
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
         assert!(!otel_config.enabled);
     }
 
@@ -603,7 +1513,7 @@ key_with_empty_value =
         default_profile.insert("otel_service_name".to_string(), "test-service".to_string());
         aws_config.insert("default".to_string(), default_profile);
 
-        let otel_config = configure_otel(&aws_config).unwrap();
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
         assert!(otel_config.enabled);
         assert_eq!(otel_config.endpoint, Some("http://test:4317".to_string()));
         assert_eq!(otel_config.service_name, "test-service");
@@ -631,7 +1541,7 @@ key_with_empty_value =
         std::env::remove_var("OTEL_SERVICE_NAME");
         std::env::set_var("HOME", "/tmp/nonexistent"); // Fake home directory
 
-        let otel_config = configure_otel(&aws_config).unwrap();
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
         assert!(!otel_config.enabled);
         assert!(otel_config.endpoint.is_none());
 
@@ -655,7 +1565,7 @@ key_with_empty_value =
 
         // Test with real environment (when OTEL file exists)
         let aws_config = HashMap::new();
-        let otel_config = configure_otel(&aws_config).unwrap();
+        let otel_config = configure_otel(&aws_config, "default").unwrap();
 
         // This will pass if ~/.aws/otel exists with enabled=true
         // or fail if it doesn't exist (which is the expected default behavior)
@@ -671,7 +1581,34 @@ key_with_empty_value =
             debug: "info".to_string(),
             endpoint: None,
             region: "ru-moscow-1".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 10,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Ls {
                 path: None,
                 long: false,
@@ -679,17 +1616,31 @@ key_with_empty_value =
                 human_readable: false,
                 summarize: false,
                 pattern: None,
+                ignore_case: false,
                 created_after: None,
                 created_before: None,
                 modified_after: None,
                 modified_before: None,
                 min_size: None,
                 max_size: None,
+                extension: Vec::new(),
+                storage_class: Vec::new(),
                 max_results: None,
                 head: None,
                 tail: None,
                 sort_by: None,
                 reverse: false,
+                folders_only: false,
+                files_only: false,
+                show_owner: false,
+                show_acl: false,
+                show_etag: false,
+                enrich: false,
+                count: false,
+                versions: false,
+                parallel: None,
+                follow: false,
+                interval: 5,
             },
         };
 
@@ -707,7 +1658,34 @@ key_with_empty_value =
             debug: "debug".to_string(),
             endpoint: Some("https://custom.endpoint.com".to_string()),
             region: "us-west-2".to_string(),
+            profile: None,
+            role_arn: None,
+            external_id: None,
             timeout: 30,
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: 3,
+            max_bandwidth: None,
+            otel_read_ops: false,
+            otel_no_bucket_labels: false,
+            metrics_file: None,
+            summary_json: false,
+            metrics_backend: crate::args::MetricsBackend::Otlp,
+            statsd_addr: "127.0.0.1:8125".to_string(),
+            output: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            expected_bucket_owner: None,
+            progress: ProgressMode::Auto,
+            no_progress: false,
+            temp_dir: None,
+            signature_version: None,
+            addressing_style: None,
+            ca_bundle: None,
+            no_verify_ssl: false,
+            proxy: None,
+            sse_c_key: None,
+            sse_c_algorithm: None,
+            sse_c_copy_source_key: None,
             command: Commands::Ls {
                 path: None,
                 long: false,
@@ -715,17 +1693,31 @@ key_with_empty_value =
                 human_readable: false,
                 summarize: false,
                 pattern: None,
+                ignore_case: false,
                 created_after: None,
                 created_before: None,
                 modified_after: None,
                 modified_before: None,
                 min_size: None,
                 max_size: None,
+                extension: Vec::new(),
+                storage_class: Vec::new(),
                 max_results: None,
                 head: None,
                 tail: None,
                 sort_by: None,
                 reverse: false,
+                folders_only: false,
+                files_only: false,
+                show_owner: false,
+                show_acl: false,
+                show_etag: false,
+                enrich: false,
+                count: false,
+                versions: false,
+                parallel: None,
+                follow: false,
+                interval: 5,
             },
         };
 
@@ -747,7 +1739,34 @@ key_with_empty_value =
                 debug: level.to_string(),
                 endpoint: None,
                 region: "ru-moscow-1".to_string(),
+                profile: None,
+                role_arn: None,
+                external_id: None,
                 timeout: 10,
+                connect_timeout: None,
+                read_timeout: None,
+                max_retries: 3,
+                max_bandwidth: None,
+                otel_read_ops: false,
+                otel_no_bucket_labels: false,
+                metrics_file: None,
+                summary_json: false,
+                metrics_backend: crate::args::MetricsBackend::Otlp,
+                statsd_addr: "127.0.0.1:8125".to_string(),
+                output: OutputFormat::Text,
+                color: ColorChoice::Auto,
+                expected_bucket_owner: None,
+                progress: ProgressMode::Auto,
+                no_progress: false,
+                temp_dir: None,
+                signature_version: None,
+                addressing_style: None,
+                ca_bundle: None,
+                no_verify_ssl: false,
+                proxy: None,
+                sse_c_key: None,
+                sse_c_algorithm: None,
+                sse_c_copy_source_key: None,
                 command: Commands::Ls {
                     path: None,
                     long: false,
@@ -755,17 +1774,31 @@ key_with_empty_value =
                     human_readable: false,
                     summarize: false,
                     pattern: None,
+                    ignore_case: false,
                     created_after: None,
                     created_before: None,
                     modified_after: None,
                     modified_before: None,
                     min_size: None,
                     max_size: None,
+                    extension: Vec::new(),
+                    storage_class: Vec::new(),
                     max_results: None,
                     head: None,
                     tail: None,
                     sort_by: None,
                     reverse: false,
+                    folders_only: false,
+                    files_only: false,
+                    show_owner: false,
+                    show_acl: false,
+                    show_etag: false,
+                    enrich: false,
+                    count: false,
+                    versions: false,
+                    parallel: None,
+                    follow: false,
+                    interval: 5,
                 },
             };
 
@@ -782,7 +1815,34 @@ key_with_empty_value =
                 debug: "info".to_string(),
                 endpoint: None,
                 region: "ru-moscow-1".to_string(),
+                profile: None,
+                role_arn: None,
+                external_id: None,
                 timeout,
+                connect_timeout: None,
+                read_timeout: None,
+                max_retries: 3,
+                max_bandwidth: None,
+                otel_read_ops: false,
+                otel_no_bucket_labels: false,
+                metrics_file: None,
+                summary_json: false,
+                metrics_backend: crate::args::MetricsBackend::Otlp,
+                statsd_addr: "127.0.0.1:8125".to_string(),
+                output: OutputFormat::Text,
+                color: ColorChoice::Auto,
+                expected_bucket_owner: None,
+                progress: ProgressMode::Auto,
+                no_progress: false,
+                temp_dir: None,
+                signature_version: None,
+                addressing_style: None,
+                ca_bundle: None,
+                no_verify_ssl: false,
+                proxy: None,
+                sse_c_key: None,
+                sse_c_algorithm: None,
+                sse_c_copy_source_key: None,
                 command: Commands::Ls {
                     path: None,
                     long: false,
@@ -790,17 +1850,31 @@ key_with_empty_value =
                     human_readable: false,
                     summarize: false,
                     pattern: None,
+                    ignore_case: false,
                     created_after: None,
                     created_before: None,
                     modified_after: None,
                     modified_before: None,
                     min_size: None,
                     max_size: None,
+                    extension: Vec::new(),
+                    storage_class: Vec::new(),
                     max_results: None,
                     head: None,
                     tail: None,
                     sort_by: None,
                     reverse: false,
+                    folders_only: false,
+                    files_only: false,
+                    show_owner: false,
+                    show_acl: false,
+                    show_etag: false,
+                    enrich: false,
+                    count: false,
+                    versions: false,
+                    parallel: None,
+                    follow: false,
+                    interval: 5,
                 },
             };
 
@@ -817,7 +1891,34 @@ key_with_empty_value =
                 debug: "info".to_string(),
                 endpoint: None,
                 region: region.to_string(),
+                profile: None,
+                role_arn: None,
+                external_id: None,
                 timeout: 10,
+                connect_timeout: None,
+                read_timeout: None,
+                max_retries: 3,
+                max_bandwidth: None,
+                otel_read_ops: false,
+                otel_no_bucket_labels: false,
+                metrics_file: None,
+                summary_json: false,
+                metrics_backend: crate::args::MetricsBackend::Otlp,
+                statsd_addr: "127.0.0.1:8125".to_string(),
+                output: OutputFormat::Text,
+                color: ColorChoice::Auto,
+                expected_bucket_owner: None,
+                progress: ProgressMode::Auto,
+                no_progress: false,
+                temp_dir: None,
+                signature_version: None,
+                addressing_style: None,
+                ca_bundle: None,
+                no_verify_ssl: false,
+                proxy: None,
+                sse_c_key: None,
+                sse_c_algorithm: None,
+                sse_c_copy_source_key: None,
                 command: Commands::Ls {
                     path: None,
                     long: false,
@@ -825,21 +1926,433 @@ key_with_empty_value =
                     human_readable: false,
                     summarize: false,
                     pattern: None,
+                    ignore_case: false,
                     created_after: None,
                     created_before: None,
                     modified_after: None,
                     modified_before: None,
                     min_size: None,
                     max_size: None,
+                    extension: Vec::new(),
+                    storage_class: Vec::new(),
                     max_results: None,
                     head: None,
                     tail: None,
                     sort_by: None,
                     reverse: false,
+                    folders_only: false,
+                    files_only: false,
+                    show_owner: false,
+                    show_acl: false,
+                    show_etag: false,
+                    enrich: false,
+                    count: false,
+                    versions: false,
+                    parallel: None,
+                    follow: false,
+                    interval: 5,
                 },
             };
 
             assert_eq!(args.region, region);
         }
     }
+
+    #[test]
+    fn test_uses_refreshable_credentials() {
+        let mut aws_config = HashMap::new();
+        parse_aws_config_file(
+            "[profile role]\nrole_arn = arn:aws:iam::123456789012:role/test\nsource_profile = default\n\n[profile sso]\nsso_session = corp\n\n[static]\naws_access_key_id = AKIA\n",
+            &mut aws_config,
+        )
+        .unwrap();
+
+        assert!(uses_refreshable_credentials(&aws_config, "role"));
+        assert!(uses_refreshable_credentials(&aws_config, "sso"));
+
+        if std::env::var("AWS_WEB_IDENTITY_TOKEN_FILE").is_err()
+            && std::env::var("AWS_CONTAINER_CREDENTIALS_FULL_URI").is_err()
+            && std::env::var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI").is_err()
+        {
+            assert!(!uses_refreshable_credentials(&aws_config, "static"));
+            assert!(!uses_refreshable_credentials(&aws_config, "missing"));
+        }
+    }
+
+    #[test]
+    fn test_command_bucket() {
+        let bucket_of = |argv: &[&str]| command_bucket(&Args::parse_from(argv).command);
+
+        assert_eq!(
+            bucket_of(&["obsctl", "ls", "s3://logs/2024/"]),
+            Some("logs".to_string())
+        );
+        assert_eq!(
+            bucket_of(&["obsctl", "ls", "logs"]),
+            Some("logs".to_string())
+        );
+        assert_eq!(bucket_of(&["obsctl", "ls"]), None);
+        assert_eq!(
+            bucket_of(&["obsctl", "cp", "./file", "s3://minio-bucket/file"]),
+            Some("minio-bucket".to_string())
+        );
+        assert_eq!(
+            bucket_of(&["obsctl", "cp", "s3://src/a", "s3://dst/a"]),
+            Some("src".to_string())
+        );
+        assert_eq!(bucket_of(&["obsctl", "cp", "./a", "./b"]), None);
+        assert_eq!(
+            bucket_of(&["obsctl", "head-object", "--bucket", "b", "--key", "k"]),
+            Some("b".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bucket_endpoints_from_config_section() {
+        let mut aws_config = HashMap::new();
+        parse_aws_config_file(
+            "[endpoints]\nminio-bucket = http://localhost:9000\naws-bucket = https://s3.amazonaws.com\n",
+            &mut aws_config,
+        )
+        .unwrap();
+
+        let endpoints = aws_config.get("endpoints").unwrap();
+        assert_eq!(
+            endpoints.get("minio-bucket"),
+            Some(&"http://localhost:9000".to_string())
+        );
+        assert_eq!(endpoints.len(), 2);
+    }
+
+    #[test]
+    fn test_credentials_file_path() {
+        let aws_dir = Path::new("/home/user/.aws");
+        assert_eq!(
+            credentials_file_path(None, None, aws_dir),
+            Some(PathBuf::from("/home/user/.aws/credentials"))
+        );
+        assert_eq!(
+            credentials_file_path(None, Some("/run/secrets/aws"), aws_dir),
+            Some(PathBuf::from("/run/secrets/aws"))
+        );
+        assert_eq!(
+            credentials_file_path(Some("/etc/aws/config"), Some("/run/secrets/aws"), aws_dir),
+            Some(PathBuf::from("/run/secrets/aws"))
+        );
+        assert_eq!(
+            credentials_file_path(Some("/etc/aws/config"), None, aws_dir),
+            None
+        );
+    }
+
+    #[test]
+    fn test_validate_endpoint_url() {
+        assert!(validate_endpoint_url("http://localhost:9000").is_ok());
+        assert!(validate_endpoint_url("https://obs.ru-moscow-1.hc.sbercloud.ru").is_ok());
+
+        let err = validate_endpoint_url("localhost:9000").unwrap_err();
+        assert!(err.to_string().contains("missing scheme"));
+        assert!(validate_endpoint_url("s3.example.com").is_err());
+        assert!(validate_endpoint_url("http://localhost:9000 ").is_err());
+        assert!(validate_endpoint_url("ftp://files.example.com").is_err());
+        assert!(validate_endpoint_url("http://").is_err());
+    }
+
+    #[test]
+    fn test_resolve_addressing_style() {
+        let mut aws_config = HashMap::new();
+        assert_eq!(
+            resolve_addressing_style(None, &aws_config, "obs").unwrap(),
+            AddressingStyle::Auto
+        );
+
+        // The AWS CLI nests this under `s3 =`; the parser flattens it
+        parse_aws_config_file(
+            "[profile obs]\ns3 =\n    addressing_style = virtual\n",
+            &mut aws_config,
+        )
+        .unwrap();
+        assert_eq!(
+            resolve_addressing_style(None, &aws_config, "obs").unwrap(),
+            AddressingStyle::Virtual
+        );
+        assert_eq!(
+            resolve_addressing_style(Some(AddressingStyle::Path), &aws_config, "obs").unwrap(),
+            AddressingStyle::Path
+        );
+
+        aws_config
+            .get_mut("obs")
+            .unwrap()
+            .insert("addressing_style".to_string(), "sideways".to_string());
+        assert!(resolve_addressing_style(None, &aws_config, "obs").is_err());
+    }
+
+    #[test]
+    fn test_use_path_style() {
+        use AddressingStyle::{Auto, Path, Virtual};
+        use SignatureVersion::{V2, V4};
+
+        assert!(!use_path_style(Auto, V4, false).unwrap());
+        assert!(use_path_style(Auto, V4, true).unwrap());
+        assert!(use_path_style(Path, V4, false).unwrap());
+        assert!(!use_path_style(Virtual, V4, true).unwrap());
+
+        // SigV2 signs `/bucket/key`, so it forces path style
+        assert!(use_path_style(Auto, V2, false).unwrap());
+        assert!(use_path_style(Path, V2, true).unwrap());
+        assert!(use_path_style(Virtual, V2, true).is_err());
+    }
+
+    #[test]
+    fn test_resolve_ca_bundle() {
+        let mut aws_config = HashMap::new();
+        let mut profile_config = HashMap::new();
+        profile_config.insert("ca_bundle".to_string(), "/etc/profile-ca.pem".to_string());
+        aws_config.insert("dev".to_string(), profile_config);
+        let env_bundle = || Some("/etc/env-ca.pem".to_string());
+
+        assert_eq!(
+            resolve_ca_bundle(Some("/etc/flag-ca.pem"), env_bundle(), &aws_config, "dev")
+                .as_deref(),
+            Some("/etc/flag-ca.pem")
+        );
+        assert_eq!(
+            resolve_ca_bundle(None, env_bundle(), &aws_config, "dev").as_deref(),
+            Some("/etc/env-ca.pem")
+        );
+        assert_eq!(
+            resolve_ca_bundle(None, None, &aws_config, "dev").as_deref(),
+            Some("/etc/profile-ca.pem")
+        );
+        assert_eq!(resolve_ca_bundle(None, None, &aws_config, "default"), None);
+    }
+
+    #[test]
+    fn test_validate_role_arn() {
+        assert!(validate_role_arn("arn:aws:iam::123456789012:role/deploy").is_ok());
+        assert!(validate_role_arn("arn:aws-cn:iam::123456789012:role/path/to/role").is_ok());
+        assert!(validate_role_arn("arn:aws:iam::123456789012:user/alice").is_err());
+        assert!(validate_role_arn("arn:aws:iam::123456789012:role/").is_err());
+        assert!(validate_role_arn("deploy").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_uses_custom_http_client() {
+        let (http_client, requests) =
+            crate::commands::test_client::recording_http_client(|_, _| {
+                (500, Vec::new(), String::new())
+            });
+        let shared_config = load_shared_config(
+            RegionProviderChain::first_try(Region::new("us-east-1")),
+            "default",
+            Some(http_client),
+        )
+        .await
+        .into_builder()
+        .credentials_provider(SharedCredentialsProvider::new(
+            aws_sdk_s3::config::Credentials::new("AKID", "SECRET", None, None, "test"),
+        ))
+        .build();
+
+        let provider = assume_role_provider(
+            &shared_config,
+            "arn:aws:iam::123456789012:role/deploy",
+            None,
+        )
+        .await;
+        assert!(provider.provide_credentials().await.is_err());
+
+        let requests = requests.lock().unwrap();
+        assert!(!requests.is_empty());
+        assert!(requests.iter().all(|request| request.uri.contains("sts.")));
+    }
+
+    #[test]
+    fn test_sigv2_rejects_temporary_credentials() {
+        use aws_sdk_s3::config::Credentials;
+        use std::time::SystemTime;
+
+        assert!(sigv2_interceptor(&Credentials::new("AKID", "SECRET", None, None, "test")).is_ok());
+
+        let session = Credentials::new("AKID", "SECRET", Some("TOKEN".into()), None, "test");
+        let err = sigv2_interceptor(&session).unwrap_err();
+        assert!(err.to_string().contains("temporary"));
+
+        let expiring = Credentials::new(
+            "AKID",
+            "SECRET",
+            None,
+            Some(SystemTime::now() + Duration::from_secs(3600)),
+            "test",
+        );
+        assert!(sigv2_interceptor(&expiring).is_err());
+    }
+
+    #[test]
+    fn test_validate_account_id() {
+        assert!(validate_account_id("111122223333").is_ok());
+        assert!(validate_account_id("11112222333").is_err());
+        assert!(validate_account_id("1111-2222-3333").is_err());
+        assert!(validate_account_id("").is_err());
+    }
+
+    #[test]
+    fn test_sse_customer_key_headers() {
+        let key = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+        let interceptor = SseCustomerKeyInterceptor::new(Some(key), None, None).unwrap();
+
+        let get = interceptor.headers("GetObject");
+        assert_eq!(
+            get,
+            vec![
+                (
+                    "x-amz-server-side-encryption-customer-algorithm".to_string(),
+                    "AES256".to_string()
+                ),
+                (
+                    "x-amz-server-side-encryption-customer-key".to_string(),
+                    key.to_string()
+                ),
+                (
+                    "x-amz-server-side-encryption-customer-key-md5".to_string(),
+                    "tP/LI3N87DFaSk0aoqYgzg==".to_string()
+                ),
+            ]
+        );
+        // A plain source gets no copy-source headers unless its key is given
+        assert_eq!(interceptor.headers("CopyObject").len(), 3);
+        assert!(interceptor.headers("ListObjectsV2").is_empty());
+        assert!(interceptor.headers("DeleteObject").is_empty());
+
+        let reencrypt = SseCustomerKeyInterceptor::new(Some(key), Some(key), None).unwrap();
+        assert_eq!(reencrypt.headers("UploadPartCopy").len(), 6);
+        assert_eq!(reencrypt.headers("GetObject").len(), 3);
+
+        let decrypt = SseCustomerKeyInterceptor::new(None, Some(key), None).unwrap();
+        let copy = decrypt.headers("CopyObject");
+        assert_eq!(copy.len(), 3);
+        assert!(copy
+            .iter()
+            .all(|(name, _)| name.starts_with("x-amz-copy-source-")));
+        assert!(decrypt.headers("PutObject").is_empty());
+    }
+
+    #[test]
+    fn test_sse_customer_key_validation() {
+        let key = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+        assert!(SseCustomerKeyInterceptor::new(Some(key), None, Some("AES256")).is_ok());
+        assert!(SseCustomerKeyInterceptor::new(Some(key), None, Some("aws:kms")).is_err());
+        // 16-byte key
+        assert!(
+            SseCustomerKeyInterceptor::new(Some("AAECAwQFBgcICQoLDA0ODw=="), None, None).is_err()
+        );
+        assert!(SseCustomerKeyInterceptor::new(Some("not base64!"), None, None).is_err());
+        let err = SseCustomerKeyInterceptor::new(None, Some("not base64!"), None).unwrap_err();
+        assert!(err.to_string().contains("--sse-c-copy-source-key"));
+    }
+
+    #[test]
+    fn test_resolve_signature_version() {
+        let mut aws_config = HashMap::new();
+        assert_eq!(
+            resolve_signature_version(None, &aws_config, "prod").unwrap(),
+            SignatureVersion::V4
+        );
+
+        let mut profile_config = HashMap::new();
+        profile_config.insert("signature_version".to_string(), "s3".to_string());
+        aws_config.insert("prod".to_string(), profile_config);
+        assert_eq!(
+            resolve_signature_version(None, &aws_config, "prod").unwrap(),
+            SignatureVersion::V2
+        );
+        // The flag beats the config file
+        assert_eq!(
+            resolve_signature_version(Some(SignatureVersion::V4), &aws_config, "prod").unwrap(),
+            SignatureVersion::V4
+        );
+
+        aws_config
+            .get_mut("prod")
+            .unwrap()
+            .insert("signature_version".to_string(), "v3".to_string());
+        assert!(resolve_signature_version(None, &aws_config, "prod").is_err());
+    }
+
+    #[test]
+    fn test_build_timeout_config() {
+        let timeouts = build_timeout_config(10, None, None);
+        assert_eq!(
+            timeouts.operation_attempt_timeout(),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(timeouts.connect_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(timeouts.read_timeout(), None);
+
+        let timeouts = build_timeout_config(600, Some(3), Some(30));
+        assert_eq!(timeouts.connect_timeout(), Some(Duration::from_secs(3)));
+        assert_eq!(timeouts.read_timeout(), Some(Duration::from_secs(30)));
+
+        // Zero turns a timeout off
+        let timeouts = build_timeout_config(0, Some(5), Some(0));
+        assert_eq!(timeouts.operation_attempt_timeout(), None);
+        assert_eq!(timeouts.connect_timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.read_timeout(), None);
+    }
+
+    /// Never answers, so only a timeout ends the request
+    #[derive(Debug)]
+    struct HangingConnector;
+
+    impl aws_smithy_runtime_api::client::http::HttpConnector for HangingConnector {
+        fn call(
+            &self,
+            _request: aws_smithy_runtime_api::client::orchestrator::HttpRequest,
+        ) -> aws_smithy_runtime_api::client::http::HttpConnectorFuture {
+            aws_smithy_runtime_api::client::http::HttpConnectorFuture::new(std::future::pending())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_skip_transfers_and_are_counted() {
+        use aws_smithy_runtime_api::client::http::{http_client_fn, SharedHttpConnector};
+        use std::sync::atomic::Ordering;
+
+        let mut timeouts = TimeoutConfig::builder();
+        timeouts.set_operation_attempt_timeout(Some(Duration::from_millis(50)));
+        let client = Client::from_conf(
+            aws_sdk_s3::config::Builder::new()
+                .region(Region::new("us-east-1"))
+                .behavior_version(aws_config::BehaviorVersion::latest())
+                .credentials_provider(aws_sdk_s3::config::Credentials::new(
+                    "AKID", "SECRET", None, None, "test",
+                ))
+                .http_client(http_client_fn(|_, _| {
+                    SharedHttpConnector::new(HangingConnector)
+                }))
+                .retry_config(aws_sdk_s3::config::retry::RetryConfig::disabled())
+                .timeout_config(timeouts.build())
+                .interceptor(TimeoutInterceptor)
+                .build(),
+        );
+
+        let before = crate::otel::GLOBAL_METRICS
+            .timeouts_total
+            .load(Ordering::Relaxed);
+        let err = client.list_buckets().send().await.unwrap_err();
+        assert!(matches!(err, aws_sdk_s3::error::SdkError::TimeoutError(_)));
+        assert!(
+            crate::otel::GLOBAL_METRICS
+                .timeouts_total
+                .load(Ordering::Relaxed)
+                > before
+        );
+
+        // A download outlives the per-request timeout
+        let download = client.get_object().bucket("b").key("k").send();
+        assert!(tokio::time::timeout(Duration::from_millis(300), download)
+            .await
+            .is_err());
+    }
 }
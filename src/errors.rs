@@ -0,0 +1,250 @@
+//! Error reporting helpers shared by the CLI entry point
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::json;
+
+use crate::args::SignatureVersion;
+use crate::otel::classify_error_type;
+
+lazy_static! {
+    static ref S3_ERROR_CODE: Regex = Regex::new(r#"code: Some\("([^"]+)"\)"#).unwrap();
+}
+
+/// Lowercased fragments of the errors legacy gateways return for SigV4 requests
+const SIGV4_UNSUPPORTED_PATTERNS: &[&str] = &[
+    "unsupported authorization type",
+    "authorization mechanism you have provided is not supported",
+    "aws4-hmac-sha256 is not supported",
+    "signature version 4 is not supported",
+    "sigv4 is not supported",
+];
+
+/// Lowercased fragments of TLS certificate verification failures
+const TLS_VERIFICATION_PATTERNS: &[&str] = &[
+    "invalid peer certificate",
+    "unknownissuer",
+    "unknown issuer",
+    "certificate verify failed",
+    "self signed certificate",
+    "self-signed certificate",
+];
+
+/// Lowercased fragments of S3's answer to SSE-C object access without the key
+const SSE_C_KEY_REQUIRED_PATTERNS: &[&str] = &[
+    "stored using a form of server side encryption",
+    "the correct parameters must be provided",
+];
+
+/// Lowercased fragments of S3's answer to SSE-C access with the wrong key
+const SSE_C_KEY_MISMATCH_PATTERNS: &[&str] = &[
+    "the calculated md5 hash of the key did not match",
+    "provided key does not match",
+];
+
+/// Extract the S3 error code (e.g. `NoSuchKey`, `AccessDenied`) from an SDK
+/// error anywhere in the chain, if the service returned one
+pub fn extract_s3_error_code(err: &anyhow::Error) -> Option<String> {
+    let details = format!("{err:?}");
+    S3_ERROR_CODE
+        .captures(&details)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Explain an access-denied failure when `--expected-bucket-owner` is in
+/// effect: S3 answers an owner mismatch with a plain 403 AccessDenied
+pub fn bucket_owner_mismatch_hint(
+    err: &anyhow::Error,
+    expected_bucket_owner: Option<&str>,
+) -> Option<String> {
+    let account_id = expected_bucket_owner?;
+    if extract_s3_error_code(err).as_deref() != Some("AccessDenied") {
+        return None;
+    }
+
+    Some(format!(
+        "Access denied with --expected-bucket-owner {account_id}: the bucket may be owned by a different account \
+         (for example, deleted and recreated elsewhere). Confirm the bucket's owner before retrying, \
+         or check that your credentials can access it"
+    ))
+}
+
+/// Suggest `--signature-version v2` when a gateway rejects SigV4 signing
+pub fn signature_version_hint(
+    err: &anyhow::Error,
+    signature_version: Option<SignatureVersion>,
+) -> Option<String> {
+    if signature_version == Some(SignatureVersion::V2) {
+        return None;
+    }
+
+    let details = format!("{err:?}").to_lowercase();
+    SIGV4_UNSUPPORTED_PATTERNS
+        .iter()
+        .any(|pattern| details.contains(pattern))
+        .then(|| {
+            "The endpoint does not appear to support SigV4 request signing. For legacy S3-compatible \
+             gateways, retry with --signature-version v2 (or set signature_version = v2 in your profile)"
+                .to_string()
+        })
+}
+
+/// Point at `--ca-bundle` / `--no-verify-ssl` when the endpoint's certificate
+/// couldn't be verified (private CA or self-signed)
+pub fn tls_verification_hint(err: &anyhow::Error) -> Option<String> {
+    let details = format!("{err:?}").to_lowercase();
+    TLS_VERIFICATION_PATTERNS
+        .iter()
+        .any(|pattern| details.contains(pattern))
+        .then(|| {
+            "TLS certificate verification failed for the endpoint. Trust a private CA with \
+             --ca-bundle <path>, or skip verification for development with --no-verify-ssl"
+                .to_string()
+        })
+}
+
+/// Explain SSE-C failures: the object needs `--sse-c-key`, or the key given
+/// isn't the one it was written with
+pub fn sse_customer_key_hint(err: &anyhow::Error, key_provided: bool) -> Option<String> {
+    let details = format!("{err:?}").to_lowercase();
+    let matches = |patterns: &[&str]| patterns.iter().any(|pattern| details.contains(pattern));
+
+    if matches(SSE_C_KEY_MISMATCH_PATTERNS)
+        || (key_provided && matches(SSE_C_KEY_REQUIRED_PATTERNS))
+    {
+        Some(
+            "The object is encrypted with a different customer key (SSE-C). Check that \
+             --sse-c-key is the key the object was written with"
+                .to_string(),
+        )
+    } else if matches(SSE_C_KEY_REQUIRED_PATTERNS) {
+        Some(
+            "The object is encrypted with a customer-provided key (SSE-C). Pass the key with \
+             --sse-c-key <base64-key>, or --sse-c-copy-source-key when copying from it"
+                .to_string(),
+        )
+    } else {
+        None
+    }
+}
+
+/// A failure that ends the process with a specific exit code, for commands
+/// scripts branch on (`exists`). `message` is `None` when `--quiet` asked
+/// for nothing on stderr.
+#[derive(Debug)]
+pub struct ExitCodeError {
+    pub code: i32,
+    pub message: Option<String>,
+}
+
+impl std::fmt::Display for ExitCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(message) => f.write_str(message),
+            None => write!(f, "exit code {}", self.code),
+        }
+    }
+}
+
+impl std::error::Error for ExitCodeError {}
+
+/// Render an error as the single JSON object emitted in `--output json` mode
+pub fn error_to_json(err: &anyhow::Error) -> serde_json::Value {
+    let message = format!("{err:#}");
+
+    json!({
+        "error": {
+            "type": classify_error_type(&message),
+            "message": message,
+            "code": extract_s3_error_code(err),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_s3_error_code() {
+        let err = anyhow::anyhow!(
+            "ServiceError {{ meta: ErrorMetadata {{ code: Some(\"NoSuchBucket\"), message: None }} }}"
+        );
+        assert_eq!(
+            extract_s3_error_code(&err),
+            Some("NoSuchBucket".to_string())
+        );
+
+        let err = anyhow::anyhow!("Local file does not exist: /tmp/x");
+        assert_eq!(extract_s3_error_code(&err), None);
+    }
+
+    #[test]
+    fn test_bucket_owner_mismatch_hint() {
+        let denied = anyhow::anyhow!(
+            "ServiceError {{ meta: ErrorMetadata {{ code: Some(\"AccessDenied\"), message: Some(\"Access Denied\") }} }}"
+        );
+        let hint = bucket_owner_mismatch_hint(&denied, Some("111122223333")).unwrap();
+        assert!(hint.contains("--expected-bucket-owner 111122223333"));
+
+        // Only relevant when the guard is on, and only for access-denied errors
+        assert!(bucket_owner_mismatch_hint(&denied, None).is_none());
+        let missing = anyhow::anyhow!("ErrorMetadata {{ code: Some(\"NoSuchKey\") }}");
+        assert!(bucket_owner_mismatch_hint(&missing, Some("111122223333")).is_none());
+    }
+
+    #[test]
+    fn test_signature_version_hint() {
+        let rejected = anyhow::anyhow!(
+            "ServiceError {{ meta: ErrorMetadata {{ code: Some(\"InvalidRequest\"), message: Some(\"Unsupported Authorization Type\") }} }}"
+        );
+        let hint = signature_version_hint(&rejected, None).unwrap();
+        assert!(hint.contains("--signature-version v2"));
+
+        // No hint once v2 is already in use, or for unrelated errors
+        assert!(signature_version_hint(&rejected, Some(SignatureVersion::V2)).is_none());
+        let missing = anyhow::anyhow!("ErrorMetadata {{ code: Some(\"NoSuchKey\") }}");
+        assert!(signature_version_hint(&missing, None).is_none());
+    }
+
+    #[test]
+    fn test_tls_verification_hint() {
+        let err =
+            anyhow::anyhow!("dispatch failure: io error: invalid peer certificate: UnknownIssuer");
+        let hint = tls_verification_hint(&err).unwrap();
+        assert!(hint.contains("--ca-bundle") && hint.contains("--no-verify-ssl"));
+
+        assert!(tls_verification_hint(&anyhow::anyhow!("dispatch failure: timeout")).is_none());
+    }
+
+    #[test]
+    fn test_sse_customer_key_hint() {
+        let err = anyhow::anyhow!(
+            "InvalidRequest: The object was stored using a form of Server Side Encryption. \
+             The correct parameters must be provided to retrieve the object."
+        );
+        assert!(sse_customer_key_hint(&err, false)
+            .unwrap()
+            .contains("Pass the key with --sse-c-key"));
+        assert!(sse_customer_key_hint(&err, true)
+            .unwrap()
+            .contains("different customer key"));
+
+        assert!(sse_customer_key_hint(&anyhow::anyhow!("NoSuchKey"), true).is_none());
+    }
+
+    #[test]
+    fn test_error_to_json_shape() {
+        let err = anyhow::anyhow!("dispatch failure").context("Failed to list s3://bucket");
+        let value = error_to_json(&err);
+
+        assert_eq!(value["error"]["type"], "dns_network");
+        assert_eq!(
+            value["error"]["message"],
+            "Failed to list s3://bucket: dispatch failure"
+        );
+        assert!(value["error"]["code"].is_null());
+        // Serializes to a single line for consumers reading line-delimited JSON
+        assert!(!value.to_string().contains('\n'));
+    }
+}
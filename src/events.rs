@@ -0,0 +1,208 @@
+//! Hooks for observing transfers, retries and errors from embedding code
+//!
+//! Commands report through the [`EventSink`] on [`crate::Config`]. The CLI
+//! installs [`CliEventSink`], which records the usual metrics and log lines;
+//! library users can supply their own sink or use [`NoopEventSink`].
+
+use log::warn;
+use std::time::Duration;
+
+use crate::commands::s3_uri::{is_s3_uri, S3Uri};
+
+/// Direction of a single-object transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferKind {
+    Upload,
+    Download,
+}
+
+/// Callbacks fired by command implementations. Every method defaults to a
+/// no-op, so sinks only implement the events they care about.
+pub trait EventSink: Send + Sync {
+    /// A single-object transfer is about to begin; `size` is known for uploads
+    fn on_transfer_start(
+        &self,
+        _kind: TransferKind,
+        _source: &str,
+        _dest: &str,
+        _size: Option<u64>,
+    ) {
+    }
+
+    /// A single-object transfer finished successfully
+    fn on_transfer_complete(
+        &self,
+        _kind: TransferKind,
+        _source: &str,
+        _dest: &str,
+        _bytes: u64,
+        _duration: Duration,
+    ) {
+    }
+
+    /// A request failed transiently and is being retried
+    fn on_retry(&self, _operation: &str, _attempt: usize, _reason: &str) {}
+
+    /// An operation failed
+    fn on_error(&self, _operation: &str, _error: &anyhow::Error) {}
+}
+
+/// Ignores every event; the default for library use
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopEventSink;
+
+impl EventSink for NoopEventSink {}
+
+/// The CLI's sink: OpenTelemetry metrics plus warnings in the log
+#[derive(Debug, Clone, Copy)]
+pub struct CliEventSink {
+    bucket_labels: bool,
+}
+
+impl Default for CliEventSink {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl CliEventSink {
+    /// `bucket_labels` adds the bucket to transfer metrics
+    pub fn new(bucket_labels: bool) -> Self {
+        Self { bucket_labels }
+    }
+
+    /// Bucket of the S3 side of a transfer, unless bucket labels are off
+    fn bucket_label(&self, kind: TransferKind, source: &str, dest: &str) -> Option<String> {
+        let remote = match kind {
+            TransferKind::Upload => dest,
+            TransferKind::Download => source,
+        };
+        if !self.bucket_labels || !is_s3_uri(remote) {
+            return None;
+        }
+        S3Uri::parse(remote).ok().map(|uri| uri.bucket)
+    }
+}
+
+impl EventSink for CliEventSink {
+    fn on_transfer_complete(
+        &self,
+        kind: TransferKind,
+        source: &str,
+        dest: &str,
+        bytes: u64,
+        duration: Duration,
+    ) {
+        use crate::otel::OTEL_INSTRUMENTS;
+
+        let duration_ms = duration.as_millis() as u64;
+        let bucket = self.bucket_label(kind, source, dest);
+        match kind {
+            TransferKind::Upload => {
+                OTEL_INSTRUMENTS.record_upload(bytes, duration_ms, bucket.as_deref())
+            }
+            TransferKind::Download => {
+                OTEL_INSTRUMENTS.record_download(bytes, duration_ms, bucket.as_deref())
+            }
+        }
+    }
+
+    fn on_retry(&self, operation: &str, attempt: usize, reason: &str) {
+        warn!("{operation}: {reason}, retrying (attempt {attempt})");
+    }
+
+    fn on_error(&self, _operation: &str, error: &anyhow::Error) {
+        use crate::otel::OTEL_INSTRUMENTS;
+
+        OTEL_INSTRUMENTS.record_error_with_type(&error.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Captures transfer events the way an embedding application might
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl EventSink for Recorder {
+        fn on_transfer_start(
+            &self,
+            kind: TransferKind,
+            source: &str,
+            dest: &str,
+            _size: Option<u64>,
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("start {kind:?} {source} -> {dest}"));
+        }
+
+        fn on_transfer_complete(
+            &self,
+            kind: TransferKind,
+            _source: &str,
+            _dest: &str,
+            bytes: u64,
+            _duration: Duration,
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("complete {kind:?} {bytes}"));
+        }
+    }
+
+    #[test]
+    fn test_sinks_only_see_overridden_events() {
+        let recorder = Recorder::default();
+        let sink: &dyn EventSink = &recorder;
+
+        sink.on_transfer_start(TransferKind::Upload, "a.txt", "s3://b/a.txt", Some(3));
+        sink.on_retry("list", 1, "credentials expired");
+        sink.on_error("upload", &anyhow::anyhow!("boom"));
+        sink.on_transfer_complete(
+            TransferKind::Upload,
+            "a.txt",
+            "s3://b/a.txt",
+            3,
+            Duration::from_millis(5),
+        );
+
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec!["start Upload a.txt -> s3://b/a.txt", "complete Upload 3"]
+        );
+
+        // The no-op sink accepts everything silently
+        NoopEventSink.on_error("upload", &anyhow::anyhow!("ignored"));
+    }
+
+    #[test]
+    fn test_cli_sink_bucket_label() {
+        let sink = CliEventSink::default();
+        assert_eq!(
+            sink.bucket_label(TransferKind::Upload, "a.txt", "s3://photos/a.txt"),
+            Some("photos".to_string())
+        );
+        assert_eq!(
+            sink.bucket_label(TransferKind::Download, "s3://logs/x.gz", "x.gz"),
+            Some("logs".to_string())
+        );
+        assert_eq!(
+            sink.bucket_label(TransferKind::Upload, "s3://logs/x.gz", "x.gz"),
+            None
+        );
+
+        let unlabelled = CliEventSink::new(false);
+        assert_eq!(
+            unlabelled.bucket_label(TransferKind::Upload, "a.txt", "s3://photos/a.txt"),
+            None
+        );
+    }
+}
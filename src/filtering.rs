@@ -1,17 +1,29 @@
 use anyhow::{anyhow, Result};
 #[allow(unused_imports)] // Used in tests for .year(), .month(), .day() methods
 use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde::Serialize;
 use std::cmp::Ordering;
 
-/// Enhanced object information for filtering operations
-#[derive(Debug, Clone)]
+/// Enhanced object information for filtering operations. Serializes to the
+/// `ls --output json` row: S3 has no creation time, so `created` is left out.
+#[derive(Debug, Clone, Serialize)]
 pub struct EnhancedObjectInfo {
     pub key: String,
     pub size: i64,
+    #[serde(skip)]
     pub created: Option<DateTime<Utc>>,
+    #[serde(rename = "last_modified")]
     pub modified: Option<DateTime<Utc>>,
     pub storage_class: Option<String>,
     pub etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Set for rows from `ls --versions`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+    /// The row is a delete marker rather than a stored version
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub delete_marker: bool,
 }
 
 /// Filter configuration for advanced filtering operations
@@ -23,6 +35,10 @@ pub struct FilterConfig {
     pub modified_before: Option<DateTime<Utc>>,
     pub min_size: Option<i64>,
     pub max_size: Option<i64>,
+    /// Keep only keys ending in one of these extensions (without the dot)
+    pub extensions: Vec<String>,
+    /// Keep only objects in one of these storage classes (uppercase S3 names)
+    pub storage_classes: Vec<String>,
     pub max_results: Option<usize>,
     pub head: Option<usize>,
     pub tail: Option<usize>,
@@ -56,6 +72,8 @@ pub enum SortFieldType {
 pub enum SortDirection {
     Ascending,
     Descending,
+    /// Ascending, with runs of digits compared as numbers (names only)
+    Natural,
 }
 
 /// Date parsing errors
@@ -82,14 +100,15 @@ pub enum SizeParseError {
     UnsupportedUnit(String),
 }
 
-/// Parse date filter input (YYYYMMDD or relative format)
-pub fn parse_date_filter(input: &str) -> Result<DateTime<Utc>, DateParseError> {
+/// Parse date filter input (YYYYMMDD or relative format). Relative dates are
+/// measured back from `now`; the CLI passes `Utc::now()`.
+pub fn parse_date_filter(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, DateParseError> {
     match input {
         // YYYYMMDD format (20240101)
         s if s.len() == 8 && s.chars().all(|c| c.is_ascii_digit()) => parse_yyyymmdd(s),
         // Relative format (7d, 30d, 1y)
         s if s.ends_with('d') || s.ends_with('w') || s.ends_with('m') || s.ends_with('y') => {
-            parse_relative_date(s)
+            parse_relative_date(s, now)
         }
         _ => Err(DateParseError::InvalidFormat(input.to_string())),
     }
@@ -127,8 +146,8 @@ fn parse_yyyymmdd(input: &str) -> Result<DateTime<Utc>, DateParseError> {
         .ok_or_else(|| DateParseError::InvalidDate(input.to_string()))
 }
 
-/// Parse relative date format (7d, 30d, 1y)
-fn parse_relative_date(input: &str) -> Result<DateTime<Utc>, DateParseError> {
+/// Parse relative date format (7d, 30d, 1y) as an offset before `now`
+fn parse_relative_date(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, DateParseError> {
     let (number_part, unit_part) = input.split_at(input.len() - 1);
 
     let number: i64 = number_part
@@ -149,7 +168,7 @@ fn parse_relative_date(input: &str) -> Result<DateTime<Utc>, DateParseError> {
         _ => return Err(DateParseError::InvalidRelativeDate(input.to_string())),
     };
 
-    Ok(Utc::now() - duration)
+    Ok(now - duration)
 }
 
 /// Parse size filter input (with MB default)
@@ -191,6 +210,24 @@ pub fn parse_size_filter(input: &str) -> Result<i64, SizeParseError> {
     Ok((number * multiplier as f64) as i64)
 }
 
+/// Parse a transfer rate such as `10MB/s` or `512KiB/s` into bytes per
+/// second. The `/s` is optional and a bare number means MB/s, as with sizes.
+pub fn parse_rate(input: &str) -> Result<u64, SizeParseError> {
+    let input = input.trim();
+    let size = input
+        .strip_suffix("/s")
+        .or_else(|| input.strip_suffix("/S"))
+        .unwrap_or(input);
+
+    let bytes = parse_size_filter(size)?;
+    if bytes <= 0 {
+        return Err(SizeParseError::InvalidValue(format!(
+            "{input}: rate must be greater than zero"
+        )));
+    }
+    Ok(bytes as u64)
+}
+
 /// Extract number and unit from size string
 fn extract_number_and_unit(input: &str) -> Result<(String, String), SizeParseError> {
     let mut number_end = 0;
@@ -243,9 +280,17 @@ pub fn parse_sort_config(input: &str) -> Result<SortConfig> {
         let direction = match direction.to_lowercase().as_str() {
             "asc" | "ascending" => SortDirection::Ascending,
             "desc" | "descending" => SortDirection::Descending,
+            "natural" => SortDirection::Natural,
             _ => return Err(anyhow!("Invalid sort direction: {}", direction)),
         };
 
+        if direction == SortDirection::Natural && field_type != SortFieldType::Name {
+            return Err(anyhow!(
+                "Natural sort only applies to name, not {}",
+                field_name
+            ));
+        }
+
         fields.push(SortField {
             field_type,
             direction,
@@ -430,7 +475,7 @@ where
 }
 
 /// Check if an object passes all filters
-fn passes_filters(obj: &EnhancedObjectInfo, config: &FilterConfig) -> bool {
+pub fn passes_filters(obj: &EnhancedObjectInfo, config: &FilterConfig) -> bool {
     // Date filters
     if let Some(created_after) = config.created_after {
         if let Some(created) = obj.created {
@@ -485,7 +530,30 @@ fn passes_filters(obj: &EnhancedObjectInfo, config: &FilterConfig) -> bool {
         }
     }
 
-    true
+    // Storage class filter; S3-compatible stores may omit the class for
+    // STANDARD objects, so a missing one counts as STANDARD
+    if !config.storage_classes.is_empty() {
+        let class = obj.storage_class.as_deref().unwrap_or("STANDARD");
+        if !config.storage_classes.iter().any(|wanted| wanted == class) {
+            return false;
+        }
+    }
+
+    // Extension filter
+    config.extensions.is_empty() || has_extension(&obj.key, &config.extensions)
+}
+
+/// Check whether the key's final path segment ends in `.ext` for one of the
+/// extensions, ignoring case (`photo.JPG` matches `jpg`)
+pub fn has_extension(key: &str, extensions: &[String]) -> bool {
+    let name = key.rsplit('/').next().unwrap_or(key);
+    let Some((stem, ext)) = name.rsplit_once('.') else {
+        return false;
+    };
+    !stem.is_empty()
+        && extensions
+            .iter()
+            .any(|wanted| wanted.trim_start_matches('.').eq_ignore_ascii_case(ext))
 }
 
 /// Compare two objects for sorting
@@ -496,6 +564,9 @@ fn compare_objects(
 ) -> Ordering {
     for field in &sort_config.fields {
         let ordering = match field.field_type {
+            SortFieldType::Name if field.direction == SortDirection::Natural => {
+                natural_cmp(&a.key, &b.key)
+            }
             SortFieldType::Name => a.key.cmp(&b.key),
             SortFieldType::Size => a.size.cmp(&b.size),
             SortFieldType::Created => match (a.created, b.created) {
@@ -513,7 +584,7 @@ fn compare_objects(
         };
 
         let final_ordering = match field.direction {
-            SortDirection::Ascending => ordering,
+            SortDirection::Ascending | SortDirection::Natural => ordering,
             SortDirection::Descending => ordering.reverse(),
         };
 
@@ -525,6 +596,55 @@ fn compare_objects(
     Ordering::Equal
 }
 
+/// Compare keys chunk by chunk, with runs of digits ordered by numeric value,
+/// so `file2` sorts before `file10`. Keys that only differ in leading zeros
+/// fall back to plain byte order.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (a_chunks, b_chunks) = (natural_chunks(a), natural_chunks(b));
+
+    for (x, y) in a_chunks.iter().zip(&b_chunks) {
+        let both_numeric = x.starts_with(|c: char| c.is_ascii_digit())
+            && y.starts_with(|c: char| c.is_ascii_digit());
+        let ordering = if both_numeric {
+            compare_digit_runs(x, y)
+        } else {
+            x.cmp(y)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a_chunks.len().cmp(&b_chunks.len()).then_with(|| a.cmp(b))
+}
+
+/// Split text into alternating runs of ASCII digits and everything else
+fn natural_chunks(text: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut in_digits = None;
+
+    for (index, c) in text.char_indices() {
+        let is_digit = c.is_ascii_digit();
+        if in_digits.is_some_and(|current| current != is_digit) {
+            chunks.push(&text[start..index]);
+            start = index;
+        }
+        in_digits = Some(is_digit);
+    }
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+    chunks
+}
+
+/// Numeric comparison of two digit runs of any length, without parsing them
+fn compare_digit_runs(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 /// Validate filter configuration for conflicts
 pub fn validate_filter_config(config: &FilterConfig) -> Result<()> {
     // Check date range validity
@@ -574,7 +694,7 @@ mod tests {
 
     #[test]
     fn test_parse_yyyymmdd() {
-        let result = parse_date_filter("20240101").unwrap();
+        let result = parse_date_filter("20240101", Utc::now()).unwrap();
         assert_eq!(result.year(), 2024);
         assert_eq!(result.month(), 1);
         assert_eq!(result.day(), 1);
@@ -582,20 +702,33 @@ mod tests {
 
     #[test]
     fn test_parse_yyyymmdd_invalid() {
-        assert!(parse_date_filter("20241301").is_err()); // Invalid month
-        assert!(parse_date_filter("20240132").is_err()); // Invalid day
-        assert!(parse_date_filter("2024010").is_err()); // Wrong length
+        assert!(parse_date_filter("20241301", Utc::now()).is_err()); // Invalid month
+        assert!(parse_date_filter("20240132", Utc::now()).is_err()); // Invalid day
+        assert!(parse_date_filter("2024010", Utc::now()).is_err()); // Wrong length
     }
 
     #[test]
     fn test_parse_relative_date() {
-        let result = parse_date_filter("7d").unwrap();
-        let expected = Utc::now() - Duration::days(7);
-        assert!((result - expected).num_seconds().abs() < 60); // Within 1 minute
-
-        let result = parse_date_filter("2w").unwrap();
-        let expected = Utc::now() - Duration::weeks(2);
-        assert!((result - expected).num_seconds().abs() < 60);
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 12, 30, 0).unwrap();
+
+        assert_eq!(
+            parse_date_filter("7d", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 8, 12, 30, 0).unwrap()
+        );
+        assert_eq!(
+            parse_date_filter("2w", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 30, 0).unwrap()
+        );
+        // Months and years are approximated as 30 and 365 days
+        assert_eq!(
+            parse_date_filter("1m", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 14, 12, 30, 0).unwrap()
+        );
+        assert_eq!(
+            parse_date_filter("1y", now).unwrap(),
+            Utc.with_ymd_and_hms(2023, 3, 16, 12, 30, 0).unwrap()
+        );
+        assert!(parse_date_filter("0d", now).is_err());
     }
 
     #[test]
@@ -607,6 +740,17 @@ mod tests {
         assert_eq!(parse_size_filter("1024B").unwrap(), 1024);
     }
 
+    #[test]
+    fn test_parse_rate() {
+        assert_eq!(parse_rate("10MB/s").unwrap(), 10_000_000);
+        assert_eq!(parse_rate("512KiB/s").unwrap(), 524_288);
+        assert_eq!(parse_rate("1GB").unwrap(), 1_000_000_000);
+        assert_eq!(parse_rate("5").unwrap(), 5 * 1_048_576);
+        assert!(parse_rate("0MB/s").is_err());
+        assert!(parse_rate("10XB/s").is_err());
+        assert!(parse_rate("fast").is_err());
+    }
+
     #[test]
     fn test_parse_size_filter_invalid() {
         assert!(parse_size_filter("-100MB").is_err()); // Negative
@@ -624,6 +768,67 @@ mod tests {
         assert_eq!(config.fields[1].direction, SortDirection::Ascending);
     }
 
+    #[test]
+    fn test_natural_sort() {
+        let object = |key: &str| EnhancedObjectInfo {
+            key: key.to_string(),
+            size: 0,
+            created: None,
+            modified: None,
+            storage_class: None,
+            etag: None,
+            owner: None,
+            version_id: None,
+            delete_marker: false,
+        };
+        let objects: Vec<EnhancedObjectInfo> =
+            ["file10.txt", "file2.txt", "file20.txt", "file1.txt"]
+                .into_iter()
+                .map(object)
+                .collect();
+
+        let sort = |spec: &str| -> Vec<String> {
+            let config = FilterConfig {
+                sort_config: parse_sort_config(spec).unwrap(),
+                ..Default::default()
+            };
+            apply_filters(&objects, &config)
+                .into_iter()
+                .map(|obj| obj.key)
+                .collect()
+        };
+
+        assert_eq!(
+            sort("name:natural"),
+            vec!["file1.txt", "file2.txt", "file10.txt", "file20.txt"]
+        );
+        // Plain ascending order is unchanged
+        assert_eq!(
+            sort("name:asc"),
+            vec!["file1.txt", "file10.txt", "file2.txt", "file20.txt"]
+        );
+        assert_eq!(
+            sort("name:desc"),
+            vec!["file20.txt", "file2.txt", "file10.txt", "file1.txt"]
+        );
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("a2", "a10"), Ordering::Less);
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+        assert_eq!(natural_cmp("img", "img1"), Ordering::Less);
+        assert_eq!(natural_cmp("a/2/b", "a/10"), Ordering::Less);
+        assert_eq!(
+            natural_cmp("99999999999999999999999", "100000000000000000000000"),
+            Ordering::Less
+        );
+        // Same numeric value: leading zeros decide by byte order, never Equal
+        assert_eq!(natural_cmp("f01", "f1"), Ordering::Less);
+        assert_eq!(natural_cmp("f1", "f1"), Ordering::Equal);
+        assert!(parse_sort_config("size:natural").is_err());
+    }
+
     #[test]
     fn test_parse_sort_config_default_direction() {
         let config = parse_sort_config("name,size").unwrap();
@@ -669,6 +874,9 @@ mod tests {
                 modified: Some(old_date),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "recent_file.txt".to_string(),
@@ -677,6 +885,9 @@ mod tests {
                 modified: Some(recent_date),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
         ];
 
@@ -700,6 +911,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "large_file.txt".to_string(),
@@ -708,6 +922,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
         ];
 
@@ -722,6 +939,86 @@ mod tests {
         assert_eq!(filtered[0].key, "large_file.txt");
     }
 
+    #[test]
+    fn test_apply_filters_extension() {
+        let object = |key: &str, size: i64| EnhancedObjectInfo {
+            key: key.to_string(),
+            size,
+            created: None,
+            modified: None,
+            storage_class: None,
+            etag: None,
+            owner: None,
+            version_id: None,
+            delete_marker: false,
+        };
+        let objects = vec![
+            object("photos/a.jpg", 5000),
+            object("photos/b.PNG", 5000),
+            object("photos/c.png", 10),
+            object("docs/readme.txt", 5000),
+            object("photos.jpg/notes", 5000),
+            object("photos/.jpg", 5000),
+        ];
+
+        let config = FilterConfig {
+            extensions: vec!["jpg".to_string(), ".png".to_string()],
+            min_size: Some(1000),
+            ..Default::default()
+        };
+
+        let keys: Vec<String> = apply_filters(&objects, &config)
+            .into_iter()
+            .map(|obj| obj.key)
+            .collect();
+        assert_eq!(keys, vec!["photos/a.jpg", "photos/b.PNG"]);
+    }
+
+    #[test]
+    fn test_apply_filters_storage_class() {
+        let object = |key: &str, size: i64, class: Option<&str>| EnhancedObjectInfo {
+            key: key.to_string(),
+            size,
+            created: None,
+            modified: None,
+            storage_class: class.map(str::to_string),
+            etag: None,
+            owner: None,
+            version_id: None,
+            delete_marker: false,
+        };
+        let objects = vec![
+            object("hot.bin", 5000, Some("STANDARD")),
+            object("small.bin", 10, Some("STANDARD")),
+            object("cold.bin", 5000, Some("GLACIER")),
+            object("infrequent.bin", 5000, Some("STANDARD_IA")),
+            object("unknown.bin", 5000, None),
+        ];
+
+        let config = FilterConfig {
+            storage_classes: vec!["STANDARD".to_string(), "GLACIER".to_string()],
+            min_size: Some(1000),
+            ..Default::default()
+        };
+
+        let keys: Vec<String> = apply_filters(&objects, &config)
+            .into_iter()
+            .map(|obj| obj.key)
+            .collect();
+        assert_eq!(keys, vec!["hot.bin", "cold.bin", "unknown.bin"]);
+
+        // An object listed without a class is STANDARD, not any other class
+        let glacier_only = FilterConfig {
+            storage_classes: vec!["GLACIER".to_string()],
+            ..Default::default()
+        };
+        let keys: Vec<String> = apply_filters(&objects, &glacier_only)
+            .into_iter()
+            .map(|obj| obj.key)
+            .collect();
+        assert_eq!(keys, vec!["cold.bin"]);
+    }
+
     #[test]
     fn test_apply_filters_sorting() {
         let objects = vec![
@@ -732,6 +1029,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "a_file.txt".to_string(),
@@ -740,6 +1040,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "b_file.txt".to_string(),
@@ -748,6 +1051,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
         ];
 
@@ -773,6 +1079,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "file2.txt".to_string(),
@@ -781,6 +1090,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "file3.txt".to_string(),
@@ -789,6 +1101,9 @@ mod tests {
                 modified: None,
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
         ];
 
@@ -828,6 +1143,9 @@ mod tests {
                 modified: Some(now),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "small.txt".to_string(),
@@ -836,6 +1154,9 @@ mod tests {
                 modified: Some(now),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "medium.txt".to_string(),
@@ -844,6 +1165,9 @@ mod tests {
                 modified: Some(now),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
         ];
 
@@ -871,6 +1195,9 @@ mod tests {
                 modified: Some(Utc::now()),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             })
             .collect();
 
@@ -898,6 +1225,9 @@ mod tests {
                 modified: Some(Utc::now()),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "large.txt".to_string(),
@@ -906,6 +1236,9 @@ mod tests {
                 modified: Some(Utc::now()),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "medium.txt".to_string(),
@@ -914,6 +1247,9 @@ mod tests {
                 modified: Some(Utc::now()),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "tiny.txt".to_string(),
@@ -922,6 +1258,9 @@ mod tests {
                 modified: Some(Utc::now()),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
         ];
 
@@ -951,6 +1290,9 @@ mod tests {
                 modified: Some(now - Duration::hours(2)),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "recent.txt".to_string(),
@@ -959,6 +1301,9 @@ mod tests {
                 modified: Some(now - Duration::minutes(30)),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
             EnhancedObjectInfo {
                 key: "newest.txt".to_string(),
@@ -967,6 +1312,9 @@ mod tests {
                 modified: Some(now),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             },
         ];
 
@@ -994,6 +1342,9 @@ mod tests {
             modified: Some(Utc::now()),
             storage_class: None,
             etag: None,
+            owner: None,
+            version_id: None,
+            delete_marker: false,
         });
 
         let filtered = apply_filters_streaming(objects_iter, &config, Some(10000));
@@ -1061,6 +1412,9 @@ mod tests {
                 modified: Some(Utc::now()),
                 storage_class: None,
                 etag: None,
+                owner: None,
+                version_id: None,
+                delete_marker: false,
             })
             .collect();
 
@@ -1,9 +1,16 @@
 pub mod args;
 pub mod commands;
 pub mod config;
+pub mod errors;
+pub mod events;
 pub mod filtering;
 pub mod logging;
 pub mod otel;
+pub mod progress;
+pub mod proxy;
+pub mod sigv2;
+pub mod statsd;
+pub mod tls;
 pub mod upload;
 pub mod utils;
 
@@ -3,12 +3,17 @@ use clap::Parser;
 #[cfg(target_os = "linux")]
 use sd_notify::NotifyState;
 use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
 
-use obsctl::args::Args;
+use obsctl::args::{Args, MetricsBackend, OutputFormat};
 use obsctl::commands::execute_command;
+use obsctl::commands::exists::EXIT_ERROR;
 use obsctl::config::Config;
+use obsctl::errors;
 use obsctl::logging::init_logging;
 use obsctl::otel;
+use obsctl::statsd;
 
 /// Set up broken pipe handling to prevent panics when output is piped to commands like `head`
 fn setup_broken_pipe_handling() {
@@ -42,34 +47,118 @@ fn flush_output() {
     let _ = io::stderr().flush();
 }
 
+/// Logging, configuration and telemetry, set up before any command runs.
+/// The telemetry providers are handed back through `telemetry` so they can
+/// be shut down even when a later step fails.
+async fn setup(args: &Args, telemetry: &mut otel::TelemetryProviders) -> Result<Config> {
+    init_logging(&args.debug)?;
+    let config = Config::new(args).await?;
+
+    *telemetry = otel::init_tracing(&config.otel, &args.debug)?;
+    if args.metrics_backend != MetricsBackend::Otlp {
+        statsd::install(
+            &args.statsd_addr,
+            args.metrics_backend == MetricsBackend::Dogstatsd,
+        )?;
+    }
+    Ok(config)
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Set up broken pipe handling before any output
     setup_broken_pipe_handling();
 
+    let start_time = Instant::now();
     let args = Args::parse();
 
-    // Initialize logging
-    init_logging(&args.debug)?;
-
-    // Initialize configuration
-    let config = Config::new(&args).await?;
-
-    // Initialize OpenTelemetry if enabled
-    otel::init_tracing(&config.otel, &args.debug)?;
+    // Resolve --color before anything is printed
+    args.color.apply();
+
+    // Setup failures are reported like command failures, so they honour
+    // --output json and still write --metrics-file and --summary-json
+    let errors_before = otel::GLOBAL_METRICS
+        .errors_total
+        .load(std::sync::atomic::Ordering::Relaxed);
+    let mut telemetry = otel::TelemetryProviders::default();
+    let (result, setup_failed) = match setup(&args, &mut telemetry).await {
+        Ok(config) => {
+            #[cfg(target_os = "linux")]
+            sd_notify::notify(true, &[NotifyState::Ready]).ok();
+
+            // Execute the appropriate command
+            let result = execute_command(&args, &config).await.map_err(|e| {
+                let hint =
+                    errors::bucket_owner_mismatch_hint(&e, args.expected_bucket_owner.as_deref())
+                        .or_else(|| errors::signature_version_hint(&e, args.signature_version))
+                        .or_else(|| errors::tls_verification_hint(&e))
+                        .or_else(|| errors::sse_customer_key_hint(&e, args.sse_c_key.is_some()));
+                match hint {
+                    Some(hint) => e.context(hint),
+                    None => e,
+                }
+            });
+            (result, false)
+        }
+        Err(e) => (Err(e), true),
+    };
 
-    #[cfg(target_os = "linux")]
-    sd_notify::notify(true, &[NotifyState::Ready]).ok();
+    // Count failures the command didn't classify itself
+    if let Err(e) = &result {
+        if e.downcast_ref::<errors::ExitCodeError>().is_none() {
+            otel::record_command_error(e, errors_before);
+        }
+    }
 
-    // Execute the appropriate command
-    let result = execute_command(&args, &config).await;
+    // Report this run's counters, whether or not the command succeeded
+    let snapshot = otel::GLOBAL_METRICS.get_metrics_snapshot().await;
+    if let Some(path) = &args.metrics_file {
+        if let Err(e) = otel::write_metrics_file(Path::new(path), &snapshot.to_prometheus_text()) {
+            log::warn!("Failed to write metrics file {path}: {e}");
+        }
+    }
+    if args.summary_json {
+        eprintln!(
+            "{}",
+            snapshot.to_summary_json(start_time.elapsed(), result.is_ok())
+        );
+    }
 
     // Shutdown OpenTelemetry
-    otel::shutdown_tracing();
+    otel::shutdown_tracing(telemetry, &args.debug);
 
     #[cfg(target_os = "linux")]
     sd_notify::notify(true, &[NotifyState::Stopping]).ok();
 
+    // Commands answering with an exit code print at most a one-line message
+    if let Some(exit) = result
+        .as_ref()
+        .err()
+        .and_then(|e| e.downcast_ref::<errors::ExitCodeError>())
+    {
+        if let Some(message) = &exit.message {
+            eprintln!("obsctl: {message}");
+        }
+        flush_output();
+        std::process::exit(exit.code);
+    }
+
+    // Setup failures exit with the generic error code rather than 1, which
+    // `exists` and `wait` use for not found and timed out
+    let exit_code = if setup_failed { EXIT_ERROR } else { 1 };
+
+    // In JSON mode errors are a single machine-readable object instead of text
+    if let (Err(e), OutputFormat::Json) = (&result, args.output) {
+        eprintln!("{}", errors::error_to_json(e));
+        flush_output();
+        std::process::exit(exit_code);
+    }
+    if let (Err(e), true) = (&result, setup_failed) {
+        eprintln!("Error: {e:?}");
+        flush_output();
+        std::process::exit(exit_code);
+    }
+
     // Flush output before exit
     flush_output();
 
@@ -16,10 +16,12 @@ pub struct ObsctlMetrics {
     pub deletes_total: Arc<AtomicU64>,
     pub lists_total: Arc<AtomicU64>,
     pub sync_operations_total: Arc<AtomicU64>,
+    pub copies_total: Arc<AtomicU64>,
 
     // Volume metrics (bytes)
     pub bytes_uploaded_total: Arc<AtomicU64>,
     pub bytes_downloaded_total: Arc<AtomicU64>,
+    pub bytes_copied_total: Arc<AtomicU64>,
 
     // File counters
     pub files_uploaded_total: Arc<AtomicU64>,
@@ -75,8 +77,10 @@ impl ObsctlMetrics {
             deletes_total: Arc::new(AtomicU64::new(0)),
             lists_total: Arc::new(AtomicU64::new(0)),
             sync_operations_total: Arc::new(AtomicU64::new(0)),
+            copies_total: Arc::new(AtomicU64::new(0)),
             bytes_uploaded_total: Arc::new(AtomicU64::new(0)),
             bytes_downloaded_total: Arc::new(AtomicU64::new(0)),
+            bytes_copied_total: Arc::new(AtomicU64::new(0)),
             files_uploaded_total: Arc::new(AtomicU64::new(0)),
             files_downloaded_total: Arc::new(AtomicU64::new(0)),
             files_deleted_total: Arc::new(AtomicU64::new(0)),
@@ -107,17 +111,7 @@ impl ObsctlMetrics {
 
     /// Record a file upload operation with enhanced analytics
     pub async fn record_upload(&self, bytes: u64, duration_ms: u64) {
-        self.operations_total.fetch_add(1, Ordering::Relaxed);
-        self.uploads_total.fetch_add(1, Ordering::Relaxed);
-        self.files_uploaded_total.fetch_add(1, Ordering::Relaxed);
-        self.bytes_uploaded_total
-            .fetch_add(bytes, Ordering::Relaxed);
-        self.total_transfer_time_ms
-            .fetch_add(duration_ms, Ordering::Relaxed);
-
-        // Update file size tracking
-        self.update_file_size_distribution(bytes);
-        self.update_file_size_extremes(bytes);
+        self.count_upload(bytes, duration_ms);
 
         // Calculate and record transfer rate
         let kb_per_sec = if duration_ms > 0 {
@@ -141,17 +135,7 @@ impl ObsctlMetrics {
 
     /// Record a file download operation with enhanced analytics
     pub async fn record_download(&self, bytes: u64, duration_ms: u64) {
-        self.operations_total.fetch_add(1, Ordering::Relaxed);
-        self.downloads_total.fetch_add(1, Ordering::Relaxed);
-        self.files_downloaded_total.fetch_add(1, Ordering::Relaxed);
-        self.bytes_downloaded_total
-            .fetch_add(bytes, Ordering::Relaxed);
-        self.total_transfer_time_ms
-            .fetch_add(duration_ms, Ordering::Relaxed);
-
-        // Update file size tracking
-        self.update_file_size_distribution(bytes);
-        self.update_file_size_extremes(bytes);
+        self.count_download(bytes, duration_ms);
 
         // Calculate and record transfer rate
         let kb_per_sec = if duration_ms > 0 {
@@ -173,6 +157,51 @@ impl ObsctlMetrics {
         }
     }
 
+    /// Update the upload counters and size tracking; the synchronous part of
+    /// `record_upload`, for callers that can't await
+    pub fn count_upload(&self, bytes: u64, duration_ms: u64) {
+        self.operations_total.fetch_add(1, Ordering::Relaxed);
+        self.uploads_total.fetch_add(1, Ordering::Relaxed);
+        self.files_uploaded_total.fetch_add(1, Ordering::Relaxed);
+        self.bytes_uploaded_total
+            .fetch_add(bytes, Ordering::Relaxed);
+        self.total_transfer_time_ms
+            .fetch_add(duration_ms, Ordering::Relaxed);
+
+        // Update file size tracking
+        self.update_file_size_distribution(bytes);
+        self.update_file_size_extremes(bytes);
+    }
+
+    /// Update the download counters and size tracking; the synchronous part
+    /// of `record_download`
+    pub fn count_download(&self, bytes: u64, duration_ms: u64) {
+        self.operations_total.fetch_add(1, Ordering::Relaxed);
+        self.downloads_total.fetch_add(1, Ordering::Relaxed);
+        self.files_downloaded_total.fetch_add(1, Ordering::Relaxed);
+        self.bytes_downloaded_total
+            .fetch_add(bytes, Ordering::Relaxed);
+        self.total_transfer_time_ms
+            .fetch_add(duration_ms, Ordering::Relaxed);
+
+        // Update file size tracking
+        self.update_file_size_distribution(bytes);
+        self.update_file_size_extremes(bytes);
+    }
+
+    /// Record a server-side copy of one object between S3 locations
+    pub async fn record_copy(&self, bytes: u64, duration_ms: u64) {
+        self.operations_total.fetch_add(1, Ordering::Relaxed);
+        self.copies_total.fetch_add(1, Ordering::Relaxed);
+        self.bytes_copied_total.fetch_add(bytes, Ordering::Relaxed);
+
+        let mut durations = self.operation_duration_ms.lock().await;
+        durations.push(("copy".to_string(), duration_ms));
+        if durations.len() > 1000 {
+            durations.remove(0);
+        }
+    }
+
     /// Record a delete operation
     pub async fn record_delete(&self, file_count: u64, duration_ms: u64) {
         self.operations_total.fetch_add(1, Ordering::Relaxed);
@@ -229,48 +258,18 @@ impl ObsctlMetrics {
     pub fn record_error_with_type(&self, error_message: &str) {
         self.errors_total.fetch_add(1, Ordering::Relaxed);
 
-        // Classify error type based on message content
-        let error_lower = error_message.to_lowercase();
-
-        if error_lower.contains("dns")
-            || error_lower.contains("dispatch failure")
-            || error_lower.contains("connection")
-            || error_lower.contains("network")
-            || error_lower.contains("failed to lookup address")
-        {
-            self.errors_dns.fetch_add(1, Ordering::Relaxed);
-            log::debug!("Recorded DNS/network error: {error_message}");
-        } else if error_lower.contains("bucket")
-            || error_lower.contains("bucketalreadyownedby")
-            || error_lower.contains("nosuchbucket")
-        {
-            self.errors_bucket.fetch_add(1, Ordering::Relaxed);
-            log::debug!("Recorded bucket error: {error_message}");
-        } else if error_lower.contains("file")
-            || error_lower.contains("no such file")
-            || error_lower.contains("permission")
-            || error_lower.contains("access denied")
-        {
-            self.errors_file.fetch_add(1, Ordering::Relaxed);
-            log::debug!("Recorded file error: {error_message}");
-        } else if error_lower.contains("auth")
-            || error_lower.contains("credential")
-            || error_lower.contains("unauthorized")
-            || error_lower.contains("forbidden")
-        {
-            self.errors_auth.fetch_add(1, Ordering::Relaxed);
-            log::debug!("Recorded auth error: {error_message}");
-        } else if error_lower.contains("service error")
-            || error_lower.contains("throttle")
-            || error_lower.contains("rate limit")
-            || error_lower.contains("slow down")
-        {
-            self.errors_service.fetch_add(1, Ordering::Relaxed);
-            log::debug!("Recorded service error: {error_message}");
-        } else {
-            self.errors_unknown.fetch_add(1, Ordering::Relaxed);
-            log::debug!("Recorded unknown error: {error_message}");
-        }
+        // Same categories as the OTEL instruments and JSON error output
+        let error_type = classify_error_type(error_message);
+        let counter = match error_type {
+            "dns_network" => &self.errors_dns,
+            "bucket" => &self.errors_bucket,
+            "file" => &self.errors_file,
+            "auth" => &self.errors_auth,
+            "service" => &self.errors_service,
+            _ => &self.errors_unknown,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        log::debug!("Recorded {error_type} error: {error_message}");
     }
 
     /// Record a timeout
@@ -341,71 +340,19 @@ impl ObsctlMetrics {
         }
     }
 
-    /// Detect MIME type from file extension
+    /// Detect MIME type from file extension, keeping unknown extensions
+    /// apart in the analytics
     fn detect_mime_type(&self, file_path: &str) -> String {
-        let extension = std::path::Path::new(file_path)
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .map(|ext| ext.to_lowercase())
-            .unwrap_or_else(|| "unknown".to_string());
-
-        match extension.as_str() {
-            // Images
-            "jpg" | "jpeg" => "image/jpeg".to_string(),
-            "png" => "image/png".to_string(),
-            "gif" => "image/gif".to_string(),
-            "webp" => "image/webp".to_string(),
-            "svg" => "image/svg+xml".to_string(),
-            "bmp" => "image/bmp".to_string(),
-
-            // Documents
-            "pdf" => "application/pdf".to_string(),
-            "doc" => "application/msword".to_string(),
-            "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
-                .to_string(),
-            "xls" => "application/vnd.ms-excel".to_string(),
-            "xlsx" => {
-                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string()
+        match crate::utils::detect_mime_type(file_path) {
+            Some(mime_type) => mime_type.to_string(),
+            None => {
+                let extension = std::path::Path::new(file_path)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| ext.to_lowercase())
+                    .unwrap_or_else(|| "unknown".to_string());
+                format!("application/octet-stream ({extension})")
             }
-            "ppt" => "application/vnd.ms-powerpoint".to_string(),
-            "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation"
-                .to_string(),
-
-            // Text
-            "txt" => "text/plain".to_string(),
-            "csv" => "text/csv".to_string(),
-            "json" => "application/json".to_string(),
-            "xml" => "application/xml".to_string(),
-            "html" | "htm" => "text/html".to_string(),
-            "css" => "text/css".to_string(),
-            "js" => "application/javascript".to_string(),
-
-            // Code
-            "py" => "text/x-python".to_string(),
-            "rs" => "text/x-rust".to_string(),
-            "java" => "text/x-java-source".to_string(),
-            "cpp" | "cc" | "cxx" => "text/x-c++src".to_string(),
-            "c" => "text/x-csrc".to_string(),
-            "h" => "text/x-chdr".to_string(),
-            "go" => "text/x-go".to_string(),
-
-            // Archives
-            "zip" => "application/zip".to_string(),
-            "tar" => "application/x-tar".to_string(),
-            "gz" => "application/gzip".to_string(),
-            "7z" => "application/x-7z-compressed".to_string(),
-            "rar" => "application/vnd.rar".to_string(),
-
-            // Media
-            "mp4" => "video/mp4".to_string(),
-            "avi" => "video/x-msvideo".to_string(),
-            "mov" => "video/quicktime".to_string(),
-            "mp3" => "audio/mpeg".to_string(),
-            "wav" => "audio/wav".to_string(),
-            "flac" => "audio/flac".to_string(),
-
-            // Default
-            _ => format!("application/octet-stream ({extension})"),
         }
     }
 
@@ -435,8 +382,10 @@ impl ObsctlMetrics {
             deletes_total: self.deletes_total.load(Ordering::Relaxed),
             lists_total: self.lists_total.load(Ordering::Relaxed),
             sync_operations_total: self.sync_operations_total.load(Ordering::Relaxed),
+            copies_total: self.copies_total.load(Ordering::Relaxed),
             bytes_uploaded_total: self.bytes_uploaded_total.load(Ordering::Relaxed),
             bytes_downloaded_total: self.bytes_downloaded_total.load(Ordering::Relaxed),
+            bytes_copied_total: self.bytes_copied_total.load(Ordering::Relaxed),
             files_uploaded_total: self.files_uploaded_total.load(Ordering::Relaxed),
             files_downloaded_total: self.files_downloaded_total.load(Ordering::Relaxed),
             files_deleted_total: self.files_deleted_total.load(Ordering::Relaxed),
@@ -482,8 +431,10 @@ pub struct MetricsSnapshot {
     pub deletes_total: u64,
     pub lists_total: u64,
     pub sync_operations_total: u64,
+    pub copies_total: u64,
     pub bytes_uploaded_total: u64,
     pub bytes_downloaded_total: u64,
+    pub bytes_copied_total: u64,
     pub files_uploaded_total: u64,
     pub files_downloaded_total: u64,
     pub files_deleted_total: u64,
@@ -512,11 +463,253 @@ pub struct MetricsSnapshot {
     pub errors_unknown: u64,
 }
 
+impl MetricsSnapshot {
+    /// Render the counters in Prometheus text exposition format, for
+    /// node_exporter's textfile collector (`--metrics-file`)
+    pub fn to_prometheus_text(&self) -> String {
+        let mut out = String::new();
+
+        let counters = [
+            (
+                "operations_total",
+                "Total number of obsctl operations",
+                self.operations_total,
+            ),
+            (
+                "uploads_total",
+                "Total number of upload operations",
+                self.uploads_total,
+            ),
+            (
+                "downloads_total",
+                "Total number of download operations",
+                self.downloads_total,
+            ),
+            (
+                "deletes_total",
+                "Total number of delete operations",
+                self.deletes_total,
+            ),
+            (
+                "lists_total",
+                "Total number of list operations",
+                self.lists_total,
+            ),
+            (
+                "sync_operations_total",
+                "Total number of sync operations",
+                self.sync_operations_total,
+            ),
+            (
+                "copies_total",
+                "Total number of S3 to S3 copies",
+                self.copies_total,
+            ),
+            (
+                "bytes_uploaded_total",
+                "Total bytes uploaded",
+                self.bytes_uploaded_total,
+            ),
+            (
+                "bytes_downloaded_total",
+                "Total bytes downloaded",
+                self.bytes_downloaded_total,
+            ),
+            (
+                "bytes_copied_total",
+                "Total bytes copied between S3 locations",
+                self.bytes_copied_total,
+            ),
+            (
+                "files_uploaded_total",
+                "Total files uploaded",
+                self.files_uploaded_total,
+            ),
+            (
+                "files_downloaded_total",
+                "Total files downloaded",
+                self.files_downloaded_total,
+            ),
+            (
+                "files_deleted_total",
+                "Total files deleted",
+                self.files_deleted_total,
+            ),
+            ("errors_total", "Total number of errors", self.errors_total),
+            (
+                "timeouts_total",
+                "Total number of timeouts",
+                self.timeouts_total,
+            ),
+            (
+                "transfer_time_ms_total",
+                "Total time spent transferring files, in milliseconds",
+                self.total_transfer_time_ms,
+            ),
+        ];
+        for (name, help, value) in counters {
+            write_prometheus_metric(&mut out, name, "counter", help, &[(String::new(), value)]);
+        }
+
+        let errors_by_type = [
+            ("dns_network", self.errors_dns),
+            ("bucket", self.errors_bucket),
+            ("file", self.errors_file),
+            ("auth", self.errors_auth),
+            ("service", self.errors_service),
+            ("unknown", self.errors_unknown),
+        ];
+        write_prometheus_metric(
+            &mut out,
+            "errors_by_type_total",
+            "counter",
+            "Errors by classification",
+            &errors_by_type.map(|(kind, value)| (format!("{{type=\"{kind}\"}}"), value)),
+        );
+
+        let files_by_size = [
+            ("small", self.files_by_size_small),
+            ("medium", self.files_by_size_medium),
+            ("large", self.files_by_size_large),
+            ("xlarge", self.files_by_size_xlarge),
+        ];
+        write_prometheus_metric(
+            &mut out,
+            "files_by_size_total",
+            "counter",
+            "Files transferred by size bucket (<1MB, <100MB, <1GB, larger)",
+            &files_by_size.map(|(size, value)| (format!("{{size=\"{size}\"}}"), value)),
+        );
+
+        if !self.mime_types.is_empty() {
+            let mut mime_types: Vec<_> = self.mime_types.iter().collect();
+            mime_types.sort();
+            let samples: Vec<_> = mime_types
+                .into_iter()
+                .map(|(mime_type, count)| {
+                    let label = escape_prometheus_label(mime_type);
+                    (format!("{{mime_type=\"{label}\"}}"), *count)
+                })
+                .collect();
+            write_prometheus_metric(
+                &mut out,
+                "files_by_mime_type_total",
+                "counter",
+                "Files transferred by MIME type",
+                &samples,
+            );
+        }
+
+        write_prometheus_metric(
+            &mut out,
+            "largest_file_bytes",
+            "gauge",
+            "Largest file transferred, in bytes",
+            &[(String::new(), self.largest_file_bytes)],
+        );
+        write_prometheus_metric(
+            &mut out,
+            "smallest_file_bytes",
+            "gauge",
+            "Smallest file transferred, in bytes",
+            &[(String::new(), self.smallest_file_bytes)],
+        );
+
+        out
+    }
+}
+
+impl MetricsSnapshot {
+    /// One-line run summary for `--summary-json`, built from the in-process
+    /// counters so it doesn't depend on an OTLP exporter
+    pub fn to_summary_json(
+        &self,
+        duration: std::time::Duration,
+        success: bool,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "success": success,
+            "duration_seconds": duration.as_secs_f64(),
+            "operations": self.operations_total,
+            "bytes_uploaded": self.bytes_uploaded_total,
+            "bytes_downloaded": self.bytes_downloaded_total,
+            "bytes_copied": self.bytes_copied_total,
+            "files_uploaded": self.files_uploaded_total,
+            "files_downloaded": self.files_downloaded_total,
+            "files_copied": self.copies_total,
+            "files_deleted": self.files_deleted_total,
+            "errors": self.errors_total,
+        })
+    }
+}
+
+/// Append one metric family: `# HELP` and `# TYPE` headers, then a line per
+/// (label set, value) sample
+fn write_prometheus_metric(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: &[(String, u64)],
+) {
+    use std::fmt::Write;
+
+    let _ = writeln!(out, "# HELP obsctl_{name} {help}");
+    let _ = writeln!(out, "# TYPE obsctl_{name} {kind}");
+    for (labels, value) in samples {
+        let _ = writeln!(out, "obsctl_{name}{labels} {value}");
+    }
+}
+
+/// Escape a label value for the text format: backslash, quote and newline
+fn escape_prometheus_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Write metrics text to `path` through a temporary file in the same
+/// directory, so a collector never reads a half-written file
+pub fn write_metrics_file(path: &std::path::Path, text: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid metrics file path: {}", path.display()))?;
+    let mut temp_name = file_name.to_os_string();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    std::fs::write(&temp_path, text)?;
+    if let Err(e) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
 // Global metrics instance
 lazy_static::lazy_static! {
     pub static ref GLOBAL_METRICS: ObsctlMetrics = ObsctlMetrics::new();
 }
 
+/// Longest `shutdown_tracing` blocks waiting for exports to finish
+const SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Providers installed by `init_tracing`, handed back to `shutdown_tracing`
+/// so pending metrics and spans are flushed before the process exits
+#[derive(Debug, Default)]
+pub struct TelemetryProviders {
+    meter_provider: Option<opentelemetry_sdk::metrics::SdkMeterProvider>,
+    tracer_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl TelemetryProviders {
+    /// True when OTEL is disabled or neither exporter could be built
+    pub fn is_empty(&self) -> bool {
+        self.meter_provider.is_none() && self.tracer_provider.is_none()
+    }
+}
+
 // OpenTelemetry instruments using the global meter provider
 lazy_static::lazy_static! {
     pub static ref OTEL_INSTRUMENTS: OtelInstruments = OtelInstruments::new();
@@ -537,6 +730,10 @@ pub struct OtelInstruments {
     pub bytes_uploaded_total: opentelemetry::metrics::Counter<u64>,
     pub bytes_downloaded_total: opentelemetry::metrics::Counter<u64>,
 
+    // Server-side copies, kept apart from uploads
+    pub copies_total: opentelemetry::metrics::Counter<u64>,
+    pub bytes_copied_total: opentelemetry::metrics::Counter<u64>,
+
     // File counters
     pub files_uploaded_total: opentelemetry::metrics::Counter<u64>,
     pub files_downloaded_total: opentelemetry::metrics::Counter<u64>,
@@ -549,6 +746,7 @@ pub struct OtelInstruments {
     // Error counters
     pub errors_total: opentelemetry::metrics::Counter<u64>,
     pub timeouts_total: opentelemetry::metrics::Counter<u64>,
+    pub partial_failures_total: opentelemetry::metrics::Counter<u64>,
 
     // Detailed Error Type Tracking
     pub errors_dns: opentelemetry::metrics::Counter<u64>,
@@ -609,6 +807,16 @@ impl OtelInstruments {
                 .with_description("Total bytes downloaded")
                 .build(),
 
+            // Server-side copies
+            copies_total: meter
+                .u64_counter("copies_total")
+                .with_description("Total number of server-side object copies")
+                .build(),
+            bytes_copied_total: meter
+                .u64_counter("bytes_copied_total")
+                .with_description("Total bytes copied server-side")
+                .build(),
+
             // File counters
             files_uploaded_total: meter
                 .u64_counter("files_uploaded_total")
@@ -642,6 +850,10 @@ impl OtelInstruments {
                 .u64_counter("timeouts_total")
                 .with_description("Total number of timeouts")
                 .build(),
+            partial_failures_total: meter
+                .u64_counter("partial_failures_total")
+                .with_description("Items that failed within otherwise completed batch operations")
+                .build(),
 
             // Detailed error tracking
             errors_dns: meter
@@ -696,109 +908,169 @@ impl OtelInstruments {
     }
 
     /// Record an upload operation using OTEL instruments
-    pub fn record_upload(&self, bytes: u64, duration_ms: u64) {
+    pub fn record_upload(&self, bytes: u64, duration_ms: u64, bucket: Option<&str>) {
         // Record operation counters
-        self.operations_total.add(1, &[]);
-        self.uploads_total.add(1, &[]);
-        self.files_uploaded_total.add(1, &[]);
-        self.bytes_uploaded_total.add(bytes, &[]);
+        let labels = bucket_attributes(bucket);
+        self.operations_total.add(1, &labels);
+        self.uploads_total.add(1, &labels);
+        self.files_uploaded_total.add(1, &labels);
+        self.bytes_uploaded_total.add(bytes, &labels);
 
         // Record performance metrics
         let duration_seconds = duration_ms as f64 / 1000.0;
-        self.operation_duration.record(
-            duration_seconds,
-            &[opentelemetry::KeyValue::new("operation", "upload")],
-        );
+        self.operation_duration
+            .record(duration_seconds, &operation_attributes("upload", bucket));
 
         // Record transfer rate
         if duration_ms > 0 {
             let kb_per_sec = (bytes as f64 / 1024.0) / duration_seconds;
-            self.transfer_rate.record(
-                kb_per_sec,
-                &[opentelemetry::KeyValue::new("operation", "upload")],
-            );
+            self.transfer_rate
+                .record(kb_per_sec, &operation_attributes("upload", bucket));
         }
 
         // Record file size
-        self.file_size_bytes.record(
-            bytes as f64,
-            &[opentelemetry::KeyValue::new("operation", "upload")],
-        );
+        self.file_size_bytes
+            .record(bytes as f64, &operation_attributes("upload", bucket));
 
         // Record file size distribution
         self.record_file_size_distribution(bytes);
+
+        // Keep the in-process totals for --metrics-file
+        GLOBAL_METRICS.count_upload(bytes, duration_ms);
+
+        send_statsd(
+            "upload",
+            bucket,
+            &[
+                ("operations_total", 1),
+                ("uploads_total", 1),
+                ("files_uploaded_total", 1),
+                ("bytes_uploaded_total", bytes),
+            ],
+            duration_ms,
+        );
     }
 
     /// Record a download operation using OTEL instruments
-    pub fn record_download(&self, bytes: u64, duration_ms: u64) {
+    pub fn record_download(&self, bytes: u64, duration_ms: u64, bucket: Option<&str>) {
         // Record operation counters
-        self.operations_total.add(1, &[]);
-        self.downloads_total.add(1, &[]);
-        self.files_downloaded_total.add(1, &[]);
-        self.bytes_downloaded_total.add(bytes, &[]);
+        let labels = bucket_attributes(bucket);
+        self.operations_total.add(1, &labels);
+        self.downloads_total.add(1, &labels);
+        self.files_downloaded_total.add(1, &labels);
+        self.bytes_downloaded_total.add(bytes, &labels);
 
         // Record performance metrics
         let duration_seconds = duration_ms as f64 / 1000.0;
-        self.operation_duration.record(
-            duration_seconds,
-            &[opentelemetry::KeyValue::new("operation", "download")],
-        );
+        self.operation_duration
+            .record(duration_seconds, &operation_attributes("download", bucket));
 
         // Record transfer rate
         if duration_ms > 0 {
             let kb_per_sec = (bytes as f64 / 1024.0) / duration_seconds;
-            self.transfer_rate.record(
-                kb_per_sec,
-                &[opentelemetry::KeyValue::new("operation", "download")],
-            );
+            self.transfer_rate
+                .record(kb_per_sec, &operation_attributes("download", bucket));
         }
 
         // Record file size
-        self.file_size_bytes.record(
-            bytes as f64,
-            &[opentelemetry::KeyValue::new("operation", "download")],
-        );
+        self.file_size_bytes
+            .record(bytes as f64, &operation_attributes("download", bucket));
 
         // Record file size distribution
         self.record_file_size_distribution(bytes);
+
+        GLOBAL_METRICS.count_download(bytes, duration_ms);
+
+        send_statsd(
+            "download",
+            bucket,
+            &[
+                ("operations_total", 1),
+                ("downloads_total", 1),
+                ("files_downloaded_total", 1),
+                ("bytes_downloaded_total", bytes),
+            ],
+            duration_ms,
+        );
     }
 
-    /// Record a delete operation using OTEL instruments
-    pub fn record_delete(&self, file_count: u64, duration_ms: u64) {
-        self.operations_total.add(1, &[]);
-        self.deletes_total.add(1, &[]);
-        self.files_deleted_total.add(file_count, &[]);
+    /// Record a delete operation (`rm_single`, `rm_recursive`, ...) using OTEL instruments
+    pub fn record_delete(
+        &self,
+        operation: &'static str,
+        file_count: u64,
+        duration_ms: u64,
+        bucket: Option<&str>,
+    ) {
+        let labels = bucket_attributes(bucket);
+        self.operations_total
+            .add(1, &operation_attributes(operation, bucket));
+        self.deletes_total.add(1, &labels);
+        self.files_deleted_total.add(file_count, &labels);
 
         let duration_seconds = duration_ms as f64 / 1000.0;
-        self.operation_duration.record(
-            duration_seconds,
-            &[opentelemetry::KeyValue::new("operation", "delete")],
+        self.operation_duration
+            .record(duration_seconds, &operation_attributes(operation, bucket));
+
+        send_statsd(
+            operation,
+            bucket,
+            &[
+                ("operations_total", 1),
+                ("deletes_total", 1),
+                ("files_deleted_total", file_count),
+            ],
+            duration_ms,
         );
     }
 
-    /// Record a list operation using OTEL instruments
-    pub fn record_list(&self, duration_ms: u64) {
-        self.operations_total.add(1, &[]);
-        self.lists_total.add(1, &[]);
+    /// Record a list operation (`ls_objects`, `du`, ...) using OTEL instruments
+    pub fn record_list(&self, operation: &'static str, duration_ms: u64, bucket: Option<&str>) {
+        let labels = bucket_attributes(bucket);
+        self.operations_total
+            .add(1, &operation_attributes(operation, bucket));
+        self.lists_total.add(1, &labels);
 
         let duration_seconds = duration_ms as f64 / 1000.0;
-        self.operation_duration.record(
-            duration_seconds,
-            &[opentelemetry::KeyValue::new("operation", "list")],
+        self.operation_duration
+            .record(duration_seconds, &operation_attributes(operation, bucket));
+
+        send_statsd(
+            operation,
+            bucket,
+            &[("operations_total", 1), ("lists_total", 1)],
+            duration_ms,
         );
     }
 
     /// Record a sync operation using OTEL instruments
-    pub fn record_sync(&self, files_transferred: u64, bytes_transferred: u64, duration_ms: u64) {
-        self.operations_total.add(1, &[]);
-        self.sync_operations_total.add(1, &[]);
-        self.files_uploaded_total.add(files_transferred, &[]);
-        self.bytes_uploaded_total.add(bytes_transferred, &[]);
+    pub fn record_sync(
+        &self,
+        files_transferred: u64,
+        bytes_transferred: u64,
+        duration_ms: u64,
+        bucket: Option<&str>,
+    ) {
+        let labels = bucket_attributes(bucket);
+        self.operations_total.add(1, &labels);
+        self.sync_operations_total.add(1, &labels);
+        self.files_uploaded_total.add(files_transferred, &labels);
+        self.bytes_uploaded_total.add(bytes_transferred, &labels);
 
         let duration_seconds = duration_ms as f64 / 1000.0;
-        self.operation_duration.record(
-            duration_seconds,
-            &[opentelemetry::KeyValue::new("operation", "sync")],
+        self.operation_duration
+            .record(duration_seconds, &operation_attributes("sync", bucket));
+
+        send_statsd(
+            "sync",
+            bucket,
+            &[
+                ("operations_total", 1),
+                ("sync_operations_total", 1),
+                ("files_uploaded_total", files_transferred),
+                ("bytes_uploaded_total", bytes_transferred),
+            ],
+            duration_ms,
         );
     }
 
@@ -818,12 +1090,29 @@ impl OtelInstruments {
             _ => self.errors_unknown.add(1, &[]),
         }
 
+        GLOBAL_METRICS.record_error_with_type(error_message);
+
+        if let Some(statsd) = crate::statsd::sink() {
+            // Plain StatsD has no tags, so the type also gets its own counter
+            let counter = match error_type {
+                "dns_network" => "errors_dns_total".to_string(),
+                other => format!("errors_{other}_total"),
+            };
+            statsd.count("errors_total", 1, &[("error_type", error_type)]);
+            statsd.count(&counter, 1, &[]);
+        }
+
         log::debug!("Recorded {error_type} error via OTEL: {error_message}");
     }
 
     /// Record a timeout using OTEL instruments
     pub fn record_timeout(&self) {
         self.timeouts_total.add(1, &[]);
+        GLOBAL_METRICS.record_timeout();
+
+        if let Some(statsd) = crate::statsd::sink() {
+            statsd.count("timeouts_total", 1, &[]);
+        }
     }
 
     /// Record file size distribution using OTEL instruments
@@ -858,31 +1147,92 @@ impl Default for OtelInstruments {
     }
 }
 
-/// Initialize OpenTelemetry SDK with proper gRPC instrumentation - NO MORE MANUAL HTTP!
-pub fn init_tracing(otel_config: &OtelConfig, debug_level: &str) -> Result<()> {
+/// OTLP transport (`OTEL_EXPORTER_OTLP_PROTOCOL`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    Grpc,
+    HttpProtobuf,
+}
+
+impl OtlpProtocol {
+    /// Parse the values the OTEL spec defines; `http/json` isn't supported
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "grpc" => Ok(OtlpProtocol::Grpc),
+            "http/protobuf" | "http" => Ok(OtlpProtocol::HttpProtobuf),
+            other => Err(anyhow::anyhow!(
+                "Unsupported OTLP protocol '{other}' (expected 'grpc' or 'http/protobuf')"
+            )),
+        }
+    }
+
+    /// Guess from the endpoint when no protocol is configured: the standard
+    /// HTTP port 4318 or a `/v1/...` signal path means HTTP, anything else gRPC
+    pub fn detect(endpoint: &str) -> Self {
+        let without_scheme = endpoint
+            .split_once("://")
+            .map_or(endpoint, |(_, rest)| rest);
+        let (authority, path) = without_scheme
+            .split_once('/')
+            .unwrap_or((without_scheme, ""));
+
+        if authority.ends_with(":4318") || path.starts_with("v1/") {
+            OtlpProtocol::HttpProtobuf
+        } else {
+            OtlpProtocol::Grpc
+        }
+    }
+
+    fn default_endpoint(self) -> &'static str {
+        match self {
+            OtlpProtocol::Grpc => "http://localhost:4317",
+            OtlpProtocol::HttpProtobuf => "http://localhost:4318",
+        }
+    }
+}
+
+/// OTLP/HTTP exporters post to the URL as given, so each signal needs its
+/// own path; a base endpoint or one already pointing at a signal both work
+fn http_signal_endpoint(endpoint: &str, signal_path: &str) -> String {
+    let base = endpoint.trim_end_matches('/');
+    let base = base
+        .strip_suffix("/v1/traces")
+        .or_else(|| base.strip_suffix("/v1/metrics"))
+        .unwrap_or(base);
+    format!("{base}{signal_path}")
+}
+
+/// Initialize OpenTelemetry SDK over OTLP gRPC or HTTP/protobuf
+pub fn init_tracing(otel_config: &OtelConfig, debug_level: &str) -> Result<TelemetryProviders> {
     let is_debug = matches!(debug_level, "debug" | "trace");
+    let mut providers = TelemetryProviders::default();
 
     if !otel_config.enabled {
         if is_debug {
             log::debug!("OpenTelemetry is disabled");
         }
-        return Ok(());
+        return Ok(providers);
     }
 
     {
         use opentelemetry::global;
-        use opentelemetry::KeyValue;
         use opentelemetry_otlp::WithExportConfig;
         use opentelemetry_sdk::Resource;
         use std::time::Duration;
 
+        let protocol = otel_config.protocol.unwrap_or_else(|| {
+            otel_config
+                .endpoint
+                .as_deref()
+                .map_or(OtlpProtocol::Grpc, OtlpProtocol::detect)
+        });
         let endpoint = otel_config
             .endpoint
             .as_deref()
-            .unwrap_or("http://localhost:4317"); // gRPC endpoint only
+            .unwrap_or(protocol.default_endpoint());
 
         if is_debug {
-            log::debug!("🚀 Initializing OpenTelemetry SDK with gRPC endpoint: {endpoint}");
+            log::debug!("🚀 Initializing OpenTelemetry SDK with {protocol:?} endpoint: {endpoint}");
             log::debug!(
                 "📊 Service: {} v{}",
                 otel_config.service_name,
@@ -897,27 +1247,31 @@ pub fn init_tracing(otel_config: &OtelConfig, debug_level: &str) -> Result<()> {
             log::debug!("📋 Creating OTEL resource with service info");
         }
         let resource = Resource::builder()
-            .with_attributes(vec![
-                KeyValue::new("service.name", otel_config.service_name.clone()),
-                KeyValue::new("service.version", otel_config.service_version.clone()),
-                KeyValue::new("deployment.environment", "development"),
-            ])
+            .with_attributes(resource_attributes(otel_config))
             .build();
 
         // Initialize Tracer Provider for traces using the correct 0.30 API
-        match opentelemetry_otlp::SpanExporter::builder()
-            .with_tonic()
-            .with_endpoint(endpoint)
-            .with_timeout(Duration::from_secs(10))
-            .build()
-        {
+        let span_exporter = match protocol {
+            OtlpProtocol::Grpc => opentelemetry_otlp::SpanExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_timeout(Duration::from_secs(10))
+                .build(),
+            OtlpProtocol::HttpProtobuf => opentelemetry_otlp::SpanExporter::builder()
+                .with_http()
+                .with_endpoint(http_signal_endpoint(endpoint, "/v1/traces"))
+                .with_timeout(Duration::from_secs(10))
+                .build(),
+        };
+        match span_exporter {
             Ok(exporter) => {
                 let tracer_provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
                     .with_batch_exporter(exporter)
                     .with_resource(resource.clone())
                     .build();
 
-                global::set_tracer_provider(tracer_provider);
+                global::set_tracer_provider(tracer_provider.clone());
+                providers.tracer_provider = Some(tracer_provider);
                 if is_debug {
                     log::debug!("✅ Tracer provider initialized successfully");
                 }
@@ -928,12 +1282,19 @@ pub fn init_tracing(otel_config: &OtelConfig, debug_level: &str) -> Result<()> {
         }
 
         // Initialize Meter Provider for metrics using the correct 0.30 API
-        match opentelemetry_otlp::MetricExporter::builder()
-            .with_tonic()
-            .with_endpoint(endpoint)
-            .with_timeout(Duration::from_secs(10))
-            .build()
-        {
+        let metric_exporter = match protocol {
+            OtlpProtocol::Grpc => opentelemetry_otlp::MetricExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_timeout(Duration::from_secs(10))
+                .build(),
+            OtlpProtocol::HttpProtobuf => opentelemetry_otlp::MetricExporter::builder()
+                .with_http()
+                .with_endpoint(http_signal_endpoint(endpoint, "/v1/metrics"))
+                .with_timeout(Duration::from_secs(10))
+                .build(),
+        };
+        match metric_exporter {
             Ok(exporter) => {
                 let reader = opentelemetry_sdk::metrics::PeriodicReader::builder(exporter)
                     .with_interval(Duration::from_secs(1)) // Very short interval for immediate export
@@ -944,7 +1305,8 @@ pub fn init_tracing(otel_config: &OtelConfig, debug_level: &str) -> Result<()> {
                     .with_resource(resource)
                     .build();
 
-                global::set_meter_provider(meter_provider);
+                global::set_meter_provider(meter_provider.clone());
+                providers.meter_provider = Some(meter_provider);
                 if is_debug {
                     log::debug!("✅ Meter provider initialized with 1-second export interval");
                 }
@@ -959,26 +1321,132 @@ pub fn init_tracing(otel_config: &OtelConfig, debug_level: &str) -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(providers)
 }
 
-/// Shutdown OpenTelemetry tracing with proper metric flushing
-pub fn shutdown_tracing() {
-    {
-        use std::time::Duration;
+/// Attributes for the OTEL resource: `OTEL_RESOURCE_ATTRIBUTES` first, then
+/// the service name and version, then `deployment.environment`, which falls
+/// back to the one in `OTEL_RESOURCE_ATTRIBUTES` and then "development"
+fn resource_attributes(otel_config: &OtelConfig) -> Vec<opentelemetry::KeyValue> {
+    use opentelemetry::KeyValue;
+
+    let mut environment = otel_config.environment.clone();
+    let mut attributes = Vec::new();
+    for (key, value) in &otel_config.resource_attributes {
+        match key.as_str() {
+            "service.name" | "service.version" => {}
+            "deployment.environment" => {
+                environment.get_or_insert_with(|| value.clone());
+            }
+            _ => attributes.push(KeyValue::new(key.clone(), value.clone())),
+        }
+    }
 
-        log::info!("🔄 OpenTelemetry shutdown requested - flushing metrics and traces...");
+    attributes.push(KeyValue::new(
+        "service.name",
+        otel_config.service_name.clone(),
+    ));
+    attributes.push(KeyValue::new(
+        "service.version",
+        otel_config.service_version.clone(),
+    ));
+    attributes.push(KeyValue::new(
+        "deployment.environment",
+        environment.unwrap_or_else(|| "development".to_string()),
+    ));
+    attributes
+}
 
-        // Give enough time for at least 2 export cycles (1 second interval + buffer)
-        // This ensures all pending metrics and traces are exported before shutdown
-        std::thread::sleep(Duration::from_millis(2500));
+/// Shutdown OpenTelemetry tracing with proper metric flushing. Each provider
+/// exports what it has buffered and this blocks only until that completes or
+/// `SHUTDOWN_TIMEOUT` passes; with no providers it returns at once.
+pub fn shutdown_tracing(providers: TelemetryProviders, debug_level: &str) {
+    let is_debug = matches!(debug_level, "debug" | "trace");
 
-        log::info!("🎉 OpenTelemetry shutdown complete - all pending metrics and traces flushed");
+    if providers.is_empty() {
+        if is_debug {
+            log::debug!("OpenTelemetry not enabled, nothing to shutdown");
+        }
+        return;
     }
 
-    {
-        log::debug!("OpenTelemetry not enabled, nothing to shutdown");
+    log::info!("🔄 OpenTelemetry shutdown requested - flushing metrics and traces...");
+
+    // Shutting a provider down flushes it first
+    if let Some(meter_provider) = providers.meter_provider {
+        if let Err(e) = meter_provider.shutdown_with_timeout(SHUTDOWN_TIMEOUT) {
+            log::warn!("Failed to flush OpenTelemetry metrics: {e}");
+        }
+    }
+    if let Some(tracer_provider) = providers.tracer_provider {
+        if let Err(e) = tracer_provider.shutdown_with_timeout(SHUTDOWN_TIMEOUT) {
+            log::warn!("Failed to flush OpenTelemetry traces: {e}");
+        }
     }
+
+    log::info!("🎉 OpenTelemetry shutdown complete - all pending metrics and traces flushed");
+}
+
+/// `bucket` label for the instruments; `None` when the bucket is unknown or
+/// bucket labels are turned off (`--otel-no-bucket-labels`)
+fn bucket_attributes(bucket: Option<&str>) -> Vec<opentelemetry::KeyValue> {
+    bucket
+        .map(|bucket| opentelemetry::KeyValue::new("bucket", bucket.to_string()))
+        .into_iter()
+        .collect()
+}
+
+/// Mirror a recording call's counters and duration to the StatsD sink, when
+/// `--metrics-backend` installed one
+fn send_statsd(operation: &str, bucket: Option<&str>, counters: &[(&str, u64)], duration_ms: u64) {
+    if let Some(statsd) = crate::statsd::sink() {
+        let tags = crate::statsd::operation_tags(operation, bucket);
+        for (name, value) in counters {
+            statsd.count(name, *value, &tags);
+        }
+        statsd.timing("operation_duration", duration_ms, &tags);
+    }
+}
+
+/// `operation` label plus the `bucket` label, when there is one
+fn operation_attributes(
+    operation: &'static str,
+    bucket: Option<&str>,
+) -> Vec<opentelemetry::KeyValue> {
+    let mut attributes = vec![opentelemetry::KeyValue::new("operation", operation)];
+    attributes.extend(bucket_attributes(bucket));
+    attributes
+}
+
+/// Count a read-only operation (ls, du, head-object) and its duration, unless
+/// read metrics are turned off (`--otel-read-ops` / `otel_read_operations`).
+/// Returns whether anything was recorded.
+pub fn record_read_operation(
+    otel_config: &OtelConfig,
+    attributes: &[opentelemetry::KeyValue],
+    duration: std::time::Duration,
+) -> bool {
+    if !otel_config.read_operations {
+        return false;
+    }
+
+    OTEL_INSTRUMENTS.operations_total.add(1, attributes);
+    OTEL_INSTRUMENTS
+        .operation_duration
+        .record(duration.as_millis() as f64 / 1000.0, attributes);
+    true
+}
+
+/// Count a failed command's error from the top-level handler, unless the
+/// command already recorded one since `errors_before` (a `GLOBAL_METRICS`
+/// `errors_total` reading taken when it started). Returns whether it counted.
+pub fn record_command_error(error: &anyhow::Error, errors_before: u64) -> bool {
+    if GLOBAL_METRICS.errors_total.load(Ordering::Relaxed) > errors_before {
+        return false;
+    }
+
+    OTEL_INSTRUMENTS.record_error_with_type(&format!("{error:#}"));
+    true
 }
 
 /// Helper function to classify error types for consistent categorization
@@ -1034,6 +1502,11 @@ mod tests {
             endpoint: Some("http://localhost:4317".to_string()),
             service_name: "test-service".to_string(),
             service_version: "1.0.0".to_string(),
+            read_operations: false,
+            environment: None,
+            resource_attributes: Vec::new(),
+            bucket_labels: true,
+            protocol: None,
         };
 
         assert!(config.enabled);
@@ -1049,12 +1522,248 @@ mod tests {
             endpoint: None,
             service_name: "test".to_string(),
             service_version: "1.0.0".to_string(),
+            read_operations: false,
+            environment: None,
+            resource_attributes: Vec::new(),
+            bucket_labels: true,
+            protocol: None,
         };
 
         let result = init_tracing(&config, "info");
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_shutdown_without_providers_returns_immediately() {
+        let providers = init_tracing(
+            &OtelConfig {
+                enabled: false,
+                endpoint: None,
+                service_name: "test".to_string(),
+                service_version: "1.0.0".to_string(),
+                read_operations: false,
+                environment: None,
+                resource_attributes: Vec::new(),
+                bucket_labels: true,
+                protocol: None,
+            },
+            "debug",
+        )
+        .unwrap();
+        assert!(providers.is_empty());
+
+        let start = std::time::Instant::now();
+        shutdown_tracing(providers, "debug");
+        assert!(start.elapsed() < std::time::Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_shutdown_flushes_installed_providers() {
+        let meter_provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder().build();
+        let tracer_provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let providers = TelemetryProviders {
+            meter_provider: Some(meter_provider.clone()),
+            tracer_provider: Some(tracer_provider.clone()),
+        };
+        assert!(!providers.is_empty());
+
+        shutdown_tracing(providers, "info");
+        // Both were shut down, so a second shutdown is refused
+        assert!(meter_provider.shutdown().is_err());
+        assert!(tracer_provider.shutdown().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_snapshot_to_prometheus_text() {
+        let metrics = ObsctlMetrics::new();
+        metrics.record_upload(2048, 10).await;
+        metrics.record_upload(4096, 10).await;
+        metrics.record_copy(512, 10).await;
+        metrics.record_error_with_type("Throttled: rate limit exceeded");
+        metrics
+            .mime_types
+            .lock()
+            .await
+            .insert("text/\"odd\"".to_string(), 1);
+
+        let text = metrics.get_metrics_snapshot().await.to_prometheus_text();
+
+        assert!(text.contains(
+            "# HELP obsctl_uploads_total Total number of upload operations\n\
+             # TYPE obsctl_uploads_total counter\n\
+             obsctl_uploads_total 2\n"
+        ));
+        assert!(text.contains("obsctl_bytes_uploaded_total 6144\n"));
+        assert!(text.contains("obsctl_copies_total 1\n"));
+        assert!(text.contains("obsctl_bytes_copied_total 512\n"));
+        assert!(text.contains("obsctl_errors_by_type_total{type=\"service\"} 1\n"));
+        assert!(text.contains("obsctl_files_by_size_total{size=\"small\"} 2\n"));
+        assert!(
+            text.contains("obsctl_files_by_mime_type_total{mime_type=\"text/\\\"odd\\\"\"} 1\n")
+        );
+        assert!(text
+            .contains("# TYPE obsctl_largest_file_bytes gauge\nobsctl_largest_file_bytes 4096\n"));
+
+        // Every sample line belongs to a declared family
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(text.contains(&format!("# TYPE {name} ")), "{line}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_snapshot_to_summary_json() {
+        let metrics = ObsctlMetrics::new();
+        metrics.record_download(1000, 5).await;
+        metrics.record_error_with_type("connection refused");
+
+        let summary = metrics
+            .get_metrics_snapshot()
+            .await
+            .to_summary_json(std::time::Duration::from_millis(1500), false);
+
+        assert_eq!(summary["success"], false);
+        assert_eq!(summary["duration_seconds"], 1.5);
+        assert_eq!(summary["operations"], 1);
+        assert_eq!(summary["bytes_downloaded"], 1000);
+        assert_eq!(summary["files_downloaded"], 1);
+        assert_eq!(summary["bytes_uploaded"], 0);
+        assert_eq!(summary["files_copied"], 0);
+        assert_eq!(summary["errors"], 1);
+        assert!(!summary.to_string().contains('\n'));
+    }
+
+    #[test]
+    fn test_write_metrics_file_replaces_contents() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("obsctl.prom");
+
+        write_metrics_file(&path, "old\n").unwrap();
+        write_metrics_file(&path, "obsctl_uploads_total 1\n").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "obsctl_uploads_total 1\n"
+        );
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_otlp_protocol_parse_and_detect() {
+        assert_eq!(OtlpProtocol::parse("grpc").unwrap(), OtlpProtocol::Grpc);
+        assert_eq!(
+            OtlpProtocol::parse("HTTP/protobuf").unwrap(),
+            OtlpProtocol::HttpProtobuf
+        );
+        assert!(OtlpProtocol::parse("http/json").is_err());
+
+        assert_eq!(
+            OtlpProtocol::detect("http://localhost:4317"),
+            OtlpProtocol::Grpc
+        );
+        assert_eq!(
+            OtlpProtocol::detect("http://collector:4318"),
+            OtlpProtocol::HttpProtobuf
+        );
+        assert_eq!(
+            OtlpProtocol::detect("https://otlp.example.com/v1/metrics"),
+            OtlpProtocol::HttpProtobuf
+        );
+        assert_eq!(
+            OtlpProtocol::detect("https://otlp.example.com"),
+            OtlpProtocol::Grpc
+        );
+    }
+
+    #[test]
+    fn test_http_signal_endpoint() {
+        assert_eq!(
+            http_signal_endpoint("http://localhost:4318", "/v1/traces"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            http_signal_endpoint("https://otlp.example.com/otlp/", "/v1/metrics"),
+            "https://otlp.example.com/otlp/v1/metrics"
+        );
+        assert_eq!(
+            http_signal_endpoint("http://localhost:4318/v1/traces", "/v1/metrics"),
+            "http://localhost:4318/v1/metrics"
+        );
+    }
+
+    #[test]
+    fn test_resource_attributes() {
+        let value_of = |attributes: &[opentelemetry::KeyValue], key: &str| {
+            attributes
+                .iter()
+                .find(|kv| kv.key.as_str() == key)
+                .map(|kv| kv.value.to_string())
+        };
+
+        let mut config = OtelConfig::default();
+        let attributes = resource_attributes(&config);
+        assert_eq!(
+            value_of(&attributes, "deployment.environment").as_deref(),
+            Some("development")
+        );
+
+        config.resource_attributes = vec![
+            ("team".to_string(), "storage".to_string()),
+            ("deployment.environment".to_string(), "staging".to_string()),
+            ("service.name".to_string(), "ignored".to_string()),
+        ];
+        let attributes = resource_attributes(&config);
+        assert_eq!(value_of(&attributes, "team").as_deref(), Some("storage"));
+        assert_eq!(
+            value_of(&attributes, "deployment.environment").as_deref(),
+            Some("staging")
+        );
+        assert_eq!(
+            value_of(&attributes, "service.name").as_deref(),
+            Some("obsctl")
+        );
+
+        config.environment = Some("production".to_string());
+        let attributes = resource_attributes(&config);
+        assert_eq!(
+            value_of(&attributes, "deployment.environment").as_deref(),
+            Some("production")
+        );
+        assert_eq!(
+            attributes
+                .iter()
+                .filter(|kv| kv.key.as_str() == "deployment.environment")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_record_command_error_counts_unrecorded_failures_once() {
+        let error = anyhow::anyhow!("dispatch failure").context("Failed to list bucket");
+
+        // Other tests share the global counters, so use baselines no
+        // concurrent recording can invalidate
+        let dns_before = GLOBAL_METRICS.errors_dns.load(Ordering::Relaxed);
+        assert!(record_command_error(&error, u64::MAX));
+        assert!(GLOBAL_METRICS.errors_dns.load(Ordering::Relaxed) > dns_before);
+
+        // A failure was counted since the run started (the call above)
+        assert!(!record_command_error(&error, 0));
+    }
+
+    #[test]
+    fn test_read_operations_are_gated() {
+        let attributes = [opentelemetry::KeyValue::new("operation", "ls_objects")];
+        let duration = std::time::Duration::from_millis(5);
+
+        let mut config = OtelConfig::default();
+        assert!(!record_read_operation(&config, &attributes, duration));
+
+        config.read_operations = true;
+        assert!(record_read_operation(&config, &attributes, duration));
+    }
+
     #[test]
     fn test_init_tracing_enabled() {
         // Skip test if OTEL infrastructure is not available
@@ -1068,6 +1777,11 @@ mod tests {
             endpoint: Some("http://localhost:4317".to_string()),
             service_name: "obsctl".to_string(),
             service_version: crate::get_service_version(),
+            read_operations: false,
+            environment: None,
+            resource_attributes: Vec::new(),
+            bucket_labels: true,
+            protocol: None,
         };
 
         // Use a simple runtime for the test
@@ -1091,6 +1805,11 @@ mod tests {
             endpoint: Some("http://localhost:4317".to_string()),
             service_name: "obsctl-test".to_string(),
             service_version: "test".to_string(),
+            read_operations: false,
+            environment: None,
+            resource_attributes: Vec::new(),
+            bucket_labels: true,
+            protocol: None,
         };
 
         // Test with actual OTEL collector
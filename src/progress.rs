@@ -0,0 +1,319 @@
+//! Transfer progress display for recursive cp and sync
+
+use indicatif::{HumanBytes, MultiProgress, ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::args::ProgressMode;
+
+/// Minimum gap between status lines in `--progress lines` mode
+const LINE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Progress display after applying `--no-progress` and terminal detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressDisplay {
+    /// Single updating bar on stderr
+    Bar,
+    /// Periodic status lines on stderr
+    Lines,
+    /// No progress output
+    #[default]
+    None,
+}
+
+impl ProgressDisplay {
+    /// Resolve the requested mode; `auto` shows a bar when stderr is a
+    /// terminal and falls back to status lines when it isn't
+    pub fn resolve(mode: ProgressMode, no_progress: bool, is_tty: bool) -> Self {
+        if no_progress {
+            return ProgressDisplay::None;
+        }
+
+        match mode {
+            ProgressMode::Auto if is_tty => ProgressDisplay::Bar,
+            ProgressMode::Auto => ProgressDisplay::Lines,
+            ProgressMode::None => ProgressDisplay::None,
+            ProgressMode::Bar => ProgressDisplay::Bar,
+            ProgressMode::Lines => ProgressDisplay::Lines,
+        }
+    }
+
+    /// Resolve against the real stderr
+    pub fn detect(mode: ProgressMode, no_progress: bool) -> Self {
+        Self::resolve(mode, no_progress, std::io::stderr().is_terminal())
+    }
+}
+
+/// Per-file byte bars. In a batch they are drawn under the aggregate bar;
+/// only bar mode draws them at all.
+#[derive(Debug, Clone, Default)]
+pub struct FileBars(Option<MultiProgress>);
+
+impl FileBars {
+    /// Bars for a single-file transfer with no aggregate bar above them
+    pub fn new(display: ProgressDisplay) -> Self {
+        Self((display == ProgressDisplay::Bar).then(MultiProgress::new))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Start a bar for one file; `size` is `None` for streams of unknown length
+    pub fn start(&self, name: &str, size: Option<u64>) -> FileProgress {
+        let Some(multi) = &self.0 else {
+            return FileProgress::default();
+        };
+
+        let (bar, template) = match size {
+            Some(size) => (
+                ProgressBar::new(size),
+                "  {msg} [{bar:30}] {bytes}/{total_bytes} ({bytes_per_sec})",
+            ),
+            None => (
+                ProgressBar::no_length(),
+                "  {spinner} {msg} {bytes} ({bytes_per_sec})",
+            ),
+        };
+        bar.set_style(
+            ProgressStyle::with_template(template)
+                .expect("valid progress template")
+                .progress_chars("=> "),
+        );
+        bar.set_message(name.to_string());
+        FileProgress(Some(multi.add(bar)))
+    }
+}
+
+/// Bytes moved so far for one file; the bar disappears when this is dropped
+#[derive(Debug, Default)]
+pub struct FileProgress(Option<ProgressBar>);
+
+impl FileProgress {
+    pub fn inc(&self, bytes: u64) {
+        if let Some(bar) = &self.0 {
+            bar.inc(bytes);
+        }
+    }
+
+    /// Count bytes as they are read from `reader`
+    pub fn wrap<R: AsyncRead + Unpin>(&self, reader: R) -> ProgressReader<R> {
+        ProgressReader {
+            inner: reader,
+            bar: self.0.clone(),
+        }
+    }
+}
+
+impl Drop for FileProgress {
+    fn drop(&mut self) {
+        if let Some(bar) = &self.0 {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+/// Reader that advances a file bar by every byte it yields
+pub struct ProgressReader<R> {
+    inner: R,
+    bar: Option<ProgressBar>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ProgressReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Some(bar) = &self.bar {
+            bar.inc((buf.filled().len() - before) as u64);
+        }
+        result
+    }
+}
+
+/// Aggregate progress over a batch of file transfers
+pub struct TransferProgress {
+    display: ProgressDisplay,
+    multi: Option<MultiProgress>,
+    bar: Option<ProgressBar>,
+    total_files: u64,
+    total_bytes: u64,
+    files_done: u64,
+    bytes_done: u64,
+    started: Instant,
+    last_line: Option<Instant>,
+}
+
+impl TransferProgress {
+    pub fn new(display: ProgressDisplay, total_files: u64, total_bytes: u64) -> Self {
+        let multi = (display == ProgressDisplay::Bar).then(MultiProgress::new);
+        let bar = multi.as_ref().map(|multi| {
+            let bar = multi.add(ProgressBar::new(total_bytes));
+            bar.set_style(
+                ProgressStyle::with_template(
+                    "{spinner} [{elapsed_precise}] [{wide_bar}] {bytes}/{total_bytes} ({bytes_per_sec}, ETA {eta}) {msg}",
+                )
+                .expect("valid progress template")
+                .progress_chars("=> "),
+            );
+            bar.set_message(format!("0/{total_files} files"));
+            bar.enable_steady_tick(Duration::from_millis(200));
+            bar
+        });
+
+        Self {
+            display,
+            multi,
+            bar,
+            total_files,
+            total_bytes,
+            files_done: 0,
+            bytes_done: 0,
+            started: Instant::now(),
+            last_line: None,
+        }
+    }
+
+    /// Per-file bars drawn under this batch's aggregate bar
+    pub fn file_bars(&self) -> FileBars {
+        FileBars(self.multi.clone())
+    }
+
+    /// Print a per-file stdout line without tearing the bars
+    pub fn println(&self, line: &str) {
+        match &self.multi {
+            Some(multi) => multi.suspend(|| println!("{line}")),
+            None => println!("{line}"),
+        }
+    }
+
+    /// Print a per-file error line without tearing the bars
+    pub fn eprintln(&self, line: &str) {
+        match &self.multi {
+            Some(multi) => multi.suspend(|| eprintln!("{line}")),
+            None => eprintln!("{line}"),
+        }
+    }
+
+    /// Record a completed file
+    pub fn file_done(&mut self, bytes: u64) {
+        self.files_done += 1;
+        self.bytes_done += bytes;
+
+        match self.display {
+            ProgressDisplay::Bar => {
+                if let Some(bar) = &self.bar {
+                    bar.inc(bytes);
+                    bar.set_message(format!("{}/{} files", self.files_done, self.total_files));
+                }
+            }
+            ProgressDisplay::Lines => {
+                let due = self
+                    .last_line
+                    .is_none_or(|last| last.elapsed() >= LINE_INTERVAL);
+                if due || self.files_done == self.total_files {
+                    eprintln!("{}", self.status_line());
+                    self.last_line = Some(Instant::now());
+                }
+            }
+            ProgressDisplay::None => {}
+        }
+    }
+
+    /// Clear the bar once the batch is over
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+
+    fn status_line(&self) -> String {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            (self.bytes_done as f64 / elapsed) as u64
+        } else {
+            0
+        };
+
+        format!(
+            "progress: {}/{} files, {}/{} ({}/s)",
+            self.files_done,
+            self.total_files,
+            HumanBytes(self.bytes_done),
+            HumanBytes(self.total_bytes),
+            HumanBytes(rate)
+        )
+    }
+}
+
+impl Drop for TransferProgress {
+    fn drop(&mut self) {
+        // Don't leave a half-drawn bar behind when a transfer fails midway
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_progress_display() {
+        use ProgressDisplay as D;
+
+        assert_eq!(D::resolve(ProgressMode::Auto, false, true), D::Bar);
+        // Without a terminal, auto degrades to plain status lines
+        assert_eq!(D::resolve(ProgressMode::Auto, false, false), D::Lines);
+        // Explicit modes ignore terminal detection
+        assert_eq!(D::resolve(ProgressMode::Bar, false, false), D::Bar);
+        assert_eq!(D::resolve(ProgressMode::Lines, false, true), D::Lines);
+        assert_eq!(D::resolve(ProgressMode::None, false, true), D::None);
+        // --no-progress always wins
+        assert_eq!(D::resolve(ProgressMode::Bar, true, true), D::None);
+    }
+
+    #[test]
+    fn test_file_bars_only_in_bar_mode() {
+        assert!(FileBars::new(ProgressDisplay::Bar).is_enabled());
+        assert!(!FileBars::new(ProgressDisplay::Lines).is_enabled());
+        assert!(!TransferProgress::new(ProgressDisplay::None, 1, 1)
+            .file_bars()
+            .is_enabled());
+
+        // Disabled bars still accept updates
+        let progress = FileBars::default().start("a.txt", Some(10));
+        progress.inc(10);
+    }
+
+    #[tokio::test]
+    async fn test_progress_reader_counts_bytes() {
+        use tokio::io::AsyncReadExt;
+
+        let progress = FileProgress(Some(ProgressBar::hidden()));
+        let mut read = Vec::new();
+        progress
+            .wrap(b"0123456789".as_slice())
+            .read_to_end(&mut read)
+            .await
+            .unwrap();
+
+        assert_eq!(read, b"0123456789");
+        assert_eq!(progress.0.as_ref().unwrap().position(), 10);
+    }
+
+    #[test]
+    fn test_status_line_counts() {
+        let mut progress = TransferProgress::new(ProgressDisplay::None, 3, 3072);
+        progress.file_done(1024);
+        progress.file_done(1024);
+
+        let line = progress.status_line();
+        assert!(line.starts_with("progress: 2/3 files, 2.00 KiB/3.00 KiB"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Legacy S3 Signature Version 2 request signing for old S3-compatible gateways
+//!
+//! The SDK only signs with SigV4, so `--signature-version v2` lets it sign as
+//! usual and then replaces the `Authorization` header just before the request
+//! is sent.
+
+use aws_sdk_s3::config::interceptors::BeforeTransmitInterceptorContextMut;
+use aws_sdk_s3::config::{ConfigBag, Intercept, RuntimeComponents};
+use aws_sdk_s3::error::BoxError;
+use base64::{engine::general_purpose::STANDARD as b64, Engine as _};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::collections::BTreeMap;
+
+/// Query parameters that are part of the SigV2 canonicalized resource
+const SIGNED_SUBRESOURCES: &[&str] = &[
+    "acl",
+    "cors",
+    "delete",
+    "lifecycle",
+    "location",
+    "logging",
+    "notification",
+    "partNumber",
+    "policy",
+    "requestPayment",
+    "response-cache-control",
+    "response-content-disposition",
+    "response-content-encoding",
+    "response-content-language",
+    "response-content-type",
+    "response-expires",
+    "restore",
+    "tagging",
+    "torrent",
+    "uploadId",
+    "uploads",
+    "versionId",
+    "versioning",
+    "versions",
+    "website",
+];
+
+/// Re-signs every request with SigV2 using static credentials
+#[derive(Debug)]
+pub struct SigV2Interceptor {
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl SigV2Interceptor {
+    pub fn new(access_key_id: String, secret_access_key: String) -> Self {
+        Self {
+            access_key_id,
+            secret_access_key,
+        }
+    }
+}
+
+impl Intercept for SigV2Interceptor {
+    fn name(&self) -> &'static str {
+        "SigV2Interceptor"
+    }
+
+    fn modify_before_transmit(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let request = context.request_mut();
+        // SigV2 wants an RFC 1123 Date rather than SigV4's compact x-amz-date
+        request.headers_mut().remove("x-amz-date");
+        request.headers_mut().insert(
+            "date",
+            chrono::Utc::now()
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string(),
+        );
+
+        let uri = url::Url::parse(request.uri())?;
+        let headers: Vec<(String, String)> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.to_string()))
+            .collect();
+
+        let to_sign = string_to_sign(request.method(), &headers, uri.path(), uri.query());
+        let signature = sign(&self.secret_access_key, &to_sign);
+
+        request.headers_mut().insert(
+            "authorization",
+            format!("AWS {}:{}", self.access_key_id, signature),
+        );
+        Ok(())
+    }
+}
+
+/// Build the SigV2 string to sign from a request's method, lowercase-named
+/// headers, encoded path and raw query string
+pub fn string_to_sign(
+    method: &str,
+    headers: &[(String, String)],
+    path: &str,
+    query: Option<&str>,
+) -> String {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim())
+            .unwrap_or("")
+    };
+
+    // x-amz-date replaces Date when present
+    let date = if headers.iter().any(|(key, _)| key == "x-amz-date") {
+        ""
+    } else {
+        header("date")
+    };
+
+    let mut amz_headers: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (name, value) in headers {
+        if name.starts_with("x-amz-") {
+            amz_headers
+                .entry(name.as_str())
+                .or_default()
+                .push(value.trim());
+        }
+    }
+    let canonical_amz_headers: String = amz_headers
+        .iter()
+        .map(|(name, values)| format!("{name}:{}\n", values.join(",")))
+        .collect();
+
+    format!(
+        "{}\n{}\n{}\n{}\n{}{}",
+        method,
+        header("content-md5"),
+        header("content-type"),
+        date,
+        canonical_amz_headers,
+        canonical_resource(path, query)
+    )
+}
+
+/// Path plus the sorted signed subresources; expects path-style addressing,
+/// which the client forces whenever SigV2 is on
+fn canonical_resource(path: &str, query: Option<&str>) -> String {
+    let mut subresources: Vec<(String, String)> = query
+        .map(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .filter(|(name, _)| SIGNED_SUBRESOURCES.contains(&name.as_ref()))
+                .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+    subresources.sort();
+
+    if subresources.is_empty() {
+        return path.to_string();
+    }
+
+    let query = subresources
+        .iter()
+        .map(|(name, value)| {
+            if value.is_empty() {
+                name.clone()
+            } else {
+                format!("{name}={value}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{path}?{query}")
+}
+
+/// Base64 HMAC-SHA1 signature of the string to sign
+pub fn sign(secret_access_key: &str, string_to_sign: &str) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret_access_key.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(string_to_sign.as_bytes());
+    b64.encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sign_matches_documented_example() {
+        // Object GET example from the S3 SigV2 documentation
+        let to_sign = string_to_sign(
+            "GET",
+            &headers(&[
+                ("host", "johnsmith.s3.amazonaws.com"),
+                ("date", "Tue, 27 Mar 2007 19:36:42 +0000"),
+            ]),
+            "/johnsmith/photos/puppy.jpg",
+            None,
+        );
+        assert_eq!(
+            to_sign,
+            "GET\n\n\nTue, 27 Mar 2007 19:36:42 +0000\n/johnsmith/photos/puppy.jpg"
+        );
+        assert_eq!(
+            sign("wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY", &to_sign),
+            "bWq2s1WEIj+Ydj0vQ697zp+IXMU="
+        );
+    }
+
+    #[test]
+    fn test_string_to_sign_amz_headers_and_subresources() {
+        let to_sign = string_to_sign(
+            "PUT",
+            &headers(&[
+                ("content-type", "text/plain"),
+                ("x-amz-date", "Tue, 27 Mar 2007 21:06:08 +0000"),
+                ("date", "ignored"),
+                ("x-amz-meta-b", "2"),
+                ("x-amz-meta-a", " 1 "),
+            ]),
+            "/bucket/key",
+            Some("x-id=PutObject&uploadId=abc&partNumber=2"),
+        );
+
+        assert_eq!(
+            to_sign,
+            "PUT\n\ntext/plain\n\n\
+             x-amz-date:Tue, 27 Mar 2007 21:06:08 +0000\n\
+             x-amz-meta-a:1\n\
+             x-amz-meta-b:2\n\
+             /bucket/key?partNumber=2&uploadId=abc"
+        );
+    }
+}
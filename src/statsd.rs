@@ -0,0 +1,178 @@
+//! StatsD/DogStatsD metrics sink (`--metrics-backend statsd|dogstatsd`)
+//!
+//! Mirrors the [`crate::otel::OtelInstruments`] recording calls as UDP
+//! datagrams, one metric per packet, for stacks without an OTLP collector.
+//! Sends are fire-and-forget: a missing agent never fails a command.
+
+use anyhow::Result;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::OnceLock;
+
+/// Prefix on every metric name
+const METRIC_PREFIX: &str = "obsctl";
+
+static SINK: OnceLock<StatsdSink> = OnceLock::new();
+
+/// StatsD metric types used here
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Timer,
+}
+
+impl MetricKind {
+    fn suffix(self) -> &'static str {
+        match self {
+            MetricKind::Counter => "c",
+            MetricKind::Timer => "ms",
+        }
+    }
+}
+
+/// UDP connection to a StatsD agent
+#[derive(Debug)]
+pub struct StatsdSink {
+    socket: UdpSocket,
+    /// Append DogStatsD `|#key:value` tags; plain StatsD drops them
+    tags: bool,
+}
+
+impl StatsdSink {
+    pub fn new(addr: &str, tags: bool) -> Result<Self> {
+        let target: SocketAddr = addr
+            .to_socket_addrs()
+            .map_err(|e| anyhow::anyhow!("Invalid --statsd-addr '{addr}': {e}"))?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Invalid --statsd-addr '{addr}': no address"))?;
+
+        let local: SocketAddr = if target.is_ipv4() {
+            "0.0.0.0:0".parse()?
+        } else {
+            "[::]:0".parse()?
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(target)?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Self { socket, tags })
+    }
+
+    pub fn count(&self, name: &str, value: u64, tags: &[(&str, &str)]) {
+        self.send(name, value, MetricKind::Counter, tags);
+    }
+
+    pub fn timing(&self, name: &str, duration_ms: u64, tags: &[(&str, &str)]) {
+        self.send(name, duration_ms, MetricKind::Timer, tags);
+    }
+
+    fn send(&self, name: &str, value: u64, kind: MetricKind, tags: &[(&str, &str)]) {
+        let tags = if self.tags { tags } else { &[] };
+        let line = format_metric(name, value, kind, tags);
+        if let Err(e) = self.socket.send(line.as_bytes()) {
+            log::debug!("Failed to send StatsD metric {name}: {e}");
+        }
+    }
+}
+
+/// One StatsD line: `obsctl.name:value|type`, plus `|#k:v,...` when tagged
+pub fn format_metric(name: &str, value: u64, kind: MetricKind, tags: &[(&str, &str)]) -> String {
+    let mut line = format!("{METRIC_PREFIX}.{name}:{value}|{}", kind.suffix());
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|(key, value)| format!("{key}:{}", sanitize_tag(value)))
+            .collect();
+        line.push_str("|#");
+        line.push_str(&tags.join(","));
+    }
+    line
+}
+
+/// Tag values can't carry the separators DogStatsD parses on
+fn sanitize_tag(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            ',' | '|' | '#' | ':' | '\n' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Start sending metrics to `addr` for the rest of the process
+pub fn install(addr: &str, tags: bool) -> Result<()> {
+    let sink = StatsdSink::new(addr, tags)?;
+    if SINK.set(sink).is_err() {
+        log::debug!("StatsD sink already installed");
+    }
+    Ok(())
+}
+
+/// The installed sink, if `--metrics-backend` asked for one
+pub fn sink() -> Option<&'static StatsdSink> {
+    SINK.get()
+}
+
+/// `operation` and `bucket` tags for a recording call
+pub fn operation_tags<'a>(operation: &'a str, bucket: Option<&'a str>) -> Vec<(&'a str, &'a str)> {
+    let mut tags = vec![("operation", operation)];
+    if let Some(bucket) = bucket {
+        tags.push(("bucket", bucket));
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_metric() {
+        assert_eq!(
+            format_metric("uploads_total", 1, MetricKind::Counter, &[]),
+            "obsctl.uploads_total:1|c"
+        );
+        assert_eq!(
+            format_metric(
+                "operation_duration",
+                250,
+                MetricKind::Timer,
+                &operation_tags("upload", Some("my|bucket"))
+            ),
+            "obsctl.operation_duration:250|ms|#operation:upload,bucket:my_bucket"
+        );
+    }
+
+    #[test]
+    fn test_sink_sends_datagrams() {
+        let agent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        agent
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let addr = agent.local_addr().unwrap().to_string();
+        let mut buf = [0u8; 256];
+
+        let sink = StatsdSink::new(&addr, true).unwrap();
+        sink.count("bytes_uploaded_total", 2048, &[("bucket", "photos")]);
+        let len = agent.recv(&mut buf).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&buf[..len]).unwrap(),
+            "obsctl.bytes_uploaded_total:2048|c|#bucket:photos"
+        );
+
+        // Plain StatsD leaves the tags off
+        let sink = StatsdSink::new(&addr, false).unwrap();
+        sink.timing("operation_duration", 12, &[("operation", "list")]);
+        let len = agent.recv(&mut buf).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&buf[..len]).unwrap(),
+            "obsctl.operation_duration:12|ms"
+        );
+    }
+
+    #[test]
+    fn test_invalid_address() {
+        assert!(StatsdSink::new("not an address", false).is_err());
+    }
+}
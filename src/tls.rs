@@ -0,0 +1,120 @@
+//! Custom TLS trust for S3 endpoints with private-CA or self-signed certificates
+//!
+//! `--ca-bundle` adds PEM certificates on top of the platform roots and
+//! `--no-verify-ssl` turns certificate verification off entirely. Either one,
+//! or a proxy, swaps in an HTTPS connector built here for the S3 client.
+
+use anyhow::Result;
+use aws_sdk_s3::config::SharedHttpClient;
+use aws_smithy_http_client::hyper_014::HyperClientBuilder;
+use log::debug;
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, ClientConfig, RootCertStore, ServerName};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::proxy::{ProxyConfig, ProxyConnector};
+
+/// Build the S3 HTTP client for `--ca-bundle` / `--no-verify-ssl` / a proxy,
+/// or `None` to keep the SDK's default connector
+pub fn build_http_client(
+    ca_bundle: Option<&Path>,
+    no_verify_ssl: bool,
+    proxy: Option<ProxyConfig>,
+) -> Result<Option<SharedHttpClient>> {
+    if ca_bundle.is_none() && !no_verify_ssl && proxy.is_none() {
+        return Ok(None);
+    }
+
+    let builder = ClientConfig::builder().with_safe_defaults();
+    let tls_config = if no_verify_ssl {
+        builder
+            .with_custom_certificate_verifier(Arc::new(NoCertificateVerification))
+            .with_no_client_auth()
+    } else {
+        let mut roots = RootCertStore::empty();
+        match rustls_native_certs::load_native_certs() {
+            Ok(certs) => {
+                let native: Vec<Vec<u8>> = certs.into_iter().map(|cert| cert.0).collect();
+                roots.add_parsable_certificates(&native);
+            }
+            Err(e) => debug!("Could not load platform root certificates: {e}"),
+        }
+
+        if let Some(path) = ca_bundle {
+            let pem = std::fs::read(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read CA bundle {}: {e}", path.display()))?;
+            for cert in parse_ca_bundle(&pem)
+                .map_err(|e| anyhow::anyhow!("Invalid CA bundle {}: {e}", path.display()))?
+            {
+                roots
+                    .add(&cert)
+                    .map_err(|e| anyhow::anyhow!("Invalid CA bundle {}: {e}", path.display()))?;
+            }
+        }
+
+        builder.with_root_certificates(roots).with_no_client_auth()
+    };
+
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config)
+        .https_or_http()
+        .enable_http1()
+        .enable_http2()
+        .wrap_connector(ProxyConnector::new(proxy));
+
+    Ok(Some(HyperClientBuilder::new().build(connector)))
+}
+
+/// Parse the certificates out of a PEM bundle, rejecting bundles with none
+fn parse_ca_bundle(pem: &[u8]) -> Result<Vec<Certificate>> {
+    let certs = rustls_pemfile::certs(&mut &pem[..])?;
+    if certs.is_empty() {
+        return Err(anyhow::anyhow!("no PEM certificates found"));
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// Accepts any server certificate (`--no-verify-ssl`)
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ca_bundle() {
+        let pem = b"-----BEGIN CERTIFICATE-----\nAQIDBA==\n-----END CERTIFICATE-----\n\
+                    -----BEGIN CERTIFICATE-----\nBQYHCA==\n-----END CERTIFICATE-----\n";
+        let certs = parse_ca_bundle(pem).unwrap();
+        assert_eq!(certs.len(), 2);
+        assert_eq!(certs[0].0, vec![1, 2, 3, 4]);
+
+        assert!(parse_ca_bundle(b"not a certificate").is_err());
+    }
+
+    #[test]
+    fn test_default_connector_is_kept() {
+        assert!(build_http_client(None, false, None).unwrap().is_none());
+        assert!(build_http_client(None, true, None).unwrap().is_some());
+        assert!(build_http_client(Some(Path::new("/nonexistent/ca.pem")), false, None).is_err());
+        assert!(build_http_client(None, false, Some(ProxyConfig::default()))
+            .unwrap()
+            .is_some());
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use std::path::Path;
 
 #[cfg(target_os = "linux")]
@@ -57,70 +57,50 @@ pub fn has_open_writers(_path: &Path) -> Result<bool> {
 /// - `[abc]*` matches any string starting with 'a', 'b', or 'c'
 /// - `*[0-9]` matches any string ending with a digit
 pub fn wildcard_match(pattern: &str, text: &str) -> bool {
-    wildcard_match_recursive(pattern.chars().collect(), text.chars().collect(), 0, 0)
-}
-
-fn wildcard_match_recursive(
-    pattern: Vec<char>,
-    text: Vec<char>,
-    p_idx: usize,
-    t_idx: usize,
-) -> bool {
-    // If we've consumed both pattern and text, it's a match
-    if p_idx >= pattern.len() && t_idx >= text.len() {
-        return true;
-    }
-
-    // If pattern is exhausted but text remains, no match
-    if p_idx >= pattern.len() {
-        return false;
-    }
-
-    match pattern[p_idx] {
-        '*' => {
-            // Try matching '*' with empty string first
-            if wildcard_match_recursive(pattern.clone(), text.clone(), p_idx + 1, t_idx) {
-                return true;
-            }
-
-            // Try matching '*' with one or more characters
-            for i in t_idx..text.len() {
-                if wildcard_match_recursive(pattern.clone(), text.clone(), p_idx + 1, i + 1) {
-                    return true;
-                }
-            }
-            false
-        }
-        '?' => {
-            // '?' matches exactly one character
-            if t_idx >= text.len() {
-                false
-            } else {
-                wildcard_match_recursive(pattern, text, p_idx + 1, t_idx + 1)
-            }
-        }
-        '[' => {
-            // Character class matching
-            if t_idx >= text.len() {
-                return false;
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let mut p_idx = 0;
+    let mut t_idx = 0;
+    // Pattern position just past the last '*' seen, and the text position
+    // that '*' currently stops absorbing at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t_idx < text.len() {
+        let advanced = match pattern.get(p_idx) {
+            Some('*') => {
+                p_idx += 1;
+                backtrack = Some((p_idx, t_idx));
+                continue;
             }
+            Some('?') => Some(p_idx + 1),
+            Some('[') => match match_character_class(&pattern, p_idx, text[t_idx]) {
+                (true, next) => Some(next),
+                (false, _) => None,
+            },
+            Some(&c) if c == text[t_idx] => Some(p_idx + 1),
+            _ => None,
+        };
 
-            let (matches, new_p_idx) = match_character_class(&pattern, p_idx, text[t_idx]);
-            if matches {
-                wildcard_match_recursive(pattern, text, new_p_idx, t_idx + 1)
-            } else {
-                false
+        match (advanced, backtrack) {
+            (Some(next), _) => {
+                p_idx = next;
+                t_idx += 1;
             }
-        }
-        c => {
-            // Literal character matching
-            if t_idx >= text.len() || text[t_idx] != c {
-                false
-            } else {
-                wildcard_match_recursive(pattern, text, p_idx + 1, t_idx + 1)
+            // On a mismatch, let the last '*' absorb one more character and
+            // retry the rest of the pattern from there. Earlier stars never
+            // need revisiting, which keeps this O(n·m).
+            (None, Some((star_p, star_t))) => {
+                p_idx = star_p;
+                t_idx = star_t + 1;
+                backtrack = Some((star_p, star_t + 1));
             }
+            (None, None) => return false,
         }
     }
+
+    // Only trailing stars may be left once the text is used up
+    pattern[p_idx..].iter().all(|&c| c == '*')
 }
 
 fn match_character_class(pattern: &[char], start_idx: usize, ch: char) -> (bool, usize) {
@@ -166,13 +146,180 @@ fn match_character_class(pattern: &[char], start_idx: usize, ch: char) -> (bool,
     (matches, idx)
 }
 
-/// Filter a list of strings by a wildcard pattern
-pub fn filter_by_pattern(items: &[String], pattern: &str) -> Vec<String> {
-    items
+/// Most patterns a single brace expression may expand to
+const MAX_BRACE_EXPANSIONS: usize = 1024;
+
+/// Expand shell-style brace groups: `logs-{dev,prod}-*` becomes
+/// `logs-dev-*` and `logs-prod-*`. Groups may nest; a group without a
+/// comma or without its closing brace is kept literally. Errors once the
+/// expansion would exceed 1024 patterns.
+pub fn expand_braces(pattern: &str) -> Result<Vec<String>> {
+    let mut expansions = Vec::new();
+    expand_braces_into(pattern, &mut expansions)?;
+    Ok(expansions)
+}
+
+fn expand_braces_into(pattern: &str, expansions: &mut Vec<String>) -> Result<()> {
+    let Some((open, close, alternatives)) = first_brace_group(pattern) else {
+        if expansions.len() >= MAX_BRACE_EXPANSIONS {
+            return Err(anyhow::anyhow!(
+                "Pattern '{pattern}' expands to more than {MAX_BRACE_EXPANSIONS} alternatives"
+            ));
+        }
+        expansions.push(pattern.to_string());
+        return Ok(());
+    };
+
+    let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+    for alternative in alternatives {
+        expand_braces_into(&format!("{prefix}{alternative}{suffix}"), expansions)?;
+    }
+    Ok(())
+}
+
+/// Byte offsets of the first `{...}` group with a top-level comma, and its
+/// alternatives (nested groups are left for the next round)
+fn first_brace_group(pattern: &str) -> Option<(usize, usize, Vec<&str>)> {
+    for (open, _) in pattern.match_indices('{') {
+        let mut depth = 0;
+        let mut commas = Vec::new();
+
+        for (offset, c) in pattern[open..].char_indices() {
+            let idx = open + offset;
+            match c {
+                '{' => depth += 1,
+                ',' if depth == 1 => commas.push(idx),
+                '}' => {
+                    depth -= 1;
+                    if depth > 0 {
+                        continue;
+                    }
+                    if commas.is_empty() {
+                        break;
+                    }
+
+                    let mut alternatives = Vec::with_capacity(commas.len() + 1);
+                    let mut start = open + 1;
+                    for comma in commas {
+                        alternatives.push(&pattern[start..comma]);
+                        start = comma + 1;
+                    }
+                    alternatives.push(&pattern[start..idx]);
+                    return Some((open, idx, alternatives));
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Wildcard match against any of a pattern's brace expansions
+fn brace_wildcard_match(patterns: &[String], text: &str) -> bool {
+    patterns.iter().any(|pattern| wildcard_match(pattern, text))
+}
+
+/// Filter a list of strings by a wildcard pattern, with brace expansion
+pub fn filter_by_pattern(items: &[String], pattern: &str) -> Result<Vec<String>> {
+    let patterns = expand_braces(pattern)?;
+    Ok(items
         .iter()
-        .filter(|item| wildcard_match(pattern, item))
+        .filter(|item| brace_wildcard_match(&patterns, item))
         .cloned()
-        .collect()
+        .collect())
+}
+
+/// MIME type for a file based on its extension (case-insensitive), or
+/// `None` when the extension is missing or unknown
+pub fn detect_mime_type(file_path: &str) -> Option<&'static str> {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())?
+        .to_lowercase();
+
+    match extension.as_str() {
+        // Images
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "svg" => Some("image/svg+xml"),
+        "bmp" => Some("image/bmp"),
+
+        // Documents
+        "pdf" => Some("application/pdf"),
+        "doc" => Some("application/msword"),
+        "docx" => Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+        "xls" => Some("application/vnd.ms-excel"),
+        "xlsx" => Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        "ppt" => Some("application/vnd.ms-powerpoint"),
+        "pptx" => Some("application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+
+        // Text
+        "txt" => Some("text/plain"),
+        "csv" => Some("text/csv"),
+        "json" => Some("application/json"),
+        "xml" => Some("application/xml"),
+        "html" | "htm" => Some("text/html"),
+        "css" => Some("text/css"),
+        "js" => Some("application/javascript"),
+
+        // Code
+        "py" => Some("text/x-python"),
+        "rs" => Some("text/x-rust"),
+        "java" => Some("text/x-java-source"),
+        "cpp" | "cc" | "cxx" => Some("text/x-c++src"),
+        "c" => Some("text/x-csrc"),
+        "h" => Some("text/x-chdr"),
+        "go" => Some("text/x-go"),
+
+        // Archives
+        "zip" => Some("application/zip"),
+        "tar" => Some("application/x-tar"),
+        "gz" => Some("application/gzip"),
+        "7z" => Some("application/x-7z-compressed"),
+        "rar" => Some("application/vnd.rar"),
+
+        // Media
+        "mp4" => Some("video/mp4"),
+        "avi" => Some("video/x-msvideo"),
+        "mov" => Some("video/quicktime"),
+        "mp3" => Some("audio/mpeg"),
+        "wav" => Some("audio/wav"),
+        "flac" => Some("audio/flac"),
+
+        _ => None,
+    }
+}
+
+/// Whether a path contains glob characters (`*`, `?` or `[`)
+pub fn has_wildcards(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Match a `/`-separated path against a glob pattern
+///
+/// Each segment is matched with [`wildcard_match`], so `*` stays within a
+/// single directory, while a `**` segment matches zero or more whole
+/// directories:
+/// - `**/*.log` matches `a.log` and `logs/2024/a.log`
+/// - `logs/*.log` matches `logs/a.log` but not `logs/old/a.log`
+pub fn glob_path_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    glob_segments_match(&pattern, &path)
+}
+
+fn glob_segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=path.len()).any(|skip| glob_segments_match(rest, &path[skip..]))
+        }
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            wildcard_match(segment, name) && glob_segments_match(rest, path_rest)
+        }),
+    }
 }
 
 /// Cross-platform file descriptor/handle monitoring
@@ -537,30 +684,46 @@ pub enum PatternType {
 pub fn detect_pattern_type(pattern: &str) -> PatternType {
     // If pattern contains regex metacharacters, treat as regex
     // Otherwise, treat as wildcard for backward compatibility
-    let regex_chars = ['(', ')', '{', '}', '+', '^', '$', '\\', '|'];
+    let regex_chars = ['(', ')', '+', '^', '$', '\\', '|'];
+
+    // Braces are a repetition count in `\w{3,8}` but alternatives in
+    // `logs-{dev,prod}-*`
+    let quantifier = Regex::new(r"\{\d+(,\d*)?\}").expect("valid quantifier regex");
 
-    if pattern.chars().any(|c| regex_chars.contains(&c)) {
+    if pattern.chars().any(|c| regex_chars.contains(&c)) || quantifier.is_match(pattern) {
         PatternType::Regex
     } else {
         PatternType::Wildcard
     }
 }
 
-/// Enhanced pattern matching with both wildcard and regex support
-pub fn enhanced_pattern_match(pattern: &str, text: &str, force_regex: bool) -> Result<bool> {
+/// Enhanced pattern matching with both wildcard and regex support;
+/// `ignore_case` applies to either kind of pattern
+pub fn enhanced_pattern_match(
+    pattern: &str,
+    text: &str,
+    force_regex: bool,
+    ignore_case: bool,
+) -> Result<bool> {
     if force_regex {
-        regex_match(pattern, text)
-    } else {
-        match detect_pattern_type(pattern) {
-            PatternType::Regex => regex_match(pattern, text),
-            PatternType::Wildcard => Ok(wildcard_match(pattern, text)),
-        }
+        return regex_match(pattern, text, ignore_case);
+    }
+
+    match detect_pattern_type(pattern) {
+        PatternType::Regex => regex_match(pattern, text, ignore_case),
+        PatternType::Wildcard if ignore_case => Ok(brace_wildcard_match(
+            &expand_braces(&pattern.to_lowercase())?,
+            &text.to_lowercase(),
+        )),
+        PatternType::Wildcard => Ok(brace_wildcard_match(&expand_braces(pattern)?, text)),
     }
 }
 
 /// Regex pattern matching using the regex crate
-pub fn regex_match(pattern: &str, text: &str) -> Result<bool> {
-    let regex = Regex::new(pattern)
+pub fn regex_match(pattern: &str, text: &str, ignore_case: bool) -> Result<bool> {
+    let regex = RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case)
+        .build()
         .map_err(|e| anyhow::anyhow!("Invalid regex pattern '{}': {}", pattern, e))?;
 
     Ok(regex.is_match(text))
@@ -571,11 +734,12 @@ pub fn filter_by_enhanced_pattern(
     items: &[String],
     pattern: &str,
     force_regex: bool,
+    ignore_case: bool,
 ) -> Result<Vec<String>> {
     let mut results = Vec::new();
 
     for item in items {
-        if enhanced_pattern_match(pattern, item, force_regex)? {
+        if enhanced_pattern_match(pattern, item, force_regex, ignore_case)? {
             results.push(item.clone());
         }
     }
@@ -729,7 +893,6 @@ mod tests {
     #[test]
     #[cfg(target_os = "linux")]
     fn test_has_open_writers_with_temp_file() {
-        use std::fs::File;
         use tempfile::NamedTempFile;
 
         // Create a temporary file
@@ -844,6 +1007,15 @@ mod tests {
         assert!(!wildcard_match("[^0-9]*", "1abc"));
     }
 
+    #[test]
+    fn test_wildcard_pathological_backtracking() {
+        // Exponential for a recursive matcher; the text never contains a 'b'
+        let text = "a".repeat(10_000);
+        assert!(!wildcard_match("*a*a*a*a*a*a*a*a*a*a*b", &text));
+        assert!(wildcard_match("*a*a*a*a*a*a*a*a*a*a*", &text));
+        assert!(!wildcard_match("*[a]*?*[!c]*a*b", &text));
+    }
+
     #[test]
     fn test_wildcard_complex_patterns() {
         // Realistic bucket name patterns
@@ -900,34 +1072,126 @@ mod tests {
         ];
 
         // Test various patterns
-        let prod_buckets = filter_by_pattern(&bucket_names, "*-prod");
+        let prod_buckets = filter_by_pattern(&bucket_names, "*-prod").unwrap();
         assert_eq!(prod_buckets, vec!["app-prod", "api-prod", "web-prod"]);
 
-        let app_buckets = filter_by_pattern(&bucket_names, "app-*");
+        let app_buckets = filter_by_pattern(&bucket_names, "app-*").unwrap();
         assert_eq!(app_buckets, vec!["app-prod", "app-staging", "app-dev"]);
 
-        let test_buckets = filter_by_pattern(&bucket_names, "test-*");
+        let test_buckets = filter_by_pattern(&bucket_names, "test-*").unwrap();
         assert_eq!(test_buckets, vec!["test-bucket-1", "test-bucket-2"]);
 
-        let numbered_buckets = filter_by_pattern(&bucket_names, "*-[0-9]");
+        let numbered_buckets = filter_by_pattern(&bucket_names, "*-[0-9]").unwrap();
         assert_eq!(numbered_buckets, vec!["test-bucket-1", "test-bucket-2"]);
 
         // Pattern that matches nothing
-        let no_match = filter_by_pattern(&bucket_names, "nonexistent-*");
+        let no_match = filter_by_pattern(&bucket_names, "nonexistent-*").unwrap();
         assert!(no_match.is_empty());
 
         // Pattern that matches everything
-        let all_match = filter_by_pattern(&bucket_names, "*");
+        let all_match = filter_by_pattern(&bucket_names, "*").unwrap();
         assert_eq!(all_match.len(), bucket_names.len());
     }
 
+    #[test]
+    fn test_expand_braces() {
+        assert_eq!(
+            expand_braces("logs-{dev,prod}-*").unwrap(),
+            vec!["logs-dev-*", "logs-prod-*"]
+        );
+        assert_eq!(
+            expand_braces("{a,b}-{1,2}").unwrap(),
+            vec!["a-1", "a-2", "b-1", "b-2"]
+        );
+        // Nested groups and empty alternatives
+        assert_eq!(
+            expand_braces("app{,-{dev,prod}}").unwrap(),
+            vec!["app", "app-dev", "app-prod"]
+        );
+        // No comma or no closing brace: kept literally
+        assert_eq!(expand_braces("x{a}y").unwrap(), vec!["x{a}y"]);
+        assert_eq!(expand_braces("x{a,b").unwrap(), vec!["x{a,b"]);
+        assert_eq!(expand_braces("plain-*").unwrap(), vec!["plain-*"]);
+
+        // 2^11 = 2048 alternatives is past the cap
+        let explosive = "{a,b}".repeat(11);
+        assert!(expand_braces(&explosive)
+            .unwrap_err()
+            .to_string()
+            .contains("more than 1024"));
+    }
+
+    #[test]
+    fn test_brace_patterns_filter_buckets() {
+        let buckets = vec![
+            "logs-dev-1".to_string(),
+            "logs-prod-1".to_string(),
+            "logs-test-1".to_string(),
+        ];
+
+        assert!(matches!(
+            detect_pattern_type("logs-{dev,prod}-*"),
+            PatternType::Wildcard
+        ));
+        assert_eq!(
+            filter_by_enhanced_pattern(&buckets, "logs-{dev,prod}-*", false, false).unwrap(),
+            vec!["logs-dev-1", "logs-prod-1"]
+        );
+        assert_eq!(
+            filter_by_pattern(&buckets, "logs-{test,prod}-?").unwrap(),
+            vec!["logs-prod-1", "logs-test-1"]
+        );
+    }
+
     #[test]
     fn test_filter_by_pattern_empty_input() {
         let empty_list: Vec<String> = vec![];
-        let result = filter_by_pattern(&empty_list, "*");
+        let result = filter_by_pattern(&empty_list, "*").unwrap();
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_detect_mime_type() {
+        assert_eq!(
+            detect_mime_type("data/report.json"),
+            Some("application/json")
+        );
+        assert_eq!(detect_mime_type("site/INDEX.HTML"), Some("text/html"));
+        assert_eq!(detect_mime_type("photo.jpeg"), Some("image/jpeg"));
+        assert_eq!(detect_mime_type("archive.unknownext"), None);
+        assert_eq!(detect_mime_type("Makefile"), None);
+    }
+
+    #[test]
+    fn test_glob_path_match() {
+        assert!(glob_path_match("**/*.log", "a.log"));
+        assert!(glob_path_match("**/*.log", "logs/2024/a.log"));
+        assert!(!glob_path_match("**/*.log", "logs/a.txt"));
+
+        assert!(glob_path_match("logs/*.log", "logs/a.log"));
+        assert!(!glob_path_match("logs/*.log", "logs/old/a.log"));
+        assert!(!glob_path_match("*.log", "logs/a.log"));
+
+        assert!(glob_path_match("logs/**/a.log", "logs/a.log"));
+        assert!(glob_path_match("logs/**/a.log", "logs/x/y/a.log"));
+        assert!(glob_path_match("logs/**", "logs/x/y/a.log"));
+        assert!(!glob_path_match("logs/**/a.log", "other/a.log"));
+
+        assert!(glob_path_match("data/file-?.[ct]sv", "data/file-1.csv"));
+        assert!(glob_path_match(
+            "/var/log/**/*.gz",
+            "/var/log/nginx/access.gz"
+        ));
+    }
+
+    #[test]
+    fn test_has_wildcards() {
+        assert!(has_wildcards("**/*.log"));
+        assert!(has_wildcards("file-?.txt"));
+        assert!(has_wildcards("[ab].txt"));
+        assert!(!has_wildcards("logs/a.log"));
+    }
+
     #[test]
     fn test_wildcard_case_sensitivity() {
         // Test case sensitivity in wildcard patterns
@@ -937,6 +1201,21 @@ mod tests {
     }
 
     // New tests for enhanced pattern matching
+    #[test]
+    fn test_ignore_case_matching() {
+        assert!(!enhanced_pattern_match("app-*", "APP-Prod", false, false).unwrap());
+        assert!(enhanced_pattern_match("app-*", "APP-Prod", false, true).unwrap());
+        assert!(enhanced_pattern_match("LOGS-{dev,prod}", "logs-Prod", false, true).unwrap());
+        assert!(enhanced_pattern_match("^backup-\\d+$", "BACKUP-1", false, true).unwrap());
+        assert!(!enhanced_pattern_match("^backup-\\d+$", "BACKUP-1", false, false).unwrap());
+
+        let buckets = vec!["Media-Prod".to_string(), "media-dev".to_string()];
+        assert_eq!(
+            filter_by_enhanced_pattern(&buckets, "media-*", false, true).unwrap(),
+            buckets
+        );
+    }
+
     #[test]
     fn test_pattern_type_detection() {
         // Wildcard patterns
@@ -979,38 +1258,38 @@ mod tests {
     #[test]
     fn test_regex_matching() {
         // Basic regex patterns
-        assert!(regex_match("^test", "test-bucket").unwrap());
-        assert!(!regex_match("^test", "my-test-bucket").unwrap());
+        assert!(regex_match("^test", "test-bucket", false).unwrap());
+        assert!(!regex_match("^test", "my-test-bucket", false).unwrap());
 
-        assert!(regex_match("prod$", "app-prod").unwrap());
-        assert!(!regex_match("prod$", "prod-backup").unwrap());
+        assert!(regex_match("prod$", "app-prod", false).unwrap());
+        assert!(!regex_match("prod$", "prod-backup", false).unwrap());
 
         // Digit patterns
-        assert!(regex_match("\\d+", "backup-123").unwrap());
-        assert!(!regex_match("\\d+", "backup-abc").unwrap());
+        assert!(regex_match("\\d+", "backup-123", false).unwrap());
+        assert!(!regex_match("\\d+", "backup-abc", false).unwrap());
 
         // Word boundaries and character classes - fix the failing test
-        assert!(regex_match("^\\w{3,8}$", "bucket").unwrap());
-        assert!(!regex_match("^\\w{3,8}$", "verylongbucketname").unwrap());
+        assert!(regex_match("^\\w{3,8}$", "bucket", false).unwrap());
+        assert!(!regex_match("^\\w{3,8}$", "verylongbucketname", false).unwrap());
 
         // Alternation
-        assert!(regex_match("(dev|test|prod)", "test-bucket").unwrap());
-        assert!(regex_match("(dev|test|prod)", "prod-data").unwrap());
-        assert!(!regex_match("(dev|test|prod)", "staging-app").unwrap());
+        assert!(regex_match("(dev|test|prod)", "test-bucket", false).unwrap());
+        assert!(regex_match("(dev|test|prod)", "prod-data", false).unwrap());
+        assert!(!regex_match("(dev|test|prod)", "staging-app", false).unwrap());
     }
 
     #[test]
     fn test_enhanced_pattern_match_auto_detection() {
         // Should use wildcard matching automatically
-        assert!(enhanced_pattern_match("*-prod", "app-prod", false).unwrap());
-        assert!(enhanced_pattern_match("test-?", "test-1", false).unwrap());
+        assert!(enhanced_pattern_match("*-prod", "app-prod", false, false).unwrap());
+        assert!(enhanced_pattern_match("test-?", "test-1", false, false).unwrap());
 
         // Should use regex matching automatically
-        assert!(enhanced_pattern_match("^backup-\\d{4}$", "backup-2024", false).unwrap());
-        assert!(!enhanced_pattern_match("^backup-\\d{4}$", "backup-24", false).unwrap());
+        assert!(enhanced_pattern_match("^backup-\\d{4}$", "backup-2024", false, false).unwrap());
+        assert!(!enhanced_pattern_match("^backup-\\d{4}$", "backup-24", false, false).unwrap());
 
         // Force regex mode
-        assert!(enhanced_pattern_match(".*-prod", "app-prod", true).unwrap());
+        assert!(enhanced_pattern_match(".*-prod", "app-prod", true, false).unwrap());
     }
 
     #[test]
@@ -1043,26 +1322,27 @@ mod tests {
         ];
 
         // Wildcard patterns
-        let prod_buckets = filter_by_enhanced_pattern(&buckets, "*-prod", false).unwrap();
+        let prod_buckets = filter_by_enhanced_pattern(&buckets, "*-prod", false, false).unwrap();
         assert_eq!(prod_buckets, vec!["app-prod"]);
 
         // Regex patterns (auto-detected)
         let backup_buckets =
-            filter_by_enhanced_pattern(&buckets, "^backup-\\d{4}-\\d{2}$", false).unwrap();
+            filter_by_enhanced_pattern(&buckets, "^backup-\\d{4}-\\d{2}$", false, false).unwrap();
         assert_eq!(backup_buckets, vec!["backup-2024-01", "backup-2023-12"]);
 
         // Alternation pattern
-        let env_buckets = filter_by_enhanced_pattern(&buckets, "(app|test)-.*", false).unwrap();
+        let env_buckets =
+            filter_by_enhanced_pattern(&buckets, "(app|test)-.*", false, false).unwrap();
         assert_eq!(env_buckets, vec!["app-prod", "app-dev", "test-bucket-1"]);
     }
 
     #[test]
     fn test_regex_error_handling() {
         // Invalid regex should return error
-        let result = regex_match("[invalid", "test");
+        let result = regex_match("[invalid", "test", false);
         assert!(result.is_err());
 
-        let result = enhanced_pattern_match("(unclosed", "test", true);
+        let result = enhanced_pattern_match("(unclosed", "test", true, false);
         assert!(result.is_err());
     }
 
@@ -1080,21 +1360,23 @@ mod tests {
 
         // Date-based log buckets
         let log_buckets =
-            filter_by_enhanced_pattern(&buckets, "^logs-\\d{4}-\\d{2}-\\d{2}$", false).unwrap();
+            filter_by_enhanced_pattern(&buckets, "^logs-\\d{4}-\\d{2}-\\d{2}$", false, false)
+                .unwrap();
         assert_eq!(log_buckets, vec!["logs-2024-01-15", "logs-2024-02-20"]);
 
         // Versioned backup buckets
         let backup_buckets =
-            filter_by_enhanced_pattern(&buckets, "^backup-v\\d+-(prod|dev)$", false).unwrap();
+            filter_by_enhanced_pattern(&buckets, "^backup-v\\d+-(prod|dev)$", false, false)
+                .unwrap();
         assert_eq!(backup_buckets, vec!["backup-v1-prod", "backup-v2-dev"]);
 
         // Numeric user buckets only
         let numeric_user_buckets =
-            filter_by_enhanced_pattern(&buckets, "^user-\\d+-data$", false).unwrap();
+            filter_by_enhanced_pattern(&buckets, "^user-\\d+-data$", false, false).unwrap();
         assert_eq!(numeric_user_buckets, vec!["user-123-data"]);
 
         // Temporary buckets
-        let temp_buckets = filter_by_enhanced_pattern(&buckets, "^temp-.*", false).unwrap();
+        let temp_buckets = filter_by_enhanced_pattern(&buckets, "^temp-.*", false, false).unwrap();
         assert_eq!(temp_buckets, vec!["temp-session-xyz"]);
     }
 }